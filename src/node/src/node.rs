@@ -14,82 +14,279 @@
 
 use async_dup::Arc as AsyncArc;
 use async_io::Async;
-use futures_lite::future::block_on;
-use protocol::{Command, ProtocolConfiguration, Receiver};
+use futures_lite::future::{self, block_on};
+use protocol::{results::QueryErrorBuilder, Command, Error as ProtocolError, ProtocolConfiguration, Receiver, Sender};
 use smol::{self, Task};
-use sql_engine::QueryExecutor;
+use sql_engine::{DatabaseRegistry, QueryExecutor, SessionRegistry};
 use std::{
-    env,
-    net::TcpListener,
+    env, io,
+    net::{SocketAddr, TcpListener},
     path::{Path, PathBuf},
     sync::{
         atomic::{AtomicU8, Ordering},
-        Arc, Mutex,
+        Arc, Mutex, RwLock,
     },
 };
-use storage::{backend::SledBackendStorage, frontend::FrontendStorage};
+use storage::{
+    backend::SledBackendStorage,
+    frontend::{FrontendStorage, SharedStorage},
+};
 
 const PORT: u16 = 5432;
 const HOST: [u8; 4] = [0, 0, 0, 0];
+const MAX_CONNECTIONS: usize = 100;
 
 pub const RUNNING: u8 = 0;
 pub const STOPPED: u8 = 1;
 
 pub fn start() {
-    block_on(async {
-        let storage: Arc<Mutex<FrontendStorage<SledBackendStorage>>> =
-            Arc::new(Mutex::new(FrontendStorage::default().unwrap()));
-        let listener = Async::<TcpListener>::bind((HOST, PORT)).expect("OK");
-
-        let state = Arc::new(AtomicU8::new(RUNNING));
-        let config = protocol_configuration();
-
-        while let Ok((tcp_stream, address)) = listener.accept().await {
-            let tcp_stream = AsyncArc::new(tcp_stream);
-            if let Ok((mut receiver, sender)) = protocol::hand_shake(tcp_stream, address, &config)
-                .await
-                .expect("no io errors")
-            {
-                if state.load(Ordering::SeqCst) == STOPPED {
-                    return;
-                }
-                let state = state.clone();
-                let storage = storage.clone();
-                let sender = Arc::new(sender);
-                let s = sender.clone();
-                Task::spawn(async move {
-                    let mut query_executor = QueryExecutor::new(storage.clone(), s);
-                    log::debug!("ready to handle query");
+    Server::bind((HOST, PORT)).expect("OK").run();
+}
+
+/// Accepts PostgreSQL clients and executes their queries against storage
+/// shared between all connections
+pub struct Server {
+    listener: Async<TcpListener>,
+    storage: SharedStorage<SledBackendStorage>,
+    config: ProtocolConfiguration,
+    max_connections: usize,
+    shutdown: ShutdownHandle,
+    // closed by the shutdown handle, every waiting side is woken up at once
+    shutdown_signal: async_channel::Receiver<()>,
+}
+
+/// Stops a running server: new clients are no longer accepted, queries that
+/// are being executed are finished and idle clients are disconnected
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    sender: Arc<Mutex<Option<async_channel::Sender<()>>>>,
+}
+
+impl ShutdownHandle {
+    pub fn shutdown(&self) {
+        // dropping the only sender closes the channel
+        self.sender.lock().unwrap().take();
+    }
+}
+
+impl Server {
+    /// Binds server to the `address`, clients are not accepted until the
+    /// server is run
+    pub fn bind<A: Into<SocketAddr>>(address: A) -> io::Result<Server> {
+        let (sender, shutdown_signal) = async_channel::bounded(1);
+        Ok(Server {
+            listener: Async::<TcpListener>::bind(address)?,
+            storage: Arc::new(RwLock::new(FrontendStorage::default().unwrap())),
+            config: protocol_configuration(),
+            max_connections: MAX_CONNECTIONS,
+            shutdown: ShutdownHandle {
+                sender: Arc::new(Mutex::new(Some(sender))),
+            },
+            shutdown_signal,
+        })
+    }
+
+    /// Number of clients served at the same time, when all of them are
+    /// connected the server doesn't accept new ones until somebody leaves
+    pub fn with_max_connections(mut self, max_connections: usize) -> Server {
+        self.max_connections = max_connections.max(1);
+        self
+    }
+
+    /// Handle to stop the server once it is run
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.get_ref().local_addr()
+    }
 
+    /// Accepts clients until the listener fails, a client connection breaks
+    /// or the server is shut down. In the last case returns once all clients
+    /// are disconnected
+    pub fn run(self) {
+        let Server {
+            listener,
+            storage,
+            config,
+            max_connections,
+            shutdown_signal,
+            ..
+        } = self;
+        block_on(async {
+            let state = Arc::new(AtomicU8::new(RUNNING));
+            // every connection holds a sender, so the channel is closed when the last one is gone
+            let (connected, all_disconnected) = async_channel::bounded::<()>(1);
+            // works as a semaphore, a client takes a slot before it is accepted
+            let (slots, freed_slots) = async_channel::bounded::<()>(max_connections);
+            let sessions = SessionRegistry::new();
+            let databases = DatabaseRegistry::new(storage.clone(), FrontendStorage::default);
+
+            loop {
+                let accepted = future::race(
+                    async {
+                        slots.send(()).await.expect("slots channel is not closed");
+                        let slot = ConnectionSlot(freed_slots.clone());
+                        Some(listener.accept().await.map(|accepted| (accepted, slot)))
+                    },
+                    async {
+                        shutdown_signal.recv().await.ok();
+                        None
+                    },
+                )
+                .await;
+                let ((tcp_stream, address), slot) = match accepted {
+                    Some(Ok(accepted)) => accepted,
+                    Some(Err(_)) => break,
+                    None => {
+                        log::info!("server is shutting down");
+                        break;
+                    }
+                };
+                let tcp_stream = AsyncArc::new(tcp_stream);
+                if let Ok((mut receiver, sender)) = protocol::hand_shake(tcp_stream, address, &config)
+                    .await
+                    .expect("no io errors")
+                {
+                    if state.load(Ordering::SeqCst) == STOPPED {
+                        return;
+                    }
+                    log::debug!(
+                        "{:?} connected from {:?}",
+                        receiver.application_name().unwrap_or("unnamed client"),
+                        address
+                    );
+                    let application_name = receiver.application_name().unwrap_or_default().to_owned();
+                    let sessions = sessions.clone();
+                    let databases = databases.clone();
+                    let user = receiver.user().map(ToOwned::to_owned);
+                    let options = receiver.options().map(ToOwned::to_owned);
+                    let database = receiver.database().map(ToOwned::to_owned);
+                    let state = state.clone();
+                    let shutdown_signal = shutdown_signal.clone();
+                    let connected = connected.clone();
+                    let storage = storage.clone();
+                    let sender = Arc::new(sender);
+                    let s = sender.clone();
                     Task::spawn(async move {
-                        loop {
-                            match receiver.receive().await {
-                                Err(e) => {
-                                    log::error!("UNEXPECTED ERROR: {:?}", e);
-                                    state.store(STOPPED, Ordering::SeqCst);
-                                    return;
-                                }
-                                Ok(Err(e)) => {
-                                    log::error!("UNEXPECTED ERROR: {:?}", e);
-                                    state.store(STOPPED, Ordering::SeqCst);
-                                    return;
-                                }
-                                Ok(Ok(Command::Terminate)) => {
-                                    log::debug!("Closing connection with client");
-                                    break;
-                                }
-                                Ok(Ok(Command::Query(sql_query))) => {
-                                    query_executor.execute(sql_query.as_str()).expect("no system error");
-                                }
+                        let mut query_executor = QueryExecutor::new(storage.clone(), s)
+                            .with_session_registry(&sessions, application_name.as_str())
+                            .with_database_registry(&databases);
+                        if let Some(user) = user {
+                            query_executor = query_executor.with_user(user.as_str());
+                        }
+                        if let Some(options) = options {
+                            query_executor = query_executor.with_startup_options(options.as_str());
+                        }
+                        if let Some(database) = database {
+                            if !query_executor.connect_to_database(database.as_str()) {
+                                log::debug!("database {:?} does not exist, closing connection", database);
+                                return;
                             }
                         }
+                        log::debug!("ready to handle query");
+
+                        Task::spawn(async move {
+                            let _connected = connected;
+                            let _slot = slot;
+                            loop {
+                                let command = future::race(async { Some(receiver.receive().await) }, async {
+                                    shutdown_signal.recv().await.ok();
+                                    None
+                                })
+                                .await;
+                                let command = match command {
+                                    Some(command) => command,
+                                    None => {
+                                        log::debug!("Closing idle connection on server shutdown");
+                                        sender
+                                            .send(Err(QueryErrorBuilder::new().admin_shutdown().build()))
+                                            .expect("To Send Query Result to Client");
+                                        break;
+                                    }
+                                };
+                                match command {
+                                    Err(e) => {
+                                        log::error!("UNEXPECTED ERROR: {:?}", e);
+                                        state.store(STOPPED, Ordering::SeqCst);
+                                        return;
+                                    }
+                                    Ok(Err(ProtocolError::ConnectionClosedDuringMessage)) => {
+                                        log::warn!("Client closed connection in the middle of a message");
+                                        break;
+                                    }
+                                    Ok(Err(e)) => {
+                                        log::error!("UNEXPECTED ERROR: {:?}", e);
+                                        state.store(STOPPED, Ordering::SeqCst);
+                                        return;
+                                    }
+                                    Ok(Ok(Command::Terminate)) => {
+                                        log::debug!("Closing connection with client");
+                                        break;
+                                    }
+                                    Ok(Ok(Command::Query(sql_query))) => {
+                                        query_executor.execute(sql_query.as_str()).expect("no system error");
+                                    }
+                                    Ok(Ok(Command::Parse {
+                                        statement_name,
+                                        sql,
+                                        param_types,
+                                    })) => {
+                                        query_executor
+                                            .parse(&statement_name, &sql, &param_types)
+                                            .expect("no system error");
+                                    }
+                                    Ok(Ok(Command::Bind {
+                                        portal_name,
+                                        statement_name,
+                                        param_formats,
+                                        params,
+                                        ..
+                                    })) => {
+                                        query_executor
+                                            .bind(&portal_name, &statement_name, &param_formats, &params)
+                                            .expect("no system error");
+                                    }
+                                    Ok(Ok(Command::Execute { portal_name, max_rows })) => {
+                                        query_executor
+                                            .execute_portal(&portal_name, max_rows)
+                                            .expect("no system error");
+                                    }
+                                    Ok(Ok(Command::Sync)) => {}
+                                    Ok(Ok(Command::CopyData(data))) => {
+                                        query_executor.copy_data(&data).expect("no system error");
+                                    }
+                                    Ok(Ok(Command::CopyDone)) => {
+                                        query_executor.copy_done().expect("no system error");
+                                    }
+                                    Ok(Ok(Command::CopyFail(message))) => {
+                                        query_executor.copy_fail(&message).expect("no system error");
+                                    }
+                                }
+                            }
+                        })
+                        .detach();
                     })
                     .detach();
-                })
-                .detach();
+                }
             }
-        }
-    });
+
+            drop(connected);
+            all_disconnected.recv().await.ok();
+        });
+    }
+}
+
+// taken place of a connected client, the place is freed once the client
+// is disconnected
+struct ConnectionSlot(async_channel::Receiver<()>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.try_recv().ok();
+    }
 }
 
 fn pfx_certificate_path() -> PathBuf {
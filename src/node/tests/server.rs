@@ -0,0 +1,257 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use node::node::Server;
+use std::{
+    io::{Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpStream},
+    thread,
+    time::Duration,
+};
+
+const COMMAND_COMPLETE: u8 = b'C';
+const DATA_ROW: u8 = b'D';
+const EMPTY_QUERY_RESPONSE: u8 = b'I';
+const ERROR_RESPONSE: u8 = b'E';
+const READY_FOR_QUERY: u8 = b'Z';
+
+struct Client {
+    stream: TcpStream,
+}
+
+impl Client {
+    fn connect(address: SocketAddr) -> Client {
+        let mut client = Client::open(address);
+        client.start_up("postgres");
+        client.authenticate();
+        client
+    }
+
+    fn open(address: SocketAddr) -> Client {
+        Client {
+            stream: TcpStream::connect(address).expect("server accepts connection"),
+        }
+    }
+
+    fn start_up(&mut self, database: &str) {
+        let mut startup = vec![];
+        startup.extend_from_slice(&0x30000i32.to_be_bytes());
+        for parameter in &["user", "username", "database", database] {
+            startup.extend_from_slice(parameter.as_bytes());
+            startup.push(0);
+        }
+        startup.push(0);
+        self.send(None, &startup);
+    }
+
+    fn authenticate(&mut self) {
+        // authentication request
+        self.receive();
+        self.send(Some(b'p'), b"password\0");
+        self.receive_until_ready();
+    }
+
+    fn terminate(&mut self) {
+        self.send(Some(b'X'), &[]);
+    }
+
+    fn query(&mut self, sql: &str) -> Vec<(u8, Vec<u8>)> {
+        self.send(Some(b'Q'), (sql.to_owned() + "\0").as_bytes());
+        self.receive_until_ready()
+    }
+
+    fn send(&mut self, tag: Option<u8>, body: &[u8]) {
+        let mut message = vec![];
+        message.extend(tag);
+        message.extend_from_slice(&(body.len() as i32 + 4).to_be_bytes());
+        message.extend_from_slice(body);
+        self.stream.write_all(&message).expect("message is sent");
+    }
+
+    fn receive(&mut self) -> (u8, Vec<u8>) {
+        let mut header = [0u8; 5];
+        self.stream.read_exact(&mut header).expect("message header");
+        let len = i32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        let mut body = vec![0u8; len - 4];
+        self.stream.read_exact(&mut body).expect("message body");
+        (header[0], body)
+    }
+
+    fn receive_until_ready(&mut self) -> Vec<(u8, Vec<u8>)> {
+        let mut messages = vec![];
+        loop {
+            let (tag, body) = self.receive();
+            if tag == READY_FOR_QUERY {
+                return messages;
+            }
+            messages.push((tag, body));
+        }
+    }
+}
+
+fn command_tags(messages: &[(u8, Vec<u8>)]) -> Vec<String> {
+    messages
+        .iter()
+        .filter(|(tag, _)| *tag == COMMAND_COMPLETE)
+        .map(|(_, body)| String::from_utf8(body[..body.len() - 1].to_vec()).expect("utf8 command tag"))
+        .collect()
+}
+
+fn data_rows(messages: &[(u8, Vec<u8>)]) -> Vec<Vec<String>> {
+    messages
+        .iter()
+        .filter(|(tag, _)| *tag == DATA_ROW)
+        .map(|(_, body)| {
+            let mut values = vec![];
+            let mut rest = &body[2..];
+            while !rest.is_empty() {
+                let len = i32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+                values.push(String::from_utf8(rest[4..4 + len].to_vec()).expect("utf8 value"));
+                rest = &rest[4 + len..];
+            }
+            values
+        })
+        .collect()
+}
+
+#[test]
+fn create_insert_select_round_trip() {
+    let server = Server::bind((Ipv4Addr::LOCALHOST, 0)).expect("server is bound");
+    let address = server.local_addr().expect("server address");
+    thread::spawn(move || server.run());
+
+    let mut client = Client::connect(address);
+
+    assert_eq!(
+        command_tags(&client.query("create schema schema_name;")),
+        vec!["CREATE SCHEMA".to_owned()]
+    );
+    assert_eq!(
+        command_tags(&client.query("create table schema_name.table_name (column_1 smallint, column_2 integer);")),
+        vec!["CREATE TABLE".to_owned()]
+    );
+    assert_eq!(
+        command_tags(&client.query("insert into schema_name.table_name values (1, 2), (3, 4);")),
+        vec!["INSERT 0 2".to_owned()]
+    );
+
+    let selected = client.query("select * from schema_name.table_name;");
+    assert_eq!(command_tags(&selected), vec!["SELECT 2".to_owned()]);
+    assert_eq!(
+        data_rows(&selected),
+        vec![
+            vec!["1".to_owned(), "2".to_owned()],
+            vec!["3".to_owned(), "4".to_owned()]
+        ]
+    );
+}
+
+#[test]
+fn shutdown_closes_idle_connection() {
+    let server = Server::bind((Ipv4Addr::LOCALHOST, 0)).expect("server is bound");
+    let address = server.local_addr().expect("server address");
+    let shutdown = server.shutdown_handle();
+    let running = thread::spawn(move || server.run());
+
+    let mut client = Client::connect(address);
+    assert_eq!(
+        command_tags(&client.query("create schema schema_name;")),
+        vec!["CREATE SCHEMA".to_owned()]
+    );
+
+    shutdown.shutdown();
+
+    let (tag, body) = client.receive();
+    assert_eq!(tag, ERROR_RESPONSE);
+    assert!(String::from_utf8_lossy(&body).contains("57P01"));
+    assert_eq!(client.stream.read(&mut [0u8; 1]).expect("connection is closed"), 0);
+
+    running.join().expect("server is stopped");
+    assert!(TcpStream::connect(address).is_err());
+}
+
+#[test]
+fn connection_to_unknown_database_is_closed() {
+    let server = Server::bind((Ipv4Addr::LOCALHOST, 0)).expect("server is bound");
+    let address = server.local_addr().expect("server address");
+    thread::spawn(move || server.run());
+
+    let mut client = Client::open(address);
+    client.start_up("unknown");
+    // authentication request
+    client.receive();
+    client.send(Some(b'p'), b"password\0");
+
+    let error = loop {
+        let (tag, body) = client.receive();
+        if tag == ERROR_RESPONSE {
+            break body;
+        }
+    };
+    assert!(String::from_utf8_lossy(&error).contains("3D000"));
+    assert_eq!(client.stream.read(&mut [0u8; 1]).expect("connection is closed"), 0);
+}
+
+#[test]
+fn empty_query_ping() {
+    let server = Server::bind((Ipv4Addr::LOCALHOST, 0)).expect("server is bound");
+    let address = server.local_addr().expect("server address");
+    thread::spawn(move || server.run());
+
+    let mut client = Client::connect(address);
+
+    assert_eq!(client.query(""), vec![(EMPTY_QUERY_RESPONSE, vec![])]);
+}
+
+#[test]
+fn max_connections_bounds_accepted_clients() {
+    let server = Server::bind((Ipv4Addr::LOCALHOST, 0))
+        .expect("server is bound")
+        .with_max_connections(1);
+    let address = server.local_addr().expect("server address");
+    thread::spawn(move || server.run());
+
+    let mut first = Client::connect(address);
+    let mut second = Client::open(address);
+    second.start_up("postgres");
+    second
+        .stream
+        .set_read_timeout(Some(Duration::from_millis(200)))
+        .expect("read timeout is set");
+    assert!(second.stream.read(&mut [0u8; 1]).is_err());
+
+    first.terminate();
+    second.stream.set_read_timeout(None).expect("read timeout is unset");
+    second.authenticate();
+
+    assert_eq!(second.query(""), vec![(EMPTY_QUERY_RESPONSE, vec![])]);
+}
+
+#[test]
+fn ssl_request_is_refused_and_client_continues_in_cleartext() {
+    let server = Server::bind((Ipv4Addr::LOCALHOST, 0)).expect("server is bound");
+    let address = server.local_addr().expect("server address");
+    thread::spawn(move || server.run());
+
+    let mut client = Client::open(address);
+    client.send(None, &80_877_103i32.to_be_bytes());
+    let mut refusal = [0u8; 1];
+    client.stream.read_exact(&mut refusal).expect("ssl refusal");
+    assert_eq!(&refusal, b"N");
+
+    client.start_up("postgres");
+    client.authenticate();
+
+    assert_eq!(client.query(""), vec![(EMPTY_QUERY_RESPONSE, vec![])]);
+}
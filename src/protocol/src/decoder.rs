@@ -0,0 +1,182 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of client messages. Decoders never panic, malformed input is
+//! reported as an `Error`.
+use crate::{
+    messages::FormatCodes, Command, Error, Params, Result, Version, VERSION_1, VERSION_2, VERSION_3, VERSION_CANCEL,
+    VERSION_GSSENC, VERSION_SSL,
+};
+use byteorder::{ByteOrder, NetworkEndian};
+use itertools::Itertools;
+
+const PARSE: u8 = b'P';
+const BIND: u8 = b'B';
+const EXECUTE: u8 = b'E';
+const SYNC: u8 = b'S';
+const COPY_DATA: u8 = b'd';
+const COPY_DONE: u8 = b'c';
+const COPY_FAIL: u8 = b'f';
+const TERMINATE: u8 = b'X';
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ClientHandshake {
+    SslRequest,
+    GssEncryptRequest,
+    Startup(Version, Params),
+}
+
+/// length of a message body given value of message length field, which
+/// includes the field itself
+pub(crate) fn body_len(len: u32) -> Result<usize> {
+    (len as usize).checked_sub(4).ok_or(Error::MalformedMessage)
+}
+
+pub(crate) fn decode_startup(message: &[u8]) -> Result<ClientHandshake> {
+    let mut body = MessageBody { bytes: message };
+    let version = body.read_i32()?;
+    log::debug!("VERSION FOR TEST = {:#?}", version);
+
+    match version {
+        VERSION_1 => Err(Error::UnsupportedVersion),
+        VERSION_2 => Err(Error::UnsupportedVersion),
+        VERSION_3 => {
+            let params = body
+                .bytes
+                .split(|b| *b == 0)
+                .filter(|b| !b.is_empty())
+                .map(|b| std::str::from_utf8(b).map(ToOwned::to_owned))
+                .collect::<std::result::Result<Vec<String>, _>>()
+                .map_err(|_| Error::MalformedMessage)?
+                .into_iter()
+                .tuples()
+                .collect::<Params>();
+            Ok(ClientHandshake::Startup(version, params))
+        }
+        VERSION_CANCEL => Err(Error::UnsupportedVersion),
+        VERSION_GSSENC => Ok(ClientHandshake::GssEncryptRequest),
+        VERSION_SSL => Ok(ClientHandshake::SslRequest),
+        _ => Err(Error::UnrecognizedVersion),
+    }
+}
+
+/// decodes body of a message by its tag, messages with unknown tags are
+/// treated as queries
+pub(crate) fn decode_command(tag: u8, body: &[u8]) -> Result<Command> {
+    match tag {
+        PARSE => decode_parse(body),
+        BIND => decode_bind(body),
+        EXECUTE => decode_execute(body),
+        SYNC => Ok(Command::Sync),
+        COPY_DATA => Ok(Command::CopyData(body.to_vec())),
+        COPY_DONE => Ok(Command::CopyDone),
+        COPY_FAIL => MessageBody { bytes: body }.read_cstr().map(Command::CopyFail),
+        TERMINATE => Ok(Command::Terminate),
+        _ => decode_query(body),
+    }
+}
+
+// reads fields of a client message body one after another
+struct MessageBody<'b> {
+    bytes: &'b [u8],
+}
+
+impl<'b> MessageBody<'b> {
+    fn take(&mut self, len: usize) -> Result<&'b [u8]> {
+        if self.bytes.len() < len {
+            return Err(Error::MalformedMessage);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn read_i16(&mut self) -> Result<i16> {
+        self.take(2).map(NetworkEndian::read_i16)
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        self.take(4).map(NetworkEndian::read_i32)
+    }
+
+    fn read_cstr(&mut self) -> Result<String> {
+        let end = self.bytes.iter().position(|b| *b == 0).ok_or(Error::MalformedMessage)?;
+        let string = self.take(end)?;
+        self.take(1)?;
+        String::from_utf8(string.to_vec()).map_err(|_| Error::QueryIsNotValidUtfString)
+    }
+
+    fn read_count(&mut self) -> Result<usize> {
+        match self.read_i16()? {
+            count if count < 0 => Err(Error::MalformedMessage),
+            count => Ok(count as usize),
+        }
+    }
+}
+
+fn decode_query(body: &[u8]) -> Result<Command> {
+    log::debug!("FOR TEST sql = {:?}", body);
+    let sql = MessageBody { bytes: body }.read_cstr()?;
+    log::debug!("SQL = {}", sql);
+    Ok(Command::Query(sql))
+}
+
+fn decode_parse(body: &[u8]) -> Result<Command> {
+    let mut body = MessageBody { bytes: body };
+    let statement_name = body.read_cstr()?;
+    let sql = body.read_cstr()?;
+    let param_types = (0..body.read_count()?)
+        .map(|_| body.read_i32())
+        .collect::<Result<Vec<i32>>>()?;
+    Ok(Command::Parse {
+        statement_name,
+        sql,
+        param_types,
+    })
+}
+
+fn decode_bind(body: &[u8]) -> Result<Command> {
+    let mut body = MessageBody { bytes: body };
+    let portal_name = body.read_cstr()?;
+    let statement_name = body.read_cstr()?;
+    let param_formats = (0..body.read_count()?)
+        .map(|_| body.read_i16())
+        .collect::<Result<Vec<i16>>>()?;
+    let mut params = vec![];
+    for _ in 0..body.read_count()? {
+        match body.read_i32()? {
+            -1 => params.push(None),
+            len if len < 0 => return Err(Error::MalformedMessage),
+            len => params.push(Some(body.take(len as usize)?.to_vec())),
+        }
+    }
+    let result_formats = (0..body.read_count()?)
+        .map(|_| body.read_i16())
+        .collect::<Result<Vec<i16>>>()?;
+    let result_formats = FormatCodes::from_codes(&result_formats).ok_or(Error::MalformedMessage)?;
+    Ok(Command::Bind {
+        portal_name,
+        statement_name,
+        param_formats,
+        params,
+        result_formats,
+    })
+}
+
+fn decode_execute(body: &[u8]) -> Result<Command> {
+    let mut body = MessageBody { bytes: body };
+    let portal_name = body.read_cstr()?;
+    let max_rows = body.read_i32()?;
+    Ok(Command::Execute { portal_name, max_rows })
+}
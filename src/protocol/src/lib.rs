@@ -17,7 +17,7 @@
 extern crate log;
 
 use crate::{
-    messages::{Encryption, Message},
+    messages::{Encryption, FormatCodes, Message},
     results::QueryResult,
 };
 use async_mutex::Mutex as AsyncMutex;
@@ -29,16 +29,19 @@ use futures_lite::{
     future::block_on,
     io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ErrorKind},
 };
-use itertools::Itertools;
 use std::{
     fs::File,
     net::SocketAddr,
     path::PathBuf,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
 
+mod decoder;
 /// Module contains backend messages that could be send by server implementation
 /// to a client
 pub mod messages;
@@ -78,6 +81,12 @@ pub enum Error {
     UnsupportedRequest,
     /// Indicates that during handshake client sent unrecognized protocol version
     UnrecognizedVersion,
+    /// Indicates that client requested `client_encoding` other than UTF8
+    UnsupportedClientEncoding,
+    /// Indicates that content of client message does not match its type
+    MalformedMessage,
+    /// Indicates that client closed connection in the middle of a message
+    ConnectionClosedDuringMessage,
 }
 
 /// Result of handling incoming bytes from a client
@@ -87,6 +96,46 @@ pub enum Command {
     Query(String),
     /// Client commands to terminate current connection
     Terminate,
+    /// Client prepares a statement of extended query protocol. `param_types`
+    /// are OIDs of `$n` placeholders types, `0` leaves a type unspecified
+    Parse {
+        /// name of prepared statement, empty for unnamed one
+        statement_name: String,
+        /// query text with `$n` placeholders
+        sql: String,
+        /// OIDs of parameters types
+        param_types: Vec<i32>,
+    },
+    /// Client binds parameters values to a prepared statement and creates a
+    /// portal. Format code `0` is for text and `1` is for binary format, no
+    /// codes means all in text, a single code applies to all values
+    Bind {
+        /// name of created portal, empty for unnamed one
+        portal_name: String,
+        /// name of prepared statement
+        statement_name: String,
+        /// format codes of parameters values
+        param_formats: Vec<i16>,
+        /// parameters values, `None` is for NULL
+        params: Vec<Option<Vec<u8>>>,
+        /// formats of result columns
+        result_formats: FormatCodes,
+    },
+    /// Client executes a portal
+    Execute {
+        /// name of portal
+        portal_name: String,
+        /// maximum number of rows to return, `0` is for no limit
+        max_rows: i32,
+    },
+    /// Client ends a sequence of extended query protocol messages
+    Sync,
+    /// Client sends a chunk of data of `COPY ... FROM STDIN`
+    CopyData(Vec<u8>),
+    /// Client completes data of `COPY ... FROM STDIN`
+    CopyDone,
+    /// Client aborts `COPY ... FROM STDIN` with an error message
+    CopyFail(String),
 }
 
 /// Perform `PostgreSql` wire protocol hand shake to establish connection with
@@ -110,15 +159,36 @@ where
         let len = channel
             .read_exact(&mut buffer)
             .await
-            .map(|_| NetworkEndian::read_u32(&buffer) as usize)?;
-        let len = len - 4;
-        let mut buffer = Vec::with_capacity(len);
-        buffer.resize(len, b'0');
+            .map(|_| NetworkEndian::read_u32(&buffer))?;
+        let len = match decoder::body_len(len) {
+            Ok(len) => len,
+            Err(error) => return Ok(Err(error)),
+        };
+        let mut buffer = vec![0u8; len];
         let message = channel.read_exact(&mut buffer).await.map(|_| buffer)?;
         log::debug!("MESSAGE FOR TEST = {:#?}", message);
 
-        match decode_startup(message) {
-            Ok(ClientHandshake::Startup(version, params)) => {
+        match decoder::decode_startup(&message) {
+            Ok(decoder::ClientHandshake::Startup(version, params)) => {
+                if let Some(encoding) = parameter(&params, "client_encoding") {
+                    if !is_utf8(encoding) {
+                        channel
+                            .write_all(
+                                Message::ErrorResponse(
+                                    Some("FATAL"),
+                                    Some("22023"),
+                                    Some(format!(
+                                        "invalid value for parameter \"client_encoding\": \"{}\"",
+                                        encoding
+                                    )),
+                                )
+                                .as_vec()
+                                .as_slice(),
+                            )
+                            .await?;
+                        return Ok(Err(Error::UnsupportedClientEncoding));
+                    }
+                }
                 channel
                     .write_all(Message::AuthenticationCleartextPassword.as_vec().as_slice())
                     .await?;
@@ -130,10 +200,12 @@ where
                 let len = channel
                     .read_exact(&mut buffer)
                     .await
-                    .map(|_| NetworkEndian::read_u32(&buffer) as usize)?;
-                let len = len - 4;
-                let mut buffer = Vec::with_capacity(len);
-                buffer.resize(len, b'0');
+                    .map(|_| NetworkEndian::read_u32(&buffer))?;
+                let len = match decoder::body_len(len) {
+                    Ok(len) => len,
+                    Err(error) => return Ok(Err(error)),
+                };
+                let mut buffer = vec![0u8; len];
                 let _message = channel.read_exact(&mut buffer).await.map(|_| buffer)?;
                 channel.write_all(Message::AuthenticationOk.as_vec().as_slice()).await?;
 
@@ -153,13 +225,22 @@ where
                     )
                     .await?;
 
+                if let Some(application_name) = parameter(&params, "application_name") {
+                    channel
+                        .write_all(
+                            Message::ParameterStatus("application_name".to_owned(), application_name.to_owned())
+                                .as_vec()
+                                .as_slice(),
+                        )
+                        .await?;
+                }
+
                 let channel = Arc::new(AsyncMutex::new(channel));
-                return Ok(Ok((
-                    RequestReceiver::new((version, params.clone()), channel.clone()),
-                    ResponseSender::new((version, params), channel),
-                )));
+                let receiver = RequestReceiver::new((version, params.clone()), channel.clone());
+                let sender = ResponseSender::new((version, params), channel).with_copy_in(receiver.copy_in.clone());
+                return Ok(Ok((receiver, sender)));
             }
-            Ok(ClientHandshake::SslRequest) => {
+            Ok(decoder::ClientHandshake::SslRequest) => {
                 channel = match channel {
                     Channel::Plain(mut channel) if config.ssl_support() => {
                         channel.write_all(Encryption::AcceptSsl.into()).await?;
@@ -171,7 +252,10 @@ where
                     }
                 };
             }
-            Ok(ClientHandshake::GssEncryptRequest) => return Ok(Err(Error::UnsupportedRequest)),
+            // refused client falls back to unencrypted connection or ssl
+            Ok(decoder::ClientHandshake::GssEncryptRequest) => {
+                channel.write_all(Encryption::RejectGss.into()).await?;
+            }
             Err(error) => return Ok(Err(error)),
         }
     }
@@ -192,38 +276,63 @@ where
     }
 }
 
-fn decode_startup(message: Vec<u8>) -> Result<ClientHandshake> {
-    let version = NetworkEndian::read_i32(&message);
-    log::debug!("VERSION FOR TEST = {:#?}", version);
-
-    match version {
-        VERSION_1 => Err(Error::UnsupportedVersion),
-        VERSION_2 => Err(Error::UnsupportedVersion),
-        VERSION_3 => {
-            let params = message[4..]
-                .split(|b| *b == 0)
-                .filter(|b| !b.is_empty())
-                .map(|b| std::str::from_utf8(b).unwrap().to_owned())
-                .tuples()
-                .collect::<Params>();
-            Ok(ClientHandshake::Startup(version, params))
-        }
-        VERSION_CANCEL => Err(Error::UnsupportedVersion),
-        VERSION_GSSENC => Ok(ClientHandshake::GssEncryptRequest),
-        VERSION_SSL => Ok(ClientHandshake::SslRequest),
-        _ => Err(Error::UnrecognizedVersion),
-    }
+fn parameter<'p>(params: &'p [(String, String)], name: &str) -> Option<&'p str> {
+    params
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+// values accepted by PostgreSQL as names of UTF8 encoding
+fn is_utf8(encoding: &str) -> bool {
+    ["utf8", "utf-8", "unicode"]
+        .iter()
+        .any(|name| encoding.eq_ignore_ascii_case(name))
 }
 
 struct RequestReceiver<RW: AsyncRead + AsyncWrite + Unpin> {
     properties: (Version, Params),
     channel: Arc<AsyncMutex<Channel<RW>>>,
+    // messages of extended query protocol are answered with `ReadyForQuery`
+    // only after `Sync`
+    ready_for_query: bool,
+    // set by sender when server waits for data of `COPY ... FROM STDIN`,
+    // no `ReadyForQuery` is sent until `CopyDone` or `CopyFail`
+    copy_in: Arc<AtomicBool>,
 }
 
 impl<RW: AsyncRead + AsyncWrite + Unpin> RequestReceiver<RW> {
+    // reads length and body of a message which tag is already read
+    async fn read_message(&mut self) -> io::Result<Result<Vec<u8>>> {
+        let mut channel = self.channel.lock().await;
+        let mut buffer = [0u8; 4];
+        let len = match channel.read_exact(&mut buffer).await {
+            Ok(()) => NetworkEndian::read_u32(&buffer),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => {
+                return Ok(Err(Error::ConnectionClosedDuringMessage))
+            }
+            Err(error) => return Err(error),
+        };
+        let len = match decoder::body_len(len) {
+            Ok(len) => len,
+            Err(error) => return Ok(Err(error)),
+        };
+        let mut buffer = vec![0u8; len];
+        match channel.read_exact(&mut buffer).await {
+            Ok(()) => Ok(Ok(buffer)),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(Err(Error::ConnectionClosedDuringMessage)),
+            Err(error) => Err(error),
+        }
+    }
+
     /// Creates new Connection with properties and read-write socket
     pub(crate) fn new(properties: (Version, Params), channel: Arc<AsyncMutex<Channel<RW>>>) -> RequestReceiver<RW> {
-        RequestReceiver { properties, channel }
+        RequestReceiver {
+            properties,
+            channel,
+            ready_for_query: true,
+            copy_in: Arc::new(AtomicBool::new(false)),
+        }
     }
 
     /// connection properties tuple
@@ -235,12 +344,14 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> RequestReceiver<RW> {
 #[async_trait]
 impl<RW: AsyncRead + AsyncWrite + Unpin> Receiver for RequestReceiver<RW> {
     async fn receive(&mut self) -> io::Result<Result<Command>> {
-        log::debug!("send ready for query message");
-        self.channel
-            .lock()
-            .await
-            .write_all(Message::ReadyForQuery.as_vec().as_slice())
-            .await?;
+        if self.ready_for_query && !self.copy_in.load(Ordering::SeqCst) {
+            log::debug!("send ready for query message");
+            self.channel
+                .lock()
+                .await
+                .write_all(Message::ReadyForQuery.as_vec().as_slice())
+                .await?;
+        }
         let mut buffer = [0u8; 1];
         let tag = self
             .channel
@@ -251,33 +362,40 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> Receiver for RequestReceiver<RW> {
             .map(|_| buffer[0])?;
         log::debug!("tag {:?}", tag);
         if b'X' == tag {
-            Ok(Ok(Command::Terminate))
-        } else {
-            let mut buffer = [0u8; 4];
-            let len = self
-                .channel
-                .lock()
-                .await
-                .read_exact(&mut buffer)
-                .await
-                .map(|_| NetworkEndian::read_u32(&buffer))?;
-            let mut buffer = Vec::with_capacity(len as usize - 4);
-            buffer.resize(len as usize - 4, b'0');
-            let sql_buff = self
-                .channel
-                .lock()
-                .await
-                .read_exact(&mut buffer)
-                .await
-                .map(|_| buffer)?;
-            log::debug!("FOR TEST sql = {:?}", sql_buff);
-            let sql = match String::from_utf8(sql_buff[..sql_buff.len() - 1].to_vec()) {
-                Ok(sql) => sql,
-                Err(_e) => return Ok(Err(Error::QueryIsNotValidUtfString)),
-            };
-            log::debug!("SQL = {}", sql);
-            Ok(Ok(Command::Query(sql)))
+            return Ok(Ok(Command::Terminate));
+        }
+        let body = match self.read_message().await? {
+            Ok(body) => body,
+            Err(error) => return Ok(Err(error)),
+        };
+        let command = decoder::decode_command(tag, &body);
+        if let Ok(Command::CopyDone) | Ok(Command::CopyFail(_)) = command {
+            self.copy_in.store(false, Ordering::SeqCst);
         }
+        self.ready_for_query = match command {
+            Ok(Command::Parse { .. }) | Ok(Command::Bind { .. }) | Ok(Command::Execute { .. }) => false,
+            // COPY started by a simple query is completed with `ReadyForQuery`
+            // and by extended query protocol only after `Sync`
+            Ok(Command::CopyData(_)) | Ok(Command::CopyDone) | Ok(Command::CopyFail(_)) => self.ready_for_query,
+            _ => true,
+        };
+        Ok(command)
+    }
+
+    fn application_name(&self) -> Option<&str> {
+        parameter(&self.properties.1, "application_name")
+    }
+
+    fn user(&self) -> Option<&str> {
+        parameter(&self.properties.1, "user")
+    }
+
+    fn options(&self) -> Option<&str> {
+        parameter(&self.properties.1, "options")
+    }
+
+    fn database(&self) -> Option<&str> {
+        parameter(&self.properties.1, "database").or_else(|| self.user())
     }
 }
 
@@ -286,11 +404,30 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> Receiver for RequestReceiver<RW> {
 pub trait Receiver: Send + Sync {
     /// receives and decodes a command from remote client
     async fn receive(&mut self) -> io::Result<Result<Command>>;
+
+    /// name of the client application sent in startup parameters, if any
+    fn application_name(&self) -> Option<&str>;
+
+    /// name of the database user sent in startup parameters, if any
+    fn user(&self) -> Option<&str>;
+
+    /// command-line style settings sent in `options` startup parameter, if any
+    fn options(&self) -> Option<&str>;
+
+    /// name of the database sent in startup parameters, as in PostgreSQL it
+    /// is the user name if the parameter is not given
+    fn database(&self) -> Option<&str>;
 }
 
+/// encoded messages are accumulated up to this number of bytes before they are
+/// written to a socket, so a large result set is not written row by row
+const WRITE_BUFFER_SIZE: usize = 8 * 1024;
+
 struct ResponseSender<RW: AsyncRead + AsyncWrite + Unpin> {
     properties: (Version, Params),
     channel: Arc<AsyncMutex<Channel<RW>>>,
+    // shared with receiver, see `RequestReceiver::copy_in`
+    copy_in: Arc<AtomicBool>,
 }
 
 impl<RW: AsyncRead + AsyncWrite + Unpin> Clone for ResponseSender<RW> {
@@ -298,6 +435,7 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> Clone for ResponseSender<RW> {
         Self {
             properties: (self.properties.0, self.properties.1.clone()),
             channel: self.channel.clone(),
+            copy_in: self.copy_in.clone(),
         }
     }
 }
@@ -305,7 +443,16 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> Clone for ResponseSender<RW> {
 impl<RW: AsyncRead + AsyncWrite + Unpin> ResponseSender<RW> {
     /// Creates new Connection with properties and read-write socket
     pub(crate) fn new(properties: (Version, Params), channel: Arc<AsyncMutex<Channel<RW>>>) -> ResponseSender<RW> {
-        ResponseSender { properties, channel }
+        ResponseSender {
+            properties,
+            channel,
+            copy_in: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// shares state of `COPY ... FROM STDIN` with a receiver of the connection
+    pub(crate) fn with_copy_in(self, copy_in: Arc<AtomicBool>) -> ResponseSender<RW> {
+        ResponseSender { copy_in, ..self }
     }
 }
 
@@ -313,14 +460,21 @@ impl<RW: AsyncRead + AsyncWrite + Unpin> Sender for ResponseSender<RW> {
     fn send(&self, query_result: QueryResult) -> io::Result<()> {
         block_on(async {
             let messages: Vec<Message> = query_result.map_or_else(|event| event.into(), |err| err.into());
+            let mut channel = self.channel.lock().await;
+            let mut buffer = Vec::with_capacity(WRITE_BUFFER_SIZE);
             for message in messages {
                 log::debug!("{:?}", message);
-                self.channel
-                    .lock()
-                    .await
-                    .write_all(message.as_vec().as_slice())
-                    .await
-                    .expect("OK");
+                if let Message::CopyInResponse(_) = message {
+                    self.copy_in.store(true, Ordering::SeqCst);
+                }
+                buffer.extend_from_slice(message.as_vec().as_slice());
+                if buffer.len() >= WRITE_BUFFER_SIZE {
+                    channel.write_all(buffer.as_slice()).await.expect("OK");
+                    buffer.clear();
+                }
+            }
+            if !buffer.is_empty() {
+                channel.write_all(buffer.as_slice()).await.expect("OK");
             }
             log::debug!("end of the command is sent");
         });
@@ -418,11 +572,5 @@ impl ProtocolConfiguration {
     }
 }
 
-enum ClientHandshake {
-    SslRequest,
-    GssEncryptRequest,
-    Startup(Version, Params),
-}
-
 #[cfg(test)]
 mod tests;
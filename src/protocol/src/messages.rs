@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::sql_types::{PostgreSqlType, TypeInfo};
+
 const COMMAND_COMPLETE: u8 = b'C';
+const COPY_DATA: u8 = b'd';
+const COPY_DONE: u8 = b'c';
+const COPY_IN_RESPONSE: u8 = b'G';
+const COPY_OUT_RESPONSE: u8 = b'H';
 const DATA_ROW: u8 = b'D';
 const ERROR_RESPONSE: u8 = b'E';
 const SEVERITY: u8 = b'S';
@@ -20,6 +26,8 @@ const CODE: u8 = b'C';
 const MESSAGE: u8 = b'M';
 const EMPTY_QUERY_RESPONSE: u8 = b'I';
 const NOTICE_RESPONSE: u8 = b'N';
+const PARSE_COMPLETE: u8 = b'1';
+const BIND_COMPLETE: u8 = b'2';
 const AUTHENTICATION: u8 = b'R';
 const PARAMETER_STATUS: u8 = b'S';
 const ROW_DESCRIPTION: u8 = b'T';
@@ -28,6 +36,7 @@ const READY_FOR_QUERY: u8 = b'Z';
 pub(crate) enum Encryption {
     AcceptSsl,
     RejectSsl,
+    RejectGss,
 }
 
 impl Into<&'_ [u8]> for Encryption {
@@ -35,6 +44,7 @@ impl Into<&'_ [u8]> for Encryption {
         match self {
             Self::AcceptSsl => &[b'S'],
             Self::RejectSsl => &[b'N'],
+            Self::RejectGss => b"N",
         }
     }
 }
@@ -45,8 +55,9 @@ impl Into<&'_ [u8]> for Encryption {
 #[derive(Debug, PartialEq)]
 pub enum Message {
     /// A warning message has been issued. The frontend should display the message
-    /// but continue listening for ReadyForQuery or ErrorResponse.
-    NoticeResponse,
+    /// but continue listening for ReadyForQuery or ErrorResponse. Contains
+    /// (`Severity`, `Code`, `Message`) all of them are optional
+    NoticeResponse(Option<&'static str>, Option<&'static str>, Option<String>),
     /// The frontend must now send a PasswordMessage containing the password in
     /// clear-text form. If this is the correct password, the server responds
     /// with an AuthenticationOk, otherwise it responds with an ErrorResponse.
@@ -65,8 +76,10 @@ pub enum Message {
     AuthenticationOk,
     /// Start-up is completed. The frontend can now issue commands.
     ReadyForQuery,
-    /// One of the set of rows returned by a SELECT, FETCH, etc query.
-    DataRow(Vec<String>),
+    /// One of the set of rows returned by a SELECT, FETCH, etc query. Contains
+    /// values in text format, `None` for NULL, types of the values and formats
+    /// they are sent in
+    DataRow(Vec<Option<String>>, Vec<PostgreSqlType>, FormatCodes),
     /// Indicates that rows are about to be returned in response to a SELECT, FETCH,
     /// etc query. The contents of this message describe the column layout of
     /// the rows. This will be followed by a DataRow message for each row being
@@ -75,7 +88,6 @@ pub enum Message {
     /// An SQL command completed normally.
     CommandComplete(String),
     /// An empty query string was recognized.
-    #[allow(dead_code)]
     EmptyQueryResponse,
     /// An error has occurred. Contains (`Severity`, `Error Code`, `Error Message`)
     /// all of them are optional
@@ -86,22 +98,48 @@ pub enum Message {
     /// see https://www.postgresql.org/docs/12/protocol-flow.html#PROTOCOL-ASYNC
     /// 3rd and 4th paragraph
     ParameterStatus(String, String),
+    /// A Parse command of extended query protocol is completed.
+    ParseComplete,
+    /// A Bind command of extended query protocol is completed.
+    BindComplete,
+    /// The backend is ready to copy data from the frontend. Contains number of
+    /// columns, all of them are in text format
+    CopyInResponse(i16),
+    /// The backend is ready to copy data to the frontend. Contains number of
+    /// columns, all of them are in text format
+    CopyOutResponse(i16),
+    /// A chunk of data of COPY operation, a row in text format
+    CopyData(Vec<u8>),
+    /// COPY data stream is completed
+    CopyDone,
 }
 
 impl Message {
     /// returns binary representation of a backend message
     pub fn as_vec(&self) -> Vec<u8> {
         match self {
-            Message::NoticeResponse => vec![NOTICE_RESPONSE],
             Message::AuthenticationCleartextPassword => vec![AUTHENTICATION, 0, 0, 0, 8, 0, 0, 0, 3],
             Message::AuthenticationMD5Password => vec![AUTHENTICATION, 0, 0, 0, 12, 0, 0, 0, 5, 1, 1, 1, 1],
             Message::AuthenticationOk => vec![AUTHENTICATION, 0, 0, 0, 8, 0, 0, 0, 0],
             Message::ReadyForQuery => vec![READY_FOR_QUERY, 0, 0, 0, 5, EMPTY_QUERY_RESPONSE],
-            Message::DataRow(row) => {
+            Message::DataRow(row, types, formats) => {
                 let mut row_buff = Vec::new();
-                for field in row.iter() {
+                for (index, field) in row.iter().enumerate() {
+                    // NULL is sent as a value of `-1` length without any bytes
+                    let field = match field {
+                        Some(field) => field,
+                        None => {
+                            row_buff.extend_from_slice(&(-1i32).to_be_bytes());
+                            continue;
+                        }
+                    };
+                    let binary = types
+                        .get(index)
+                        .filter(|pg_type| formats.column_format(index, **pg_type) == FormatCode::Binary)
+                        .and_then(|pg_type| binary_value(field, *pg_type));
+                    let field = binary.unwrap_or_else(|| field.as_bytes().to_vec());
                     row_buff.extend_from_slice(&(field.len() as i32).to_be_bytes());
-                    row_buff.extend_from_slice(field.as_str().as_bytes());
+                    row_buff.extend_from_slice(&field);
                 }
                 let mut len_buff = Vec::new();
                 len_buff.extend_from_slice(&[DATA_ROW]);
@@ -119,8 +157,8 @@ impl Message {
                     buff.extend_from_slice(&(0i16).to_be_bytes()); // column id
                     buff.extend_from_slice(&field.type_id.to_be_bytes());
                     buff.extend_from_slice(&field.type_size.to_be_bytes());
-                    buff.extend_from_slice(&(-1i32).to_be_bytes()); // type modifier
-                    buff.extend_from_slice(&0i16.to_be_bytes());
+                    buff.extend_from_slice(&field.type_modifier.to_be_bytes());
+                    buff.extend_from_slice(&field.format.code().to_be_bytes());
                 }
                 let mut len_buff = Vec::new();
                 len_buff.extend_from_slice(&[ROW_DESCRIPTION]);
@@ -139,28 +177,10 @@ impl Message {
             }
             Message::EmptyQueryResponse => vec![EMPTY_QUERY_RESPONSE, 0, 0, 0, 4],
             Message::ErrorResponse(severity, code, message) => {
-                let mut error_response_buff = Vec::new();
-                error_response_buff.extend_from_slice(&[ERROR_RESPONSE]);
-                let mut message_buff = Vec::new();
-                if let Some(severity) = severity.as_ref() {
-                    message_buff.extend_from_slice(&[SEVERITY]);
-                    message_buff.extend_from_slice(severity.as_bytes());
-                    message_buff.extend_from_slice(&[0]);
-                }
-                if let Some(code) = code.as_ref() {
-                    message_buff.extend_from_slice(&[CODE]);
-                    message_buff.extend_from_slice(code.as_bytes());
-                    message_buff.extend_from_slice(&[0]);
-                }
-                if let Some(message) = message.as_ref() {
-                    message_buff.extend_from_slice(&[MESSAGE]);
-                    message_buff.extend_from_slice(message.as_bytes());
-                    message_buff.extend_from_slice(&[0]);
-                }
-                error_response_buff.extend_from_slice(&(message_buff.len() as i32 + 4 + 1).to_be_bytes());
-                error_response_buff.extend_from_slice(message_buff.as_ref());
-                error_response_buff.extend_from_slice(&[0]);
-                error_response_buff.to_vec()
+                response_with_fields(ERROR_RESPONSE, *severity, *code, message.as_deref())
+            }
+            Message::NoticeResponse(severity, code, message) => {
+                response_with_fields(NOTICE_RESPONSE, *severity, *code, message.as_deref())
             }
             Message::ParameterStatus(name, value) => {
                 let mut parameter_status_buff = Vec::new();
@@ -174,10 +194,68 @@ impl Message {
                 parameter_status_buff.extend_from_slice(parameters.as_ref());
                 parameter_status_buff
             }
+            Message::ParseComplete => vec![PARSE_COMPLETE, 0, 0, 0, 4],
+            Message::BindComplete => vec![BIND_COMPLETE, 0, 0, 0, 4],
+            Message::CopyInResponse(columns) => {
+                let mut buff = Vec::new();
+                buff.extend_from_slice(&[COPY_IN_RESPONSE]);
+                buff.extend_from_slice(&(4 + 1 + 2 + 2 * *columns as i32).to_be_bytes());
+                buff.extend_from_slice(&[0]); // overall text format
+                buff.extend_from_slice(&columns.to_be_bytes());
+                for _ in 0..*columns {
+                    buff.extend_from_slice(&0i16.to_be_bytes());
+                }
+                buff
+            }
+            Message::CopyOutResponse(columns) => {
+                let mut buff = Vec::new();
+                buff.extend_from_slice(&[COPY_OUT_RESPONSE]);
+                buff.extend_from_slice(&(4 + 1 + 2 + 2 * *columns as i32).to_be_bytes());
+                buff.extend_from_slice(&[0]); // overall text format
+                buff.extend_from_slice(&columns.to_be_bytes());
+                for _ in 0..*columns {
+                    buff.extend_from_slice(&0i16.to_be_bytes());
+                }
+                buff
+            }
+            Message::CopyData(data) => {
+                let mut buff = Vec::new();
+                buff.extend_from_slice(&[COPY_DATA]);
+                buff.extend_from_slice(&(4 + data.len() as i32).to_be_bytes());
+                buff.extend_from_slice(data);
+                buff
+            }
+            Message::CopyDone => vec![COPY_DONE, 0, 0, 0, 4],
         }
     }
 }
 
+// `ErrorResponse` and `NoticeResponse` share the same layout of fields
+fn response_with_fields(tag: u8, severity: Option<&str>, code: Option<&str>, message: Option<&str>) -> Vec<u8> {
+    let mut message_buff = Vec::new();
+    if let Some(severity) = severity {
+        message_buff.extend_from_slice(&[SEVERITY]);
+        message_buff.extend_from_slice(severity.as_bytes());
+        message_buff.extend_from_slice(&[0]);
+    }
+    if let Some(code) = code {
+        message_buff.extend_from_slice(&[CODE]);
+        message_buff.extend_from_slice(code.as_bytes());
+        message_buff.extend_from_slice(&[0]);
+    }
+    if let Some(message) = message {
+        message_buff.extend_from_slice(&[MESSAGE]);
+        message_buff.extend_from_slice(message.as_bytes());
+        message_buff.extend_from_slice(&[0]);
+    }
+    let mut response_buff = Vec::new();
+    response_buff.extend_from_slice(&[tag]);
+    response_buff.extend_from_slice(&(message_buff.len() as i32 + 4 + 1).to_be_bytes());
+    response_buff.extend_from_slice(message_buff.as_ref());
+    response_buff.extend_from_slice(&[0]);
+    response_buff
+}
+
 /// Struct description of metadata that describes how client should interpret
 /// outgoing selected data
 #[derive(Clone, Debug, PartialEq)]
@@ -188,17 +266,150 @@ pub struct ColumnMetadata {
     pub type_id: i32,
     /// PostgreSQL data type size
     pub type_size: i16,
+    /// PostgreSQL data type modifier
+    pub type_modifier: i32,
+    /// format values of the column are sent in
+    pub format: FormatCode,
 }
 
 impl ColumnMetadata {
-    /// Creates new column metadata
+    /// Creates new column metadata of a type without modifier
     pub fn new(name: String, type_id: i32, type_size: i16) -> Self {
         Self {
             name,
             type_id,
             type_size,
+            type_modifier: -1,
+            format: FormatCode::Text,
         }
     }
+
+    /// Creates new column metadata described by type catalog values
+    pub fn of_type(name: String, pg_type: PostgreSqlType) -> Self {
+        let TypeInfo { oid, size, modifier } = pg_type.type_info();
+        Self {
+            name,
+            type_id: oid,
+            type_size: size,
+            type_modifier: modifier,
+            format: FormatCode::Text,
+        }
+    }
+
+    /// Sets format values of the column are sent in
+    pub fn with_format(mut self, format: FormatCode) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// Format of values exchanged with a client, code `0` is for text and `1` is
+/// for binary format
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FormatCode {
+    /// text format, as values are written in queries
+    Text,
+    /// binary format, e.g. integers as big-endian bytes
+    Binary,
+}
+
+impl FormatCode {
+    /// Format of a code, `None` if the code is neither `0` nor `1`
+    pub fn from_code(code: i16) -> Option<Self> {
+        match code {
+            0 => Some(FormatCode::Text),
+            1 => Some(FormatCode::Binary),
+            _ => None,
+        }
+    }
+
+    /// Code of the format sent over the wire
+    pub fn code(self) -> i16 {
+        match self {
+            FormatCode::Text => 0,
+            FormatCode::Binary => 1,
+        }
+    }
+}
+
+/// Formats of result columns requested by a client, either the same format
+/// for all columns or a format of every column
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatCodes {
+    /// all columns are in the same format
+    All(FormatCode),
+    /// format of every column by its position
+    PerColumn(Vec<FormatCode>),
+}
+
+impl Default for FormatCodes {
+    fn default() -> Self {
+        FormatCodes::All(FormatCode::Text)
+    }
+}
+
+impl FormatCodes {
+    /// Formats of wire codes, no codes means all columns are in text format
+    /// and a single code applies to all columns. `None` if there is an
+    /// unknown code
+    pub fn from_codes(codes: &[i16]) -> Option<Self> {
+        match codes {
+            [] => Some(FormatCodes::default()),
+            [code] => FormatCode::from_code(*code).map(FormatCodes::All),
+            codes => codes
+                .iter()
+                .map(|code| FormatCode::from_code(*code))
+                .collect::<Option<Vec<FormatCode>>>()
+                .map(FormatCodes::PerColumn),
+        }
+    }
+
+    /// Format values of a column are sent in. Types that don't have binary
+    /// representation are always sent in text format
+    pub fn column_format(&self, column: usize, pg_type: PostgreSqlType) -> FormatCode {
+        let format = match self {
+            FormatCodes::All(format) => *format,
+            FormatCodes::PerColumn(formats) => formats.get(column).copied().unwrap_or(FormatCode::Text),
+        };
+        match format {
+            FormatCode::Binary if has_binary_format(pg_type) => FormatCode::Binary,
+            _ => FormatCode::Text,
+        }
+    }
+}
+
+fn has_binary_format(pg_type: PostgreSqlType) -> bool {
+    matches!(
+        pg_type,
+        PostgreSqlType::Bool
+            | PostgreSqlType::SmallInt
+            | PostgreSqlType::Integer
+            | PostgreSqlType::BigInt
+            | PostgreSqlType::Real
+            | PostgreSqlType::DoublePrecision
+            | PostgreSqlType::Char
+            | PostgreSqlType::VarChar
+            | PostgreSqlType::Json
+    )
+}
+
+// binary representation of a value given in text format, `None` if the value
+// can't be represented in binary format of the type
+fn binary_value(value: &str, pg_type: PostgreSqlType) -> Option<Vec<u8>> {
+    match pg_type {
+        PostgreSqlType::Bool => match value {
+            "t" | "true" => Some(vec![1]),
+            "f" | "false" => Some(vec![0]),
+            _ => None,
+        },
+        PostgreSqlType::SmallInt => value.parse::<i16>().ok().map(|v| v.to_be_bytes().to_vec()),
+        PostgreSqlType::Integer => value.parse::<i32>().ok().map(|v| v.to_be_bytes().to_vec()),
+        PostgreSqlType::BigInt => value.parse::<i64>().ok().map(|v| v.to_be_bytes().to_vec()),
+        PostgreSqlType::Real => value.parse::<f32>().ok().map(|v| v.to_be_bytes().to_vec()),
+        PostgreSqlType::DoublePrecision => value.parse::<f64>().ok().map(|v| v.to_be_bytes().to_vec()),
+        PostgreSqlType::Char | PostgreSqlType::VarChar | PostgreSqlType::Json => Some(value.as_bytes().to_vec()),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -207,7 +418,25 @@ mod serialized_messages {
 
     #[test]
     fn notice() {
-        assert_eq!(Message::NoticeResponse.as_vec(), vec![NOTICE_RESPONSE]);
+        assert_eq!(
+            Message::NoticeResponse(Some("NOTICE"), None, None).as_vec(),
+            vec![
+                NOTICE_RESPONSE,
+                0,
+                0,
+                0,
+                13,
+                SEVERITY,
+                b'N',
+                b'O',
+                b'T',
+                b'I',
+                b'C',
+                b'E',
+                0,
+                0
+            ]
+        );
     }
 
     #[test]
@@ -234,6 +463,45 @@ mod serialized_messages {
         )
     }
 
+    #[test]
+    fn parse_complete() {
+        assert_eq!(Message::ParseComplete.as_vec(), vec![PARSE_COMPLETE, 0, 0, 0, 4])
+    }
+
+    #[test]
+    fn bind_complete() {
+        assert_eq!(Message::BindComplete.as_vec(), vec![BIND_COMPLETE, 0, 0, 0, 4])
+    }
+
+    #[test]
+    fn copy_in_response() {
+        assert_eq!(
+            Message::CopyInResponse(2).as_vec(),
+            vec![COPY_IN_RESPONSE, 0, 0, 0, 11, 0, 0, 2, 0, 0, 0, 0]
+        )
+    }
+
+    #[test]
+    fn copy_out_response() {
+        assert_eq!(
+            Message::CopyOutResponse(2).as_vec(),
+            vec![COPY_OUT_RESPONSE, 0, 0, 0, 11, 0, 0, 2, 0, 0, 0, 0]
+        )
+    }
+
+    #[test]
+    fn copy_data() {
+        assert_eq!(
+            Message::CopyData(b"1\ta\n".to_vec()).as_vec(),
+            vec![COPY_DATA, 0, 0, 0, 8, b'1', b'\t', b'a', b'\n']
+        )
+    }
+
+    #[test]
+    fn copy_done() {
+        assert_eq!(Message::CopyDone.as_vec(), vec![COPY_DONE, 0, 0, 0, 4])
+    }
+
     #[test]
     fn parameter_status() {
         assert_eq!(
@@ -280,11 +548,71 @@ mod serialized_messages {
     #[test]
     fn data_row() {
         assert_eq!(
-            Message::DataRow(vec!["1".to_owned(), "2".to_owned(), "3".to_owned()]).as_vec(),
+            Message::DataRow(
+                vec![Some("1".to_owned()), Some("2".to_owned()), Some("3".to_owned())],
+                vec![PostgreSqlType::SmallInt; 3],
+                FormatCodes::default()
+            )
+            .as_vec(),
             vec![DATA_ROW, 0, 0, 0, 21, 0, 3, 0, 0, 0, 1, 49, 0, 0, 0, 1, 50, 0, 0, 0, 1, 51]
         )
     }
 
+    #[test]
+    fn data_row_with_null() {
+        assert_eq!(
+            Message::DataRow(
+                vec![Some("1".to_owned()), None],
+                vec![PostgreSqlType::SmallInt; 2],
+                FormatCodes::default()
+            )
+            .as_vec(),
+            vec![DATA_ROW, 0, 0, 0, 15, 0, 2, 0, 0, 0, 1, 49, 255, 255, 255, 255]
+        )
+    }
+
+    #[test]
+    fn data_row_in_mixed_formats() {
+        assert_eq!(
+            Message::DataRow(
+                vec![Some("1".to_owned()), Some("2".to_owned())],
+                vec![PostgreSqlType::SmallInt, PostgreSqlType::Integer],
+                FormatCodes::PerColumn(vec![FormatCode::Text, FormatCode::Binary])
+            )
+            .as_vec(),
+            vec![DATA_ROW, 0, 0, 0, 19, 0, 2, 0, 0, 0, 1, 49, 0, 0, 0, 4, 0, 0, 0, 2]
+        )
+    }
+
+    #[test]
+    fn data_row_in_binary_format() {
+        assert_eq!(
+            Message::DataRow(
+                vec![Some("t".to_owned()), Some("-2".to_owned()), Some("ab".to_owned())],
+                vec![PostgreSqlType::Bool, PostgreSqlType::BigInt, PostgreSqlType::VarChar],
+                FormatCodes::All(FormatCode::Binary)
+            )
+            .as_vec(),
+            vec![
+                DATA_ROW, 0, 0, 0, 29, 0, 3, 0, 0, 0, 1, 1, 0, 0, 0, 8, 255, 255, 255, 255, 255, 255, 255, 254, 0, 0,
+                0, 2, 97, 98
+            ]
+        )
+    }
+
+    #[test]
+    fn types_without_binary_format_are_sent_in_text() {
+        assert_eq!(
+            Message::DataRow(
+                vec![Some("1.5".to_owned())],
+                vec![PostgreSqlType::Decimal],
+                FormatCodes::All(FormatCode::Binary)
+            )
+            .as_vec(),
+            vec![DATA_ROW, 0, 0, 0, 13, 0, 1, 0, 0, 0, 3, 49, 46, 53]
+        )
+    }
+
     #[test]
     fn row_description() {
         assert_eq!(
@@ -346,3 +674,41 @@ mod serialized_messages {
         )
     }
 }
+
+#[cfg(test)]
+mod format_codes {
+    use super::*;
+
+    #[test]
+    fn no_codes_mean_text_for_all_columns() {
+        assert_eq!(FormatCodes::from_codes(&[]), Some(FormatCodes::All(FormatCode::Text)));
+    }
+
+    #[test]
+    fn single_code_applies_to_all_columns() {
+        let formats = FormatCodes::from_codes(&[1]).expect("valid codes");
+
+        assert_eq!(formats.column_format(0, PostgreSqlType::Integer), FormatCode::Binary);
+        assert_eq!(formats.column_format(5, PostgreSqlType::Integer), FormatCode::Binary);
+    }
+
+    #[test]
+    fn code_per_column() {
+        let formats = FormatCodes::from_codes(&[1, 0]).expect("valid codes");
+
+        assert_eq!(formats.column_format(0, PostgreSqlType::Integer), FormatCode::Binary);
+        assert_eq!(formats.column_format(1, PostgreSqlType::Integer), FormatCode::Text);
+    }
+
+    #[test]
+    fn unknown_code() {
+        assert_eq!(FormatCodes::from_codes(&[0, 2]), None);
+    }
+
+    #[test]
+    fn types_without_binary_representation_are_in_text() {
+        let formats = FormatCodes::All(FormatCode::Binary);
+
+        assert_eq!(formats.column_format(0, PostgreSqlType::Date), FormatCode::Text);
+    }
+}
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    messages::{ColumnMetadata, Message},
+    messages::{ColumnMetadata, FormatCodes, Message},
     sql_types::PostgreSqlType,
 };
 use std::fmt::{self, Display, Formatter};
@@ -21,11 +21,15 @@ use std::fmt::{self, Display, Formatter};
 /// Represents result of SQL query execution
 pub type QueryResult = std::result::Result<QueryEvent, QueryError>;
 /// Represents selected data from tables
-pub type Projection = (Vec<(String, PostgreSqlType)>, Vec<Vec<String>>);
+pub type Projection = (Vec<(String, PostgreSqlType)>, Vec<Vec<Option<String>>>);
 
 /// Represents successful events that can happen in server backend
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum QueryEvent {
+    /// Database successfully created
+    DatabaseCreated,
+    /// Database successfully dropped
+    DatabaseDropped,
     /// Schema successfully created
     SchemaCreated,
     /// Schema successfully dropped
@@ -34,47 +38,128 @@ pub enum QueryEvent {
     TableCreated,
     /// Table successfully dropped
     TableDropped,
+    /// Table successfully altered
+    TableAltered,
+    /// Sequence successfully created
+    SequenceCreated,
+    /// Table storage successfully compacted
+    TableVacuumed,
+    /// Privileges successfully granted
+    PrivilegesGranted,
+    /// Privileges successfully revoked
+    PrivilegesRevoked,
     /// Variable successfully set
     VariableSet,
+    /// Variable is reset to its default value
+    VariableReset,
     /// Transaction is started
     TransactionStarted,
+    /// Transaction is committed
+    TransactionCommitted,
+    /// Transaction is rolled back
+    TransactionRolledBack,
+    /// Query string contains no statements
+    QueryIsEmpty,
     /// Number of records inserted into a table
     RecordsInserted(usize),
     /// Records selected from database
     RecordsSelected(Projection),
+    /// Records inserted by `INSERT ... RETURNING` along with values of its
+    /// returning list
+    RecordsInsertedReturning(Projection),
     /// Number of records updated into a table
     RecordsUpdated(usize),
     /// Number of records deleted into a table
     RecordsDeleted(usize),
+    /// Number of columns and table records encoded as lines of COPY text format
+    RecordsCopiedOut(usize, Vec<String>),
+    /// Server waits for data of `COPY ... FROM STDIN` with the number of
+    /// copied columns
+    CopyInStarted(usize),
+    /// Number of records copied into a table
+    RecordsCopiedIn(usize),
+    /// Statement of extended query protocol is prepared
+    ParseComplete,
+    /// Parameters are bound to a prepared statement
+    BindComplete,
+    /// Run-time parameter that clients track changed its value
+    ParameterStatus(String, String),
+}
+
+impl QueryEvent {
+    /// Messages of the event, values of selected columns are sent in formats
+    /// requested by a client
+    pub fn into_messages(self, formats: &FormatCodes) -> Vec<Message> {
+        match self {
+            QueryEvent::RecordsSelected(projection) => selected_records(projection, formats, "SELECT"),
+            QueryEvent::RecordsInsertedReturning(projection) => selected_records(projection, formats, "INSERT 0"),
+            event => event.into(),
+        }
+    }
+}
+
+// rows of a result followed by completion of the command that returned them
+fn selected_records(projection: Projection, formats: &FormatCodes, command: &str) -> Vec<Message> {
+    let (definition, records) = projection;
+    let types = definition.iter().map(|(_, sql_type)| *sql_type).collect::<Vec<_>>();
+    let description: Vec<ColumnMetadata> = definition
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, sql_type))| {
+            ColumnMetadata::of_type(name, sql_type).with_format(formats.column_format(index, sql_type))
+        })
+        .collect();
+    let len = records.len();
+    let mut messages = vec![Message::RowDescription(description)];
+    for record in records {
+        messages.push(Message::DataRow(record, types.clone(), formats.clone()));
+    }
+    messages.push(Message::CommandComplete(format!("{} {}", command, len)));
+    messages
 }
 
 impl Into<Vec<Message>> for QueryEvent {
     fn into(self) -> Vec<Message> {
         match self {
+            QueryEvent::DatabaseCreated => vec![Message::CommandComplete("CREATE DATABASE".to_owned())],
+            QueryEvent::DatabaseDropped => vec![Message::CommandComplete("DROP DATABASE".to_owned())],
             QueryEvent::SchemaCreated => vec![Message::CommandComplete("CREATE SCHEMA".to_owned())],
             QueryEvent::SchemaDropped => vec![Message::CommandComplete("DROP SCHEMA".to_owned())],
             QueryEvent::TableCreated => vec![Message::CommandComplete("CREATE TABLE".to_owned())],
             QueryEvent::TableDropped => vec![Message::CommandComplete("DROP TABLE".to_owned())],
+            QueryEvent::TableAltered => vec![Message::CommandComplete("ALTER TABLE".to_owned())],
+            QueryEvent::SequenceCreated => vec![Message::CommandComplete("CREATE SEQUENCE".to_owned())],
+            QueryEvent::TableVacuumed => vec![Message::CommandComplete("VACUUM".to_owned())],
+            QueryEvent::PrivilegesGranted => vec![Message::CommandComplete("GRANT".to_owned())],
+            QueryEvent::PrivilegesRevoked => vec![Message::CommandComplete("REVOKE".to_owned())],
             QueryEvent::VariableSet => vec![Message::CommandComplete("SET".to_owned())],
+            QueryEvent::VariableReset => vec![Message::CommandComplete("RESET".to_owned())],
+            QueryEvent::ParameterStatus(name, value) => vec![Message::ParameterStatus(name, value)],
             QueryEvent::TransactionStarted => vec![Message::CommandComplete("BEGIN".to_owned())],
+            QueryEvent::TransactionCommitted => vec![Message::CommandComplete("COMMIT".to_owned())],
+            QueryEvent::TransactionRolledBack => vec![Message::CommandComplete("ROLLBACK".to_owned())],
+            QueryEvent::QueryIsEmpty => vec![Message::EmptyQueryResponse],
             QueryEvent::RecordsInserted(records) => vec![Message::CommandComplete(format!("INSERT 0 {}", records))],
-            QueryEvent::RecordsSelected(projection) => {
-                let definition = projection.0;
-                let description: Vec<ColumnMetadata> = definition
-                    .into_iter()
-                    .map(|(name, sql_type)| ColumnMetadata::new(name, sql_type.pg_oid(), sql_type.pg_len()))
-                    .collect();
-                let records = projection.1;
-                let len = records.len();
-                let mut messages = vec![Message::RowDescription(description)];
-                for record in records {
-                    messages.push(Message::DataRow(record));
-                }
-                messages.push(Message::CommandComplete(format!("SELECT {}", len)));
-                messages
+            QueryEvent::RecordsSelected(projection) => selected_records(projection, &FormatCodes::default(), "SELECT"),
+            QueryEvent::RecordsInsertedReturning(projection) => {
+                selected_records(projection, &FormatCodes::default(), "INSERT 0")
             }
             QueryEvent::RecordsUpdated(records) => vec![Message::CommandComplete(format!("UPDATE {}", records))],
             QueryEvent::RecordsDeleted(records) => vec![Message::CommandComplete(format!("DELETE {}", records))],
+            QueryEvent::RecordsCopiedOut(columns, lines) => {
+                let len = lines.len();
+                let mut messages = vec![Message::CopyOutResponse(columns as i16)];
+                for line in lines {
+                    messages.push(Message::CopyData(line.into_bytes()));
+                }
+                messages.push(Message::CopyDone);
+                messages.push(Message::CommandComplete(format!("COPY {}", len)));
+                messages
+            }
+            QueryEvent::CopyInStarted(columns) => vec![Message::CopyInResponse(columns as i16)],
+            QueryEvent::RecordsCopiedIn(records) => vec![Message::CommandComplete(format!("COPY {}", records))],
+            QueryEvent::ParseComplete => vec![Message::ParseComplete],
+            QueryEvent::BindComplete => vec![Message::BindComplete],
         }
     }
 }
@@ -116,8 +201,36 @@ pub(crate) enum QueryErrorKind {
     SchemaDoesNotExist(String),
     TableDoesNotExist(String),
     ColumnDoesNotExist(Vec<String>),
+    ColumnAlreadyExists(String),
+    DuplicateColumn(String),
+    InvalidStartupOption(String),
+    ResultRowsTruncated(usize),
+    SchemaAlreadyExistsSkipping(String),
+    TableAlreadyExistsSkipping(String),
+    SchemaDoesNotExistSkipping(String),
+    TableDoesNotExistSkipping(String),
+    UnrecognizedConfigurationParameter(String),
+    NameTooLong {
+        identifier: String,
+        max_length: usize,
+    },
+    IdentifierTruncated {
+        identifier: String,
+        truncated: String,
+    },
+    AmbiguousColumn(String),
+    PermissionDenied(String),
+    SequenceAlreadyExists(String),
+    SequenceDoesNotExist(String),
+    CurrentValueIsNotDefined(String),
+    InvalidParameterValue {
+        parameter: String,
+        value: String,
+    },
     FeatureNotSupported(String),
+    CrossDatabaseReference(String),
     TooManyInsertExpressions,
+    TooManyInsertTargetColumns,
     NumericTypeOutOfRange {
         pg_type: PostgreSqlType,
         column_name: String,
@@ -135,12 +248,57 @@ pub(crate) enum QueryErrorKind {
         column_name: String,
         row_index: usize,
     },
+    UndefinedCollation(String),
+    CollationsNotSupported(PostgreSqlType),
     UndefinedFunction {
         operator: String,
         left_type: String,
         right_type: String,
     },
+    SetOperationColumnsMismatch(String),
+    SubqueryHasTooManyColumns,
+    SubqueryReturnedMoreThanOneRow,
+    SubqueryWithoutAlias,
+    ValuesListsLengthMismatch,
+    TooManyColumnAliases {
+        table_name: String,
+        available: usize,
+        specified: usize,
+    },
+    WhereClauseRequired(String),
+    SetOperationTypesMismatch {
+        operation: String,
+        left_type: PostgreSqlType,
+        right_type: PostgreSqlType,
+    },
+    ArgumentMustBeBoolean {
+        clause: String,
+        pg_type: PostgreSqlType,
+    },
     SyntaxError(String),
+    DivisionByZero,
+    BadCopyFileFormat(String),
+    CopyFromStdinFailed(String),
+    NotNullViolation(String),
+    SerializationFailure,
+    QueryTooLong {
+        length: usize,
+        max_length: usize,
+    },
+    IntegerOutOfRange(PostgreSqlType),
+    InvalidDatetimeFormat {
+        pg_type: PostgreSqlType,
+        value: String,
+    },
+    DatetimeOutOfRange(PostgreSqlType),
+    PreparedStatementDoesNotExist(String),
+    PortalDoesNotExist(String),
+    ProtocolViolation(String),
+    InvalidBinaryFormat(usize),
+    AdminShutdown,
+    DatabaseDoesNotExist(String),
+    DatabaseAlreadyExists(String),
+    CurrentDatabaseDropped,
 }
 
 impl QueryErrorKind {
@@ -151,13 +309,60 @@ impl QueryErrorKind {
             Self::SchemaDoesNotExist(_) => "3F000",
             Self::TableDoesNotExist(_) => "42P01",
             Self::ColumnDoesNotExist(_) => "42703",
+            Self::ColumnAlreadyExists(_) => "42701",
+            Self::DuplicateColumn(_) => "42701",
+            Self::InvalidStartupOption(_) => "22023",
+            Self::ResultRowsTruncated(_) => "01000",
+            Self::SchemaAlreadyExistsSkipping(_) => "42P06",
+            Self::TableAlreadyExistsSkipping(_) => "42P07",
+            Self::SchemaDoesNotExistSkipping(_) => "00000",
+            Self::TableDoesNotExistSkipping(_) => "00000",
+            Self::UnrecognizedConfigurationParameter(_) => "42704",
+            Self::NameTooLong { .. } => "42622",
+            Self::IdentifierTruncated { .. } => "42622",
+            Self::AmbiguousColumn(_) => "42702",
+            Self::PermissionDenied(_) => "42501",
+            Self::SequenceAlreadyExists(_) => "42P07",
+            Self::SequenceDoesNotExist(_) => "42P01",
+            Self::CurrentValueIsNotDefined(_) => "55000",
+            Self::InvalidParameterValue { .. } => "22023",
             Self::FeatureNotSupported(_) => "0A000",
             Self::TooManyInsertExpressions => "42601",
+            Self::TooManyInsertTargetColumns => "42601",
+            Self::CrossDatabaseReference(_) => "0A000",
             Self::NumericTypeOutOfRange { .. } => "22003",
             Self::DataTypeMismatch { .. } => "2200G",
             Self::StringTypeLengthMismatch { .. } => "22026",
             Self::UndefinedFunction { .. } => "42883",
+            Self::UndefinedCollation(_) => "42704",
+            Self::CollationsNotSupported(_) => "42804",
+            Self::SetOperationColumnsMismatch(_) => "42601",
+            Self::SubqueryHasTooManyColumns => "42601",
+            Self::SubqueryReturnedMoreThanOneRow => "21000",
+            Self::SubqueryWithoutAlias => "42601",
+            Self::ValuesListsLengthMismatch => "42601",
+            Self::TooManyColumnAliases { .. } => "42P10",
+            Self::WhereClauseRequired(_) => "21000",
+            Self::SetOperationTypesMismatch { .. } => "42804",
+            Self::ArgumentMustBeBoolean { .. } => "42804",
             Self::SyntaxError(_) => "42601",
+            Self::DivisionByZero => "22012",
+            Self::BadCopyFileFormat(_) => "22P04",
+            Self::CopyFromStdinFailed(_) => "57014",
+            Self::NotNullViolation(_) => "23502",
+            Self::SerializationFailure => "40001",
+            Self::QueryTooLong { .. } => "54000",
+            Self::IntegerOutOfRange(_) => "22003",
+            Self::InvalidDatetimeFormat { .. } => "22007",
+            Self::DatetimeOutOfRange(_) => "22008",
+            Self::PreparedStatementDoesNotExist(_) => "26000",
+            Self::PortalDoesNotExist(_) => "34000",
+            Self::ProtocolViolation(_) => "08P01",
+            Self::InvalidBinaryFormat(_) => "22P03",
+            Self::AdminShutdown => "57P01",
+            Self::DatabaseDoesNotExist(_) => "3D000",
+            Self::DatabaseAlreadyExists(_) => "42P04",
+            Self::CurrentDatabaseDropped => "55006",
         }
     }
 }
@@ -176,10 +381,53 @@ impl Display for QueryErrorKind {
                     write!(f, "column {} does not exist", columns[0])
                 }
             }
+            Self::ColumnAlreadyExists(column_name) => write!(f, "column \"{}\" already exists", column_name),
+            Self::DuplicateColumn(column_name) => write!(f, "column \"{}\" specified more than once", column_name),
+            Self::InvalidStartupOption(option) => write!(f, "ignoring invalid startup option \"{}\"", option),
+            Self::ResultRowsTruncated(max_rows) => write!(f, "query result is truncated to {} rows", max_rows),
+            Self::SchemaAlreadyExistsSkipping(schema_name) => {
+                write!(f, "schema \"{}\" already exists, skipping", schema_name)
+            }
+            Self::TableAlreadyExistsSkipping(table_name) => {
+                write!(f, "table \"{}\" already exists, skipping", table_name)
+            }
+            Self::SchemaDoesNotExistSkipping(schema_name) => {
+                write!(f, "schema \"{}\" does not exist, skipping", schema_name)
+            }
+            Self::TableDoesNotExistSkipping(table_name) => {
+                write!(f, "table \"{}\" does not exist, skipping", table_name)
+            }
+            Self::UnrecognizedConfigurationParameter(name) => {
+                write!(f, "unrecognized configuration parameter \"{}\"", name)
+            }
+            Self::NameTooLong { identifier, max_length } => {
+                write!(f, "identifier \"{}\" is longer than {} bytes", identifier, max_length)
+            }
+            Self::IdentifierTruncated { identifier, truncated } => {
+                write!(
+                    f,
+                    "identifier \"{}\" will be truncated to \"{}\"",
+                    identifier, truncated
+                )
+            }
+            Self::AmbiguousColumn(column_name) => write!(f, "column reference \"{}\" is ambiguous", column_name),
+            Self::PermissionDenied(table_name) => write!(f, "permission denied for table {}", table_name),
+            Self::SequenceAlreadyExists(sequence_name) => write!(f, "sequence \"{}\" already exists", sequence_name),
+            Self::SequenceDoesNotExist(sequence_name) => write!(f, "sequence \"{}\" does not exist", sequence_name),
+            Self::CurrentValueIsNotDefined(sequence_name) => write!(
+                f,
+                "currval of sequence \"{}\" is not yet defined in this session",
+                sequence_name
+            ),
+            Self::InvalidParameterValue { parameter, value } => {
+                write!(f, "invalid value for parameter \"{}\": \"{}\"", parameter, value)
+            }
             Self::FeatureNotSupported(raw_sql_query) => {
                 write!(f, "Currently, Query '{}' can't be executed", raw_sql_query)
             }
             Self::TooManyInsertExpressions => write!(f, "INSERT has more expressions than target columns"),
+            Self::TooManyInsertTargetColumns => write!(f, "INSERT has more target columns than expressions"),
+            Self::CrossDatabaseReference(name) => write!(f, "cross-database references are not supported: {}", name),
             Self::NumericTypeOutOfRange {
                 pg_type,
                 column_name,
@@ -209,6 +457,8 @@ impl Display for QueryErrorKind {
                 "value too long for type {}({}) for column '{}' at row {}",
                 pg_type, len, column_name, row_index
             ),
+            Self::UndefinedCollation(collation) => write!(f, "collation \"{}\" does not exist", collation),
+            Self::CollationsNotSupported(pg_type) => write!(f, "collations are not supported by type {}", pg_type),
             Self::UndefinedFunction {
                 operator,
                 left_type,
@@ -218,7 +468,69 @@ impl Display for QueryErrorKind {
                 "operator does not exist: ({} {} {})",
                 left_type, operator, right_type
             ),
+            Self::SetOperationColumnsMismatch(operation) => {
+                write!(f, "each {} query must have the same number of columns", operation)
+            }
+            Self::SubqueryHasTooManyColumns => write!(f, "subquery has too many columns"),
+            Self::SubqueryReturnedMoreThanOneRow => {
+                write!(f, "more than one row returned by a subquery used as an expression")
+            }
+            Self::SubqueryWithoutAlias => write!(f, "subquery in FROM must have an alias"),
+            Self::ValuesListsLengthMismatch => write!(f, "VALUES lists must all be the same length"),
+            Self::TooManyColumnAliases {
+                table_name,
+                available,
+                specified,
+            } => write!(
+                f,
+                "table \"{}\" has {} columns available but {} columns specified",
+                table_name, available, specified
+            ),
+            Self::WhereClauseRequired(operation) => write!(f, "{} requires a WHERE clause", operation),
+            Self::SetOperationTypesMismatch {
+                operation,
+                left_type,
+                right_type,
+            } => write!(
+                f,
+                "{} types {} and {} cannot be matched",
+                operation, left_type, right_type
+            ),
+            Self::ArgumentMustBeBoolean { clause, pg_type } => {
+                write!(f, "argument of {} must be type boolean, not type {}", clause, pg_type)
+            }
             Self::SyntaxError(expression) => write!(f, "syntax error in {}", expression),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::BadCopyFileFormat(message) => write!(f, "{}", message),
+            Self::CopyFromStdinFailed(message) => write!(f, "COPY from stdin failed: {}", message),
+            Self::NotNullViolation(column_name) => write!(
+                f,
+                "null value in column \"{}\" violates not-null constraint",
+                column_name
+            ),
+            Self::SerializationFailure => write!(f, "could not serialize access due to concurrent update"),
+            Self::QueryTooLong { length, max_length } => write!(
+                f,
+                "query of {} bytes is longer than maximum of {} bytes",
+                length, max_length
+            ),
+            Self::IntegerOutOfRange(pg_type) => write!(f, "{} out of range", pg_type),
+            Self::InvalidDatetimeFormat { pg_type, value } => {
+                write!(f, "invalid input syntax for type {}: \"{}\"", pg_type, value)
+            }
+            Self::DatetimeOutOfRange(pg_type) => write!(f, "{} out of range", pg_type),
+            Self::PreparedStatementDoesNotExist(statement_name) => {
+                write!(f, "prepared statement \"{}\" does not exist", statement_name)
+            }
+            Self::PortalDoesNotExist(portal_name) => write!(f, "portal \"{}\" does not exist", portal_name),
+            Self::ProtocolViolation(message) => write!(f, "{}", message),
+            Self::InvalidBinaryFormat(parameter) => {
+                write!(f, "incorrect binary data format in bind parameter {}", parameter)
+            }
+            Self::AdminShutdown => write!(f, "terminating connection due to administrator command"),
+            Self::DatabaseDoesNotExist(database_name) => write!(f, "database \"{}\" does not exist", database_name),
+            Self::DatabaseAlreadyExists(database_name) => write!(f, "database \"{}\" already exists", database_name),
+            Self::CurrentDatabaseDropped => write!(f, "cannot drop the currently open database"),
         }
     }
 }
@@ -261,7 +573,12 @@ impl Into<Vec<Message>> for QueryError {
     fn into(self) -> Vec<Message> {
         self.errors
             .into_iter()
-            .map(|inner| Message::ErrorResponse(inner.severity(), inner.code(), inner.message()))
+            .map(|inner| match inner.severity {
+                Severity::Error | Severity::Fatal | Severity::Panic => {
+                    Message::ErrorResponse(inner.severity(), inner.code(), inner.message())
+                }
+                _ => Message::NoticeResponse(inner.severity(), inner.code(), inner.message()),
+            })
             .collect::<Vec<_>>()
     }
 }
@@ -331,6 +648,162 @@ impl QueryErrorBuilder {
         self
     }
 
+    /// column already exists error constructor
+    pub fn column_already_exists(mut self, column_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ColumnAlreadyExists(column_name),
+        });
+        self
+    }
+
+    /// column is specified more than once in a table definition
+    pub fn duplicate_column(mut self, column_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DuplicateColumn(column_name),
+        });
+        self
+    }
+
+    /// option of `options` startup parameter can't be applied, it is a notice
+    /// and the connection goes on
+    pub fn invalid_startup_option(mut self, option: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Notice,
+            kind: QueryErrorKind::InvalidStartupOption(option),
+        });
+        self
+    }
+
+    /// query result has more rows than the server sends, it is a notice and
+    /// the truncated result is sent
+    pub fn result_rows_truncated(mut self, max_rows: usize) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Notice,
+            kind: QueryErrorKind::ResultRowsTruncated(max_rows),
+        });
+        self
+    }
+
+    /// `CREATE SCHEMA IF NOT EXISTS` of an existing schema, it is a notice
+    pub fn schema_already_exists_skipping(mut self, schema_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Notice,
+            kind: QueryErrorKind::SchemaAlreadyExistsSkipping(schema_name),
+        });
+        self
+    }
+
+    /// `CREATE TABLE IF NOT EXISTS` of an existing table, it is a notice
+    pub fn table_already_exists_skipping(mut self, table_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Notice,
+            kind: QueryErrorKind::TableAlreadyExistsSkipping(table_name),
+        });
+        self
+    }
+
+    /// `DROP ... IF EXISTS` of a missing schema, it is a notice
+    pub fn schema_does_not_exist_skipping(mut self, schema_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Notice,
+            kind: QueryErrorKind::SchemaDoesNotExistSkipping(schema_name),
+        });
+        self
+    }
+
+    /// `DROP TABLE IF EXISTS` of a missing table, it is a notice
+    pub fn table_does_not_exist_skipping(mut self, table_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Notice,
+            kind: QueryErrorKind::TableDoesNotExistSkipping(table_name),
+        });
+        self
+    }
+
+    /// session setting is neither known to the server nor set by a client
+    pub fn unrecognized_configuration_parameter(mut self, name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::UnrecognizedConfigurationParameter(name),
+        });
+        self
+    }
+
+    /// identifier exceeds maximum length and is rejected
+    pub fn name_too_long(mut self, identifier: String, max_length: usize) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::NameTooLong { identifier, max_length },
+        });
+        self
+    }
+
+    /// identifier exceeds maximum length and is truncated, it is a notice
+    /// and query execution goes on
+    pub fn identifier_truncated(mut self, identifier: String, truncated: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Notice,
+            kind: QueryErrorKind::IdentifierTruncated { identifier, truncated },
+        });
+        self
+    }
+
+    /// column name refers to columns of more than one table error constructor
+    pub fn ambiguous_column(mut self, column_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::AmbiguousColumn(column_name),
+        });
+        self
+    }
+
+    /// insufficient privilege to access a table error constructor
+    pub fn permission_denied(mut self, table_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::PermissionDenied(table_name),
+        });
+        self
+    }
+
+    /// sequence already exists error constructor
+    pub fn sequence_already_exists(mut self, sequence_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SequenceAlreadyExists(sequence_name),
+        });
+        self
+    }
+
+    /// sequence does not exist error constructor
+    pub fn sequence_does_not_exist(mut self, sequence_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SequenceDoesNotExist(sequence_name),
+        });
+        self
+    }
+
+    /// `currval` is called before `nextval` in the session error constructor
+    pub fn current_value_is_not_defined(mut self, sequence_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::CurrentValueIsNotDefined(sequence_name),
+        });
+        self
+    }
+
+    /// invalid value of run-time parameter error constructor
+    pub fn invalid_parameter_value(mut self, parameter: String, value: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InvalidParameterValue { parameter, value },
+        });
+        self
+    }
+
     /// not supported operation error constructor
     pub fn feature_not_supported(mut self, feature_description: String) -> Self {
         self.errors.push(QueryErrorInner {
@@ -349,21 +822,44 @@ impl QueryErrorBuilder {
         self
     }
 
-    /// syntax error in the expression as part of query
-    pub fn syntax_error(mut self, expression: String) -> Self {
+    /// named insert target columns outnumber given expressions
+    pub fn too_many_insert_target_columns(mut self) -> Self {
         self.errors.push(QueryErrorInner {
             severity: Severity::Error,
-            kind: QueryErrorKind::SyntaxError(expression),
+            kind: QueryErrorKind::TooManyInsertTargetColumns,
         });
         self
     }
 
-    /// operator or function is not found for operands
-    pub fn undefined_function(mut self, operator: String, left_type: String, right_type: String) -> Self {
+    /// object name is qualified with a database name
+    pub fn cross_database_reference(mut self, name: String) -> Self {
         self.errors.push(QueryErrorInner {
             severity: Severity::Error,
-            kind: QueryErrorKind::UndefinedFunction {
-                operator,
+            kind: QueryErrorKind::CrossDatabaseReference(name),
+        });
+        self
+    }
+
+    /// queries combined by set operation return different number of columns error constructor
+    pub fn set_operation_columns_mismatch(mut self, operation: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SetOperationColumnsMismatch(operation),
+        });
+        self
+    }
+
+    /// queries combined by set operation return incompatible column types error constructor
+    pub fn set_operation_types_mismatch(
+        mut self,
+        operation: String,
+        left_type: PostgreSqlType,
+        right_type: PostgreSqlType,
+    ) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SetOperationTypesMismatch {
+                operation,
                 left_type,
                 right_type,
             },
@@ -371,222 +867,1346 @@ impl QueryErrorBuilder {
         self
     }
 
-    // These errors can be generated multiple at a time which is why they are &mut self
-    // and the rest are mut self.
+    /// condition of a clause, e.g. WHERE, is not a boolean expression
+    pub fn argument_must_be_boolean(mut self, clause: String, pg_type: PostgreSqlType) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ArgumentMustBeBoolean { clause, pg_type },
+        });
+        self
+    }
 
-    /// numeric out of range constructor
-    pub fn out_of_range(&mut self, pg_type: PostgreSqlType, column_name: String, row_index: usize) {
+    /// subquery used as an expression returns more than one column error constructor
+    pub fn subquery_has_too_many_columns(mut self) -> Self {
         self.errors.push(QueryErrorInner {
             severity: Severity::Error,
-            kind: QueryErrorKind::NumericTypeOutOfRange {
-                pg_type,
-                column_name,
-                row_index,
-            },
+            kind: QueryErrorKind::SubqueryHasTooManyColumns,
         });
+        self
     }
 
-    /// type mismatch constructor
-    pub fn type_mismatch(&mut self, value: &str, pg_type: PostgreSqlType, column_name: String, row_index: usize) {
+    /// subquery used as an expression returns more than one row error constructor
+    pub fn subquery_returned_more_than_one_row(mut self) -> Self {
         self.errors.push(QueryErrorInner {
             severity: Severity::Error,
-            kind: QueryErrorKind::DataTypeMismatch {
-                pg_type,
-                value: value.to_owned(),
-                column_name,
-                row_index,
-            },
+            kind: QueryErrorKind::SubqueryReturnedMoreThanOneRow,
         });
+        self
     }
 
-    /// length of string types do not match constructor
-    pub fn string_length_mismatch(&mut self, pg_type: PostgreSqlType, len: u64, column_name: String, row_index: usize) {
+    /// derived table in FROM clause is not given an alias
+    pub fn subquery_without_alias(mut self) -> Self {
         self.errors.push(QueryErrorInner {
             severity: Severity::Error,
-            kind: QueryErrorKind::StringTypeLengthMismatch {
-                pg_type,
-                len,
-                column_name,
-                row_index,
-            },
+            kind: QueryErrorKind::SubqueryWithoutAlias,
         });
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// rows of VALUES list have different number of values
+    pub fn values_lists_length_mismatch(mut self) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ValuesListsLengthMismatch,
+        });
+        self
+    }
 
-    #[cfg(test)]
-    mod query_event {
-        use super::*;
+    /// derived table alias names more columns than the table has
+    pub fn too_many_column_aliases(mut self, table_name: String, available: usize, specified: usize) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::TooManyColumnAliases {
+                table_name,
+                available,
+                specified,
+            },
+        });
+        self
+    }
 
-        #[test]
-        fn create_schema() {
-            let messages: Vec<Message> = QueryEvent::SchemaCreated.into();
-            assert_eq!(messages, vec![Message::CommandComplete("CREATE SCHEMA".to_owned())])
-        }
+    /// syntax error in the expression as part of query
+    pub fn syntax_error(mut self, expression: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SyntaxError(expression),
+        });
+        self
+    }
 
-        #[test]
-        fn drop_schema() {
-            let messages: Vec<Message> = QueryEvent::SchemaDropped.into();
-            assert_eq!(messages, vec![Message::CommandComplete("DROP SCHEMA".to_owned())])
-        }
+    /// division or modulo by zero error constructor
+    pub fn division_by_zero(mut self) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DivisionByZero,
+        });
+        self
+    }
 
-        #[test]
-        fn create_table() {
-            let messages: Vec<Message> = QueryEvent::TableCreated.into();
-            assert_eq!(messages, vec![Message::CommandComplete("CREATE TABLE".to_owned())]);
-        }
+    /// data of COPY operation can't be split into rows of a table
+    pub fn bad_copy_file_format(mut self, message: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::BadCopyFileFormat(message),
+        });
+        self
+    }
 
-        #[test]
-        fn drop_table() {
-            let messages: Vec<Message> = QueryEvent::TableDropped.into();
+    /// client aborted data transfer of `COPY ... FROM STDIN`
+    pub fn copy_from_stdin_failed(mut self, message: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::CopyFromStdinFailed(message),
+        });
+        self
+    }
+
+    /// column can't store NULL
+    pub fn not_null_violation(mut self, column_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::NotNullViolation(column_name),
+        });
+        self
+    }
+
+    /// operation conflicted with a concurrent one and can be retried
+    pub fn serialization_failure(mut self) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::SerializationFailure,
+        });
+        self
+    }
+
+    /// query text is rejected without parsing as it is over the length limit
+    pub fn query_too_long(mut self, length: usize, max_length: usize) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::QueryTooLong { length, max_length },
+        });
+        self
+    }
+
+    /// result of integer arithmetic does not fit into its integer type
+    pub fn integer_out_of_range(mut self, pg_type: PostgreSqlType) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::IntegerOutOfRange(pg_type),
+        });
+        self
+    }
+
+    /// update or delete of all records of a table is rejected in safe updates mode
+    pub fn where_clause_required(mut self, operation: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::WhereClauseRequired(operation),
+        });
+        self
+    }
+
+    /// date, time or interval literal can't be parsed
+    pub fn invalid_datetime_format(mut self, pg_type: PostgreSqlType, value: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InvalidDatetimeFormat { pg_type, value },
+        });
+        self
+    }
+
+    /// result of date, time or interval arithmetic is out of supported range
+    pub fn datetime_out_of_range(mut self, pg_type: PostgreSqlType) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DatetimeOutOfRange(pg_type),
+        });
+        self
+    }
+
+    /// `Bind` refers to a statement that was not prepared by `Parse`
+    pub fn prepared_statement_does_not_exist(mut self, statement_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::PreparedStatementDoesNotExist(statement_name),
+        });
+        self
+    }
+
+    /// `Execute` refers to a portal that was not created by `Bind`
+    pub fn portal_does_not_exist(mut self, portal_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::PortalDoesNotExist(portal_name),
+        });
+        self
+    }
+
+    /// client message contradicts the state of extended query protocol
+    pub fn protocol_violation(mut self, message: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::ProtocolViolation(message),
+        });
+        self
+    }
+
+    /// binary value of a bind parameter does not match its type, parameters
+    /// are numbered from 1
+    pub fn invalid_binary_format(mut self, parameter: usize) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::InvalidBinaryFormat(parameter),
+        });
+        self
+    }
+
+    /// connection is closed because server shuts down
+    pub fn admin_shutdown(mut self) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Fatal,
+            kind: QueryErrorKind::AdminShutdown,
+        });
+        self
+    }
+
+    /// connection is closed because requested database does not exist
+    pub fn database_does_not_exist(mut self, database_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Fatal,
+            kind: QueryErrorKind::DatabaseDoesNotExist(database_name),
+        });
+        self
+    }
+
+    /// dropped database does not exist error constructor
+    pub fn dropped_database_does_not_exist(mut self, database_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DatabaseDoesNotExist(database_name),
+        });
+        self
+    }
+
+    /// database already exists error constructor
+    pub fn database_already_exists(mut self, database_name: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DatabaseAlreadyExists(database_name),
+        });
+        self
+    }
+
+    /// session can't drop the database it is connected to
+    pub fn current_database_dropped(mut self) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::CurrentDatabaseDropped,
+        });
+        self
+    }
+
+    /// collation in `COLLATE` clause is not known
+    pub fn undefined_collation(mut self, collation: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::UndefinedCollation(collation),
+        });
+        self
+    }
+
+    /// `COLLATE` clause is applied to a non character type
+    pub fn collations_not_supported(mut self, pg_type: PostgreSqlType) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::CollationsNotSupported(pg_type),
+        });
+        self
+    }
+
+    /// operator or function is not found for operands
+    pub fn undefined_function(mut self, operator: String, left_type: String, right_type: String) -> Self {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::UndefinedFunction {
+                operator,
+                left_type,
+                right_type,
+            },
+        });
+        self
+    }
+
+    // These errors can be generated multiple at a time which is why they are &mut self
+    // and the rest are mut self.
+
+    /// numeric out of range constructor
+    pub fn out_of_range(&mut self, pg_type: PostgreSqlType, column_name: String, row_index: usize) {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::NumericTypeOutOfRange {
+                pg_type,
+                column_name,
+                row_index,
+            },
+        });
+    }
+
+    /// type mismatch constructor
+    pub fn type_mismatch(&mut self, value: &str, pg_type: PostgreSqlType, column_name: String, row_index: usize) {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::DataTypeMismatch {
+                pg_type,
+                value: value.to_owned(),
+                column_name,
+                row_index,
+            },
+        });
+    }
+
+    /// length of string types do not match constructor
+    pub fn string_length_mismatch(&mut self, pg_type: PostgreSqlType, len: u64, column_name: String, row_index: usize) {
+        self.errors.push(QueryErrorInner {
+            severity: Severity::Error,
+            kind: QueryErrorKind::StringTypeLengthMismatch {
+                pg_type,
+                len,
+                column_name,
+                row_index,
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::FormatCode;
+
+    #[cfg(test)]
+    mod query_event {
+        use super::*;
+
+        #[test]
+        fn create_database() {
+            let messages: Vec<Message> = QueryEvent::DatabaseCreated.into();
+            assert_eq!(messages, vec![Message::CommandComplete("CREATE DATABASE".to_owned())])
+        }
+
+        #[test]
+        fn drop_database() {
+            let messages: Vec<Message> = QueryEvent::DatabaseDropped.into();
+            assert_eq!(messages, vec![Message::CommandComplete("DROP DATABASE".to_owned())])
+        }
+
+        #[test]
+        fn create_schema() {
+            let messages: Vec<Message> = QueryEvent::SchemaCreated.into();
+            assert_eq!(messages, vec![Message::CommandComplete("CREATE SCHEMA".to_owned())])
+        }
+
+        #[test]
+        fn drop_schema() {
+            let messages: Vec<Message> = QueryEvent::SchemaDropped.into();
+            assert_eq!(messages, vec![Message::CommandComplete("DROP SCHEMA".to_owned())])
+        }
+
+        #[test]
+        fn create_table() {
+            let messages: Vec<Message> = QueryEvent::TableCreated.into();
+            assert_eq!(messages, vec![Message::CommandComplete("CREATE TABLE".to_owned())]);
+        }
+
+        #[test]
+        fn drop_table() {
+            let messages: Vec<Message> = QueryEvent::TableDropped.into();
             assert_eq!(messages, vec![Message::CommandComplete("DROP TABLE".to_owned())]);
         }
 
         #[test]
-        fn insert_record() {
-            let records_number = 3;
-            let messages: Vec<Message> = QueryEvent::RecordsInserted(records_number).into();
+        fn alter_table() {
+            let messages: Vec<Message> = QueryEvent::TableAltered.into();
+            assert_eq!(messages, vec![Message::CommandComplete("ALTER TABLE".to_owned())]);
+        }
+
+        #[test]
+        fn empty_query() {
+            let messages: Vec<Message> = QueryEvent::QueryIsEmpty.into();
+            assert_eq!(messages, vec![Message::EmptyQueryResponse]);
+        }
+
+        #[test]
+        fn create_sequence() {
+            let messages: Vec<Message> = QueryEvent::SequenceCreated.into();
+            assert_eq!(messages, vec![Message::CommandComplete("CREATE SEQUENCE".to_owned())]);
+        }
+
+        #[test]
+        fn vacuum_table() {
+            let messages: Vec<Message> = QueryEvent::TableVacuumed.into();
+            assert_eq!(messages, vec![Message::CommandComplete("VACUUM".to_owned())]);
+        }
+
+        #[test]
+        fn grant_privileges() {
+            let messages: Vec<Message> = QueryEvent::PrivilegesGranted.into();
+            assert_eq!(messages, vec![Message::CommandComplete("GRANT".to_owned())]);
+        }
+
+        #[test]
+        fn revoke_privileges() {
+            let messages: Vec<Message> = QueryEvent::PrivilegesRevoked.into();
+            assert_eq!(messages, vec![Message::CommandComplete("REVOKE".to_owned())]);
+        }
+
+        #[test]
+        fn parse_complete() {
+            let messages: Vec<Message> = QueryEvent::ParseComplete.into();
+            assert_eq!(messages, vec![Message::ParseComplete]);
+        }
+
+        #[test]
+        fn bind_complete() {
+            let messages: Vec<Message> = QueryEvent::BindComplete.into();
+            assert_eq!(messages, vec![Message::BindComplete]);
+        }
+
+        #[test]
+        fn variable_reset() {
+            let messages: Vec<Message> = QueryEvent::VariableReset.into();
+            assert_eq!(messages, vec![Message::CommandComplete("RESET".to_owned())]);
+        }
+
+        #[test]
+        fn parameter_status() {
+            let messages: Vec<Message> = QueryEvent::ParameterStatus("TimeZone".to_owned(), "UTC".to_owned()).into();
+            assert_eq!(
+                messages,
+                vec![Message::ParameterStatus("TimeZone".to_owned(), "UTC".to_owned())]
+            );
+        }
+
+        #[test]
+        fn insert_record() {
+            let records_number = 3;
+            let messages: Vec<Message> = QueryEvent::RecordsInserted(records_number).into();
+            assert_eq!(
+                messages,
+                vec![Message::CommandComplete(format!("INSERT 0 {}", records_number))]
+            )
+        }
+
+        #[test]
+        fn select_records() {
+            let projection = (
+                vec![
+                    ("column_name_1".to_owned(), PostgreSqlType::SmallInt),
+                    ("column_name_2".to_owned(), PostgreSqlType::SmallInt),
+                ],
+                vec![
+                    vec![Some("1".to_owned()), Some("2".to_owned())],
+                    vec![Some("3".to_owned()), Some("4".to_owned())],
+                ],
+            );
+            let messages: Vec<Message> = QueryEvent::RecordsSelected(projection).into();
+            assert_eq!(
+                messages,
+                vec![
+                    Message::RowDescription(vec![
+                        ColumnMetadata::new("column_name_1".to_owned(), 21, 2),
+                        ColumnMetadata::new("column_name_2".to_owned(), 21, 2)
+                    ]),
+                    Message::DataRow(
+                        vec![Some("1".to_owned()), Some("2".to_owned())],
+                        vec![PostgreSqlType::SmallInt; 2],
+                        FormatCodes::default()
+                    ),
+                    Message::DataRow(
+                        vec![Some("3".to_owned()), Some("4".to_owned())],
+                        vec![PostgreSqlType::SmallInt; 2],
+                        FormatCodes::default()
+                    ),
+                    Message::CommandComplete("SELECT 2".to_owned())
+                ]
+            );
+        }
+
+        #[test]
+        fn select_records_in_mixed_formats() {
+            let projection = (
+                vec![
+                    ("column_name_1".to_owned(), PostgreSqlType::SmallInt),
+                    ("column_name_2".to_owned(), PostgreSqlType::SmallInt),
+                ],
+                vec![vec![Some("1".to_owned()), Some("2".to_owned())]],
+            );
+            let formats = FormatCodes::PerColumn(vec![FormatCode::Text, FormatCode::Binary]);
+            let messages = QueryEvent::RecordsSelected(projection).into_messages(&formats);
+            assert_eq!(
+                messages,
+                vec![
+                    Message::RowDescription(vec![
+                        ColumnMetadata::new("column_name_1".to_owned(), 21, 2),
+                        ColumnMetadata::new("column_name_2".to_owned(), 21, 2).with_format(FormatCode::Binary)
+                    ]),
+                    Message::DataRow(
+                        vec![Some("1".to_owned()), Some("2".to_owned())],
+                        vec![PostgreSqlType::SmallInt; 2],
+                        formats
+                    ),
+                    Message::CommandComplete("SELECT 1".to_owned())
+                ]
+            );
+        }
+
+        #[test]
+        fn insert_records_returning() {
+            let projection = (
+                vec![("column_name".to_owned(), PostgreSqlType::SmallInt)],
+                vec![vec![Some("1".to_owned())], vec![Some("2".to_owned())]],
+            );
+            let messages: Vec<Message> = QueryEvent::RecordsInsertedReturning(projection).into();
+            assert_eq!(
+                messages,
+                vec![
+                    Message::RowDescription(vec![ColumnMetadata::new("column_name".to_owned(), 21, 2)]),
+                    Message::DataRow(
+                        vec![Some("1".to_owned())],
+                        vec![PostgreSqlType::SmallInt],
+                        FormatCodes::default()
+                    ),
+                    Message::DataRow(
+                        vec![Some("2".to_owned())],
+                        vec![PostgreSqlType::SmallInt],
+                        FormatCodes::default()
+                    ),
+                    Message::CommandComplete("INSERT 0 2".to_owned())
+                ]
+            );
+        }
+
+        #[test]
+        fn commit_transaction() {
+            let messages: Vec<Message> = QueryEvent::TransactionCommitted.into();
+            assert_eq!(messages, vec![Message::CommandComplete("COMMIT".to_owned())])
+        }
+
+        #[test]
+        fn rollback_transaction() {
+            let messages: Vec<Message> = QueryEvent::TransactionRolledBack.into();
+            assert_eq!(messages, vec![Message::CommandComplete("ROLLBACK".to_owned())])
+        }
+
+        #[test]
+        fn update_records() {
+            let records_number = 3;
+            let messages: Vec<Message> = QueryEvent::RecordsUpdated(records_number).into();
+            assert_eq!(
+                messages,
+                vec![Message::CommandComplete(format!("UPDATE {}", records_number))]
+            );
+        }
+
+        #[test]
+        fn delete_records() {
+            let records_number = 3;
+            let messages: Vec<Message> = QueryEvent::RecordsDeleted(records_number).into();
+            assert_eq!(
+                messages,
+                vec![Message::CommandComplete(format!("DELETE {}", records_number))]
+            )
+        }
+
+        #[test]
+        fn copy_in_started() {
+            let messages: Vec<Message> = QueryEvent::CopyInStarted(2).into();
+            assert_eq!(messages, vec![Message::CopyInResponse(2)])
+        }
+
+        #[test]
+        fn copy_in_records() {
+            let messages: Vec<Message> = QueryEvent::RecordsCopiedIn(3).into();
+            assert_eq!(messages, vec![Message::CommandComplete("COPY 3".to_owned())])
+        }
+
+        #[test]
+        fn copy_out_records() {
+            let messages: Vec<Message> =
+                QueryEvent::RecordsCopiedOut(2, vec!["1\ta\n".to_owned(), "2\tb\n".to_owned()]).into();
+            assert_eq!(
+                messages,
+                vec![
+                    Message::CopyOutResponse(2),
+                    Message::CopyData(b"1\ta\n".to_vec()),
+                    Message::CopyData(b"2\tb\n".to_vec()),
+                    Message::CopyDone,
+                    Message::CommandComplete("COPY 2".to_owned()),
+                ]
+            )
+        }
+    }
+
+    #[cfg(test)]
+    mod query_error {
+        use super::*;
+
+        #[test]
+        fn schema_already_exists() {
+            let schema_name = "some_table_name".to_owned();
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .schema_already_exists(schema_name.clone())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42P06"),
+                    Some(format!("schema \"{}\" already exists", schema_name)),
+                )]
+            )
+        }
+
+        #[test]
+        fn schema_does_not_exists() {
+            let schema_name = "some_table_name".to_owned();
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .schema_does_not_exist(schema_name.clone())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("3F000"),
+                    Some(format!("schema \"{}\" does not exist", schema_name)),
+                )]
+            )
+        }
+
+        #[test]
+        fn table_already_exists() {
+            let table_name = "some_table_name".to_owned();
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .table_already_exists(table_name.clone())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42P07"),
+                    Some(format!("table \"{}\" already exists", table_name)),
+                )]
+            )
+        }
+
+        #[test]
+        fn table_does_not_exists() {
+            let table_name = "some_table_name".to_owned();
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .table_does_not_exist(table_name.clone())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42P01"),
+                    Some(format!("table \"{}\" does not exist", table_name)),
+                )]
+            )
+        }
+
+        #[test]
+        fn one_column_does_not_exists() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .column_does_not_exist(vec!["column_not_in_table".to_owned()])
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42703"),
+                    Some("column column_not_in_table does not exist".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn column_already_exists() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .column_already_exists("column_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42701"),
+                    Some("column \"column_name\" already exists".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn duplicate_column() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .duplicate_column("column_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42701"),
+                    Some("column \"column_name\" specified more than once".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn invalid_startup_option() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .invalid_startup_option("-x".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::NoticeResponse(
+                    Some("NOTICE"),
+                    Some("22023"),
+                    Some("ignoring invalid startup option \"-x\"".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn table_already_exists_skipping() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .table_already_exists_skipping("schema_name.table_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::NoticeResponse(
+                    Some("NOTICE"),
+                    Some("42P07"),
+                    Some("table \"schema_name.table_name\" already exists, skipping".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn table_does_not_exist_skipping() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .table_does_not_exist_skipping("schema_name.table_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::NoticeResponse(
+                    Some("NOTICE"),
+                    Some("00000"),
+                    Some("table \"schema_name.table_name\" does not exist, skipping".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn unrecognized_configuration_parameter() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .unrecognized_configuration_parameter("some_setting".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42704"),
+                    Some("unrecognized configuration parameter \"some_setting\"".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn name_too_long() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .name_too_long("long_name".to_owned(), 4)
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42622"),
+                    Some("identifier \"long_name\" is longer than 4 bytes".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn result_rows_truncated() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().result_rows_truncated(10).build().into();
+            assert_eq!(
+                messages,
+                vec![Message::NoticeResponse(
+                    Some("NOTICE"),
+                    Some("01000"),
+                    Some("query result is truncated to 10 rows".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn identifier_truncated() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .identifier_truncated("long_name".to_owned(), "long".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::NoticeResponse(
+                    Some("NOTICE"),
+                    Some("42622"),
+                    Some("identifier \"long_name\" will be truncated to \"long\"".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn ambiguous_column() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .ambiguous_column("column_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42702"),
+                    Some("column reference \"column_name\" is ambiguous".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn permission_denied() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .permission_denied("schema_name.table_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42501"),
+                    Some("permission denied for table schema_name.table_name".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn admin_shutdown() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().admin_shutdown().build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("FATAL"),
+                    Some("57P01"),
+                    Some("terminating connection due to administrator command".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn database_does_not_exist() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .database_does_not_exist("database_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("FATAL"),
+                    Some("3D000"),
+                    Some("database \"database_name\" does not exist".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn dropped_database_does_not_exist() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .dropped_database_does_not_exist("database_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("3D000"),
+                    Some("database \"database_name\" does not exist".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn database_already_exists() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .database_already_exists("database_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42P04"),
+                    Some("database \"database_name\" already exists".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn current_database_dropped() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().current_database_dropped().build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("55006"),
+                    Some("cannot drop the currently open database".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn sequence_already_exists() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .sequence_already_exists("sequence_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42P07"),
+                    Some("sequence \"sequence_name\" already exists".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn sequence_does_not_exist() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .sequence_does_not_exist("sequence_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42P01"),
+                    Some("sequence \"sequence_name\" does not exist".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn current_value_is_not_defined() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .current_value_is_not_defined("sequence_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("55000"),
+                    Some("currval of sequence \"sequence_name\" is not yet defined in this session".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn invalid_parameter_value() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .invalid_parameter_value("log_min_messages".to_owned(), "verbose".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("22023"),
+                    Some("invalid value for parameter \"log_min_messages\": \"verbose\"".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn set_operation_columns_mismatch() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .set_operation_columns_mismatch("UNION".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42601"),
+                    Some("each UNION query must have the same number of columns".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn set_operation_types_mismatch() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .set_operation_types_mismatch("UNION".to_owned(), PostgreSqlType::Integer, PostgreSqlType::VarChar)
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42804"),
+                    Some("UNION types integer and variable character cannot be matched".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn argument_must_be_boolean() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .argument_must_be_boolean("WHERE".to_owned(), PostgreSqlType::Integer)
+                .build()
+                .into();
             assert_eq!(
                 messages,
-                vec![Message::CommandComplete(format!("INSERT 0 {}", records_number))]
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42804"),
+                    Some("argument of WHERE must be type boolean, not type integer".to_owned()),
+                )]
             )
         }
 
         #[test]
-        fn select_records() {
-            let projection = (
-                vec![
-                    ("column_name_1".to_owned(), PostgreSqlType::SmallInt),
-                    ("column_name_2".to_owned(), PostgreSqlType::SmallInt),
-                ],
-                vec![
-                    vec!["1".to_owned(), "2".to_owned()],
-                    vec!["3".to_owned(), "4".to_owned()],
-                ],
-            );
-            let messages: Vec<Message> = QueryEvent::RecordsSelected(projection).into();
+        fn subquery_has_too_many_columns() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().subquery_has_too_many_columns().build().into();
             assert_eq!(
                 messages,
-                vec![
-                    Message::RowDescription(vec![
-                        ColumnMetadata::new("column_name_1".to_owned(), 21, 2),
-                        ColumnMetadata::new("column_name_2".to_owned(), 21, 2)
-                    ]),
-                    Message::DataRow(vec!["1".to_owned(), "2".to_owned()]),
-                    Message::DataRow(vec!["3".to_owned(), "4".to_owned()]),
-                    Message::CommandComplete("SELECT 2".to_owned())
-                ]
-            );
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42601"),
+                    Some("subquery has too many columns".to_owned()),
+                )]
+            )
         }
 
         #[test]
-        fn update_records() {
-            let records_number = 3;
-            let messages: Vec<Message> = QueryEvent::RecordsUpdated(records_number).into();
+        fn subquery_returned_more_than_one_row() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .subquery_returned_more_than_one_row()
+                .build()
+                .into();
             assert_eq!(
                 messages,
-                vec![Message::CommandComplete(format!("UPDATE {}", records_number))]
-            );
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("21000"),
+                    Some("more than one row returned by a subquery used as an expression".to_owned()),
+                )]
+            )
         }
 
         #[test]
-        fn delete_records() {
-            let records_number = 3;
-            let messages: Vec<Message> = QueryEvent::RecordsDeleted(records_number).into();
+        fn subquery_without_alias() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().subquery_without_alias().build().into();
             assert_eq!(
                 messages,
-                vec![Message::CommandComplete(format!("DELETE {}", records_number))]
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42601"),
+                    Some("subquery in FROM must have an alias".to_owned()),
+                )]
             )
         }
-    }
 
-    #[cfg(test)]
-    mod query_error {
-        use super::*;
+        #[test]
+        fn values_lists_length_mismatch() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().values_lists_length_mismatch().build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42601"),
+                    Some("VALUES lists must all be the same length".to_owned()),
+                )]
+            )
+        }
 
         #[test]
-        fn schema_already_exists() {
-            let schema_name = "some_table_name".to_owned();
+        fn too_many_column_aliases() {
             let messages: Vec<Message> = QueryErrorBuilder::new()
-                .schema_already_exists(schema_name.clone())
+                .too_many_column_aliases("t".to_owned(), 2, 3)
                 .build()
                 .into();
             assert_eq!(
                 messages,
                 vec![Message::ErrorResponse(
                     Some("ERROR"),
-                    Some("42P06"),
-                    Some(format!("schema \"{}\" already exists", schema_name)),
+                    Some("42P10"),
+                    Some("table \"t\" has 2 columns available but 3 columns specified".to_owned()),
                 )]
             )
         }
 
         #[test]
-        fn schema_does_not_exists() {
-            let schema_name = "some_table_name".to_owned();
+        fn division_by_zero() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().division_by_zero().build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("22012"),
+                    Some("division by zero".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn bad_copy_file_format() {
             let messages: Vec<Message> = QueryErrorBuilder::new()
-                .schema_does_not_exist(schema_name.clone())
+                .bad_copy_file_format("extra data after last expected column".to_owned())
                 .build()
                 .into();
             assert_eq!(
                 messages,
                 vec![Message::ErrorResponse(
                     Some("ERROR"),
-                    Some("3F000"),
-                    Some(format!("schema \"{}\" does not exist", schema_name)),
+                    Some("22P04"),
+                    Some("extra data after last expected column".to_owned()),
                 )]
             )
         }
 
         #[test]
-        fn table_already_exists() {
-            let table_name = "some_table_name".to_owned();
+        fn copy_from_stdin_failed() {
             let messages: Vec<Message> = QueryErrorBuilder::new()
-                .table_already_exists(table_name.clone())
+                .copy_from_stdin_failed("canceled by user".to_owned())
                 .build()
                 .into();
             assert_eq!(
                 messages,
                 vec![Message::ErrorResponse(
                     Some("ERROR"),
-                    Some("42P07"),
-                    Some(format!("table \"{}\" already exists", table_name)),
+                    Some("57014"),
+                    Some("COPY from stdin failed: canceled by user".to_owned()),
                 )]
             )
         }
 
         #[test]
-        fn table_does_not_exists() {
-            let table_name = "some_table_name".to_owned();
+        fn serialization_failure() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().serialization_failure().build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("40001"),
+                    Some("could not serialize access due to concurrent update".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn query_too_long() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().query_too_long(2048, 1024).build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("54000"),
+                    Some("query of 2048 bytes is longer than maximum of 1024 bytes".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn not_null_violation() {
             let messages: Vec<Message> = QueryErrorBuilder::new()
-                .table_does_not_exist(table_name.clone())
+                .not_null_violation("column_1".to_owned())
                 .build()
                 .into();
             assert_eq!(
                 messages,
                 vec![Message::ErrorResponse(
                     Some("ERROR"),
-                    Some("42P01"),
-                    Some(format!("table \"{}\" does not exist", table_name)),
+                    Some("23502"),
+                    Some("null value in column \"column_1\" violates not-null constraint".to_owned()),
                 )]
             )
         }
 
         #[test]
-        fn one_column_does_not_exists() {
+        fn integer_out_of_range() {
             let messages: Vec<Message> = QueryErrorBuilder::new()
-                .column_does_not_exist(vec!["column_not_in_table".to_owned()])
+                .integer_out_of_range(PostgreSqlType::Integer)
                 .build()
                 .into();
             assert_eq!(
                 messages,
                 vec![Message::ErrorResponse(
                     Some("ERROR"),
-                    Some("42703"),
-                    Some("column column_not_in_table does not exist".to_owned()),
+                    Some("22003"),
+                    Some("integer out of range".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn undefined_collation() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .undefined_collation("en_US".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42704"),
+                    Some("collation \"en_US\" does not exist".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn collations_not_supported() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .collations_not_supported(PostgreSqlType::Integer)
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42804"),
+                    Some("collations are not supported by type integer".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn where_clause_required() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .where_clause_required("DELETE".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("21000"),
+                    Some("DELETE requires a WHERE clause".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn invalid_datetime_format() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .invalid_datetime_format(PostgreSqlType::Date, "2020-13-01".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("22007"),
+                    Some("invalid input syntax for type date: \"2020-13-01\"".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn datetime_out_of_range() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .datetime_out_of_range(PostgreSqlType::Timestamp)
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("22008"),
+                    Some("timestamp out of range".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn prepared_statement_does_not_exist() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .prepared_statement_does_not_exist("stmt".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("26000"),
+                    Some("prepared statement \"stmt\" does not exist".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn portal_does_not_exist() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .portal_does_not_exist("portal".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("34000"),
+                    Some("portal \"portal\" does not exist".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn protocol_violation() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .protocol_violation(
+                    "bind message supplies 0 parameters, but prepared statement \"\" requires 1".to_owned(),
+                )
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("08P01"),
+                    Some("bind message supplies 0 parameters, but prepared statement \"\" requires 1".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn invalid_binary_format() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().invalid_binary_format(1).build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("22P03"),
+                    Some("incorrect binary data format in bind parameter 1".to_owned()),
                 )]
             )
         }
@@ -640,6 +2260,37 @@ mod tests {
             )
         }
 
+        #[test]
+        fn too_many_insert_target_columns() {
+            let messages: Vec<Message> = QueryErrorBuilder::new().too_many_insert_target_columns().build().into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("42601"),
+                    Some("INSERT has more target columns than expressions".to_owned()),
+                )]
+            )
+        }
+
+        #[test]
+        fn cross_database_reference() {
+            let messages: Vec<Message> = QueryErrorBuilder::new()
+                .cross_database_reference("database_name.schema_name.table_name".to_owned())
+                .build()
+                .into();
+            assert_eq!(
+                messages,
+                vec![Message::ErrorResponse(
+                    Some("ERROR"),
+                    Some("0A000"),
+                    Some(
+                        "cross-database references are not supported: database_name.schema_name.table_name".to_owned()
+                    ),
+                )]
+            )
+        }
+
         #[test]
         fn out_of_range_constraint_violation() {
             let mut builder = QueryErrorBuilder::new();
@@ -33,49 +33,104 @@ pub enum PostgreSqlType {
     TimestampWithTimeZone,
     Date,
     Interval,
+    Uuid,
+    Bytea,
+    Json,
+    BoolArray,
+    SmallIntArray,
+    IntegerArray,
+    BigIntArray,
+}
+
+/// Catalog values of a PostgreSQL type sent to a client in `RowDescription`
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct TypeInfo {
+    /// type OID as in `pg_type.oid`
+    pub oid: i32,
+    /// type size as in `pg_type.typlen`, `-1` for variable length types
+    pub size: i16,
+    /// type modifier as in `pg_attribute.atttypmod`, `-1` if the type has none
+    pub modifier: i32,
+}
+
+impl TypeInfo {
+    const fn new(oid: i32, size: i16) -> Self {
+        Self {
+            oid,
+            size,
+            modifier: -1,
+        }
+    }
 }
 
 impl PostgreSqlType {
-    /// PostgreSQL type OID
-    pub fn pg_oid(&self) -> i32 {
+    /// Registry of PostgreSQL catalog values for every supported type
+    pub fn type_info(&self) -> TypeInfo {
         match self {
-            Self::Bool => 16,
-            Self::Char => 18,
-            Self::BigInt => 20,           // PG int8
-            Self::SmallInt => 21,         // PG int2
-            Self::Integer => 23,          // PG int4
-            Self::Real => 700,            // PG float4
-            Self::DoublePrecision => 701, // PG float8
-            Self::VarChar => 1043,
-            Self::Date => 1082,
-            Self::Time => 1083,
-            Self::Timestamp => 1114,
-            Self::TimestampWithTimeZone => 1184, // PG Timestamptz
-            Self::Interval => 1186,
-            Self::TimeWithTimeZone => 1266, // PG Timetz
-            Self::Decimal => 1700,          // PG Numeric & Decimal
+            Self::Bool => TypeInfo::new(16, 1),
+            Self::Bytea => TypeInfo::new(17, -1),
+            Self::Json => TypeInfo::new(114, -1),
+            Self::Char => TypeInfo::new(18, 1),
+            Self::BigInt => TypeInfo::new(20, 8),           // PG int8
+            Self::SmallInt => TypeInfo::new(21, 2),         // PG int2
+            Self::Integer => TypeInfo::new(23, 4),          // PG int4
+            Self::Real => TypeInfo::new(700, 4),            // PG float4
+            Self::DoublePrecision => TypeInfo::new(701, 8), // PG float8
+            Self::VarChar => TypeInfo::new(1043, -1),
+            Self::Date => TypeInfo::new(1082, 4),
+            Self::Time => TypeInfo::new(1083, 8),
+            Self::Timestamp => TypeInfo::new(1114, 8),
+            Self::TimestampWithTimeZone => TypeInfo::new(1184, 8), // PG Timestamptz
+            Self::Interval => TypeInfo::new(1186, 16),
+            Self::TimeWithTimeZone => TypeInfo::new(1266, 12), // PG Timetz
+            Self::Uuid => TypeInfo::new(2950, 16),
+            Self::Decimal => TypeInfo::new(1700, -1),   // PG Numeric & Decimal
+            Self::BoolArray => TypeInfo::new(1000, -1), // PG _bool
+            Self::SmallIntArray => TypeInfo::new(1005, -1), // PG _int2
+            Self::IntegerArray => TypeInfo::new(1007, -1), // PG _int4
+            Self::BigIntArray => TypeInfo::new(1016, -1), // PG _int8
         }
     }
 
+    /// Type with PostgreSQL type OID, if it is supported
+    pub fn from_oid(oid: i32) -> Option<Self> {
+        [
+            Self::Bool,
+            Self::Char,
+            Self::VarChar,
+            Self::Decimal,
+            Self::SmallInt,
+            Self::Integer,
+            Self::BigInt,
+            Self::Real,
+            Self::DoublePrecision,
+            Self::Time,
+            Self::TimeWithTimeZone,
+            Self::Timestamp,
+            Self::TimestampWithTimeZone,
+            Self::Date,
+            Self::Interval,
+            Self::Uuid,
+            Self::Bytea,
+            Self::Json,
+            Self::BoolArray,
+            Self::SmallIntArray,
+            Self::IntegerArray,
+            Self::BigIntArray,
+        ]
+        .iter()
+        .copied()
+        .find(|pg_type| pg_type.pg_oid() == oid)
+    }
+
+    /// PostgreSQL type OID
+    pub fn pg_oid(&self) -> i32 {
+        self.type_info().oid
+    }
+
     /// PostgreSQL type length
     pub fn pg_len(&self) -> i16 {
-        match self {
-            Self::Bool => 1,
-            Self::Char => 1,
-            Self::BigInt => 8,
-            Self::SmallInt => 2,
-            Self::Integer => 4,
-            Self::Real => 4,
-            Self::DoublePrecision => 8,
-            Self::VarChar => -1,
-            Self::Date => 4,
-            Self::Time => 8,
-            Self::Timestamp => 8,
-            Self::TimestampWithTimeZone => 8,
-            Self::Interval => 16,
-            Self::TimeWithTimeZone => 12,
-            Self::Decimal => -1,
-        }
+        self.type_info().size
     }
 }
 
@@ -96,7 +151,67 @@ impl Display for PostgreSqlType {
             Self::Timestamp => write!(f, "timestamp"),
             Self::TimestampWithTimeZone => write!(f, "timestamp with timezone"),
             Self::Interval => write!(f, "interval"),
+            Self::Uuid => write!(f, "uuid"),
+            Self::Bytea => write!(f, "bytea"),
+            Self::Json => write!(f, "json"),
             Self::Decimal => write!(f, "decimal"),
+            Self::BoolArray => write!(f, "bool[]"),
+            Self::SmallIntArray => write!(f, "smallint[]"),
+            Self::IntegerArray => write!(f, "integer[]"),
+            Self::BigIntArray => write!(f, "bigint[]"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (type, oid, typlen) as in `select typname, oid, typlen from pg_type`
+    #[test]
+    fn type_info_matches_postgres_catalog() {
+        let catalog = vec![
+            (PostgreSqlType::Bool, 16, 1),
+            (PostgreSqlType::Bytea, 17, -1),
+            (PostgreSqlType::Json, 114, -1),
+            (PostgreSqlType::Char, 18, 1),
+            (PostgreSqlType::BigInt, 20, 8),
+            (PostgreSqlType::SmallInt, 21, 2),
+            (PostgreSqlType::Integer, 23, 4),
+            (PostgreSqlType::Real, 700, 4),
+            (PostgreSqlType::DoublePrecision, 701, 8),
+            (PostgreSqlType::VarChar, 1043, -1),
+            (PostgreSqlType::Date, 1082, 4),
+            (PostgreSqlType::Time, 1083, 8),
+            (PostgreSqlType::Timestamp, 1114, 8),
+            (PostgreSqlType::TimestampWithTimeZone, 1184, 8),
+            (PostgreSqlType::Interval, 1186, 16),
+            (PostgreSqlType::TimeWithTimeZone, 1266, 12),
+            (PostgreSqlType::Decimal, 1700, -1),
+            (PostgreSqlType::Uuid, 2950, 16),
+            (PostgreSqlType::BoolArray, 1000, -1),
+            (PostgreSqlType::SmallIntArray, 1005, -1),
+            (PostgreSqlType::IntegerArray, 1007, -1),
+            (PostgreSqlType::BigIntArray, 1016, -1),
+        ];
+
+        for (pg_type, oid, size) in catalog {
+            assert_eq!(
+                pg_type.type_info(),
+                TypeInfo {
+                    oid,
+                    size,
+                    modifier: -1
+                },
+                "{:?}",
+                pg_type
+            );
+            assert_eq!(PostgreSqlType::from_oid(oid), Some(pg_type));
         }
     }
+
+    #[test]
+    fn unknown_oid() {
+        assert_eq!(PostgreSqlType::from_oid(0), None);
+    }
 }
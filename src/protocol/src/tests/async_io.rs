@@ -20,6 +20,7 @@ use std::{
     fs::File,
     io::{self, Seek, SeekFrom, Write},
     pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
     task::{Context, Poll},
 };
 use tempfile::NamedTempFile;
@@ -45,6 +46,12 @@ pub struct TestCase {
     response: Mutex<Unblock<File>>,
     request_path: Arc<NamedTempFile>,
     response_path: Arc<NamedTempFile>,
+    // maximum number of bytes returned by a single read or accepted by a
+    // single write, `None` is for no limit
+    read_chunk: Option<usize>,
+    write_chunk: Option<usize>,
+    // number of completed writes shared by all clones
+    writes: Arc<AtomicUsize>,
 }
 
 impl Clone for TestCase {
@@ -54,6 +61,9 @@ impl Clone for TestCase {
             response: Mutex::new(Unblock::new(self.response_path.reopen().expect("reopen file"))),
             request_path: self.request_path.clone(),
             response_path: self.response_path.clone(),
+            read_chunk: self.read_chunk,
+            write_chunk: self.write_chunk,
+            writes: self.writes.clone(),
         }
     }
 }
@@ -74,9 +84,29 @@ impl TestCase {
             response: result,
             request_path: Arc::new(request_path),
             response_path: Arc::new(temp),
+            read_chunk: None,
+            write_chunk: None,
+            writes: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// content is delivered by chunks of at most `size` bytes per read
+    pub fn with_read_chunk(mut self, size: usize) -> Self {
+        self.read_chunk = Some(size);
+        self
+    }
+
+    /// every write accepts at most `size` bytes
+    pub fn with_write_chunk(mut self, size: usize) -> Self {
+        self.write_chunk = Some(size);
+        self
+    }
+
+    /// number of writes completed by all clones of the test case
+    pub fn write_count(&self) -> usize {
+        self.writes.load(Ordering::SeqCst)
+    }
+
     pub async fn read_result(&self) -> Vec<u8> {
         let mut result = Vec::new();
         let file = &mut *(self.response.lock()).await;
@@ -91,13 +121,21 @@ impl TestCase {
 
 impl AsyncRead for TestCase {
     fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
-        Pin::new((&mut self.get_mut().request).get_mut()).poll_read(cx, buf)
+        let this = self.get_mut();
+        let len = this.read_chunk.map_or(buf.len(), |chunk| chunk.min(buf.len()));
+        Pin::new(this.request.get_mut()).poll_read(cx, &mut buf[..len])
     }
 }
 
 impl AsyncWrite for TestCase {
     fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
-        Pin::new((&mut self.get_mut().response).get_mut()).poll_write(cx, buf)
+        let this = self.get_mut();
+        let len = this.write_chunk.map_or(buf.len(), |chunk| chunk.min(buf.len()));
+        let result = Pin::new(this.response.get_mut()).poll_write(cx, &buf[..len]);
+        if let Poll::Ready(Ok(_)) = result {
+            this.writes.fetch_add(1, Ordering::SeqCst);
+        }
+        result
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{messages::Message, tests::async_io::TestCase, Channel, Command, Receiver, RequestReceiver, VERSION_3};
+use crate::{
+    messages::{FormatCode, FormatCodes, Message},
+    results::QueryEvent,
+    sql_types::PostgreSqlType,
+    tests::async_io::TestCase,
+    Channel, Command, Error, Receiver, RequestReceiver, ResponseSender, Sender, VERSION_3,
+};
 use async_mutex::Mutex as AsyncMutex;
 use futures_lite::future::block_on;
 use std::sync::Arc;
@@ -50,6 +56,23 @@ mod read_query {
         });
     }
 
+    #[test]
+    fn read_query_delivered_by_small_chunks() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![&[81], &[0, 0, 0, 14], b"select 1;\0"])
+                .with_read_chunk(3)
+                .with_write_chunk(2);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case.clone())));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let query = receiver.receive().await.expect("no io errors");
+            assert_eq!(query, Ok(Command::Query("select 1;".to_owned())));
+
+            let actual_content = test_case.read_result().await;
+            assert_eq!(actual_content, Message::ReadyForQuery.as_vec());
+        });
+    }
+
     #[test]
     fn unexpected_eof_when_read_type_code_of_query_request() {
         block_on(async {
@@ -70,7 +93,19 @@ mod read_query {
             let mut connection = RequestReceiver::new((VERSION_3, vec![]), channel);
 
             let query = connection.receive().await;
-            assert!(query.is_err());
+            assert_eq!(query.expect("no io errors"), Err(Error::ConnectionClosedDuringMessage));
+        });
+    }
+
+    #[test]
+    fn message_length_shorter_than_length_field() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![&[81], &[0, 0, 0, 2]]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case)));
+            let mut connection = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let query = connection.receive().await;
+            assert_eq!(query.expect("no io errors"), Err(Error::MalformedMessage));
         });
     }
 
@@ -82,7 +117,250 @@ mod read_query {
             let mut connection = RequestReceiver::new((VERSION_3, vec![]), channel);
 
             let query = connection.receive().await;
-            assert!(query.is_err());
+            assert_eq!(query.expect("no io errors"), Err(Error::ConnectionClosedDuringMessage));
+        });
+    }
+}
+
+#[cfg(test)]
+mod extended_query {
+    use super::*;
+
+    #[test]
+    fn read_parse_command() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![
+                &[80],
+                &[0, 0, 0, 35],
+                b"stmt\0",
+                b"select $1 + $2;\0",
+                &[0, 2],
+                &[0, 0, 0, 23],
+                &[0, 0, 0, 0],
+            ]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case)));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(
+                command,
+                Ok(Command::Parse {
+                    statement_name: "stmt".to_owned(),
+                    sql: "select $1 + $2;".to_owned(),
+                    param_types: vec![23, 0],
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn read_bind_command() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![
+                &[66],
+                &[0, 0, 0, 37],
+                b"\0",
+                b"stmt\0",
+                &[0, 2, 0, 1, 0, 0],
+                &[0, 3],
+                &[0, 0, 0, 4, 0, 0, 0, 1],
+                &[255, 255, 255, 255],
+                &[0, 0, 0, 1, b'2'],
+                &[0, 0],
+            ]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case)));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(
+                command,
+                Ok(Command::Bind {
+                    portal_name: "".to_owned(),
+                    statement_name: "stmt".to_owned(),
+                    param_formats: vec![1, 0],
+                    params: vec![Some(vec![0, 0, 0, 1]), None, Some(b"2".to_vec())],
+                    result_formats: FormatCodes::default(),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn read_bind_command_with_result_formats() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![
+                &[66],
+                &[0, 0, 0, 20],
+                b"\0",
+                b"stmt\0",
+                &[0, 0],
+                &[0, 0],
+                &[0, 2, 0, 0, 0, 1],
+            ]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case)));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(
+                command,
+                Ok(Command::Bind {
+                    portal_name: "".to_owned(),
+                    statement_name: "stmt".to_owned(),
+                    param_formats: vec![],
+                    params: vec![],
+                    result_formats: FormatCodes::PerColumn(vec![FormatCode::Text, FormatCode::Binary]),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn read_bind_command_with_unknown_result_format() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![
+                &[66],
+                &[0, 0, 0, 18],
+                b"\0",
+                b"stmt\0",
+                &[0, 0],
+                &[0, 0],
+                &[0, 1, 0, 2],
+            ]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case)));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Err(Error::MalformedMessage));
+        });
+    }
+
+    #[test]
+    fn read_truncated_bind_command() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![&[66], &[0, 0, 0, 14], b"\0", b"stmt\0", &[0, 0], &[0, 1]]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case)));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Err(Error::MalformedMessage));
+        });
+    }
+
+    #[test]
+    fn ready_for_query_is_sent_after_sync() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![
+                &[69],
+                &[0, 0, 0, 9],
+                b"\0",
+                &[0, 0, 0, 0],
+                &[83],
+                &[0, 0, 0, 4],
+                &[88],
+                &[0, 0, 0, 4],
+            ]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case.clone())));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel);
+
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(
+                command,
+                Ok(Command::Execute {
+                    portal_name: "".to_owned(),
+                    max_rows: 0
+                })
+            );
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Ok(Command::Sync));
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Ok(Command::Terminate));
+
+            let actual_content = test_case.read_result().await;
+            let mut expected_content = Vec::new();
+            expected_content.extend_from_slice(Message::ReadyForQuery.as_vec().as_slice());
+            expected_content.extend_from_slice(Message::ReadyForQuery.as_vec().as_slice());
+            assert_eq!(actual_content, expected_content);
         });
     }
+
+    #[test]
+    fn ready_for_query_is_sent_after_copy_done() {
+        block_on(async {
+            let test_case = TestCase::with_content(vec![
+                &[81],
+                &[0, 0, 0, 23],
+                b"copy t from stdin;\0",
+                &[100],
+                &[0, 0, 0, 8],
+                b"1\ta\n",
+                &[99],
+                &[0, 0, 0, 4],
+                &[88],
+                &[0, 0, 0, 4],
+            ]);
+            let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case.clone())));
+            let mut receiver = RequestReceiver::new((VERSION_3, vec![]), channel.clone());
+            let sender = ResponseSender::new((VERSION_3, vec![]), channel).with_copy_in(receiver.copy_in.clone());
+
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Ok(Command::Query("copy t from stdin;".to_owned())));
+            sender.send(Ok(QueryEvent::CopyInStarted(2))).expect("no io errors");
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Ok(Command::CopyData(b"1\ta\n".to_vec())));
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Ok(Command::CopyDone));
+            sender.send(Ok(QueryEvent::RecordsCopiedIn(1))).expect("no io errors");
+            let command = receiver.receive().await.expect("no io errors");
+            assert_eq!(command, Ok(Command::Terminate));
+
+            let actual_content = test_case.read_result().await;
+            let mut expected_content = Vec::new();
+            expected_content.extend_from_slice(Message::ReadyForQuery.as_vec().as_slice());
+            expected_content.extend_from_slice(Message::CopyInResponse(2).as_vec().as_slice());
+            expected_content.extend_from_slice(Message::CommandComplete("COPY 1".to_owned()).as_vec().as_slice());
+            expected_content.extend_from_slice(Message::ReadyForQuery.as_vec().as_slice());
+            assert_eq!(actual_content, expected_content);
+        });
+    }
+}
+
+#[cfg(test)]
+mod send_response {
+    use super::*;
+
+    fn records(rows: usize) -> QueryEvent {
+        QueryEvent::RecordsSelected((
+            vec![("column".to_owned(), PostgreSqlType::Integer)],
+            (0..rows).map(|row| vec![Some(row.to_string())]).collect(),
+        ))
+    }
+
+    #[test]
+    fn small_result_is_written_at_once() {
+        let test_case = TestCase::with_content(vec![]);
+        let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case.clone())));
+        let sender = ResponseSender::new((VERSION_3, vec![]), channel);
+
+        sender.send(Ok(records(3))).expect("no io errors");
+
+        assert_eq!(test_case.write_count(), 1);
+    }
+
+    #[test]
+    fn data_rows_are_coalesced_into_fewer_writes() {
+        let test_case = TestCase::with_content(vec![]);
+        let channel = Arc::new(AsyncMutex::new(Channel::Plain(test_case.clone())));
+        let sender = ResponseSender::new((VERSION_3, vec![]), channel);
+
+        sender.send(Ok(records(10_000))).expect("no io errors");
+
+        let messages: Vec<Message> = records(10_000).into();
+        assert!(test_case.write_count() < messages.len() / 100);
+        let actual_content = block_on(test_case.read_result());
+        let expected_content = messages
+            .into_iter()
+            .flat_map(|message| message.as_vec())
+            .collect::<Vec<u8>>();
+        assert_eq!(actual_content, expected_content);
+    }
 }
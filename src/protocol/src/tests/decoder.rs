@@ -0,0 +1,115 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    decoder::{body_len, decode_command, decode_startup, ClientHandshake},
+    Command, Error, VERSION_3,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const TAGS: &[u8] = b"QPBESXdcf";
+const ITERATIONS: usize = 10_000;
+
+fn random_bytes(rng: &mut StdRng) -> Vec<u8> {
+    let len = rng.gen_range(0, 64);
+    (0..len)
+        .map(|_| {
+            // zeros, small counts and lengths make decoders go deeper into a message
+            if rng.gen_bool(0.3) {
+                rng.gen_range(0, 3)
+            } else {
+                rng.gen()
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn random_messages_do_not_panic() {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    for _ in 0..ITERATIONS {
+        let bytes = random_bytes(&mut rng);
+        let _ = decode_startup(&bytes);
+        let mut startup = VERSION_3.to_be_bytes().to_vec();
+        startup.extend_from_slice(&bytes);
+        let _ = decode_startup(&startup);
+        for tag in TAGS {
+            let _ = decode_command(*tag, &bytes);
+        }
+        let _ = decode_command(rng.gen(), &bytes);
+        let _ = body_len(rng.gen());
+    }
+}
+
+#[test]
+fn startup_message_shorter_than_version() {
+    assert_eq!(decode_startup(&[0, 3]), Err(Error::MalformedMessage));
+}
+
+#[test]
+fn startup_message_with_parameters() {
+    let mut message = VERSION_3.to_be_bytes().to_vec();
+    message.extend_from_slice(b"user\0postgres\0\0");
+    assert_eq!(
+        decode_startup(&message),
+        Ok(ClientHandshake::Startup(
+            VERSION_3,
+            vec![("user".to_owned(), "postgres".to_owned())]
+        ))
+    );
+}
+
+#[test]
+fn startup_parameter_is_not_valid_utf8() {
+    let mut message = VERSION_3.to_be_bytes().to_vec();
+    message.extend_from_slice(&[b'u', 0, 255, 0, 0]);
+    assert_eq!(decode_startup(&message), Err(Error::MalformedMessage));
+}
+
+#[test]
+fn query_without_terminating_zero() {
+    assert_eq!(decode_command(b'Q', b"select 1;"), Err(Error::MalformedMessage));
+}
+
+#[test]
+fn empty_query() {
+    assert_eq!(decode_command(b'Q', b"\0"), Ok(Command::Query("".to_owned())));
+}
+
+#[test]
+fn copy_data() {
+    assert_eq!(
+        decode_command(b'd', b"1\ta\n"),
+        Ok(Command::CopyData(b"1\ta\n".to_vec()))
+    );
+}
+
+#[test]
+fn copy_done() {
+    assert_eq!(decode_command(b'c', b""), Ok(Command::CopyDone));
+}
+
+#[test]
+fn copy_fail() {
+    assert_eq!(
+        decode_command(b'f', b"canceled\0"),
+        Ok(Command::CopyFail("canceled".to_owned()))
+    );
+}
+
+#[test]
+fn length_field_shorter_than_itself() {
+    assert_eq!(body_len(3), Err(Error::MalformedMessage));
+    assert_eq!(body_len(4), Ok(0));
+}
@@ -19,7 +19,7 @@ use crate::{
         async_io::{empty_file_named, TestCase},
         certificate_content, pg_frontend,
     },
-    ProtocolConfiguration,
+    Error, ProtocolConfiguration, Receiver,
 };
 use futures_lite::future::block_on;
 use std::{
@@ -95,6 +95,48 @@ fn sending_reject_notification_for_none_secure() {
     });
 }
 
+#[test]
+fn refusing_gss_encryption_continues_startup() {
+    block_on(async {
+        let test_case = TestCase::with_content(vec![
+            pg_frontend::Message::GssEncryptionRequired.as_vec().as_slice(),
+            pg_frontend::Message::Setup(vec![("user", "username"), ("database", "database_name")])
+                .as_vec()
+                .as_slice(),
+            pg_frontend::Message::Password("123").as_vec().as_slice(),
+            &[],
+        ]);
+
+        let config = ProtocolConfiguration::none();
+
+        let result = hand_shake(
+            test_case.clone(),
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080)),
+            &config,
+        )
+        .await;
+
+        assert!(matches!(result, Ok(Ok(_))));
+
+        let actual_content = test_case.read_result().await;
+        let mut expected_content = Vec::new();
+        expected_content.extend_from_slice(Encryption::RejectGss.into());
+        expected_content.extend_from_slice(Message::AuthenticationCleartextPassword.as_vec().as_slice());
+        expected_content.extend_from_slice(Message::AuthenticationOk.as_vec().as_slice());
+        expected_content.extend_from_slice(
+            Message::ParameterStatus("client_encoding".to_owned(), "UTF8".to_owned())
+                .as_vec()
+                .as_slice(),
+        );
+        expected_content.extend_from_slice(
+            Message::ParameterStatus("DateStyle".to_owned(), "ISO".to_owned())
+                .as_vec()
+                .as_slice(),
+        );
+        assert_eq!(actual_content, expected_content);
+    });
+}
+
 #[test]
 fn sending_accept_notification_for_ssl_only_secure() {
     block_on(async {
@@ -161,6 +203,11 @@ fn successful_connection_handshake_for_none_secure() {
                 .as_vec()
                 .as_slice(),
         );
+        expected_content.extend_from_slice(
+            Message::ParameterStatus("application_name".to_owned(), "psql".to_owned())
+                .as_vec()
+                .as_slice(),
+        );
         assert_eq!(actual_content, expected_content);
     });
 }
@@ -208,6 +255,155 @@ fn successful_connection_handshake_for_ssl_only_secure() {
                 .as_vec()
                 .as_slice(),
         );
+        expected_content.extend_from_slice(
+            Message::ParameterStatus("application_name".to_owned(), "psql".to_owned())
+                .as_vec()
+                .as_slice(),
+        );
         assert_eq!(actual_content, expected_content);
     });
 }
+
+#[test]
+fn startup_parameters_are_retrievable_from_connection() {
+    block_on(async {
+        let test_case = TestCase::with_content(vec![
+            pg_frontend::Message::Setup(vec![
+                ("user", "username"),
+                ("database", "database_name"),
+                ("application_name", "psql"),
+                ("options", "-c statement_timeout=1000"),
+            ])
+            .as_vec()
+            .as_slice(),
+            pg_frontend::Message::Password("123").as_vec().as_slice(),
+        ]);
+
+        let config = ProtocolConfiguration::none();
+
+        let (receiver, _sender) = hand_shake(
+            test_case,
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080)),
+            &config,
+        )
+        .await
+        .expect("no io errors")
+        .expect("connection is established");
+
+        assert_eq!(receiver.application_name(), Some("psql"));
+        assert_eq!(receiver.user(), Some("username"));
+        assert_eq!(receiver.options(), Some("-c statement_timeout=1000"));
+        assert_eq!(receiver.database(), Some("database_name"));
+    });
+}
+
+#[test]
+fn no_application_name_in_startup() {
+    block_on(async {
+        let test_case = TestCase::with_content(vec![
+            pg_frontend::Message::Setup(vec![("user", "username"), ("database", "database_name")])
+                .as_vec()
+                .as_slice(),
+            pg_frontend::Message::Password("123").as_vec().as_slice(),
+        ]);
+
+        let config = ProtocolConfiguration::none();
+
+        let (receiver, _sender) = hand_shake(
+            test_case,
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080)),
+            &config,
+        )
+        .await
+        .expect("no io errors")
+        .expect("connection is established");
+
+        assert_eq!(receiver.application_name(), None);
+    });
+}
+
+#[test]
+fn no_database_in_startup() {
+    block_on(async {
+        let test_case = TestCase::with_content(vec![
+            pg_frontend::Message::Setup(vec![("user", "username")])
+                .as_vec()
+                .as_slice(),
+            pg_frontend::Message::Password("123").as_vec().as_slice(),
+        ]);
+
+        let config = ProtocolConfiguration::none();
+
+        let (receiver, _sender) = hand_shake(
+            test_case,
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080)),
+            &config,
+        )
+        .await
+        .expect("no io errors")
+        .expect("connection is established");
+
+        assert_eq!(receiver.database(), Some("username"));
+    });
+}
+
+#[test]
+fn unsupported_client_encoding() {
+    block_on(async {
+        let test_case = TestCase::with_content(vec![
+            pg_frontend::Message::Setup(vec![
+                ("user", "username"),
+                ("database", "database_name"),
+                ("client_encoding", "LATIN1"),
+            ])
+            .as_vec()
+            .as_slice(),
+            pg_frontend::Message::Password("123").as_vec().as_slice(),
+        ]);
+
+        let config = ProtocolConfiguration::none();
+
+        let result = hand_shake(
+            test_case.clone(),
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080)),
+            &config,
+        )
+        .await
+        .expect("no io errors");
+
+        assert!(matches!(result, Err(Error::UnsupportedClientEncoding)));
+
+        let actual_content = test_case.read_result().await;
+        let expected_content = Message::ErrorResponse(
+            Some("FATAL"),
+            Some("22023"),
+            Some("invalid value for parameter \"client_encoding\": \"LATIN1\"".to_owned()),
+        )
+        .as_vec();
+        assert_eq!(actual_content, expected_content);
+    });
+}
+
+#[test]
+fn utf8_client_encoding_is_case_insensitive() {
+    block_on(async {
+        let test_case = TestCase::with_content(vec![
+            pg_frontend::Message::Setup(vec![("user", "username"), ("client_encoding", "utf-8")])
+                .as_vec()
+                .as_slice(),
+            pg_frontend::Message::Password("123").as_vec().as_slice(),
+        ]);
+
+        let config = ProtocolConfiguration::none();
+
+        let result = hand_shake(
+            test_case,
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080)),
+            &config,
+        )
+        .await
+        .expect("no io errors");
+
+        assert!(result.is_ok());
+    });
+}
@@ -16,6 +16,8 @@ mod async_io;
 #[cfg(test)]
 mod connection;
 #[cfg(test)]
+mod decoder;
+#[cfg(test)]
 mod hand_shake;
 #[cfg(test)]
 mod pg_frontend;
@@ -21,6 +21,7 @@ pub enum Message {
     Setup(Vec<(&'static str, &'static str)>),
     SslDisabled,
     SslRequired,
+    GssEncryptionRequired,
     Password(&'static str),
 }
 
@@ -61,6 +62,12 @@ impl Message {
                 buff.extend_from_slice(&80_877_103u32.to_be_bytes());
                 buff
             }
+            Message::GssEncryptionRequired => {
+                let mut buff = Vec::new();
+                buff.extend_from_slice(&8u32.to_be_bytes());
+                buff.extend_from_slice(&80_877_104u32.to_be_bytes());
+                buff
+            }
             Message::Password(password) => {
                 let mut buff = Vec::new();
                 buff.extend_from_slice(password.as_bytes());
@@ -113,6 +120,14 @@ mod tests {
         assert_eq!(Message::SslRequired.as_vec(), vec![0, 0, 0, 8, 4, 210, 22, 47])
     }
 
+    #[test]
+    fn gss_encryption_required() {
+        assert_eq!(
+            Message::GssEncryptionRequired.as_vec(),
+            vec![0, 0, 0, 8, 4, 210, 22, 48]
+        )
+    }
+
     #[test]
     fn password() {
         assert_eq!(Message::Password("123").as_vec(), vec![112, 0, 0, 0, 8, 49, 50, 51, 0])
@@ -0,0 +1,79 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::DEFAULT_DATABASE;
+use kernel::SystemResult;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+use storage::{
+    backend::BackendStorage,
+    frontend::{FrontendStorage, SharedStorage},
+};
+
+type NewStorage<P> = dyn Fn() -> SystemResult<FrontendStorage<P>> + Send + Sync;
+
+/// Databases of a node, every database keeps its schemas in its own storage,
+/// so that same-named schemas of different databases don't clash
+pub struct DatabaseRegistry<P: BackendStorage> {
+    databases: Arc<Mutex<HashMap<String, SharedStorage<P>>>>,
+    new_storage: Arc<NewStorage<P>>,
+}
+
+impl<P: BackendStorage> Clone for DatabaseRegistry<P> {
+    fn clone(&self) -> Self {
+        Self {
+            databases: self.databases.clone(),
+            new_storage: self.new_storage.clone(),
+        }
+    }
+}
+
+impl<P: BackendStorage> DatabaseRegistry<P> {
+    /// registry with the default database kept in `default_storage`, storage
+    /// of databases created later is made by `new_storage`
+    pub fn new<F>(default_storage: SharedStorage<P>, new_storage: F) -> DatabaseRegistry<P>
+    where
+        F: Fn() -> SystemResult<FrontendStorage<P>> + Send + Sync + 'static,
+    {
+        let mut databases = HashMap::new();
+        databases.insert(DEFAULT_DATABASE.to_owned(), default_storage);
+        DatabaseRegistry {
+            databases: Arc::new(Mutex::new(databases)),
+            new_storage: Arc::new(new_storage),
+        }
+    }
+
+    pub(crate) fn storage(&self, database_name: &str) -> Option<SharedStorage<P>> {
+        self.databases.lock().unwrap().get(database_name).cloned()
+    }
+
+    /// `false` if the database already exists
+    pub(crate) fn create(&self, database_name: &str) -> SystemResult<bool> {
+        let mut databases = self.databases.lock().unwrap();
+        if databases.contains_key(database_name) {
+            return Ok(false);
+        }
+        let storage = (self.new_storage)()?;
+        databases.insert(database_name.to_owned(), Arc::new(RwLock::new(storage)));
+        Ok(true)
+    }
+
+    /// `false` if the database does not exist, sessions connected to a
+    /// dropped database keep using its storage until they are closed
+    pub(crate) fn drop(&self, database_name: &str) -> bool {
+        self.databases.lock().unwrap().remove(database_name).is_some()
+    }
+}
@@ -18,19 +18,19 @@ use protocol::{
     results::{QueryErrorBuilder, QueryEvent},
     Sender,
 };
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, SchemaAlreadyExists};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, SchemaAlreadyExists};
 
 pub(crate) struct CreateSchemaCommand<P: BackendStorage> {
     schema_info: SchemaCreationInfo,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
 }
 
 impl<P: BackendStorage> CreateSchemaCommand<P> {
     pub(crate) fn new(
         schema_info: SchemaCreationInfo,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
+        storage: SharedStorage<P>,
         session: Arc<dyn Sender>,
     ) -> CreateSchemaCommand<P> {
         CreateSchemaCommand {
@@ -42,7 +42,7 @@ impl<P: BackendStorage> CreateSchemaCommand<P> {
 
     pub(crate) fn execute(&mut self) -> SystemResult<()> {
         let schema_name = &self.schema_info.schema_name;
-        match (self.storage.lock().unwrap()).create_schema(schema_name)? {
+        match (self.storage.write().unwrap()).create_schema(schema_name)? {
             Ok(()) => {
                 self.session
                     .send(Ok(QueryEvent::SchemaCreated))
@@ -0,0 +1,61 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::query::SequenceCreationInfo;
+use kernel::SystemResult;
+use protocol::{
+    results::{QueryErrorBuilder, QueryEvent},
+    Sender,
+};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, SequenceAlreadyExists};
+
+pub(crate) struct CreateSequenceCommand<P: BackendStorage> {
+    sequence_info: SequenceCreationInfo,
+    storage: SharedStorage<P>,
+    session: Arc<dyn Sender>,
+}
+
+impl<P: BackendStorage> CreateSequenceCommand<P> {
+    pub(crate) fn new(
+        sequence_info: SequenceCreationInfo,
+        storage: SharedStorage<P>,
+        session: Arc<dyn Sender>,
+    ) -> CreateSequenceCommand<P> {
+        CreateSequenceCommand {
+            sequence_info,
+            storage,
+            session,
+        }
+    }
+
+    pub(crate) fn execute(&mut self) -> SystemResult<()> {
+        let sequence_name = &self.sequence_info.sequence_name;
+        match (self.storage.write().unwrap()).create_sequence(sequence_name)? {
+            Ok(()) => {
+                self.session
+                    .send(Ok(QueryEvent::SequenceCreated))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Err(SequenceAlreadyExists) => {
+                let error = QueryErrorBuilder::new()
+                    .sequence_already_exists(sequence_name.clone())
+                    .build();
+                self.session.send(Err(error)).expect("To Send Query Result to Client");
+                Ok(())
+            }
+        }
+    }
+}
@@ -18,19 +18,19 @@ use protocol::{
     results::{QueryErrorBuilder, QueryEvent},
     Sender,
 };
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, CreateTableError};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, CreateTableError};
 
 pub(crate) struct CreateTableCommand<P: BackendStorage> {
     table_info: TableCreationInfo,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
 }
 
 impl<P: BackendStorage> CreateTableCommand<P> {
     pub(crate) fn new(
         table_info: TableCreationInfo,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
+        storage: SharedStorage<P>,
         session: Arc<dyn Sender>,
     ) -> CreateTableCommand<P> {
         CreateTableCommand {
@@ -44,7 +44,7 @@ impl<P: BackendStorage> CreateTableCommand<P> {
         let table_name = self.table_info.table_name.as_str();
         let schema_name = self.table_info.schema_name.as_str();
 
-        match (self.storage.lock().unwrap()).create_table(
+        match (self.storage.write().unwrap()).create_table(
             schema_name,
             table_name,
             self.table_info.columns.as_slice(),
@@ -72,6 +72,12 @@ impl<P: BackendStorage> CreateTableCommand<P> {
                     .expect("To Send Query Result to Client");
                 Ok(())
             }
+            Err(CreateTableError::DuplicateColumn(column_name)) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new().duplicate_column(column_name).build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
         }
     }
 }
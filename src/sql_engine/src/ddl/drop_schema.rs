@@ -18,27 +18,23 @@ use protocol::{
     results::{QueryErrorBuilder, QueryEvent},
     Sender,
 };
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, SchemaDoesNotExist};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, SchemaDoesNotExist};
 
 pub(crate) struct DropSchemaCommand<P: BackendStorage> {
     name: SchemaId,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
 }
 
 impl<P: BackendStorage> DropSchemaCommand<P> {
-    pub(crate) fn new(
-        name: SchemaId,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
-        session: Arc<dyn Sender>,
-    ) -> DropSchemaCommand<P> {
+    pub(crate) fn new(name: SchemaId, storage: SharedStorage<P>, session: Arc<dyn Sender>) -> DropSchemaCommand<P> {
         DropSchemaCommand { name, storage, session }
     }
 
     pub(crate) fn execute(&mut self) -> SystemResult<()> {
         let schema_name = self.name.name().to_string();
-        match (self.storage.lock().unwrap()).drop_schema(&schema_name)? {
+        match (self.storage.write().unwrap()).drop_schema(&schema_name)? {
             Ok(()) => {
                 self.session
                     .send(Ok(QueryEvent::SchemaDropped))
@@ -18,28 +18,24 @@ use protocol::{
     results::{QueryErrorBuilder, QueryEvent},
     Sender,
 };
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, DropTableError};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, DropTableError};
 
 pub(crate) struct DropTableCommand<P: BackendStorage> {
     name: TableId,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
 }
 
 impl<P: BackendStorage> DropTableCommand<P> {
-    pub(crate) fn new(
-        name: TableId,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
-        session: Arc<dyn Sender>,
-    ) -> DropTableCommand<P> {
+    pub(crate) fn new(name: TableId, storage: SharedStorage<P>, session: Arc<dyn Sender>) -> DropTableCommand<P> {
         DropTableCommand { name, storage, session }
     }
 
     pub(crate) fn execute(&mut self) -> SystemResult<()> {
         let table_name = self.name.name();
         let schema_name = self.name.schema_name();
-        match (self.storage.lock().unwrap()).drop_table(schema_name, table_name)? {
+        match (self.storage.write().unwrap()).drop_table(schema_name, table_name)? {
             Ok(()) => {
                 self.session
                     .send(Ok(QueryEvent::TableDropped))
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod alter_column_type;
 pub(crate) mod create_schema;
+pub(crate) mod create_sequence;
 pub(crate) mod create_table;
 pub(crate) mod drop_schema;
 pub(crate) mod drop_table;
+pub(crate) mod rename_column;
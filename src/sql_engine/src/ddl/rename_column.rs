@@ -0,0 +1,94 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::query::ColumnRenamingInfo;
+use kernel::SystemResult;
+use protocol::{
+    results::{QueryErrorBuilder, QueryEvent},
+    Sender,
+};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, AlterTableError};
+
+pub(crate) struct RenameColumnCommand<P: BackendStorage> {
+    renaming_info: ColumnRenamingInfo,
+    storage: SharedStorage<P>,
+    session: Arc<dyn Sender>,
+}
+
+impl<P: BackendStorage> RenameColumnCommand<P> {
+    pub(crate) fn new(
+        renaming_info: ColumnRenamingInfo,
+        storage: SharedStorage<P>,
+        session: Arc<dyn Sender>,
+    ) -> RenameColumnCommand<P> {
+        RenameColumnCommand {
+            renaming_info,
+            storage,
+            session,
+        }
+    }
+
+    pub(crate) fn execute(&mut self) -> SystemResult<()> {
+        let schema_name = self.renaming_info.schema_name.as_str();
+        let table_name = self.renaming_info.table_name.as_str();
+
+        match (self.storage.write().unwrap()).rename_column(
+            schema_name,
+            table_name,
+            self.renaming_info.old_column_name.as_str(),
+            self.renaming_info.new_column_name.as_str(),
+        )? {
+            Ok(()) => {
+                self.session
+                    .send(Ok(QueryEvent::TableAltered))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Err(AlterTableError::SchemaDoesNotExist) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .schema_does_not_exist(schema_name.to_owned())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Err(AlterTableError::TableDoesNotExist) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .table_does_not_exist(format!("{}.{}", schema_name, table_name))
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Err(AlterTableError::ColumnDoesNotExist(column_name)) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .column_does_not_exist(vec![column_name])
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Err(AlterTableError::ColumnAlreadyExists(column_name)) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new().column_already_exists(column_name).build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Err(AlterTableError::ConstraintViolations(..)) => {
+                unreachable!("renaming a column does not touch values stored in it")
+            }
+        }
+    }
+}
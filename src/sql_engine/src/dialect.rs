@@ -0,0 +1,39 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlparser::{
+    ast::Statement,
+    dialect::{GenericDialect, PostgreSqlDialect},
+    parser::{Parser, ParserError},
+};
+
+/// Rules of SQL syntax queries are parsed with
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SqlDialect {
+    /// strict PostgreSQL syntax, that clients speaking wire protocol expect
+    #[default]
+    PostgreSql,
+    /// permissive syntax, e.g. names can start with `#` or `@` and have them
+    /// inside
+    Generic,
+}
+
+impl SqlDialect {
+    pub(crate) fn parse_sql(self, sql: &str) -> Result<Vec<Statement>, ParserError> {
+        match self {
+            SqlDialect::PostgreSql => Parser::parse_sql(&PostgreSqlDialect {}, sql),
+            SqlDialect::Generic => Parser::parse_sql(&GenericDialect {}, sql),
+        }
+    }
+}
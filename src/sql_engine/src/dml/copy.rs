@@ -0,0 +1,260 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    dml::{check_select_privilege, report_constraint_violation},
+    extension::CopyOptions,
+    query::CopyInfo,
+};
+use kernel::SystemResult;
+use protocol::{
+    results::{QueryErrorBuilder, QueryEvent},
+    Sender,
+};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, OperationOnTableError};
+
+pub(crate) struct CopyToCommand<'ct, P: BackendStorage> {
+    copy_info: CopyInfo,
+    // copying a table out reads it, so it needs SELECT privilege
+    user: Option<&'ct str>,
+    storage: SharedStorage<P>,
+    session: Arc<dyn Sender>,
+}
+
+impl<'ct, P: BackendStorage> CopyToCommand<'ct, P> {
+    pub(crate) fn new(
+        copy_info: CopyInfo,
+        user: Option<&'ct str>,
+        storage: SharedStorage<P>,
+        session: Arc<dyn Sender>,
+    ) -> CopyToCommand<'ct, P> {
+        CopyToCommand {
+            copy_info,
+            user,
+            storage,
+            session,
+        }
+    }
+
+    pub(crate) fn execute(&mut self) -> SystemResult<()> {
+        let schema_name = self.copy_info.table_id.schema_name();
+        let table_name = self.copy_info.table_id.name();
+        if let Err(()) = check_select_privilege(&self.storage, self.user, &self.session, schema_name, table_name)? {
+            return Ok(());
+        }
+        let columns = copied_columns(&self.storage, &self.copy_info)?;
+        match (self.storage.read().unwrap()).select_all_from(schema_name, table_name, columns)? {
+            Ok((description, records)) => {
+                let lines = records
+                    .iter()
+                    .map(|record| {
+                        let fields = record.iter().map(|field| Some(field.as_str())).collect::<Vec<_>>();
+                        encode_row(&fields, &self.copy_info.options)
+                    })
+                    .collect();
+                self.session
+                    .send(Ok(QueryEvent::RecordsCopiedOut(description.len(), lines)))
+                    .expect("To Send Query Result to Client");
+            }
+            Err(error) => report_table_error(&self.session, schema_name, table_name, error),
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct CopyFromCommand<P: BackendStorage> {
+    copy_info: CopyInfo,
+    data: String,
+    storage: SharedStorage<P>,
+    session: Arc<dyn Sender>,
+}
+
+impl<P: BackendStorage> CopyFromCommand<P> {
+    pub(crate) fn new(
+        copy_info: CopyInfo,
+        data: String,
+        storage: SharedStorage<P>,
+        session: Arc<dyn Sender>,
+    ) -> CopyFromCommand<P> {
+        CopyFromCommand {
+            copy_info,
+            data,
+            storage,
+            session,
+        }
+    }
+
+    pub(crate) fn execute(&mut self) -> SystemResult<()> {
+        let schema_name = self.copy_info.table_id.schema_name();
+        let table_name = self.copy_info.table_id.name();
+        let columns = copied_columns(&self.storage, &self.copy_info)?;
+        let mut rows = vec![];
+        for line in data_lines(&self.data) {
+            let fields = decode_row(line, &self.copy_info.options);
+            if fields.len() > columns.len() {
+                self.report(
+                    QueryErrorBuilder::new().bad_copy_file_format("extra data after last expected column".to_owned()),
+                );
+                return Ok(());
+            }
+            if fields.len() < columns.len() {
+                self.report(
+                    QueryErrorBuilder::new()
+                        .bad_copy_file_format(format!("missing data for column \"{}\"", columns[fields.len()])),
+                );
+                return Ok(());
+            }
+            // columns can't store NULL
+            if let Some(index) = fields.iter().position(Option::is_none) {
+                self.report(QueryErrorBuilder::new().not_null_violation(columns[index].clone()));
+                return Ok(());
+            }
+            rows.push(fields);
+        }
+
+        let len = rows.len();
+        match (self.storage.write().unwrap()).insert_into(schema_name, table_name, columns, rows)? {
+            Ok(_) => {
+                self.session
+                    .send(Ok(QueryEvent::RecordsCopiedIn(len)))
+                    .expect("To Send Query Result to Client");
+            }
+            Err(OperationOnTableError::ConstraintViolations(constraint_errors, row_index)) => {
+                let mut builder = QueryErrorBuilder::new();
+                for (error, column_definition) in constraint_errors.iter() {
+                    report_constraint_violation(&mut builder, error, column_definition, row_index);
+                }
+                self.report(builder);
+            }
+            Err(error) => report_table_error(&self.session, schema_name, table_name, error),
+        }
+        Ok(())
+    }
+
+    fn report(&self, builder: QueryErrorBuilder) {
+        self.session
+            .send(Err(builder.build()))
+            .expect("To Send Query Result to Client");
+    }
+}
+
+// named columns or all columns of a table in their order
+pub(crate) fn copied_columns<P: BackendStorage>(
+    storage: &SharedStorage<P>,
+    copy_info: &CopyInfo,
+) -> SystemResult<Vec<String>> {
+    if copy_info.columns.is_empty() {
+        Ok((storage.read().unwrap())
+            .table_columns(copy_info.table_id.schema_name(), copy_info.table_id.name())?
+            .iter()
+            .map(|column| column.name())
+            .collect())
+    } else {
+        Ok(copy_info.columns.clone())
+    }
+}
+
+fn report_table_error(session: &Arc<dyn Sender>, schema_name: &str, table_name: &str, error: OperationOnTableError) {
+    let builder = match error {
+        OperationOnTableError::ColumnDoesNotExist(non_existing_columns) => {
+            QueryErrorBuilder::new().column_does_not_exist(non_existing_columns)
+        }
+        OperationOnTableError::SchemaDoesNotExist => {
+            QueryErrorBuilder::new().schema_does_not_exist(schema_name.to_owned())
+        }
+        OperationOnTableError::TableDoesNotExist => {
+            QueryErrorBuilder::new().table_does_not_exist(schema_name.to_owned() + "." + table_name)
+        }
+        OperationOnTableError::InsertTooManyExpressions | OperationOnTableError::InsertTooManyTargetColumns => {
+            unreachable!("number of fields is checked before rows are written")
+        }
+        OperationOnTableError::ConstraintViolations(..) => unreachable!("reading a table does not validate values"),
+    };
+    session
+        .send(Err(builder.build()))
+        .expect("To Send Query Result to Client");
+}
+
+// lines of rows up to the end-of-data marker
+fn data_lines(data: &str) -> impl Iterator<Item = &str> {
+    data.lines().take_while(|line| *line != "\\.")
+}
+
+/// a row of COPY text format: fields separated by tabs and terminated by a
+/// newline, NULL is written as the NULL string of options
+pub(crate) fn encode_row(fields: &[Option<&str>], options: &CopyOptions) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| match field {
+            Some(value) => escape(value),
+            None => options.null.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join("\t");
+    line.push('\n');
+    line
+}
+
+/// fields of a row line in COPY text format, a field equal to the NULL string
+/// of options is NULL
+pub(crate) fn decode_row(line: &str, options: &CopyOptions) -> Vec<Option<String>> {
+    line.split('\t')
+        .map(|field| {
+            if field == options.null {
+                None
+            } else {
+                Some(unescape(field))
+            }
+        })
+        .collect()
+}
+
+// backslash, delimiter and line breaks are written as backslash sequences
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// a backslash followed by any other character stands for that character
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('b') => unescaped.push('\u{8}'),
+            Some('f') => unescaped.push('\u{c}'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some('v') => unescaped.push('\u{b}'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
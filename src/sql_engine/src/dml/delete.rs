@@ -18,13 +18,13 @@ use protocol::{
     Sender,
 };
 use sqlparser::ast::ObjectName;
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, OperationOnTableError};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, OperationOnTableError};
 
 pub(crate) struct DeleteCommand<'dc, P: BackendStorage> {
     raw_sql_query: &'dc str,
     name: ObjectName,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
 }
 
@@ -32,7 +32,7 @@ impl<'dc, P: BackendStorage> DeleteCommand<'dc, P> {
     pub(crate) fn new(
         raw_sql_query: &'dc str,
         name: ObjectName,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
+        storage: SharedStorage<P>,
         session: Arc<dyn Sender>,
     ) -> DeleteCommand<'dc, P> {
         DeleteCommand {
@@ -46,7 +46,7 @@ impl<'dc, P: BackendStorage> DeleteCommand<'dc, P> {
     pub(crate) fn execute(&mut self) -> SystemResult<()> {
         let schema_name = self.name.0[0].to_string();
         let table_name = self.name.0[1].to_string();
-        match (self.storage.lock().unwrap()).delete_all_from(&schema_name, &table_name)? {
+        match (self.storage.write().unwrap()).delete_all_from(&schema_name, &table_name)? {
             Ok(records_number) => {
                 self.session
                     .send(Ok(QueryEvent::RecordsDeleted(records_number)))
@@ -12,24 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::dml::ExpressionEvaluation;
+use crate::{
+    dml::{
+        report_constraint_violation, select::SelectCommand, write_storage, ExpressionEvaluation, RetryPolicy,
+        SequenceFunctions,
+    },
+    transaction::{PendingInsert, Transaction},
+};
 use kernel::SystemResult;
 use protocol::{
-    results::{QueryErrorBuilder, QueryEvent},
+    results::{Projection as ProtocolProjection, QueryErrorBuilder, QueryEvent},
     Sender,
 };
-use sql_types::ConstraintError;
-use sqlparser::ast::{DataType, Expr, Ident, ObjectName, Query, SetExpr, UnaryOperator, Value};
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, ColumnDefinition, OperationOnTableError};
+use sqlparser::{
+    ast::{DataType, Expr, Ident, ObjectName, Query, SelectItem, SetExpr, UnaryOperator, Value},
+    dialect::PostgreSqlDialect,
+    parser::{Parser, ParserError},
+    tokenizer::Tokenizer,
+};
+use std::{collections::HashMap, sync::Arc};
+use storage::{backend::BackendStorage, frontend::SharedStorage, OperationOnTableError, Projection};
+
+// target column names along with values of rows, `None` stands for DEFAULT
+type InsertedRows = (Vec<String>, Vec<Vec<Option<String>>>);
 
 pub(crate) struct InsertCommand<'ic, P: BackendStorage> {
     raw_sql_query: &'ic str,
     name: ObjectName,
     columns: Vec<Ident>,
     source: Box<Query>,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    sequence_values: &'ic mut HashMap<String, i64>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
+    retry_policy: Option<RetryPolicy>,
+    returning: Option<Vec<SelectItem>>,
+    transaction: Option<&'ic mut Transaction>,
 }
 
 impl<'ic, P: BackendStorage> InsertCommand<'ic, P> {
@@ -38,7 +55,8 @@ impl<'ic, P: BackendStorage> InsertCommand<'ic, P> {
         name: ObjectName,
         columns: Vec<Ident>,
         source: Box<Query>,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
+        sequence_values: &'ic mut HashMap<String, i64>,
+        storage: SharedStorage<P>,
         session: Arc<dyn Sender>,
     ) -> InsertCommand<'ic, P> {
         InsertCommand {
@@ -46,11 +64,35 @@ impl<'ic, P: BackendStorage> InsertCommand<'ic, P> {
             name,
             columns,
             source,
+            sequence_values,
             storage,
             session,
+            retry_policy: None,
+            returning: None,
+            transaction: None,
         }
     }
 
+    /// retries the write when storage is busy instead of waiting for it
+    pub(crate) fn with_retry_policy(mut self, retry_policy: Option<RetryPolicy>) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// sends values of the returning list computed over inserted records
+    /// instead of their number
+    pub(crate) fn with_returning(mut self, returning: Vec<SelectItem>) -> Self {
+        self.returning = Some(returning);
+        self
+    }
+
+    /// validates rows and keeps them in the transaction until COMMIT instead
+    /// of writing them
+    pub(crate) fn deferred_to(mut self, transaction: &'ic mut Transaction) -> Self {
+        self.transaction = Some(transaction);
+        self
+    }
+
     pub(crate) fn execute(&mut self) -> SystemResult<()> {
         let table_name = self.name.0.pop().unwrap().to_string();
         let schema_name = self.name.0.pop().unwrap().to_string();
@@ -75,137 +117,335 @@ impl<'ic, P: BackendStorage> InsertCommand<'ic, P> {
             for line in values {
                 let mut row = vec![];
                 for col in line {
-                    let v = match col {
-                        Expr::Value(Value::Number(v)) => v.to_string(),
-                        Expr::Value(Value::SingleQuotedString(v)) => v.to_string(),
-                        Expr::Value(Value::Boolean(v)) => v.to_string(),
-                        Expr::Cast { expr, data_type } => match (&**expr, data_type) {
-                            (Expr::Value(Value::Boolean(v)), DataType::Boolean) => v.to_string(),
-                            (Expr::Value(Value::SingleQuotedString(v)), DataType::Boolean) => v.to_string(),
-                            _ => {
-                                self.session
-                                    .send(Err(QueryErrorBuilder::new()
-                                        .syntax_error(format!(
-                                            "Cast from {:?} to {:?} is not currently supported",
-                                            expr, data_type
-                                        ))
-                                        .build()))
-                                    .expect("To Send Query Result to Client");
-                                return Ok(());
-                            }
-                        },
-                        Expr::UnaryOp { op, expr } => match (op, &**expr) {
-                            (UnaryOperator::Minus, Expr::Value(Value::Number(v))) => {
-                                "-".to_owned() + v.to_string().as_str()
-                            }
-                            (op, expr) => {
-                                self.session
-                                    .send(Err(QueryErrorBuilder::new()
-                                        .syntax_error(op.to_string() + expr.to_string().as_str())
-                                        .build()))
-                                    .expect("To Send Query Result to Client");
-                                return Ok(());
-                            }
-                        },
-                        expr @ Expr::BinaryOp { .. } => {
-                            match ExpressionEvaluation::new(self.session.clone()).eval(expr) {
-                                Ok(expr_result) => expr_result.value(),
-                                Err(()) => return Ok(()),
-                            }
-                        }
-                        expr => {
-                            self.session
-                                .send(Err(QueryErrorBuilder::new().syntax_error(expr.to_string()).build()))
-                                .expect("To Send Query Result to Client");
-                            return Ok(());
+                    let col =
+                        &match SequenceFunctions::new(self.storage.clone(), self.sequence_values, self.session.clone())
+                            .resolve(col)?
+                        {
+                            Ok(col) => col,
+                            Err(()) => return Ok(()),
+                        };
+                    if let Expr::Identifier(Ident {
+                        value,
+                        quote_style: None,
+                    }) = col
+                    {
+                        if value.eq_ignore_ascii_case("default") {
+                            row.push(None);
+                            continue;
                         }
-                    };
-                    row.push(v);
+                    }
+                    match self.literal(col) {
+                        Ok(value) => row.push(Some(value)),
+                        Err(()) => return Ok(()),
+                    }
                 }
                 rows.push(row);
             }
 
+            let (columns, rows) = match self.apply_defaults(&schema_name, &table_name, columns, rows)? {
+                Ok(columns_and_rows) => columns_and_rows,
+                Err(()) => return Ok(()),
+            };
+            if let Err(()) = self.check_returning(&schema_name, &table_name)? {
+                return Ok(());
+            }
             let len = rows.len();
-            match (self.storage.lock().unwrap()).insert_into(&schema_name, &table_name, columns, rows)? {
-                Ok(_) => {
+            let inserted = match self.transaction.take() {
+                Some(transaction) => {
+                    let previewed = (self.storage.read().unwrap()).preview_insert(
+                        &schema_name,
+                        &table_name,
+                        columns.clone(),
+                        rows.clone(),
+                    )?;
+                    previewed.map(|(_all_columns, records)| {
+                        transaction.add(PendingInsert {
+                            schema_name: schema_name.clone(),
+                            table_name: table_name.clone(),
+                            columns,
+                            rows,
+                            records,
+                        });
+                        None
+                    })
+                }
+                // the returning list is evaluated over inserted records after
+                // storage is released, as its subqueries read storage
+                None => {
+                    let mut storage = match write_storage(&self.storage, self.retry_policy, &self.session) {
+                        Ok(storage) => storage,
+                        Err(()) => return Ok(()),
+                    };
+                    match self.returning {
+                        None => storage
+                            .insert_into(&schema_name, &table_name, columns, rows)?
+                            .map(|()| None),
+                        Some(_) => storage
+                            .insert_into_returning(&schema_name, &table_name, columns, rows)?
+                            .map(Some),
+                    }
+                }
+            };
+            match inserted {
+                Ok(None) => {
                     self.session
                         .send(Ok(QueryEvent::RecordsInserted(len)))
                         .expect("To Send Query Result to Client");
                     Ok(())
                 }
-                Err(OperationOnTableError::SchemaDoesNotExist) => {
-                    self.session
-                        .send(Err(QueryErrorBuilder::new().schema_does_not_exist(schema_name).build()))
-                        .expect("To Send Query Result to Client");
+                Ok(Some(inserted)) => {
+                    if let Ok(projection) = self.returning(table_name, inserted)? {
+                        self.session
+                            .send(Ok(QueryEvent::RecordsInsertedReturning(projection)))
+                            .expect("To Send Query Result to Client");
+                    }
                     Ok(())
                 }
-                Err(OperationOnTableError::TableDoesNotExist) => {
-                    self.session
-                        .send(Err(QueryErrorBuilder::new()
-                            .table_does_not_exist(schema_name + "." + table_name.as_str())
-                            .build()))
-                        .expect("To Send Query Result to Client");
+                Err(error) => {
+                    report_insert_error(&self.session, schema_name, table_name, error);
                     Ok(())
                 }
-                Err(OperationOnTableError::ColumnDoesNotExist(non_existing_columns)) => {
-                    self.session
-                        .send(Err(QueryErrorBuilder::new()
-                            .column_does_not_exist(non_existing_columns)
-                            .build()))
-                        .expect("To Send Query Result to Client");
-                    Ok(())
+            }
+        } else {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .feature_not_supported(self.raw_sql_query.to_owned())
+                    .build()))
+                .expect("To Send Query Result to Client");
+            Ok(())
+        }
+    }
+
+    // columns the returning list refers to have to exist before anything is
+    // inserted, they are checked against a table without records. Missing
+    // tables are left to be reported by storage
+    fn check_returning(&mut self, schema_name: &str, table_name: &str) -> SystemResult<Result<(), ()>> {
+        if self.returning.is_none() {
+            return Ok(Ok(()));
+        }
+        let table_columns = (self.storage.read().unwrap()).table_columns(schema_name, table_name)?;
+        if table_columns.is_empty() {
+            return Ok(Ok(()));
+        }
+        Ok(self
+            .returning(table_name.to_owned(), (table_columns, vec![]))?
+            .map(|_| ()))
+    }
+
+    fn returning(&mut self, table_name: String, inserted: Projection) -> SystemResult<Result<ProtocolProjection, ()>> {
+        let (table_columns, records) = inserted;
+        let description = table_columns
+            .into_iter()
+            .map(|column_definition| (column_definition.name(), column_definition.sql_type().to_pg_types()))
+            .collect();
+        let records = records
+            .into_iter()
+            .map(|record| record.into_iter().map(Some).collect())
+            .collect();
+        let returning = self.returning.clone().unwrap_or_default();
+        SelectCommand::new(
+            self.raw_sql_query,
+            self.source.clone(),
+            None,
+            self.sequence_values,
+            self.storage.clone(),
+            self.session.clone(),
+        )
+        .returning(table_name, (description, records), &returning)
+    }
+
+    // `DEFAULT` expressions are evaluated for every inserted row, so that
+    // volatile ones like `nextval('seq')` give each row its own value. Columns
+    // without a declared default would be NULL, which is not supported yet.
+    // Rows that do not match target columns are left as is to be reported by storage
+    fn apply_defaults(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        columns: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<InsertedRows, ()>> {
+        let table_columns = (self.storage.read().unwrap()).table_columns(schema_name, table_name)?;
+        if table_columns.is_empty() {
+            return Ok(Ok((columns, rows)));
+        }
+        let targets = if columns.is_empty() {
+            Some((0..table_columns.len()).collect::<Vec<usize>>())
+        } else {
+            columns
+                .iter()
+                .map(|name| table_columns.iter().position(|column| column.name() == *name))
+                .collect::<Option<Vec<usize>>>()
+        };
+        let targets = match targets {
+            Some(targets)
+                if rows
+                    .iter()
+                    .all(|row| row.len() == targets.len() || (columns.is_empty() && row.len() < targets.len())) =>
+            {
+                targets
+            }
+            _ => return Ok(Ok((columns, rows))),
+        };
+
+        let mut filled_rows = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut filled = vec![None; table_columns.len()];
+            for (value, index) in row.into_iter().zip(targets.iter()) {
+                filled[*index] = value;
+            }
+            for (index, column) in table_columns.iter().enumerate() {
+                if filled[index].is_some() || column.is_serial() {
+                    continue;
                 }
-                Err(OperationOnTableError::ConstraintViolations(constraint_errors, row_index)) => {
-                    let mut builder = QueryErrorBuilder::new();
-                    let mut constraint_error_mapper =
-                        |err: &ConstraintError, column_definition: &ColumnDefinition, row_index: usize| match err {
-                            ConstraintError::OutOfRange => {
-                                builder.out_of_range(
-                                    column_definition.sql_type().to_pg_types(),
-                                    column_definition.name(),
-                                    row_index,
-                                );
-                            }
-                            ConstraintError::TypeMismatch(value) => {
-                                builder.type_mismatch(
-                                    value,
-                                    column_definition.sql_type().to_pg_types(),
-                                    column_definition.name(),
-                                    row_index,
-                                );
-                            }
-                            ConstraintError::ValueTooLong(len) => {
-                                builder.string_length_mismatch(
-                                    column_definition.sql_type().to_pg_types(),
-                                    *len,
-                                    column_definition.name(),
-                                    row_index,
-                                );
-                            }
+                match column.default_expression() {
+                    Some(default_expression) => {
+                        filled[index] = match self.evaluate_default(default_expression)? {
+                            Ok(value) => Some(value),
+                            Err(()) => return Ok(Err(())),
                         };
+                    }
+                    None => {
+                        self.session
+                            .send(Err(QueryErrorBuilder::new()
+                                .feature_not_supported("NULL values".to_owned())
+                                .build()))
+                            .expect("To Send Query Result to Client");
+                        return Ok(Err(()));
+                    }
+                }
+            }
+            filled_rows.push(filled);
+        }
+        let all_columns = table_columns.iter().map(|column| column.name()).collect();
+        Ok(Ok((all_columns, filled_rows)))
+    }
+
+    fn evaluate_default(&mut self, default_expression: &str) -> SystemResult<Result<String, ()>> {
+        let expr = match Tokenizer::new(&PostgreSqlDialect {}, default_expression)
+            .tokenize()
+            .map_err(ParserError::from)
+            .and_then(|tokens| Parser::new(tokens).parse_expr())
+        {
+            Ok(expr) => expr,
+            Err(_) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .syntax_error(default_expression.to_owned())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                return Ok(Err(()));
+            }
+        };
+        let expr = match SequenceFunctions::new(self.storage.clone(), self.sequence_values, self.session.clone())
+            .resolve(&expr)?
+        {
+            Ok(expr) => expr,
+            Err(()) => return Ok(Err(())),
+        };
+        Ok(self.literal(&expr))
+    }
 
-                    constraint_errors.iter().for_each(|(err, column_definition)| {
-                        constraint_error_mapper(err, column_definition, row_index)
-                    });
+    // text of a value literal as it is stored in a column
+    fn literal(&self, col: &Expr) -> Result<String, ()> {
+        let value = match col {
+            Expr::Value(Value::Number(v)) => v.to_string(),
+            Expr::Value(Value::SingleQuotedString(v)) => v.to_string(),
+            Expr::Value(Value::Boolean(v)) => v.to_string(),
+            Expr::Cast { expr, data_type } => match (&**expr, data_type) {
+                (Expr::Value(Value::Boolean(v)), DataType::Boolean) => v.to_string(),
+                (Expr::Value(Value::SingleQuotedString(v)), DataType::Boolean) => v.to_string(),
+                _ => {
                     self.session
-                        .send(Err(builder.build()))
+                        .send(Err(QueryErrorBuilder::new()
+                            .syntax_error(format!(
+                                "Cast from {:?} to {:?} is not currently supported",
+                                expr, data_type
+                            ))
+                            .build()))
                         .expect("To Send Query Result to Client");
-                    Ok(())
+                    return Err(());
+                }
+            },
+            Expr::UnaryOp { op, expr } => match (op, &**expr) {
+                (UnaryOperator::Minus, Expr::Value(Value::Number(v))) => (-v).to_string(),
+                (UnaryOperator::Minus, _) | (UnaryOperator::Plus, _) => {
+                    match ExpressionEvaluation::new(self.session.clone()).eval_value(col) {
+                        Ok(value) => value,
+                        Err(()) => return Err(()),
+                    }
                 }
-                Err(OperationOnTableError::InsertTooManyExpressions) => {
+                (op, expr) => {
                     self.session
-                        .send(Err(QueryErrorBuilder::new().too_many_insert_expressions().build()))
+                        .send(Err(QueryErrorBuilder::new()
+                            .syntax_error(op.to_string() + expr.to_string().as_str())
+                            .build()))
                         .expect("To Send Query Result to Client");
-                    Ok(())
+                    return Err(());
+                }
+            },
+            expr @ Expr::BinaryOp { .. } | expr @ Expr::Nested(_) => {
+                match ExpressionEvaluation::new(self.session.clone()).eval_value(expr) {
+                    Ok(value) => value,
+                    Err(()) => return Err(()),
                 }
             }
-        } else {
-            self.session
+            expr => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new().syntax_error(expr.to_string()).build()))
+                    .expect("To Send Query Result to Client");
+                return Err(());
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// reports an error of rows that can't be inserted into the table
+pub(crate) fn report_insert_error(
+    session: &Arc<dyn Sender>,
+    schema_name: String,
+    table_name: String,
+    error: OperationOnTableError,
+) {
+    match error {
+        OperationOnTableError::SchemaDoesNotExist => {
+            session
+                .send(Err(QueryErrorBuilder::new().schema_does_not_exist(schema_name).build()))
+                .expect("To Send Query Result to Client");
+        }
+        OperationOnTableError::TableDoesNotExist => {
+            session
                 .send(Err(QueryErrorBuilder::new()
-                    .feature_not_supported(self.raw_sql_query.to_owned())
+                    .table_does_not_exist(schema_name + "." + table_name.as_str())
                     .build()))
                 .expect("To Send Query Result to Client");
-            Ok(())
+        }
+        OperationOnTableError::ColumnDoesNotExist(non_existing_columns) => {
+            session
+                .send(Err(QueryErrorBuilder::new()
+                    .column_does_not_exist(non_existing_columns)
+                    .build()))
+                .expect("To Send Query Result to Client");
+        }
+        OperationOnTableError::ConstraintViolations(constraint_errors, row_index) => {
+            let mut builder = QueryErrorBuilder::new();
+            for (error, column_definition) in constraint_errors.iter() {
+                report_constraint_violation(&mut builder, error, column_definition, row_index);
+            }
+            session
+                .send(Err(builder.build()))
+                .expect("To Send Query Result to Client");
+        }
+        OperationOnTableError::InsertTooManyExpressions => {
+            session
+                .send(Err(QueryErrorBuilder::new().too_many_insert_expressions().build()))
+                .expect("To Send Query Result to Client");
+        }
+        OperationOnTableError::InsertTooManyTargetColumns => {
+            session
+                .send(Err(QueryErrorBuilder::new().too_many_insert_target_columns().build()))
+                .expect("To Send Query Result to Client");
         }
     }
 }
@@ -12,16 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use bigdecimal::BigDecimal;
-use protocol::{results::QueryErrorBuilder, Sender};
-use sqlparser::ast::{BinaryOperator, Expr, Value};
-use std::{ops::Deref, sync::Arc};
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+use kernel::SystemResult;
+use protocol::{results::QueryErrorBuilder, sql_types::PostgreSqlType, Sender};
+use sql_types::{ConstraintError, Date, Interval, SqlType, Timestamp};
+use sqlparser::ast::{BinaryOperator, DataType, Expr, Function, UnaryOperator, Value};
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{Arc, RwLockWriteGuard, TryLockError},
+    thread,
+    time::Duration,
+};
+use storage::{
+    backend::BackendStorage,
+    frontend::{FrontendStorage, SharedStorage},
+    ColumnDefinition, SequenceDoesNotExist,
+};
 
+pub(crate) mod copy;
 pub(crate) mod delete;
 pub(crate) mod insert;
 pub(crate) mod select;
 pub(crate) mod update;
 
+/// returned by `version()` builtin function
+pub(crate) const SERVER_VERSION: &str = "PostgreSQL 12.4";
+/// returned by `current_schema()` builtin function, queries have to use
+/// qualified table names, so it is the PostgreSQL default one
+pub(crate) const CURRENT_SCHEMA: &str = "public";
+
 pub(crate) struct ExpressionEvaluation {
     session: Arc<dyn Sender>,
 }
@@ -36,31 +56,50 @@ impl ExpressionEvaluation {
             let left = self.eval(left.deref())?;
             let right = self.eval(right.deref())?;
             match (left, right) {
-                (ExprResult::Number(left), ExprResult::Number(right)) => match op {
-                    BinaryOperator::Plus => Ok(ExprResult::Number(left + right)),
-                    BinaryOperator::Minus => Ok(ExprResult::Number(left - right)),
-                    BinaryOperator::Multiply => Ok(ExprResult::Number(left * right)),
-                    BinaryOperator::Divide => Ok(ExprResult::Number(left / right)),
-                    BinaryOperator::Modulus => Ok(ExprResult::Number(left % right)),
-                    BinaryOperator::BitwiseAnd => {
-                        let (left, _) = left.as_bigint_and_exponent();
-                        let (right, _) = right.as_bigint_and_exponent();
-                        Ok(ExprResult::Number(BigDecimal::from(left & &right)))
-                    }
-                    BinaryOperator::BitwiseOr => {
-                        let (left, _) = left.as_bigint_and_exponent();
-                        let (right, _) = right.as_bigint_and_exponent();
-                        Ok(ExprResult::Number(BigDecimal::from(left | &right)))
-                    }
-                    operator => {
-                        self.session
-                            .send(Err(QueryErrorBuilder::new()
-                                .undefined_function(operator.to_string(), "NUMBER".to_owned(), "NUMBER".to_owned())
-                                .build()))
-                            .expect("To Send Query Result to Client");
-                        Err(())
-                    }
-                },
+                // operators are strict, NULL operand gives NULL, even if it is divided by zero
+                (ExprResult::Null, _) | (_, ExprResult::Null) => Ok(ExprResult::Null),
+                (ExprResult::Number(_), ExprResult::Number(right))
+                    if right.is_zero() && (*op == BinaryOperator::Divide || *op == BinaryOperator::Modulus) =>
+                {
+                    self.session
+                        .send(Err(QueryErrorBuilder::new().division_by_zero().build()))
+                        .expect("To Send Query Result to Client");
+                    Err(())
+                }
+                (ExprResult::Number(left), ExprResult::Number(right)) => {
+                    let result = match op {
+                        BinaryOperator::Plus => Ok(ExprResult::Number(left + right)),
+                        BinaryOperator::Minus => Ok(ExprResult::Number(left - right)),
+                        BinaryOperator::Multiply => Ok(ExprResult::Number(left * right)),
+                        // as in PostgreSQL division of integers truncates towards zero
+                        BinaryOperator::Divide if is_integer(&left) && is_integer(&right) => {
+                            let (left, _) = left.with_scale(0).as_bigint_and_exponent();
+                            let (right, _) = right.with_scale(0).as_bigint_and_exponent();
+                            Ok(ExprResult::Number(BigDecimal::from(left / right)))
+                        }
+                        BinaryOperator::Divide => Ok(ExprResult::Number(left / right)),
+                        BinaryOperator::Modulus => Ok(ExprResult::Number(left % right)),
+                        BinaryOperator::BitwiseAnd => {
+                            let (left, _) = left.as_bigint_and_exponent();
+                            let (right, _) = right.as_bigint_and_exponent();
+                            Ok(ExprResult::Number(BigDecimal::from(left & &right)))
+                        }
+                        BinaryOperator::BitwiseOr => {
+                            let (left, _) = left.as_bigint_and_exponent();
+                            let (right, _) = right.as_bigint_and_exponent();
+                            Ok(ExprResult::Number(BigDecimal::from(left | &right)))
+                        }
+                        operator => {
+                            self.session
+                                .send(Err(QueryErrorBuilder::new()
+                                    .undefined_function(operator.to_string(), "NUMBER".to_owned(), "NUMBER".to_owned())
+                                    .build()))
+                                .expect("To Send Query Result to Client");
+                            Err(())
+                        }
+                    }?;
+                    self.check_integer_range(expr, result)
+                }
                 (ExprResult::String(left), ExprResult::String(right)) => match op {
                     BinaryOperator::StringConcat => Ok(ExprResult::String(left + right.as_str())),
                     operator => {
@@ -94,11 +133,71 @@ impl ExpressionEvaluation {
                         Err(())
                     }
                 },
+                (left, right) => self.datetime_arithmetic(op, left, right),
             }
         } else {
             match expr {
                 Expr::Value(Value::Number(v)) => Ok(ExprResult::Number(v.clone())),
                 Expr::Value(Value::SingleQuotedString(v)) => Ok(ExprResult::String(v.clone())),
+                Expr::Value(Value::Null) => Ok(ExprResult::Null),
+                Expr::Nested(expr) => self.eval(expr.deref()),
+                Expr::TypedString {
+                    data_type: DataType::Date,
+                    value,
+                } => match Date::parse(value) {
+                    Ok(date) => Ok(ExprResult::Date(date)),
+                    Err(error) => self.report_datetime_error(&error, PostgreSqlType::Date, value),
+                },
+                Expr::TypedString {
+                    data_type: DataType::Timestamp,
+                    value,
+                } => match Timestamp::parse(value) {
+                    Ok(timestamp) => Ok(ExprResult::Timestamp(timestamp)),
+                    Err(error) => self.report_datetime_error(&error, PostgreSqlType::Timestamp, value),
+                },
+                // `INTERVAL '2' DAY` is read the same way as `INTERVAL '2 day'`
+                Expr::Value(Value::Interval {
+                    value,
+                    leading_field,
+                    last_field: None,
+                    ..
+                }) => {
+                    let value = match leading_field {
+                        Some(field) => format!("{} {}", value, field),
+                        None => value.clone(),
+                    };
+                    match Interval::parse(&value) {
+                        Ok(interval) => Ok(ExprResult::Interval(interval)),
+                        Err(error) => self.report_datetime_error(&error, PostgreSqlType::Interval, &value),
+                    }
+                }
+                Expr::Function(Function { name, args, .. }) if args.is_empty() => {
+                    match name.to_string().to_lowercase().as_str() {
+                        "version" => Ok(ExprResult::String(SERVER_VERSION.to_owned())),
+                        "current_schema" => Ok(ExprResult::String(CURRENT_SCHEMA.to_owned())),
+                        _ => {
+                            self.session
+                                .send(Err(QueryErrorBuilder::new().syntax_error(expr.to_string()).build()))
+                                .expect("To Send Query Result to Client");
+                            Err(())
+                        }
+                    }
+                }
+                Expr::UnaryOp { op, expr } => match (op, self.eval(expr.deref())?) {
+                    (UnaryOperator::Minus, ExprResult::Number(v)) => Ok(ExprResult::Number(-v)),
+                    (UnaryOperator::Plus, ExprResult::Number(v)) => Ok(ExprResult::Number(v)),
+                    (UnaryOperator::Minus, ExprResult::Null) | (UnaryOperator::Plus, ExprResult::Null) => {
+                        Ok(ExprResult::Null)
+                    }
+                    (op, operand) => {
+                        self.session
+                            .send(Err(QueryErrorBuilder::new()
+                                .syntax_error(op.to_string() + operand.value().as_str())
+                                .build()))
+                            .expect("To Send Query Result to Client");
+                        Err(())
+                    }
+                },
                 e => {
                     self.session
                         .send(Err(QueryErrorBuilder::new().syntax_error(e.to_string()).build()))
@@ -108,12 +207,381 @@ impl ExpressionEvaluation {
             }
         }
     }
+
+    /// text of the value an expression evaluates to, as it is stored, NULL
+    /// can't be stored yet
+    pub(crate) fn eval_value(&mut self, expr: &Expr) -> Result<String, ()> {
+        match self.eval(expr)? {
+            ExprResult::Null => {
+                self.report_null_value();
+                Err(())
+            }
+            value => Ok(value.value()),
+        }
+    }
+
+    pub(crate) fn report_null_value(&mut self) {
+        self.session
+            .send(Err(QueryErrorBuilder::new()
+                .feature_not_supported("NULL values".to_owned())
+                .build()))
+            .expect("To Send Query Result to Client");
+    }
+}
+
+impl ExpressionEvaluation {
+    // arithmetic over integers keeps the integer type of its widest operand,
+    // so a result that does not fit into it is an overflow, not a wider number
+    fn check_integer_range(&mut self, expr: &Expr, result: ExprResult) -> Result<ExprResult, ()> {
+        match (integer_type(expr), &result) {
+            (Some(pg_type), ExprResult::Number(value)) if !matches!(integer_type_of(value), Some(t) if t <= pg_type) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .integer_out_of_range(pg_type.into())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+            _ => Ok(result),
+        }
+    }
+
+    // follows PostgreSQL operators: shifting a date by an interval gives a
+    // timestamp, difference of dates is a number of days and difference of
+    // timestamps is an interval
+    fn datetime_arithmetic(
+        &mut self,
+        op: &BinaryOperator,
+        left: ExprResult,
+        right: ExprResult,
+    ) -> Result<ExprResult, ()> {
+        let shifted = match (op, &left, &right) {
+            (BinaryOperator::Plus, ExprResult::Date(date), ExprResult::Interval(interval))
+            | (BinaryOperator::Plus, ExprResult::Interval(interval), ExprResult::Date(date)) => {
+                Timestamp::from(*date).add_interval(*interval)
+            }
+            (BinaryOperator::Plus, ExprResult::Timestamp(timestamp), ExprResult::Interval(interval))
+            | (BinaryOperator::Plus, ExprResult::Interval(interval), ExprResult::Timestamp(timestamp)) => {
+                timestamp.add_interval(*interval)
+            }
+            (BinaryOperator::Minus, ExprResult::Date(date), ExprResult::Interval(interval)) => {
+                Timestamp::from(*date).sub_interval(*interval)
+            }
+            (BinaryOperator::Minus, ExprResult::Timestamp(timestamp), ExprResult::Interval(interval)) => {
+                timestamp.sub_interval(*interval)
+            }
+            (BinaryOperator::Minus, ExprResult::Date(left), ExprResult::Date(right)) => {
+                return Ok(ExprResult::Number(BigDecimal::from(left.days_since(*right))))
+            }
+            (BinaryOperator::Minus, ExprResult::Timestamp(left), ExprResult::Timestamp(right)) => {
+                return Ok(ExprResult::Interval(left.since(*right)))
+            }
+            (operator, left, right) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .undefined_function(
+                            operator.to_string(),
+                            left.type_name().to_owned(),
+                            right.type_name().to_owned(),
+                        )
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                return Err(());
+            }
+        };
+        match shifted {
+            Ok(timestamp) => Ok(ExprResult::Timestamp(timestamp)),
+            Err(_) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .datetime_out_of_range(PostgreSqlType::Timestamp)
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+        }
+    }
+
+    fn report_datetime_error<T>(&self, error: &ConstraintError, pg_type: PostgreSqlType, value: &str) -> Result<T, ()> {
+        let builder = match error {
+            ConstraintError::OutOfRange => QueryErrorBuilder::new().datetime_out_of_range(pg_type),
+            _ => QueryErrorBuilder::new().invalid_datetime_format(pg_type, value.to_owned()),
+        };
+        self.session
+            .send(Err(builder.build()))
+            .expect("To Send Query Result to Client");
+        Err(())
+    }
 }
 
-#[derive(Debug)]
+/// replaces `nextval` and `currval` calls with the values they return, as
+/// unlike other builtins they have to reach storage and the session state
+pub(crate) struct SequenceFunctions<'sf, P: BackendStorage> {
+    storage: SharedStorage<P>,
+    // last values returned by `nextval` in the session
+    current_values: &'sf mut HashMap<String, i64>,
+    session: Arc<dyn Sender>,
+}
+
+impl<'sf, P: BackendStorage> SequenceFunctions<'sf, P> {
+    pub(crate) fn new(
+        storage: SharedStorage<P>,
+        current_values: &'sf mut HashMap<String, i64>,
+        session: Arc<dyn Sender>,
+    ) -> SequenceFunctions<'sf, P> {
+        SequenceFunctions {
+            storage,
+            current_values,
+            session,
+        }
+    }
+
+    pub(crate) fn resolve(&mut self, expr: &Expr) -> SystemResult<Result<Expr, ()>> {
+        let resolved = match expr {
+            Expr::Function(Function { name, args, .. }) if args.len() == 1 => {
+                let function = name.to_string().to_lowercase();
+                match (function.as_str(), &args[0]) {
+                    ("nextval", Expr::Value(Value::SingleQuotedString(sequence_name))) => {
+                        match (self.storage.write().unwrap()).next_value(sequence_name)? {
+                            Ok(value) => {
+                                self.current_values.insert(sequence_name.clone(), value);
+                                Expr::Value(Value::Number(BigDecimal::from(value)))
+                            }
+                            Err(SequenceDoesNotExist) => {
+                                self.session
+                                    .send(Err(QueryErrorBuilder::new()
+                                        .sequence_does_not_exist(sequence_name.clone())
+                                        .build()))
+                                    .expect("To Send Query Result to Client");
+                                return Ok(Err(()));
+                            }
+                        }
+                    }
+                    ("currval", Expr::Value(Value::SingleQuotedString(sequence_name))) => {
+                        match self.current_values.get(sequence_name) {
+                            Some(value) => Expr::Value(Value::Number(BigDecimal::from(*value))),
+                            None => {
+                                self.session
+                                    .send(Err(QueryErrorBuilder::new()
+                                        .current_value_is_not_defined(sequence_name.clone())
+                                        .build()))
+                                    .expect("To Send Query Result to Client");
+                                return Ok(Err(()));
+                            }
+                        }
+                    }
+                    _ => expr.clone(),
+                }
+            }
+            Expr::BinaryOp { left, op, right } => {
+                let left = match self.resolve(left)? {
+                    Ok(left) => left,
+                    Err(()) => return Ok(Err(())),
+                };
+                let right = match self.resolve(right)? {
+                    Ok(right) => right,
+                    Err(()) => return Ok(Err(())),
+                };
+                Expr::BinaryOp {
+                    left: Box::new(left),
+                    op: op.clone(),
+                    right: Box::new(right),
+                }
+            }
+            Expr::UnaryOp { op, expr } => match self.resolve(expr)? {
+                Ok(expr) => Expr::UnaryOp {
+                    op: op.clone(),
+                    expr: Box::new(expr),
+                },
+                Err(()) => return Ok(Err(())),
+            },
+            Expr::Nested(expr) => match self.resolve(expr)? {
+                Ok(expr) => Expr::Nested(Box::new(expr)),
+                Err(()) => return Ok(Err(())),
+            },
+            expr => expr.clone(),
+        };
+        Ok(Ok(resolved))
+    }
+}
+
+/// How many times and after what pause a write retries to get hold of
+/// storage that is busy with a concurrent operation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// number of attempts after the first one
+    pub max_retries: usize,
+    /// pause before the first retry, every next one waits for it longer
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: usize, backoff: Duration) -> RetryPolicy {
+        RetryPolicy { max_retries, backoff }
+    }
+}
+
+/// reports permission denied when SELECT privilege on the table is granted
+/// and the user is not one of grantees, tables without grants are readable
+/// by everyone
+pub(crate) fn check_select_privilege<P: BackendStorage>(
+    storage: &SharedStorage<P>,
+    user: Option<&str>,
+    session: &Arc<dyn Sender>,
+    schema_name: &str,
+    table_name: &str,
+) -> SystemResult<Result<(), ()>> {
+    if let Some(grantees) = (storage.read().unwrap()).select_grantees(schema_name, table_name)? {
+        if !grantees.iter().any(|grantee| Some(grantee.as_str()) == user) {
+            session
+                .send(Err(QueryErrorBuilder::new()
+                    .permission_denied(schema_name.to_owned() + "." + table_name)
+                    .build()))
+                .expect("To Send Query Result to Client");
+            return Ok(Err(()));
+        }
+    }
+    Ok(Ok(()))
+}
+
+/// write access to storage, without a retry policy it waits until concurrent
+/// operations are done, with one it fails with serialization failure when
+/// storage is still busy after all retries
+pub(crate) fn write_storage<'s, P: BackendStorage>(
+    storage: &'s SharedStorage<P>,
+    retry_policy: Option<RetryPolicy>,
+    session: &Arc<dyn Sender>,
+) -> Result<RwLockWriteGuard<'s, FrontendStorage<P>>, ()> {
+    let retry_policy = match retry_policy {
+        Some(retry_policy) => retry_policy,
+        None => return Ok(storage.write().unwrap()),
+    };
+    for attempt in 0..=retry_policy.max_retries {
+        match storage.try_write() {
+            Ok(guard) => return Ok(guard),
+            Err(TryLockError::WouldBlock) if attempt < retry_policy.max_retries => {
+                thread::sleep(retry_policy.backoff * (attempt as u32 + 1))
+            }
+            Err(TryLockError::WouldBlock) => {}
+            Err(TryLockError::Poisoned(error)) => panic!("{}", error),
+        }
+    }
+    session
+        .send(Err(QueryErrorBuilder::new().serialization_failure().build()))
+        .expect("To Send Query Result to Client");
+    Err(())
+}
+
+/// reports value that violates column type constraint, violation of an array
+/// element is reported for the element, e.g. `column_name[2]`
+pub(crate) fn report_constraint_violation(
+    builder: &mut QueryErrorBuilder,
+    error: &ConstraintError,
+    column_definition: &ColumnDefinition,
+    row_index: usize,
+) {
+    report_violation(
+        builder,
+        error,
+        &column_definition.sql_type(),
+        column_definition.name(),
+        row_index,
+    )
+}
+
+fn report_violation(
+    builder: &mut QueryErrorBuilder,
+    error: &ConstraintError,
+    sql_type: &SqlType,
+    column_name: String,
+    row_index: usize,
+) {
+    match error {
+        ConstraintError::OutOfRange => builder.out_of_range(sql_type.to_pg_types(), column_name, row_index),
+        ConstraintError::TypeMismatch(value) => {
+            builder.type_mismatch(value, sql_type.to_pg_types(), column_name, row_index)
+        }
+        ConstraintError::ValueTooLong(len) => {
+            builder.string_length_mismatch(sql_type.to_pg_types(), *len, column_name, row_index)
+        }
+        ConstraintError::ArrayElement(index, element_error) => {
+            let element_type = match sql_type {
+                SqlType::Array(element_type) => element_type.sql_type(),
+                sql_type => sql_type.clone(),
+            };
+            report_violation(
+                builder,
+                element_error,
+                &element_type,
+                format!("{}[{}]", column_name, index),
+                row_index,
+            )
+        }
+    }
+}
+
+// numbers written without fractional part, e.g. `5` but not `5.0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum IntegerType {
+    Integer,
+    BigInt,
+}
+
+impl From<IntegerType> for PostgreSqlType {
+    fn from(integer_type: IntegerType) -> PostgreSqlType {
+        match integer_type {
+            IntegerType::Integer => PostgreSqlType::Integer,
+            IntegerType::BigInt => PostgreSqlType::BigInt,
+        }
+    }
+}
+
+// integer literals are typed as in PostgreSQL: `integer` when the value fits
+// into 4 bytes and `bigint` when it fits into 8
+fn integer_type_of(number: &BigDecimal) -> Option<IntegerType> {
+    if !is_integer(number) {
+        None
+    } else if number.to_i32().is_some() {
+        Some(IntegerType::Integer)
+    } else if number.to_i64().is_some() {
+        Some(IntegerType::BigInt)
+    } else {
+        None
+    }
+}
+
+fn integer_type(expr: &Expr) -> Option<IntegerType> {
+    match expr {
+        Expr::Value(Value::Number(number)) => integer_type_of(number),
+        Expr::Nested(expr) => integer_type(expr),
+        Expr::UnaryOp { expr, .. } => integer_type(expr),
+        Expr::BinaryOp {
+            op:
+                BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulus,
+            left,
+            right,
+        } => Some(integer_type(left)?.max(integer_type(right)?)),
+        _ => None,
+    }
+}
+
+fn is_integer(number: &BigDecimal) -> bool {
+    let (_, scale) = number.as_bigint_and_exponent();
+    scale <= 0
+}
+
+#[derive(Debug, PartialEq)]
 pub(crate) enum ExprResult {
     Number(BigDecimal),
     String(String),
+    Date(Date),
+    Timestamp(Timestamp),
+    Interval(Interval),
+    Null,
 }
 
 impl ExprResult {
@@ -121,6 +589,21 @@ impl ExprResult {
         match self {
             Self::Number(v) => v.to_string(),
             Self::String(v) => v,
+            Self::Date(v) => v.to_string(),
+            Self::Timestamp(v) => v.to_string(),
+            Self::Interval(v) => v.to_string(),
+            Self::Null => "NULL".to_owned(),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "NUMBER",
+            Self::String(_) => "STRING",
+            Self::Date(_) => "DATE",
+            Self::Timestamp(_) => "TIMESTAMP",
+            Self::Interval(_) => "INTERVAL",
+            Self::Null => "NULL",
         }
     }
 }
@@ -12,145 +12,1751 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::{
+    dml::{check_select_privilege, ExprResult, ExpressionEvaluation, SequenceFunctions},
+    transaction::Transaction,
+};
+use bigdecimal::BigDecimal;
 use kernel::SystemResult;
+use ordered_float::OrderedFloat;
 use protocol::{
-    results::{QueryErrorBuilder, QueryEvent},
+    results::{Projection, QueryErrorBuilder, QueryEvent},
+    sql_types::PostgreSqlType,
     Sender,
 };
-use sqlparser::ast::{Expr, Ident, Query, Select, SelectItem, SetExpr, TableFactor, TableWithJoins};
+use sql_types::Collation;
+use sqlparser::ast::{
+    BinaryOperator, DataType, Expr, Fetch, Function, Ident, Join, JoinConstraint, JoinOperator, Offset, OrderByExpr,
+    Query, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, UnaryOperator, Value,
+    Values,
+};
 use std::{
-    ops::Deref,
-    sync::{Arc, Mutex},
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    iter,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use storage::{backend::BackendStorage, frontend::FrontendStorage, OperationOnTableError};
+use storage::{backend::BackendStorage, frontend::SharedStorage, ColumnDefinition, OperationOnTableError};
 
 pub(crate) struct SelectCommand<'sc, P: BackendStorage> {
     raw_sql_query: &'sc str,
     query: Box<Query>,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    user: Option<&'sc str>,
+    sequence_values: &'sc mut HashMap<String, i64>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
+    // execution statistics of plan nodes in the order they are printed by
+    // `EXPLAIN ANALYZE`, collected only when the query is analyzed
+    analysis: Option<Vec<AnalyzedNode>>,
+    depth: usize,
+    // rows of a result over the limit are not sent to a client
+    max_rows: Option<usize>,
+    // inserts of the session that are not committed yet
+    pending: Option<&'sc Transaction>,
 }
 
 impl<'sc, P: BackendStorage> SelectCommand<'sc, P> {
     pub(crate) fn new(
         raw_sql_query: &'sc str,
         query: Box<Query>,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
+        user: Option<&'sc str>,
+        sequence_values: &'sc mut HashMap<String, i64>,
+        storage: SharedStorage<P>,
         session: Arc<dyn Sender>,
     ) -> SelectCommand<'sc, P> {
         SelectCommand {
             raw_sql_query,
             query,
+            user,
+            sequence_values,
             storage,
             session,
+            analysis: None,
+            depth: 0,
+            max_rows: None,
+            pending: None,
+        }
+    }
+
+    /// reads tables along with rows the session inserted but not committed
+    pub(crate) fn with_pending(mut self, pending: &'sc Transaction) -> Self {
+        self.pending = Some(pending);
+        self
+    }
+
+    /// limits number of rows sent to a client, a bigger result is truncated
+    /// with a notice
+    pub(crate) fn with_max_rows(mut self, max_rows: Option<usize>) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// evaluates a returning list of a statement over records the statement
+    /// changed, the records are seen as a table named as the changed one
+    pub(crate) fn returning(
+        &mut self,
+        table_name: String,
+        records: Projection,
+        returning: &[SelectItem],
+    ) -> SystemResult<Result<Projection, ()>> {
+        self.select_from_tables(
+            vec![(Relation::Derived(table_name, records), JoinKind::Cross)],
+            None,
+            returning,
+        )
+    }
+
+    /// executes the query and sends back its plan annotated with number of
+    /// rows each node produced and time it took instead of the query result
+    pub(crate) fn explain_analyze(&mut self) -> SystemResult<()> {
+        let query = self.query.clone();
+        self.analysis = Some(vec![]);
+        let start = Instant::now();
+        if let Err(()) = self.select_query(&query)? {
+            return Ok(());
         }
+        let elapsed = start.elapsed();
+        let mut lines = self
+            .analysis
+            .take()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|node| vec![Some(node.to_string())])
+            .collect::<Vec<_>>();
+        lines.push(vec![Some(format!("Execution Time: {} ms", milliseconds(elapsed)))]);
+        self.session
+            .send(Ok(QueryEvent::RecordsSelected((
+                vec![("QUERY PLAN".to_owned(), PostgreSqlType::VarChar)],
+                lines,
+            ))))
+            .expect("To Send Query Result to Client");
+        Ok(())
     }
 
     pub(crate) fn execute(&mut self) -> SystemResult<()> {
-        let Query { body, .. } = &*self.query;
-        if let SetExpr::Select(select) = body {
-            let Select { projection, from, .. } = select.deref();
-            let TableWithJoins { relation, .. } = &from[0];
-            let (schema_name, table_name) = match relation {
-                TableFactor::Table { name, .. } => {
-                    let table_name = name.0[1].to_string();
-                    let schema_name = name.0[0].to_string();
-                    (schema_name, table_name)
-                }
-                _ => {
+        let query = self.query.clone();
+        if let Ok((description, mut records)) = self.select_query(&query)? {
+            if let Some(max_rows) = self.max_rows.filter(|max_rows| records.len() > *max_rows) {
+                records.truncate(max_rows);
+                self.session
+                    .send(Err(QueryErrorBuilder::new().result_rows_truncated(max_rows).build()))
+                    .expect("To Send Query Result to Client");
+            }
+            let projection = (description, records);
+            self.session
+                .send(Ok(QueryEvent::RecordsSelected(projection)))
+                .expect("To Send Query Result to Client");
+        }
+        Ok(())
+    }
+
+    fn select_query(&mut self, query: &Query) -> SystemResult<Result<Projection, ()>> {
+        let pagination = match self.pagination(query) {
+            Ok(pagination) => pagination,
+            Err(()) => return Ok(Err(())),
+        };
+        if pagination.is_empty() && query.order_by.is_empty() {
+            return self.set_expr(&query.body);
+        }
+        let limit_node = if pagination.is_empty() {
+            None
+        } else {
+            Some(self.start_node())
+        };
+        let sort_node = if query.order_by.is_empty() {
+            None
+        } else {
+            Some(self.start_node())
+        };
+        let (description, mut records) = match self.set_expr(&query.body)? {
+            Ok(projection) => projection,
+            Err(()) => return Ok(Err(())),
+        };
+        if let Some(node) = sort_node {
+            records = match self.sort(&query.order_by, &query.body, &description, records)? {
+                Ok(records) => records,
+                Err(()) => return Ok(Err(())),
+            };
+            self.finish_node(node, "Sort".to_owned(), records.len());
+        }
+        if let Some(node) = limit_node {
+            records = pagination.apply(records);
+            self.finish_node(node, "Limit".to_owned(), records.len());
+        }
+        Ok(Ok((description, records)))
+    }
+
+    // records equal by all sort keys keep the order they were produced in,
+    // which for a table is the order its rows were inserted in, so the same
+    // query always returns them in the same order. As in PostgreSQL NULLs are
+    // greater than any value unless their place is given explicitly
+    fn sort(
+        &self,
+        order_by: &[OrderByExpr],
+        body: &SetExpr,
+        description: &[(String, PostgreSqlType)],
+        records: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<Vec<Vec<Option<String>>>, ()>> {
+        let mut keys = vec![];
+        for OrderByExpr { expr, asc, nulls_first } in order_by {
+            let index = match expr {
+                Expr::Identifier(Ident { value, .. }) => match description.iter().position(|(name, _)| name == value) {
+                    Some(index) => index,
+                    None => {
+                        self.session
+                            .send(Err(QueryErrorBuilder::new()
+                                .column_does_not_exist(vec![value.clone()])
+                                .build()))
+                            .expect("To Send Query Result to Client");
+                        return Ok(Err(()));
+                    }
+                },
+                // `ORDER BY 1` refers to the first column of a result
+                Expr::Value(Value::Number(number)) => match number.to_string().parse::<usize>() {
+                    Ok(position) if position >= 1 && position <= description.len() => position - 1,
+                    _ => return Ok(self.not_supported()),
+                },
+                _ => return Ok(self.not_supported()),
+            };
+            let (name, sql_type) = &description[index];
+            let collation = self.result_collation(body, name)?;
+            let asc = asc.unwrap_or(true);
+            keys.push((index, *sql_type, collation, asc, nulls_first.unwrap_or(!asc)));
+        }
+        let mut records = records.into_iter().enumerate().collect::<Vec<_>>();
+        records.sort_unstable_by(|(left_position, left), (right_position, right)| {
+            keys.iter()
+                .map(
+                    |(index, sql_type, collation, asc, nulls_first)| match (&left[*index], &right[*index]) {
+                        (Some(left), Some(right)) => {
+                            let ordering = compare_typed(left, right, *sql_type, *collation);
+                            if *asc {
+                                ordering
+                            } else {
+                                ordering.reverse()
+                            }
+                        }
+                        (left, right) => nulls_ordering(left.is_none(), right.is_none(), *nulls_first),
+                    },
+                )
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+                .then(left_position.cmp(right_position))
+        });
+        Ok(Ok(records.into_iter().map(|(_, record)| record).collect()))
+    }
+
+    // collation of a result column read from the only table column of the
+    // same name, other result columns are ordered in byte order
+    fn result_collation(&self, body: &SetExpr, column_name: &str) -> SystemResult<Collation> {
+        let select = match body {
+            SetExpr::Select(select) => select,
+            _ => return Ok(Collation::default()),
+        };
+        let mut collations = vec![];
+        for TableWithJoins { relation, joins } in select.from.iter() {
+            for relation in iter::once(relation).chain(joins.iter().map(|join| &join.relation)) {
+                if let TableFactor::Table { name, .. } = relation {
+                    if let [schema_name, table_name] = name.0.as_slice() {
+                        let table_columns =
+                            (self.storage.read().unwrap()).table_columns(&schema_name.value, &table_name.value)?;
+                        collations.extend(
+                            table_columns
+                                .into_iter()
+                                .filter(|column_definition| column_definition.name() == column_name)
+                                .map(|column_definition| column_definition.collation()),
+                        );
+                    }
+                }
+            }
+        }
+        Ok(match collations.as_slice() {
+            [collation] => *collation,
+            _ => Collation::default(),
+        })
+    }
+
+    // position of a plan node among analyzed ones and when its execution started
+    fn start_node(&mut self) -> (usize, Instant) {
+        self.depth += 1;
+        let position = self.analysis.as_ref().map(Vec::len).unwrap_or_default();
+        (position, Instant::now())
+    }
+
+    // plan nodes are analyzed after their children, but are printed before them
+    fn finish_node(&mut self, (position, start): (usize, Instant), label: String, rows: usize) {
+        self.depth -= 1;
+        let depth = self.depth;
+        if let Some(analysis) = self.analysis.as_mut() {
+            analysis.insert(
+                position,
+                AnalyzedNode {
+                    depth,
+                    label,
+                    rows,
+                    elapsed: start.elapsed(),
+                },
+            );
+        }
+    }
+
+    fn set_expr(&mut self, body: &SetExpr) -> SystemResult<Result<Projection, ()>> {
+        let node = self.start_node();
+        let result = self.evaluate_set_expr(body)?;
+        if let Ok((_, records)) = &result {
+            self.finish_node(node, node_label(body), records.len());
+        } else {
+            self.depth -= 1;
+        }
+        Ok(result)
+    }
+
+    fn evaluate_set_expr(&mut self, body: &SetExpr) -> SystemResult<Result<Projection, ()>> {
+        match body {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Query(query) => self.select_query(query),
+            SetExpr::Values(values) => Ok(self.values(values)),
+            SetExpr::SetOperation { op, all, left, right } => {
+                let left = match self.set_expr(left)? {
+                    Ok(left) => left,
+                    Err(()) => return Ok(Err(())),
+                };
+                let right = match self.set_expr(right)? {
+                    Ok(right) => right,
+                    Err(()) => return Ok(Err(())),
+                };
+                Ok(self.set_operation(op, *all, left, right))
+            }
+        }
+    }
+
+    // combines results of two queries, they have to have the same number of
+    // columns of compatible types
+    fn set_operation(
+        &self,
+        op: &SetOperator,
+        all: bool,
+        left: Projection,
+        right: Projection,
+    ) -> Result<Projection, ()> {
+        let operation = op.to_string();
+        let (left_description, left_records) = left;
+        let (right_description, right_records) = right;
+        if left_description.len() != right_description.len() {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .set_operation_columns_mismatch(operation)
+                    .build()))
+                .expect("To Send Query Result to Client");
+            return Err(());
+        }
+        let mut description = vec![];
+        for ((name, left_type), (_, right_type)) in left_description.into_iter().zip(right_description) {
+            match common_type(left_type, right_type) {
+                Some(sql_type) => description.push((name, sql_type)),
+                None => {
                     self.session
                         .send(Err(QueryErrorBuilder::new()
-                            .feature_not_supported(self.raw_sql_query.to_owned())
+                            .set_operation_types_mismatch(operation, left_type, right_type)
                             .build()))
                         .expect("To Send Query Result to Client");
-                    return Ok(());
+                    return Err(());
                 }
-            };
-            let table_columns = {
-                let projection = projection.clone();
-                let mut columns: Vec<String> = vec![];
-                for item in projection {
-                    match item {
-                        SelectItem::Wildcard => {
-                            let all_columns =
-                                (self.storage.lock().unwrap()).table_columns(&schema_name, &table_name)?;
-                            columns.extend(
-                                all_columns
-                                    .into_iter()
-                                    .map(|column_definition| column_definition.name())
-                                    .collect::<Vec<String>>(),
-                            )
+            }
+        }
+        let records = match op {
+            SetOperator::Union => {
+                let mut records = left_records;
+                records.extend(right_records);
+                if all {
+                    records
+                } else {
+                    distinct(records, &description)
+                }
+            }
+            SetOperator::Intersect => {
+                let mut right_counts = counts(right_records, &description);
+                let records = left_records
+                    .into_iter()
+                    .filter(|record| match right_counts.get_mut(&unified(record, &description)) {
+                        Some(count) if *count > 0 => {
+                            *count -= 1;
+                            true
                         }
-                        SelectItem::UnnamedExpr(Expr::Identifier(Ident { value, .. })) => columns.push(value.clone()),
-                        _ => {
+                        _ => false,
+                    })
+                    .collect();
+                if all {
+                    records
+                } else {
+                    distinct(records, &description)
+                }
+            }
+            SetOperator::Except => {
+                let mut right_counts = counts(right_records, &description);
+                let records = left_records
+                    .into_iter()
+                    .filter(|record| match right_counts.get_mut(&unified(record, &description)) {
+                        Some(count) if *count > 0 => {
+                            // without ALL any occurrence in the right query removes the record
+                            if all {
+                                *count -= 1;
+                            }
+                            false
+                        }
+                        _ => true,
+                    })
+                    .collect();
+                if all {
+                    records
+                } else {
+                    distinct(records, &description)
+                }
+            }
+        };
+        Ok((description, records))
+    }
+
+    fn select(&mut self, select: &Select) -> SystemResult<Result<Projection, ()>> {
+        let Select {
+            projection,
+            from,
+            selection,
+            ..
+        } = select;
+        if from.is_empty() {
+            return self.select_expressions(projection);
+        }
+        let mut relations = vec![];
+        for TableWithJoins { relation, joins } in from {
+            relations.push((relation, JoinKind::Cross));
+            for Join {
+                relation,
+                join_operator,
+            } in joins
+            {
+                match join_operator {
+                    JoinOperator::CrossJoin => relations.push((relation, JoinKind::Cross)),
+                    JoinOperator::LeftOuter(JoinConstraint::On(on)) => {
+                        relations.push((relation, JoinKind::LeftOuter(on.clone())))
+                    }
+                    _ => return Ok(self.not_supported()),
+                }
+            }
+        }
+        let mut tables = vec![];
+        for (relation, kind) in relations {
+            match relation {
+                TableFactor::Table { name, alias, .. } => match name.0.as_slice() {
+                    [schema_name, table_name] => {
+                        let alias = alias.as_ref().map(|alias| alias.name.value.clone());
+                        tables.push((
+                            Relation::Table(schema_name.to_string(), table_name.to_string(), alias),
+                            kind,
+                        ))
+                    }
+                    // system views are selected only on their own
+                    _ => return Ok(self.not_supported()),
+                },
+                TableFactor::Derived { subquery, alias, .. } => match self.derived_table(subquery, alias.as_ref())? {
+                    Ok(derived) => tables.push((derived, kind)),
+                    Err(()) => return Ok(Err(())),
+                },
+                _ => return Ok(self.not_supported()),
+            }
+        }
+        if let Some(name) = count_all(projection) {
+            return self.count(name, tables, selection.as_ref());
+        }
+        let columns_only = projection.iter().all(|item| {
+            matches!(
+                item,
+                SelectItem::Wildcard | SelectItem::UnnamedExpr(Expr::Identifier(_))
+            )
+        });
+        match (tables.as_slice(), selection) {
+            ([(Relation::Table(schema_name, table_name, _alias), _)], None) if columns_only => {
+                self.select_from_table(schema_name.clone(), table_name.clone(), projection)
+            }
+            _ => self.select_from_tables(tables, selection.as_ref(), projection),
+        }
+    }
+
+    // records of a subquery in FROM clause are computed before the outer query,
+    // its alias names the derived table and optionally its columns
+    fn derived_table(&mut self, subquery: &Query, alias: Option<&TableAlias>) -> SystemResult<Result<Relation, ()>> {
+        let alias = match alias {
+            Some(alias) => alias,
+            None => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new().subquery_without_alias().build()))
+                    .expect("To Send Query Result to Client");
+                return Ok(Err(()));
+            }
+        };
+        let (mut description, records) = match self.select_query(subquery)? {
+            Ok(projection) => projection,
+            Err(()) => return Ok(Err(())),
+        };
+        if alias.columns.len() > description.len() {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .too_many_column_aliases(alias.name.value.clone(), description.len(), alias.columns.len())
+                    .build()))
+                .expect("To Send Query Result to Client");
+            return Ok(Err(()));
+        }
+        for ((name, _), column_alias) in description.iter_mut().zip(alias.columns.iter()) {
+            *name = column_alias.value.clone();
+        }
+        Ok(Ok(Relation::Derived(alias.name.value.clone(), (description, records))))
+    }
+
+    // rows of VALUES list, as in PostgreSQL its columns are named `column1`,
+    // `column2` and so on and have common type of their values
+    fn values(&mut self, values: &Values) -> Result<Projection, ()> {
+        let width = values.0.first().map(Vec::len).unwrap_or_default();
+        if values.0.iter().any(|row| row.len() != width) {
+            self.session
+                .send(Err(QueryErrorBuilder::new().values_lists_length_mismatch().build()))
+                .expect("To Send Query Result to Client");
+            return Err(());
+        }
+        let mut types: Vec<Option<PostgreSqlType>> = vec![None; width];
+        let mut records = vec![];
+        for row in values.0.iter() {
+            let mut record = vec![];
+            for (index, expr) in row.iter().enumerate() {
+                let value = match ExpressionEvaluation::new(self.session.clone()).eval(expr)? {
+                    // NULL does not affect type of the column
+                    ExprResult::Null => {
+                        record.push(None);
+                        continue;
+                    }
+                    value => value,
+                };
+                let value_type = result_type(&value);
+                types[index] = match types[index] {
+                    None => Some(value_type),
+                    Some(column_type) => match common_type(column_type, value_type) {
+                        Some(common) => Some(common),
+                        None => {
                             self.session
                                 .send(Err(QueryErrorBuilder::new()
-                                    .feature_not_supported(self.raw_sql_query.to_owned())
+                                    .set_operation_types_mismatch("VALUES".to_owned(), column_type, value_type)
                                     .build()))
                                 .expect("To Send Query Result to Client");
-                            return Ok(());
+                            return Err(());
                         }
+                    },
+                };
+                record.push(Some(value.value()));
+            }
+            records.push(record);
+        }
+        let description = types
+            .into_iter()
+            .enumerate()
+            .map(|(index, sql_type)| {
+                (
+                    format!("column{}", index + 1),
+                    sql_type.unwrap_or(PostgreSqlType::VarChar),
+                )
+            })
+            .collect();
+        Ok((description, records))
+    }
+
+    // an unfiltered count of a single table is read from its row counter,
+    // otherwise records are counted after they are filtered
+    fn count(
+        &mut self,
+        name: String,
+        tables: Vec<(Relation, JoinKind)>,
+        selection: Option<&Expr>,
+    ) -> SystemResult<Result<Projection, ()>> {
+        let count =
+            if let ([(Relation::Table(schema_name, table_name, _alias), _)], None) = (tables.as_slice(), selection) {
+                if let Err(()) = self.check_privileges(schema_name, table_name)? {
+                    return Ok(Err(()));
+                }
+                match (self.storage.read().unwrap()).row_count(schema_name, table_name)? {
+                    Ok(count) => count + self.pending_records(schema_name, table_name, &[])?.len(),
+                    Err(error) => return Ok(self.report_table_error(schema_name.clone(), table_name.clone(), error)),
+                }
+            } else {
+                let (columns, records) = match self.join(tables)? {
+                    Ok(joined) => joined,
+                    Err(()) => return Ok(Err(())),
+                };
+                match selection {
+                    Some(selection) => match self.filter(selection, &columns, records)? {
+                        Ok(records) => records.len(),
+                        Err(()) => return Ok(Err(())),
+                    },
+                    None => records.len(),
+                }
+            };
+        Ok(Ok((
+            vec![(name, PostgreSqlType::BigInt)],
+            vec![vec![Some(count.to_string())]],
+        )))
+    }
+
+    fn select_from_table(
+        &mut self,
+        schema_name: String,
+        table_name: String,
+        projection: &[SelectItem],
+    ) -> SystemResult<Result<Projection, ()>> {
+        if let Err(()) = self.check_privileges(&schema_name, &table_name)? {
+            return Ok(Err(()));
+        }
+        let table_columns = {
+            let mut columns: Vec<String> = vec![];
+            for item in projection {
+                match item {
+                    SelectItem::Wildcard => {
+                        let all_columns = (self.storage.read().unwrap()).table_columns(&schema_name, &table_name)?;
+                        columns.extend(
+                            all_columns
+                                .into_iter()
+                                .map(|column_definition| column_definition.name())
+                                .collect::<Vec<String>>(),
+                        )
+                    }
+                    SelectItem::UnnamedExpr(Expr::Identifier(Ident { value, .. })) => columns.push(value.clone()),
+                    _ => return Ok(self.not_supported()),
+                }
+            }
+            columns
+        };
+        let selected = (self.storage.read().unwrap()).select_all_from(&schema_name, &table_name, table_columns)?;
+        match selected {
+            Ok((description, mut records)) => {
+                records.extend(self.pending_records(&schema_name, &table_name, &description)?);
+                Ok(Ok((
+                    description
+                        .into_iter()
+                        .map(|column_definition| (column_definition.name(), column_definition.sql_type().to_pg_types()))
+                        .collect(),
+                    nullable(records),
+                )))
+            }
+            Err(error) => Ok(self.report_table_error(schema_name, table_name, error)),
+        }
+    }
+
+    fn select_from_tables(
+        &mut self,
+        tables: Vec<(Relation, JoinKind)>,
+        selection: Option<&Expr>,
+        projection: &[SelectItem],
+    ) -> SystemResult<Result<Projection, ()>> {
+        let (columns, records) = match self.join(tables)? {
+            Ok(joined) => joined,
+            Err(()) => return Ok(Err(())),
+        };
+        let records = match selection {
+            Some(selection) => match self.filter(selection, &columns, records)? {
+                Ok(records) => records,
+                Err(()) => return Ok(Err(())),
+            },
+            None => records,
+        };
+
+        let mut outputs = vec![];
+        for item in projection {
+            let index = match item {
+                SelectItem::Wildcard => {
+                    outputs.extend((0..columns.len()).map(|index| Output::Column(columns[index].1.clone(), index)));
+                    continue;
+                }
+                SelectItem::UnnamedExpr(Expr::Identifier(Ident { value, .. })) => {
+                    self.column_index(&columns, None, value)
+                }
+                SelectItem::UnnamedExpr(Expr::CompoundIdentifier(identifiers)) if identifiers.len() == 2 => {
+                    self.column_index(&columns, Some(&identifiers[0].value), &identifiers[1].value)
+                }
+                SelectItem::UnnamedExpr(Expr::Subquery(subquery)) => {
+                    match self.scalar_subquery(subquery)? {
+                        Ok((name, sql_type, value)) => outputs.push(Output::Value(name, sql_type, value)),
+                        Err(()) => return Ok(Err(())),
+                    }
+                    continue;
+                }
+                SelectItem::ExprWithAlias {
+                    expr: Expr::Subquery(subquery),
+                    alias,
+                } => {
+                    match self.scalar_subquery(subquery)? {
+                        Ok((_, sql_type, value)) => outputs.push(Output::Value(alias.value.clone(), sql_type, value)),
+                        Err(()) => return Ok(Err(())),
+                    }
+                    continue;
+                }
+                SelectItem::UnnamedExpr(expr) => {
+                    if let Err(()) = self.check_columns(expr, &columns) {
+                        return Ok(Err(()));
+                    }
+                    outputs.push(Output::Computed("?column?".to_owned(), expr.clone()));
+                    continue;
+                }
+                // an alias may repeat a name of another result column
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    if let Err(()) = self.check_columns(expr, &columns) {
+                        return Ok(Err(()));
                     }
+                    outputs.push(Output::Computed(alias.value.clone(), expr.clone()));
+                    continue;
                 }
-                columns
+                _ => self.not_supported(),
             };
-            match (self.storage.lock().unwrap()).select_all_from(&schema_name, &table_name, table_columns)? {
-                Ok(records) => {
-                    let projection = (
-                        records
-                            .0
+            match index {
+                Ok(index) => outputs.push(Output::Column(columns[index].1.clone(), index)),
+                Err(()) => return Ok(Err(())),
+            }
+        }
+
+        let mut selected = vec![];
+        for record in records.iter() {
+            let mut values = vec![];
+            for output in outputs.iter() {
+                values.push(match output {
+                    Output::Column(_, index) => match &record[*index] {
+                        Some(value) => ExprResult::String(value.clone()),
+                        None => ExprResult::Null,
+                    },
+                    Output::Value(_, _, Some(value)) => ExprResult::String(value.clone()),
+                    Output::Value(_, _, None) => ExprResult::Null,
+                    Output::Computed(_, expr) => {
+                        match ExpressionEvaluation::new(self.session.clone()).eval(&with_values(expr, &columns, record))
+                        {
+                            Ok(value) => value,
+                            Err(()) => return Ok(Err(())),
+                        }
+                    }
+                });
+            }
+            selected.push(values);
+        }
+
+        let mut description = vec![];
+        for (position, output) in outputs.iter().enumerate() {
+            description.push(match output {
+                Output::Column(name, index) => (name.clone(), columns[*index].2),
+                Output::Value(name, sql_type, _) => (name.clone(), *sql_type),
+                Output::Computed(name, expr) => {
+                    let sql_type = computed_type(expr, &columns)
+                        .or_else(|| {
+                            selected
+                                .iter()
+                                .map(|values| &values[position])
+                                .find(|value| !matches!(value, ExprResult::Null))
+                                .map(result_type)
+                        })
+                        .unwrap_or(PostgreSqlType::VarChar);
+                    (name.clone(), sql_type)
+                }
+            });
+        }
+
+        let mut records = vec![];
+        for values in selected {
+            let mut record = vec![];
+            for (value, (_, sql_type)) in values.into_iter().zip(description.iter()) {
+                let value = match value {
+                    ExprResult::Null => {
+                        record.push(None);
+                        continue;
+                    }
+                    value => value.value(),
+                };
+                if let Err(()) = self.check_result_range(&value, *sql_type) {
+                    return Ok(Err(()));
+                }
+                record.push(Some(value));
+            }
+            records.push(record);
+        }
+
+        Ok(Ok((description, records)))
+    }
+
+    // column references of a computed result column have to be resolvable
+    // even when there are no records to compute it for
+    fn check_columns(&self, expr: &Expr, columns: &[JoinedColumn]) -> Result<(), ()> {
+        match expr {
+            Expr::Identifier(Ident { value, .. }) => self.column_index(columns, None, value).map(|_| ()),
+            Expr::CompoundIdentifier(identifiers) if identifiers.len() == 2 => self
+                .column_index(columns, Some(&identifiers[0].value), &identifiers[1].value)
+                .map(|_| ()),
+            Expr::Nested(expr) | Expr::UnaryOp { expr, .. } => self.check_columns(expr, columns),
+            Expr::BinaryOp { left, right, .. } => {
+                self.check_columns(left, columns)?;
+                self.check_columns(right, columns)
+            }
+            Expr::Value(_) => Ok(()),
+            _ => self.not_supported(),
+        }
+    }
+
+    // arithmetic over integer columns can overflow their type even though
+    // literals it is evaluated with are wider
+    fn check_result_range(&self, value: &str, sql_type: PostgreSqlType) -> Result<(), ()> {
+        let fits = match sql_type {
+            PostgreSqlType::SmallInt => value.parse::<i16>().is_ok(),
+            PostgreSqlType::Integer => value.parse::<i32>().is_ok(),
+            PostgreSqlType::BigInt => value.parse::<i64>().is_ok(),
+            _ => true,
+        };
+        if fits {
+            Ok(())
+        } else {
+            self.session
+                .send(Err(QueryErrorBuilder::new().integer_out_of_range(sql_type).build()))
+                .expect("To Send Query Result to Client");
+            Err(())
+        }
+    }
+
+    // every record of each table is combined with every record of the others,
+    // columns of all tables are merged in the order tables are listed in FROM
+    fn join(&mut self, tables: Vec<(Relation, JoinKind)>) -> SystemResult<Result<JoinedRecords, ()>> {
+        let mut columns: Vec<JoinedColumn> = vec![];
+        let mut records: Vec<Vec<Option<String>>> = vec![vec![]];
+        for (relation, kind) in tables {
+            let table_records = match relation {
+                Relation::Table(schema_name, table_name, alias) => {
+                    if let Err(()) = self.check_privileges(&schema_name, &table_name)? {
+                        return Ok(Err(()));
+                    }
+                    let column_names = (self.storage.read().unwrap())
+                        .table_columns(&schema_name, &table_name)?
+                        .into_iter()
+                        .map(|column_definition| column_definition.name())
+                        .collect();
+                    let (description, mut table_records) = match (self.storage.read().unwrap()).select_all_from(
+                        &schema_name,
+                        &table_name,
+                        column_names,
+                    )? {
+                        Ok(projection) => projection,
+                        Err(error) => return Ok(self.report_table_error(schema_name, table_name, error)),
+                    };
+                    table_records.extend(self.pending_records(&schema_name, &table_name, &description)?);
+                    // an alias hides the table name
+                    let qualifier = alias.unwrap_or(table_name);
+                    columns.extend(description.into_iter().map(|column_definition| {
+                        (
+                            qualifier.clone(),
+                            column_definition.name(),
+                            column_definition.sql_type().to_pg_types(),
+                            column_definition.collation(),
+                        )
+                    }));
+                    nullable(table_records)
+                }
+                Relation::Derived(alias, (description, table_records)) => {
+                    columns.extend(
+                        description
                             .into_iter()
-                            .map(|column_definition| {
-                                (column_definition.name(), column_definition.sql_type().to_pg_types())
-                            })
-                            .collect(),
-                        records.1,
+                            .map(|(name, sql_type)| (alias.clone(), name, sql_type, Collation::default())),
                     );
-                    self.session
-                        .send(Ok(QueryEvent::RecordsSelected(projection)))
-                        .expect("To Send Query Result to Client");
-                    Ok(())
+                    table_records
                 }
-                Err(OperationOnTableError::ColumnDoesNotExist(non_existing_columns)) => {
-                    self.session
-                        .send(Err(QueryErrorBuilder::new()
-                            .column_does_not_exist(non_existing_columns)
-                            .build()))
-                        .expect("To Send Query Result to Client");
-                    Ok(())
+            };
+            records = match kind {
+                JoinKind::Cross => records
+                    .into_iter()
+                    .flat_map(|record| {
+                        table_records.iter().map(move |table_record| {
+                            let mut joined = record.clone();
+                            joined.extend(table_record.iter().cloned());
+                            joined
+                        })
+                    })
+                    .collect(),
+                JoinKind::LeftOuter(on) => match self.left_outer_join(&on, &columns, records, table_records)? {
+                    Ok(records) => records,
+                    Err(()) => return Ok(Err(())),
+                },
+            };
+        }
+        Ok(Ok((columns, records)))
+    }
+
+    // a left record is joined with every right record the condition is true
+    // for, a left record that matched none of them is padded with NULLs
+    fn left_outer_join(
+        &mut self,
+        on: &Expr,
+        columns: &[JoinedColumn],
+        records: Vec<Vec<Option<String>>>,
+        table_records: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<Vec<Vec<Option<String>>>, ()>> {
+        let right_width = columns.len() - records.first().map(Vec::len).unwrap_or_default();
+        let mut subqueries = HashMap::new();
+        let mut joined_records = vec![];
+        for record in records {
+            let mut matched = false;
+            for table_record in table_records.iter() {
+                let mut joined = record.clone();
+                joined.extend(table_record.iter().cloned());
+                match self.predicate(on, columns, &joined, &mut subqueries)? {
+                    Ok(Some(true)) => {
+                        matched = true;
+                        joined_records.push(joined);
+                    }
+                    Ok(_) => {}
+                    Err(()) => return Ok(Err(())),
                 }
-                Err(OperationOnTableError::SchemaDoesNotExist) => {
+            }
+            if !matched {
+                let mut joined = record;
+                joined.extend(iter::repeat_n(None, right_width));
+                joined_records.push(joined);
+            }
+        }
+        Ok(Ok(joined_records))
+    }
+
+    // WHERE predicate, subqueries are not correlated with the outer query, so
+    // each of them is materialized once and reused for every record
+    fn filter(
+        &mut self,
+        selection: &Expr,
+        columns: &[JoinedColumn],
+        records: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<Vec<Vec<Option<String>>>, ()>> {
+        let mut subqueries = HashMap::new();
+        let mut filtered = vec![];
+        for record in records {
+            match self.predicate(selection, columns, &record, &mut subqueries)? {
+                Ok(Some(true)) => filtered.push(record),
+                Ok(_) => {}
+                Err(()) => return Ok(Err(())),
+            }
+        }
+        Ok(Ok(filtered))
+    }
+
+    // as in SQL a predicate is unknown, `None`, when it depends on NULL, such
+    // records are filtered out the same way as those it is false for
+    fn predicate(
+        &mut self,
+        expr: &Expr,
+        columns: &[JoinedColumn],
+        record: &[Option<String>],
+        subqueries: &mut HashMap<String, Projection>,
+    ) -> SystemResult<Result<Option<bool>, ()>> {
+        match expr {
+            Expr::Nested(expr) => self.predicate(expr, columns, record, subqueries),
+            Expr::Value(Value::Boolean(value)) => Ok(Ok(Some(*value))),
+            Expr::Value(Value::Null) => Ok(Ok(None)),
+            // the right operand is evaluated only if the left one does not
+            // decide the result
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => match self.predicate(left, columns, record, subqueries)? {
+                Ok(Some(false)) => Ok(Ok(Some(false))),
+                Ok(left) => Ok(self
+                    .predicate(right, columns, record, subqueries)?
+                    .map(|right| match (left, right) {
+                        (_, Some(false)) => Some(false),
+                        (Some(true), right) => right,
+                        _ => None,
+                    })),
+                Err(()) => Ok(Err(())),
+            },
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Or,
+                right,
+            } => match self.predicate(left, columns, record, subqueries)? {
+                Ok(Some(true)) => Ok(Ok(Some(true))),
+                Ok(left) => Ok(self
+                    .predicate(right, columns, record, subqueries)?
+                    .map(|right| match (left, right) {
+                        (_, Some(true)) => Some(true),
+                        (Some(false), right) => right,
+                        _ => None,
+                    })),
+                Err(()) => Ok(Err(())),
+            },
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+            } => Ok(self
+                .predicate(expr, columns, record, subqueries)?
+                .map(|value| value.map(|value| !value))),
+            // a boolean column is a condition by itself
+            Expr::Identifier(Ident { value, .. }) => Ok(self.boolean_column(columns, record, None, value)),
+            Expr::CompoundIdentifier(identifiers) if identifiers.len() == 2 => {
+                Ok(self.boolean_column(columns, record, Some(&identifiers[0].value), &identifiers[1].value))
+            }
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                let (value, value_type, collation) = match self.value(expr, columns, record) {
+                    Ok(value) => value,
+                    Err(()) => return Ok(Err(())),
+                };
+                let (description, records) = match self.subquery(subquery, subqueries)? {
+                    Ok(projection) => projection,
+                    Err(()) => return Ok(Err(())),
+                };
+                if description.len() != 1 {
                     self.session
-                        .send(Err(QueryErrorBuilder::new()
-                            .schema_does_not_exist(schema_name.to_owned())
-                            .build()))
+                        .send(Err(QueryErrorBuilder::new().subquery_has_too_many_columns().build()))
                         .expect("To Send Query Result to Client");
-                    Ok(())
+                    return Ok(Err(()));
                 }
-                Err(OperationOnTableError::TableDoesNotExist) => {
-                    self.session
-                        .send(Err(QueryErrorBuilder::new()
-                            .table_does_not_exist(schema_name.to_owned() + "." + table_name.as_str())
-                            .build()))
-                        .expect("To Send Query Result to Client");
-                    Ok(())
+                let value = match value {
+                    Some(value) => value,
+                    None => return Ok(Ok(None)),
+                };
+                let sql_type = comparison_type(value_type, Some(description[0].1));
+                let found = records.iter().any(|subquery_record| match &subquery_record[0] {
+                    Some(subquery_value) => same_value(subquery_value, &value, sql_type, collation),
+                    None => false,
+                });
+                // a value that is not found among ones with NULL may equal to it
+                if !found && records.iter().any(|subquery_record| subquery_record[0].is_none()) {
+                    return Ok(Ok(None));
                 }
-                _ => {
-                    self.session
-                        .send(Err(QueryErrorBuilder::new()
-                            .feature_not_supported(self.raw_sql_query.to_owned())
-                            .build()))
-                        .expect("To Send Query Result to Client");
-                    Ok(())
+                Ok(Ok(Some(found != *negated)))
+            }
+            Expr::Exists(subquery) => match self.subquery(subquery, subqueries)? {
+                Ok((_, records)) => Ok(Ok(Some(!records.is_empty()))),
+                Err(()) => Ok(Err(())),
+            },
+            // a literal is compared according to type and collation of the
+            // column it is compared with
+            Expr::BinaryOp { left, op, right }
+                if matches!(
+                    op,
+                    BinaryOperator::Eq
+                        | BinaryOperator::NotEq
+                        | BinaryOperator::Gt
+                        | BinaryOperator::GtEq
+                        | BinaryOperator::Lt
+                        | BinaryOperator::LtEq
+                ) =>
+            {
+                let (left, left_type, left_collation) = match self.value(left, columns, record) {
+                    Ok(value) => value,
+                    Err(()) => return Ok(Err(())),
+                };
+                let (right, right_type, right_collation) = match self.value(right, columns, record) {
+                    Ok(value) => value,
+                    Err(()) => return Ok(Err(())),
+                };
+                let (left, right) = match (left, right) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => return Ok(Ok(None)),
+                };
+                let collation = if left_collation == Collation::default() {
+                    right_collation
+                } else {
+                    left_collation
+                };
+                let ordering = compare_typed(&left, &right, comparison_type(left_type, right_type), collation);
+                Ok(Ok(Some(match op {
+                    BinaryOperator::Eq => ordering == Ordering::Equal,
+                    BinaryOperator::NotEq => ordering != Ordering::Equal,
+                    BinaryOperator::Gt => ordering == Ordering::Greater,
+                    BinaryOperator::GtEq => ordering != Ordering::Less,
+                    BinaryOperator::Lt => ordering == Ordering::Less,
+                    _ => ordering != Ordering::Greater,
+                })))
+            }
+            _ => Ok(self.not_supported()),
+        }
+    }
+
+    fn boolean_column(
+        &self,
+        columns: &[JoinedColumn],
+        record: &[Option<String>],
+        qualifier: Option<&str>,
+        column_name: &str,
+    ) -> Result<Option<bool>, ()> {
+        let index = self.column_index(columns, qualifier, column_name)?;
+        match columns[index].2 {
+            // booleans are read from storage as `t` or `f`
+            PostgreSqlType::Bool => Ok(record[index].as_ref().map(|value| value == "t")),
+            pg_type => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .argument_must_be_boolean("WHERE".to_owned(), pg_type)
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+        }
+    }
+
+    fn subquery<'s>(
+        &mut self,
+        query: &Query,
+        subqueries: &'s mut HashMap<String, Projection>,
+    ) -> SystemResult<Result<&'s Projection, ()>> {
+        let key = query.to_string();
+        if !subqueries.contains_key(&key) {
+            match self.select_query(query)? {
+                Ok(projection) => {
+                    subqueries.insert(key.clone(), projection);
                 }
+                Err(()) => return Ok(Err(())),
             }
-        } else {
+        }
+        Ok(Ok(&subqueries[&key]))
+    }
+
+    // subquery used as a value has to return a single column of a single row,
+    // it is not correlated with the outer query and is evaluated once
+    fn scalar_subquery(&mut self, query: &Query) -> SystemResult<Result<ScalarValue, ()>> {
+        let (mut description, mut records) = match self.select_query(query)? {
+            Ok(projection) => projection,
+            Err(()) => return Ok(Err(())),
+        };
+        if description.len() != 1 {
             self.session
-                .send(Err(QueryErrorBuilder::new()
-                    .feature_not_supported(self.raw_sql_query.to_owned())
-                    .build()))
+                .send(Err(QueryErrorBuilder::new().subquery_has_too_many_columns().build()))
                 .expect("To Send Query Result to Client");
-            Ok(())
+            return Ok(Err(()));
+        }
+        match records.len() {
+            1 => {
+                let (name, sql_type) = description.remove(0);
+                Ok(Ok((name, sql_type, records.remove(0).remove(0))))
+            }
+            // empty subquery evaluates to NULL
+            0 => {
+                let (name, sql_type) = description.remove(0);
+                Ok(Ok((name, sql_type, None)))
+            }
+            _ => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .subquery_returned_more_than_one_row()
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(Err(()))
+            }
+        }
+    }
+
+    // value is compared according to type and collation of the column it is
+    // read from, a string literal has no type of its own
+    fn value(&self, expr: &Expr, columns: &[JoinedColumn], record: &[Option<String>]) -> Result<ComparedValue, ()> {
+        let index = match expr {
+            Expr::Identifier(Ident { value, .. }) => self.column_index(columns, None, value)?,
+            Expr::CompoundIdentifier(identifiers) if identifiers.len() == 2 => {
+                self.column_index(columns, Some(&identifiers[0].value), &identifiers[1].value)?
+            }
+            Expr::Value(Value::Number(number)) => {
+                return Ok((
+                    Some(number.to_string()),
+                    Some(PostgreSqlType::Decimal),
+                    Collation::default(),
+                ))
+            }
+            Expr::Value(Value::SingleQuotedString(value)) => {
+                return Ok((Some(value.clone()), None, Collation::default()))
+            }
+            Expr::Value(Value::Null) => return Ok((None, None, Collation::default())),
+            _ => return self.not_supported(),
+        };
+        Ok((record[index].clone(), Some(columns[index].2), columns[index].3))
+    }
+
+    fn column_index(&self, columns: &[JoinedColumn], qualifier: Option<&str>, column_name: &str) -> Result<usize, ()> {
+        let found = columns
+            .iter()
+            .enumerate()
+            .filter(|(_, (table, name, _, _))| name == column_name && qualifier.iter().all(|q| q == table))
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+        let full_name = match qualifier {
+            Some(qualifier) => qualifier.to_owned() + "." + column_name,
+            None => column_name.to_owned(),
+        };
+        match found.as_slice() {
+            [index] => Ok(*index),
+            [] => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .column_does_not_exist(vec![full_name])
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+            _ => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new().ambiguous_column(full_name).build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
         }
     }
+
+    // values of the columns in rows the session inserted into the table and
+    // did not commit yet
+    fn pending_records(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+        columns: &[ColumnDefinition],
+    ) -> SystemResult<Vec<Vec<String>>> {
+        let pending = match self.pending {
+            Some(pending) => pending.records(schema_name, table_name).collect::<Vec<_>>(),
+            None => return Ok(vec![]),
+        };
+        if pending.is_empty() {
+            return Ok(vec![]);
+        }
+        let table_columns = (self.storage.read().unwrap()).table_columns(schema_name, table_name)?;
+        let indexes = columns
+            .iter()
+            .filter_map(|column| {
+                table_columns
+                    .iter()
+                    .position(|table_column| table_column.name() == column.name())
+            })
+            .collect::<Vec<usize>>();
+        Ok(pending
+            .into_iter()
+            .map(|record| indexes.iter().map(|index| record[*index].clone()).collect())
+            .collect())
+    }
+
+    fn check_privileges(&self, schema_name: &str, table_name: &str) -> SystemResult<Result<(), ()>> {
+        check_select_privilege(&self.storage, self.user, &self.session, schema_name, table_name)
+    }
+
+    fn report_table_error<T>(
+        &self,
+        schema_name: String,
+        table_name: String,
+        error: OperationOnTableError,
+    ) -> Result<T, ()> {
+        match error {
+            OperationOnTableError::ColumnDoesNotExist(non_existing_columns) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .column_does_not_exist(non_existing_columns)
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+            OperationOnTableError::SchemaDoesNotExist => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new().schema_does_not_exist(schema_name).build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+            OperationOnTableError::TableDoesNotExist => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .table_does_not_exist(schema_name + "." + table_name.as_str())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+            _ => self.not_supported(),
+        }
+    }
+
+    // `LIMIT n OFFSET m` and standard `OFFSET m ROWS FETCH FIRST n ROWS ONLY`
+    // have the same semantics, only one of them can be used in a query
+    fn pagination(&self, query: &Query) -> Result<Pagination, ()> {
+        let Query {
+            limit, offset, fetch, ..
+        } = query;
+        let limit = match (limit, fetch) {
+            (None, None) => None,
+            (Some(limit), None) => Some(self.row_count(limit)?),
+            (
+                None,
+                Some(Fetch {
+                    with_ties: false,
+                    percent: false,
+                    quantity,
+                }),
+            ) => match quantity {
+                Some(quantity) => Some(self.row_count(quantity)?),
+                // `FETCH FIRST ROW ONLY`
+                None => Some(1),
+            },
+            _ => return self.not_supported(),
+        };
+        let offset = match offset {
+            Some(Offset { value, .. }) => self.row_count(value)?,
+            None => 0,
+        };
+        Ok(Pagination { offset, limit })
+    }
+
+    fn row_count(&self, expr: &Expr) -> Result<usize, ()> {
+        match expr {
+            Expr::Value(Value::Number(number)) => number.to_string().parse().or_else(|_| self.not_supported()),
+            _ => self.not_supported(),
+        }
+    }
+
+    fn not_supported<T>(&self) -> Result<T, ()> {
+        self.session
+            .send(Err(QueryErrorBuilder::new()
+                .feature_not_supported(self.raw_sql_query.to_owned())
+                .build()))
+            .expect("To Send Query Result to Client");
+        Err(())
+    }
+
+    fn select_expressions(&mut self, projection: &[SelectItem]) -> SystemResult<Result<Projection, ()>> {
+        let mut description = vec![];
+        let mut row = vec![];
+        for item in projection {
+            let subquery = match item {
+                SelectItem::UnnamedExpr(Expr::Subquery(subquery)) => Some((None, subquery)),
+                SelectItem::ExprWithAlias {
+                    expr: Expr::Subquery(subquery),
+                    alias,
+                } => Some((Some(alias.value.clone()), subquery)),
+                _ => None,
+            };
+            if let Some((alias, subquery)) = subquery {
+                match self.scalar_subquery(subquery)? {
+                    Ok((name, sql_type, value)) => {
+                        description.push((alias.unwrap_or(name), sql_type));
+                        row.push(value);
+                    }
+                    Err(()) => return Ok(Err(())),
+                }
+                continue;
+            }
+            let (name, expr) = match item {
+                SelectItem::UnnamedExpr(expr) => match expr {
+                    Expr::Function(function) => (function.name.to_string().to_lowercase(), expr),
+                    _ => ("?column?".to_owned(), expr),
+                },
+                SelectItem::ExprWithAlias { expr, alias } => (alias.value.clone(), expr),
+                _ => return Ok(self.not_supported()),
+            };
+            let expr = match SequenceFunctions::new(self.storage.clone(), self.sequence_values, self.session.clone())
+                .resolve(expr)?
+            {
+                Ok(expr) => expr,
+                Err(()) => return Ok(Err(())),
+            };
+            match ExpressionEvaluation::new(self.session.clone()).eval(&expr) {
+                Ok(ExprResult::Number(value)) => {
+                    description.push((name, PostgreSqlType::Decimal));
+                    row.push(Some(value.to_string()));
+                }
+                Ok(ExprResult::String(value)) => {
+                    description.push((name, PostgreSqlType::VarChar));
+                    row.push(Some(value));
+                }
+                Ok(ExprResult::Date(value)) => {
+                    description.push((name, PostgreSqlType::Date));
+                    row.push(Some(value.to_string()));
+                }
+                Ok(ExprResult::Timestamp(value)) => {
+                    description.push((name, PostgreSqlType::Timestamp));
+                    row.push(Some(value.to_string()));
+                }
+                Ok(ExprResult::Interval(value)) => {
+                    description.push((name, PostgreSqlType::Interval));
+                    row.push(Some(value.to_string()));
+                }
+                Ok(ExprResult::Null) => {
+                    description.push((name, PostgreSqlType::VarChar));
+                    row.push(None);
+                }
+                Err(()) => return Ok(Err(())),
+            }
+        }
+        Ok(Ok((description, vec![row])))
+    }
+}
+
+// projected item is either a column of joined records, a value computed
+// once for all of them or an expression computed for each record
+enum Output {
+    Column(String, usize),
+    Value(String, PostgreSqlType, Option<String>),
+    Computed(String, Expr),
+}
+
+// relation listed in FROM clause, records of a derived table are computed
+// before they are joined with others
+enum Relation {
+    // schema name, table name and alias
+    Table(String, String, Option<String>),
+    // alias, columns and records
+    Derived(String, Projection),
+}
+
+// how records of a relation are combined with records of relations listed
+// before it in FROM clause
+enum JoinKind {
+    Cross,
+    // condition of LEFT JOIN ... ON
+    LeftOuter(Expr),
+}
+
+// table name or its alias, column name, column type and collation
+type JoinedColumn = (String, String, PostgreSqlType, Collation);
+// value of a comparison operand, `None` for NULL, along with its type, if it
+// has one, and collation
+type ComparedValue = (Option<String>, Option<PostgreSqlType>, Collation);
+type JoinedRecords = (Vec<JoinedColumn>, Vec<Vec<Option<String>>>);
+// name and type of a subquery column along with its only value, `None` for NULL
+type ScalarValue = (String, PostgreSqlType, Option<String>);
+
+#[derive(Debug, Clone, Copy)]
+struct Pagination {
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl Pagination {
+    fn is_empty(&self) -> bool {
+        self.offset == 0 && self.limit.is_none()
+    }
+
+    fn apply(self, rows: Vec<Vec<Option<String>>>) -> Vec<Vec<Option<String>>> {
+        let rows = rows.into_iter().skip(self.offset);
+        match self.limit {
+            Some(limit) => rows.take(limit).collect(),
+            None => rows.collect(),
+        }
+    }
+}
+
+struct AnalyzedNode {
+    depth: usize,
+    label: String,
+    rows: usize,
+    elapsed: Duration,
+}
+
+impl std::fmt::Display for AnalyzedNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.depth > 0 {
+            write!(f, "{}->  ", "  ".repeat(self.depth))?;
+        }
+        write!(
+            f,
+            "{} (actual time={} ms rows={})",
+            self.label,
+            milliseconds(self.elapsed),
+            self.rows
+        )
+    }
+}
+
+// name of the result column when `count(*)` is the only projected item
+fn count_all(projection: &[SelectItem]) -> Option<String> {
+    let (expr, alias) = match projection {
+        [SelectItem::UnnamedExpr(expr)] => (expr, None),
+        [SelectItem::ExprWithAlias { expr, alias }] => (expr, Some(alias)),
+        _ => return None,
+    };
+    match expr {
+        Expr::Function(Function {
+            name,
+            args,
+            over: None,
+            distinct: false,
+        }) if name.to_string().eq_ignore_ascii_case("count") && matches!(args.as_slice(), [Expr::Wildcard]) => {
+            Some(alias.map_or_else(|| "count".to_owned(), |alias| alias.value.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+// name of a plan node that evaluates the query part
+fn node_label(body: &SetExpr) -> String {
+    match body {
+        SetExpr::Select(select) => match select.from.as_slice() {
+            [] => "Result".to_owned(),
+            [TableWithJoins {
+                relation: TableFactor::Table { name, .. },
+                joins,
+            }] if joins.is_empty() => format!("Seq Scan on {}", name),
+            _ => "Nested Loop".to_owned(),
+        },
+        SetExpr::SetOperation { op, all: true, .. } => format!("SetOp {} All", op),
+        SetExpr::SetOperation { op, all: false, .. } => format!("SetOp {}", op),
+        SetExpr::Values(_) => "Values Scan".to_owned(),
+        _ => "Subquery Scan".to_owned(),
+    }
+}
+
+// the type both column types are implicitly converted to, numbers are widened
+// in `smallint`, `integer`, `bigint`, `decimal`, `real`, `double` order
+fn common_type(left: PostgreSqlType, right: PostgreSqlType) -> Option<PostgreSqlType> {
+    const NUMERIC: [PostgreSqlType; 6] = [
+        PostgreSqlType::SmallInt,
+        PostgreSqlType::Integer,
+        PostgreSqlType::BigInt,
+        PostgreSqlType::Decimal,
+        PostgreSqlType::Real,
+        PostgreSqlType::DoublePrecision,
+    ];
+    const STRING: [PostgreSqlType; 2] = [PostgreSqlType::Char, PostgreSqlType::VarChar];
+    if left == right {
+        return Some(left);
+    }
+    for category in [&NUMERIC[..], &STRING[..]].iter() {
+        let left_position = category.iter().position(|sql_type| *sql_type == left);
+        let right_position = category.iter().position(|sql_type| *sql_type == right);
+        if let (Some(left_position), Some(right_position)) = (left_position, right_position) {
+            return Some(category[left_position.max(right_position)]);
+        }
+    }
+    None
+}
+
+// operands are compared as values of the column type, a literal takes type
+// of the column it is compared with, numbers of different types are compared
+// as their common type and anything else as strings
+fn comparison_type(left: Option<PostgreSqlType>, right: Option<PostgreSqlType>) -> PostgreSqlType {
+    match (left, right) {
+        (Some(left), Some(right)) if is_numeric(left) && is_numeric(right) => {
+            common_type(left, right).unwrap_or(PostgreSqlType::Decimal)
+        }
+        (Some(left), Some(right)) if is_numeric(left) || is_numeric(right) => PostgreSqlType::VarChar,
+        (Some(sql_type), _) | (None, Some(sql_type)) => sql_type,
+        (None, None) => PostgreSqlType::VarChar,
+    }
+}
+
+// numbers are compared by value, so that `2` of `smallint` column equals to
+// `2.0` of `decimal` one, strings according to collation
+fn same_value(left: &str, right: &str, sql_type: PostgreSqlType, collation: Collation) -> bool {
+    compare_typed(left, right, sql_type, collation) == Ordering::Equal
+}
+
+// place of NULLs does not depend on whether values are sorted in ascending
+// or descending order
+fn nulls_ordering(left_is_null: bool, right_is_null: bool, nulls_first: bool) -> Ordering {
+    match (left_is_null, right_is_null) {
+        (true, false) if nulls_first => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, true) if nulls_first => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        _ => Ordering::Equal,
+    }
+}
+
+// values of numeric types are compared as numbers, floating point ones along
+// with infinities and `NaN` that is greater than any other number. Values
+// that are not numbers and values of other types are compared as strings
+fn compare_typed(left: &str, right: &str, sql_type: PostgreSqlType, collation: Collation) -> Ordering {
+    match sql_type {
+        PostgreSqlType::Real | PostgreSqlType::DoublePrecision => {
+            if let (Ok(left), Ok(right)) = (f64::from_str(left), f64::from_str(right)) {
+                return OrderedFloat(left).cmp(&OrderedFloat(right));
+            }
+        }
+        sql_type if is_numeric(sql_type) => {
+            if let (Ok(left), Ok(right)) = (BigDecimal::from_str(left), BigDecimal::from_str(right)) {
+                return left.cmp(&right);
+            }
+        }
+        _ => {}
+    }
+    collation.compare(left, right)
+}
+
+// expression where column references are replaced with values of a record,
+// columns are checked to exist beforehand
+fn with_values(expr: &Expr, columns: &[JoinedColumn], record: &[Option<String>]) -> Expr {
+    match expr {
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+            let index = columns
+                .iter()
+                .position(|(table, name, _, _)| match expr {
+                    Expr::Identifier(Ident { value, .. }) => name == value,
+                    Expr::CompoundIdentifier(identifiers) => {
+                        *table == identifiers[0].value && *name == identifiers[1].value
+                    }
+                    _ => false,
+                })
+                .expect("column is checked to exist");
+            let value = match &record[index] {
+                Some(value) => value.clone(),
+                None => return Expr::Value(Value::Null),
+            };
+            // values are substituted as literals of the column type, so that
+            // they are evaluated as values of that type
+            match columns[index].2 {
+                PostgreSqlType::Date => Expr::TypedString {
+                    data_type: DataType::Date,
+                    value,
+                },
+                PostgreSqlType::Timestamp => Expr::TypedString {
+                    data_type: DataType::Timestamp,
+                    value,
+                },
+                PostgreSqlType::Interval => Expr::Value(Value::Interval {
+                    value,
+                    leading_field: None,
+                    leading_precision: None,
+                    last_field: None,
+                    fractional_seconds_precision: None,
+                }),
+                sql_type => match BigDecimal::from_str(&value) {
+                    Ok(number) if is_numeric(sql_type) => Expr::Value(Value::Number(number)),
+                    _ => Expr::Value(Value::SingleQuotedString(value)),
+                },
+            }
+        }
+        Expr::Nested(expr) => Expr::Nested(Box::new(with_values(expr, columns, record))),
+        Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+            op: op.clone(),
+            expr: Box::new(with_values(expr, columns, record)),
+        },
+        Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
+            left: Box::new(with_values(left, columns, record)),
+            op: op.clone(),
+            right: Box::new(with_values(right, columns, record)),
+        },
+        expr => expr.clone(),
+    }
+}
+
+// type of a computed column when it can be told from types of the columns
+// it uses: arithmetic results in the widest numeric type of its operands,
+// where integer literals are at least `integer`
+fn computed_type(expr: &Expr, columns: &[JoinedColumn]) -> Option<PostgreSqlType> {
+    match expr {
+        Expr::Identifier(Ident { value, .. }) => columns
+            .iter()
+            .find(|(_, name, _, _)| name == value)
+            .map(|(_, _, sql_type, _)| *sql_type),
+        Expr::CompoundIdentifier(identifiers) if identifiers.len() == 2 => columns
+            .iter()
+            .find(|(table, name, _, _)| *table == identifiers[0].value && *name == identifiers[1].value)
+            .map(|(_, _, sql_type, _)| *sql_type),
+        Expr::Value(Value::Number(number)) => match number.to_string().parse::<i64>() {
+            Ok(number) if number >= i32::min_value() as i64 && number <= i32::max_value() as i64 => {
+                Some(PostgreSqlType::Integer)
+            }
+            Ok(_) => Some(PostgreSqlType::BigInt),
+            Err(_) => Some(PostgreSqlType::Decimal),
+        },
+        Expr::Value(Value::SingleQuotedString(_)) => Some(PostgreSqlType::VarChar),
+        Expr::Nested(expr) | Expr::UnaryOp { expr, .. } => computed_type(expr, columns),
+        Expr::BinaryOp {
+            op: BinaryOperator::StringConcat,
+            ..
+        } => Some(PostgreSqlType::VarChar),
+        Expr::BinaryOp { left, right, .. } => {
+            let left = computed_type(left, columns)?;
+            let right = computed_type(right, columns)?;
+            if is_numeric(left) && is_numeric(right) {
+                common_type(left, right)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn result_type(result: &ExprResult) -> PostgreSqlType {
+    match result {
+        ExprResult::Number(_) => PostgreSqlType::Decimal,
+        ExprResult::String(_) => PostgreSqlType::VarChar,
+        ExprResult::Date(_) => PostgreSqlType::Date,
+        ExprResult::Timestamp(_) => PostgreSqlType::Timestamp,
+        ExprResult::Interval(_) => PostgreSqlType::Interval,
+        ExprResult::Null => PostgreSqlType::VarChar,
+    }
+}
+
+fn is_numeric(sql_type: PostgreSqlType) -> bool {
+    matches!(
+        sql_type,
+        PostgreSqlType::SmallInt
+            | PostgreSqlType::Integer
+            | PostgreSqlType::BigInt
+            | PostgreSqlType::Decimal
+            | PostgreSqlType::Real
+            | PostgreSqlType::DoublePrecision
+    )
+}
+
+// number of occurrences of each record
+fn counts(
+    records: Vec<Vec<Option<String>>>,
+    description: &[(String, PostgreSqlType)],
+) -> HashMap<Vec<Option<String>>, usize> {
+    let mut counts = HashMap::new();
+    for record in records {
+        *counts.entry(unified(&record, description)).or_insert(0) += 1;
+    }
+    counts
+}
+
+// removes duplicated records keeping the first occurrence, as in PostgreSQL
+// NULLs are not distinct from each other
+fn distinct(records: Vec<Vec<Option<String>>>, description: &[(String, PostgreSqlType)]) -> Vec<Vec<Option<String>>> {
+    let mut seen = HashSet::new();
+    records
+        .into_iter()
+        .filter(|record| seen.insert(unified(record, description)))
+        .collect()
+}
+
+// values of a record converted to types of result columns, records of set
+// operation branches are compared by them, so that `1` of `integer` column is
+// the same value as `1.0` of `decimal` one
+fn unified(record: &[Option<String>], description: &[(String, PostgreSqlType)]) -> Vec<Option<String>> {
+    record
+        .iter()
+        .zip(description.iter())
+        .map(|(value, (_, sql_type))| {
+            value.as_ref().map(|value| match sql_type {
+                PostgreSqlType::Real | PostgreSqlType::DoublePrecision => match f64::from_str(value) {
+                    Ok(number) => number.to_string(),
+                    Err(_) => value.clone(),
+                },
+                sql_type if is_numeric(*sql_type) => match BigDecimal::from_str(value) {
+                    // numbers that differ only in trailing fractional zeros are equal
+                    Ok(number) if value.contains('.') => {
+                        let number = number.to_string();
+                        number.trim_end_matches('0').trim_end_matches('.').to_owned()
+                    }
+                    Ok(number) => number.to_string(),
+                    Err(_) => value.clone(),
+                },
+                _ => value.clone(),
+            })
+        })
+        .collect()
+}
+
+// records read from storage, there are no NULLs among them
+fn nullable(records: Vec<Vec<String>>) -> Vec<Vec<Option<String>>> {
+    records
+        .into_iter()
+        .map(|record| record.into_iter().map(Some).collect())
+        .collect()
 }
@@ -12,22 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::dml::ExpressionEvaluation;
+use crate::dml::{report_constraint_violation, ExpressionEvaluation};
 use kernel::SystemResult;
 use protocol::{
     results::{QueryErrorBuilder, QueryEvent},
     Sender,
 };
-use sql_types::ConstraintError;
 use sqlparser::ast::{Assignment, Expr, Ident, ObjectName, UnaryOperator, Value};
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, ColumnDefinition, OperationOnTableError};
+use std::sync::Arc;
+use storage::{backend::BackendStorage, frontend::SharedStorage, OperationOnTableError};
 
 pub(crate) struct UpdateCommand<'uc, P: BackendStorage> {
     raw_sql_query: &'uc str,
     name: ObjectName,
     assignments: Vec<Assignment>,
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    storage: SharedStorage<P>,
     session: Arc<dyn Sender>,
 }
 
@@ -36,7 +35,7 @@ impl<'uc, P: BackendStorage> UpdateCommand<'uc, P> {
         raw_sql_query: &'uc str,
         name: ObjectName,
         assignments: Vec<Assignment>,
-        storage: Arc<Mutex<FrontendStorage<P>>>,
+        storage: SharedStorage<P>,
         session: Arc<dyn Sender>,
     ) -> UpdateCommand<'uc, P> {
         UpdateCommand {
@@ -61,7 +60,13 @@ impl<'uc, P: BackendStorage> UpdateCommand<'uc, P> {
                 Expr::Value(Value::Number(val)) => val.to_string(),
                 Expr::Value(Value::SingleQuotedString(v)) => v.to_string(),
                 Expr::UnaryOp { op, expr } => match (op, &**expr) {
-                    (UnaryOperator::Minus, Expr::Value(Value::Number(v))) => "-".to_owned() + v.to_string().as_str(),
+                    (UnaryOperator::Minus, Expr::Value(Value::Number(v))) => (-v).to_string(),
+                    (UnaryOperator::Minus, _) | (UnaryOperator::Plus, _) => {
+                        match ExpressionEvaluation::new(self.session.clone()).eval_value(value) {
+                            Ok(value) => value,
+                            Err(()) => return Ok(()),
+                        }
+                    }
                     (op, expr) => {
                         self.session
                             .send(Err(QueryErrorBuilder::new()
@@ -71,10 +76,12 @@ impl<'uc, P: BackendStorage> UpdateCommand<'uc, P> {
                         return Ok(());
                     }
                 },
-                expr @ Expr::BinaryOp { .. } => match ExpressionEvaluation::new(self.session.clone()).eval(expr) {
-                    Ok(expr_result) => expr_result.value(),
-                    Err(()) => return Ok(()),
-                },
+                expr @ Expr::BinaryOp { .. } | expr @ Expr::Nested(_) => {
+                    match ExpressionEvaluation::new(self.session.clone()).eval_value(expr) {
+                        Ok(value) => value,
+                        Err(()) => return Ok(()),
+                    }
+                }
                 expr => {
                     self.session
                         .send(Err(QueryErrorBuilder::new().syntax_error(expr.to_string()).build()))
@@ -86,7 +93,7 @@ impl<'uc, P: BackendStorage> UpdateCommand<'uc, P> {
             to_update.push((column.to_owned(), value))
         }
 
-        match (self.storage.lock().unwrap()).update_all(&schema_name, &table_name, to_update)? {
+        match (self.storage.write().unwrap()).update_all(&schema_name, &table_name, to_update)? {
             Ok(records_number) => {
                 self.session
                     .send(Ok(QueryEvent::RecordsUpdated(records_number)))
@@ -117,34 +124,9 @@ impl<'uc, P: BackendStorage> UpdateCommand<'uc, P> {
             }
             Err(OperationOnTableError::ConstraintViolations(constraint_errors, row_index)) => {
                 let mut builder = QueryErrorBuilder::new();
-                let constraint_error_mapper = |(err, column_definition): &(ConstraintError, ColumnDefinition)| match err
-                {
-                    ConstraintError::OutOfRange => {
-                        builder.out_of_range(
-                            column_definition.sql_type().to_pg_types(),
-                            column_definition.name(),
-                            row_index,
-                        );
-                    }
-                    ConstraintError::TypeMismatch(value) => {
-                        builder.type_mismatch(
-                            value,
-                            column_definition.sql_type().to_pg_types(),
-                            column_definition.name(),
-                            row_index,
-                        );
-                    }
-                    ConstraintError::ValueTooLong(len) => {
-                        builder.string_length_mismatch(
-                            column_definition.sql_type().to_pg_types(),
-                            *len,
-                            column_definition.name(),
-                            row_index,
-                        );
-                    }
-                };
-
-                constraint_errors.iter().for_each(constraint_error_mapper);
+                for (error, column_definition) in constraint_errors.iter() {
+                    report_constraint_violation(&mut builder, error, column_definition, row_index);
+                }
                 self.session
                     .send(Err(builder.build()))
                     .expect("To Send Query Result to Client");
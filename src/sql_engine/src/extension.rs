@@ -0,0 +1,542 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Statements that `sqlparser` does not support are recognized here before
+// the query is handed over to it.
+use sqlparser::{
+    ast::{DataType, Ident, ObjectName, Query, SelectItem, Statement},
+    dialect::{keywords::Keyword, PostgreSqlDialect},
+    parser::{IsOptional, Parser, ParserError},
+    tokenizer::{Token, Tokenizer},
+};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtendedStatement {
+    Vacuum(Option<ObjectName>),
+    // only SELECT privilege is supported
+    Grant {
+        table_name: ObjectName,
+        grantee: String,
+    },
+    Revoke {
+        table_name: ObjectName,
+        grantee: String,
+    },
+    CreateSequence(ObjectName),
+    // `sqlparser` does not parse `CREATE SCHEMA IF NOT EXISTS`
+    CreateSchemaIfNotExists(ObjectName),
+    CreateDatabase(String),
+    DropDatabase(String),
+    // only analyzing queries is supported, plain EXPLAIN is not
+    ExplainAnalyze(Box<Query>),
+    // `SET TIME ZONE` is an alias of `SET timezone`
+    SetTimeZone(String),
+    // `None` for `RESET ALL`
+    Reset(Option<String>),
+    // only `TO STDOUT` in text format is supported
+    CopyTo {
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+        options: CopyOptions,
+    },
+    // rows in text format are sent in `CopyData` messages
+    CopyFrom {
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+        options: CopyOptions,
+    },
+    // `INSERT` statement as `sqlparser` parses it and its returning list
+    InsertReturning {
+        insert: Statement,
+        returning: Vec<SelectItem>,
+    },
+    // `ALTER TABLE ... ALTER [COLUMN] ... [SET DATA] TYPE ...`
+    AlterColumnType {
+        table_name: ObjectName,
+        column_name: Ident,
+        data_type: DataType,
+    },
+}
+
+/// representation of values in COPY text format
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyOptions {
+    /// text that stands for NULL, `\N` by default
+    pub null: String,
+}
+
+impl Default for CopyOptions {
+    fn default() -> CopyOptions {
+        CopyOptions { null: "\\N".to_owned() }
+    }
+}
+
+/// returns `None` if the query has to be parsed by `sqlparser`
+pub fn parse(raw_sql_query: &str) -> Option<Result<ExtendedStatement, ParserError>> {
+    let raw_sql_query = &escape_strings(raw_sql_query);
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, raw_sql_query).tokenize().ok()?;
+    let mut parser = Parser::new(tokens);
+    match parser.next_token() {
+        Token::Word(word) if word.value.eq_ignore_ascii_case("vacuum") => Some(parse_vacuum(&mut parser)),
+        Token::Word(word) if word.keyword == Keyword::GRANT => Some(parse_grant(&mut parser)),
+        Token::Word(word) if word.keyword == Keyword::REVOKE => Some(parse_revoke(&mut parser)),
+        Token::Word(word) if word.value.eq_ignore_ascii_case("explain") => match parser.next_token() {
+            Token::Word(word) if word.value.eq_ignore_ascii_case("analyze") => Some(parse_explain_analyze(&mut parser)),
+            _ => None,
+        },
+        Token::Word(word) if word.keyword == Keyword::SET => {
+            let _ = parser.parse_one_of_keywords(&[Keyword::SESSION, Keyword::LOCAL]);
+            match (parser.next_token(), parser.next_token()) {
+                (Token::Word(time), Token::Word(zone))
+                    if time.value.eq_ignore_ascii_case("time") && zone.value.eq_ignore_ascii_case("zone") =>
+                {
+                    Some(parse_set_time_zone(&mut parser))
+                }
+                _ => None,
+            }
+        }
+        Token::Word(word) if word.value.eq_ignore_ascii_case("reset") => Some(parse_reset(&mut parser)),
+        Token::Word(word) if word.keyword == Keyword::COPY => Some(parse_copy(&mut parser)),
+        Token::Word(word) if word.keyword == Keyword::INSERT => parse_insert_returning(raw_sql_query),
+        Token::Word(word) if word.keyword == Keyword::CREATE => match parser.next_token() {
+            Token::Word(word) if word.value.eq_ignore_ascii_case("sequence") => {
+                Some(parse_create_sequence(&mut parser))
+            }
+            Token::Word(word) if word.value.eq_ignore_ascii_case("database") => {
+                Some(parse_database_name(&mut parser).map(ExtendedStatement::CreateDatabase))
+            }
+            Token::Word(word) if word.keyword == Keyword::SCHEMA => {
+                if parser.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]) {
+                    Some(parse_create_schema_if_not_exists(&mut parser))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        },
+        Token::Word(word) if word.keyword == Keyword::ALTER => parse_alter_column_type(&mut parser),
+        Token::Word(word) if word.keyword == Keyword::DROP => match parser.next_token() {
+            Token::Word(word) if word.value.eq_ignore_ascii_case("database") => {
+                Some(parse_database_name(&mut parser).map(ExtendedStatement::DropDatabase))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// query that has nothing but whitespaces, comments and semicolons
+pub fn is_empty(raw_sql_query: &str) -> bool {
+    let trimmed = raw_sql_query.trim_matches(|c: char| c == ';' || c.is_whitespace());
+    if trimmed.is_empty() {
+        return true;
+    }
+    if !trimmed.starts_with("--") && !trimmed.starts_with("/*") {
+        return false;
+    }
+    match Tokenizer::new(&PostgreSqlDialect {}, raw_sql_query).tokenize() {
+        Ok(tokens) => tokens
+            .iter()
+            .all(|token| matches!(token, Token::Whitespace(_) | Token::SemiColon)),
+        Err(_) => false,
+    }
+}
+
+/// `sqlparser` understands only `text[]` array type, so other array types are
+/// replaced with PostgreSQL internal names, e.g. `integer[]` with `_int4`
+pub fn array_types(raw_sql_query: &str) -> Cow<'_, str> {
+    if !raw_sql_query.contains('[') {
+        return Cow::Borrowed(raw_sql_query);
+    }
+    let chars = raw_sql_query.chars().collect::<Vec<char>>();
+    let mut rewritten = String::with_capacity(raw_sql_query.len());
+    let mut quote = None;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        index += 1;
+        match quote {
+            Some(quote_char) if quote_char == c => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if c == '[' => {
+                let mut closing = index;
+                while closing < chars.len() && chars[closing].is_whitespace() {
+                    closing += 1;
+                }
+                if closing < chars.len() && chars[closing] == ']' {
+                    let type_end = rewritten.trim_end().len();
+                    let type_start = rewritten[..type_end]
+                        .char_indices()
+                        .rev()
+                        .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '_')
+                        .last()
+                        .map_or(type_end, |(start, _)| start);
+                    if let Some(array_type) = array_type_name(&rewritten[type_start..type_end]) {
+                        rewritten.truncate(type_start);
+                        rewritten.push_str(array_type);
+                        index = closing + 1;
+                        continue;
+                    }
+                }
+            }
+            None => {}
+        }
+        rewritten.push(c);
+    }
+    Cow::Owned(rewritten)
+}
+
+/// `sqlparser` does not know PostgreSQL escape strings, so `E'...'` literals
+/// are replaced with standard ones holding already decoded content, e.g.
+/// `E'it\'s'` with `'it''s'`
+pub fn escape_strings(raw_sql_query: &str) -> Cow<'_, str> {
+    if !raw_sql_query.contains("E'") && !raw_sql_query.contains("e'") {
+        return Cow::Borrowed(raw_sql_query);
+    }
+    let chars = raw_sql_query.chars().collect::<Vec<char>>();
+    let mut rewritten = String::with_capacity(raw_sql_query.len());
+    let mut quote = None;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        index += 1;
+        match quote {
+            Some(quote_char) if quote_char == c => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None if (c == 'E' || c == 'e')
+                && chars.get(index) == Some(&'\'')
+                && !matches!(rewritten.chars().last(), Some(previous) if previous.is_alphanumeric() || previous == '_') =>
+            {
+                index += 1;
+                rewritten.push('\'');
+                rewritten.push_str(&escape_string_content(&chars, &mut index).replace('\'', "''"));
+                rewritten.push('\'');
+                continue;
+            }
+            None => {}
+        }
+        rewritten.push(c);
+    }
+    Cow::Owned(rewritten)
+}
+
+// decoded content of `E'...'` literal up to its closing quote, bytes given by
+// `\xHH` and octal escapes are expected to form UTF-8 sequences, invalid ones
+// are replaced with U+FFFD
+fn escape_string_content(chars: &[char], index: &mut usize) -> String {
+    let mut bytes = vec![];
+    let mut buffer = [0; 4];
+    while *index < chars.len() {
+        let c = chars[*index];
+        *index += 1;
+        let decoded = match c {
+            '\'' if chars.get(*index) == Some(&'\'') => {
+                *index += 1;
+                '\''
+            }
+            '\'' => break,
+            '\\' if *index < chars.len() => {
+                let escaped = chars[*index];
+                *index += 1;
+                match escaped {
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'x' => match escaped_number(chars, index, 16, 1, 2) {
+                        Some(byte) => {
+                            bytes.push(byte as u8);
+                            continue;
+                        }
+                        None => 'x',
+                    },
+                    '0'..='7' => {
+                        *index -= 1;
+                        // as in PostgreSQL values over `\377` are truncated to a byte
+                        let byte = escaped_number(chars, index, 8, 1, 3).unwrap_or_default();
+                        bytes.push(byte as u8);
+                        continue;
+                    }
+                    'u' => escaped_char(chars, index, 4).unwrap_or('u'),
+                    'U' => escaped_char(chars, index, 8).unwrap_or('U'),
+                    other => other,
+                }
+            }
+            other => other,
+        };
+        bytes.extend_from_slice(decoded.encode_utf8(&mut buffer).as_bytes());
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// value of `min_len` to `max_len` digits of the radix that follow an escape
+fn escaped_number(chars: &[char], index: &mut usize, radix: u32, min_len: usize, max_len: usize) -> Option<u32> {
+    let digits = chars[*index..]
+        .iter()
+        .take(max_len)
+        .take_while(|c| c.is_digit(radix))
+        .count();
+    if digits < min_len {
+        return None;
+    }
+    let value = chars[*index..*index + digits]
+        .iter()
+        .filter_map(|c| c.to_digit(radix))
+        .fold(0, |value, digit| value * radix + digit);
+    *index += digits;
+    Some(value)
+}
+
+// `\uXXXX` and `\UXXXXXXXX` escapes, code points that are not characters are
+// replaced with U+FFFD
+fn escaped_char(chars: &[char], index: &mut usize, len: usize) -> Option<char> {
+    escaped_number(chars, index, 16, len, len)
+        .map(|code_point| std::char::from_u32(code_point).unwrap_or(std::char::REPLACEMENT_CHARACTER))
+}
+
+fn array_type_name(element_type: &str) -> Option<&'static str> {
+    match element_type.to_lowercase().as_str() {
+        "bool" | "boolean" => Some("_bool"),
+        "smallint" | "int2" => Some("_int2"),
+        "int" | "integer" | "int4" => Some("_int4"),
+        "bigint" | "int8" => Some("_int8"),
+        _ => None,
+    }
+}
+
+fn parse_create_sequence(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let sequence_name = parser.parse_object_name()?;
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::CreateSequence(sequence_name))
+}
+
+fn parse_create_schema_if_not_exists(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let schema_name = parser.parse_object_name()?;
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::CreateSchemaIfNotExists(schema_name))
+}
+
+fn parse_database_name(parser: &mut Parser) -> Result<String, ParserError> {
+    let database_name = parser.parse_identifier()?;
+    expect_end_of_statement(parser)?;
+    Ok(database_name.value)
+}
+
+// the statement before `RETURNING` is left to `sqlparser`, `None` if there is
+// no returning list
+fn parse_insert_returning(raw_sql_query: &str) -> Option<Result<ExtendedStatement, ParserError>> {
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, raw_sql_query).tokenize().ok()?;
+    let position = tokens.iter().position(|token| {
+        matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case("returning"))
+    })?;
+    let mut parser = Parser::new(tokens[..position].to_vec());
+    let insert = match parser.parse_statement() {
+        Ok(insert) => insert,
+        Err(error) => return Some(Err(error)),
+    };
+    if let Err(error) = expect_end_of_statement(&mut parser) {
+        return Some(Err(error));
+    }
+    let mut parser = Parser::new(tokens[position + 1..].to_vec());
+    Some(
+        parser
+            .parse_comma_separated(Parser::parse_select_item)
+            .and_then(|returning| {
+                expect_end_of_statement(&mut parser)?;
+                Ok(ExtendedStatement::InsertReturning { insert, returning })
+            }),
+    )
+}
+
+// other `ALTER TABLE` statements are left to `sqlparser`
+fn parse_alter_column_type(parser: &mut Parser) -> Option<Result<ExtendedStatement, ParserError>> {
+    if !parser.parse_keyword(Keyword::TABLE) {
+        return None;
+    }
+    let table_name = parser.parse_object_name().ok()?;
+    if !parser.parse_keyword(Keyword::ALTER) {
+        return None;
+    }
+    let _ = parser.parse_keyword(Keyword::COLUMN);
+    Some(parser.parse_identifier().and_then(|column_name| {
+        if parser.parse_keyword(Keyword::SET) {
+            expect_word(parser, "DATA")?;
+        }
+        expect_word(parser, "TYPE")?;
+        let data_type = parser.parse_data_type()?;
+        expect_end_of_statement(parser)?;
+        Ok(ExtendedStatement::AlterColumnType {
+            table_name,
+            column_name,
+            data_type,
+        })
+    }))
+}
+
+fn parse_explain_analyze(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let query = parser.parse_query()?;
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::ExplainAnalyze(Box::new(query)))
+}
+
+fn parse_set_time_zone(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let time_zone = match parser.next_token() {
+        Token::SingleQuotedString(time_zone) => time_zone,
+        Token::Word(word) => word.value,
+        Token::Number(number) => number,
+        Token::Minus => match parser.next_token() {
+            Token::Number(number) => format!("-{}", number),
+            token => return Err(expected("time zone offset", token)),
+        },
+        Token::Plus => match parser.next_token() {
+            Token::Number(number) => number,
+            token => return Err(expected("time zone offset", token)),
+        },
+        token => return Err(expected("time zone", token)),
+    };
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::SetTimeZone(time_zone))
+}
+
+fn parse_reset(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let name = match parser.next_token() {
+        Token::Word(word) if word.keyword == Keyword::ALL => None,
+        Token::Word(word) if word.value.eq_ignore_ascii_case("time") => match parser.next_token() {
+            Token::Word(zone) if zone.value.eq_ignore_ascii_case("zone") => Some("timezone".to_owned()),
+            token => return Err(expected("ZONE", token)),
+        },
+        Token::Word(word) => Some(word.value),
+        token => return Err(expected("configuration parameter", token)),
+    };
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::Reset(name))
+}
+
+// rows of `COPY ... FROM STDIN` are sent by a client in `CopyData` messages
+// after the server responds with `CopyInResponse`
+fn parse_copy(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let table_name = parser.parse_object_name()?;
+    let columns = parser.parse_parenthesized_column_list(IsOptional::Optional)?;
+    let direction = parser.expect_one_of_keywords(&[Keyword::TO, Keyword::FROM])?;
+    let target = match (direction, parser.next_token()) {
+        (Keyword::TO, Token::Word(word)) if word.value.eq_ignore_ascii_case("stdout") => word,
+        (Keyword::FROM, Token::Word(word)) if word.keyword == Keyword::STDIN => word,
+        (Keyword::TO, token) => return Err(expected("STDOUT", token)),
+        (_, token) => return Err(expected("STDIN", token)),
+    };
+    let options = parse_copy_options(parser)?;
+    expect_end_of_statement(parser)?;
+    if target.keyword == Keyword::STDIN {
+        Ok(ExtendedStatement::CopyFrom {
+            table_name,
+            columns,
+            options,
+        })
+    } else {
+        Ok(ExtendedStatement::CopyTo {
+            table_name,
+            columns,
+            options,
+        })
+    }
+}
+
+// `WITH (NULL 'marker')` or older `WITH NULL AS 'marker'` syntax
+fn parse_copy_options(parser: &mut Parser) -> Result<CopyOptions, ParserError> {
+    let mut options = CopyOptions::default();
+    let _ = parser.parse_keyword(Keyword::WITH);
+    if parser.consume_token(&Token::LParen) {
+        loop {
+            parser.expect_keyword(Keyword::NULL)?;
+            options.null = parse_copy_null(parser)?;
+            if !parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        parser.expect_token(&Token::RParen)?;
+    } else if parser.parse_keyword(Keyword::NULL) {
+        let _ = parser.parse_keyword(Keyword::AS);
+        options.null = parse_copy_null(parser)?;
+    }
+    Ok(options)
+}
+
+fn parse_copy_null(parser: &mut Parser) -> Result<String, ParserError> {
+    match parser.next_token() {
+        Token::SingleQuotedString(null) => Ok(null),
+        token => Err(expected("NULL string", token)),
+    }
+}
+
+fn parse_vacuum(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let table_name = match parser.peek_token() {
+        Token::EOF | Token::SemiColon => None,
+        _ => Some(parser.parse_object_name()?),
+    };
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::Vacuum(table_name))
+}
+
+fn parse_grant(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let table_name = parse_select_privilege_target(parser)?;
+    parser.expect_keyword(Keyword::TO)?;
+    let grantee = parser.parse_identifier()?.value;
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::Grant { table_name, grantee })
+}
+
+fn parse_revoke(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let table_name = parse_select_privilege_target(parser)?;
+    parser.expect_keyword(Keyword::FROM)?;
+    let grantee = parser.parse_identifier()?.value;
+    expect_end_of_statement(parser)?;
+    Ok(ExtendedStatement::Revoke { table_name, grantee })
+}
+
+fn parse_select_privilege_target(parser: &mut Parser) -> Result<ObjectName, ParserError> {
+    parser.expect_keyword(Keyword::SELECT)?;
+    parser.expect_keyword(Keyword::ON)?;
+    let _ = parser.parse_keyword(Keyword::TABLE);
+    parser.parse_object_name()
+}
+
+fn expected(what: &str, found: Token) -> ParserError {
+    ParserError::ParserError(format!("Expected {}, found: {}", what, found))
+}
+
+// words that are not keywords of `sqlparser`
+fn expect_word(parser: &mut Parser, word: &str) -> Result<(), ParserError> {
+    match parser.next_token() {
+        Token::Word(found) if found.value.eq_ignore_ascii_case(word) => Ok(()),
+        token => Err(expected(word, token)),
+    }
+}
+
+fn expect_end_of_statement(parser: &mut Parser) -> Result<(), ParserError> {
+    let token = match parser.next_token() {
+        Token::SemiColon => parser.next_token(),
+        token => token,
+    };
+    match token {
+        Token::EOF => Ok(()),
+        token => Err(ParserError::ParserError(format!(
+            "Expected end of statement, found: {}",
+            token
+        ))),
+    }
+}
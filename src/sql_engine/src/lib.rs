@@ -17,67 +17,341 @@ extern crate log;
 extern crate ordered_float;
 
 use crate::{
+    dcl::{grant::GrantCommand, revoke::RevokeCommand},
     ddl::{
-        create_schema::CreateSchemaCommand, create_table::CreateTableCommand, drop_schema::DropSchemaCommand,
-        drop_table::DropTableCommand,
+        alter_column_type::AlterColumnTypeCommand, create_schema::CreateSchemaCommand,
+        create_sequence::CreateSequenceCommand, create_table::CreateTableCommand, drop_schema::DropSchemaCommand,
+        drop_table::DropTableCommand, rename_column::RenameColumnCommand,
     },
-    dml::{delete::DeleteCommand, insert::InsertCommand, select::SelectCommand, update::UpdateCommand},
+    dml::{
+        copy::{copied_columns, CopyFromCommand, CopyToCommand},
+        delete::DeleteCommand,
+        insert::{report_insert_error, InsertCommand},
+        select::SelectCommand,
+        update::UpdateCommand,
+        write_storage,
+    },
+    maintenance::vacuum::VacuumCommand,
 };
 use kernel::SystemResult;
-use protocol::results::{QueryErrorBuilder, QueryEvent};
+use log::LevelFilter;
+use protocol::results::{QueryError, QueryErrorBuilder, QueryEvent};
 
-use crate::query::{Plan, QueryProcessor};
-use protocol::Sender;
-use sqlparser::{ast::Statement, dialect::PostgreSqlDialect, parser::Parser};
-use std::sync::{Arc, Mutex};
-use storage::{backend::BackendStorage, frontend::FrontendStorage};
+use crate::{
+    extension::ExtendedStatement,
+    parse_cache::ParseCache,
+    prepared_statement::{
+        placeholder_number, with_placeholder_identifiers, ParameterError, ParsedStatement, Portal, PreparedStatement,
+    },
+    query::{CopyInfo, InsertInfo, Plan, QueryProcessor},
+    query_log::QueryLogger,
+    session_registry::Session,
+    transaction::Transaction,
+};
+use protocol::{sql_types::PostgreSqlType, Sender};
+use sqlparser::{
+    ast::{Expr, Ident, Query, SelectItem, SetExpr, SetVariableValue, Statement, TableFactor, TableWithJoins, Value},
+    parser::ParserError,
+};
+use std::{collections::HashMap, sync::Arc};
+use storage::{backend::BackendStorage, frontend::SharedStorage};
 
+mod database_registry;
+mod dcl;
 mod ddl;
+mod dialect;
 mod dml;
+mod extension;
+mod maintenance;
+mod parse_cache;
+mod prepared_statement;
 mod query;
+mod query_log;
+mod session_registry;
+mod startup_options;
+mod time_zone;
+mod transaction;
+
+pub use database_registry::DatabaseRegistry;
+pub use dialect::SqlDialect;
+pub use dml::RetryPolicy;
+pub use query::{IdentifierLength, MAX_IDENTIFIER_LENGTH};
+pub use session_registry::SessionRegistry;
+
+const AUTOCOMMIT: &str = "autocommit";
+const PARSE_CACHE_CAPACITY: usize = 256;
+/// database sessions connect to when databases are not managed by a registry
+pub const DEFAULT_DATABASE: &str = "postgres";
+const LOG_MIN_MESSAGES: &str = "log_min_messages";
+const TIME_ZONE: &str = "timezone";
+// settings known to the server and their values until a client sets them
+const SERVER_SETTINGS: [(&str, &str); 5] = [
+    (AUTOCOMMIT, "on"),
+    ("client_encoding", "UTF8"),
+    ("datestyle", "ISO"),
+    (LOG_MIN_MESSAGES, "warning"),
+    (TIME_ZONE, time_zone::DEFAULT),
+];
 
 pub struct QueryExecutor<P: BackendStorage> {
-    storage: Arc<Mutex<FrontendStorage<P>>>,
+    storage: SharedStorage<P>,
     processor: QueryProcessor<P>,
+    logger: Arc<QueryLogger>,
     session: Arc<dyn Sender>,
+    user: Option<String>,
+    // database the session is connected to
+    database: String,
+    // present when databases can be created and dropped
+    databases: Option<DatabaseRegistry<P>>,
+    safe_updates: bool,
+    dialect: SqlDialect,
+    // longer queries are rejected before they are parsed
+    max_query_length: Option<usize>,
+    // rows of a SELECT result over the limit are not sent to a client
+    max_result_rows: Option<usize>,
+    // inserts retry instead of waiting when storage is busy
+    write_retry_policy: Option<RetryPolicy>,
+    // inserts waiting for COMMIT when autocommit is off
+    transaction: Transaction,
+    // last values returned by `nextval` in the session, read by `currval`
+    sequence_values: HashMap<String, i64>,
+    // values of session settings by lowercase names
+    settings: HashMap<String, String>,
+    // values settings are reset to, they are given by startup options
+    default_settings: HashMap<String, String>,
+    parse_cache: ParseCache,
+    // statements and portals of extended query protocol, unnamed ones have
+    // empty names
+    prepared_statements: HashMap<String, PreparedStatement>,
+    portals: HashMap<String, Portal>,
+    // `COPY ... FROM STDIN` waiting for the rest of its data from a client
+    copy_in: Option<(CopyInfo, Vec<u8>)>,
+    // present when the executor serves a client connection of a node
+    activity: Option<Session>,
 }
 
 impl<P: BackendStorage> QueryExecutor<P> {
-    pub fn new(storage: Arc<Mutex<FrontendStorage<P>>>, session: Arc<dyn Sender>) -> Self {
+    pub fn new(storage: SharedStorage<P>, session: Arc<dyn Sender>) -> Self {
+        let logger = Arc::new(QueryLogger::new(session));
+        let session: Arc<dyn Sender> = logger.clone();
         Self {
             storage: storage.clone(),
             processor: QueryProcessor::new(storage, session.clone()),
+            logger,
             session,
+            user: None,
+            database: DEFAULT_DATABASE.to_owned(),
+            databases: None,
+            safe_updates: false,
+            dialect: SqlDialect::default(),
+            max_query_length: None,
+            max_result_rows: None,
+            write_retry_policy: None,
+            transaction: Transaction::default(),
+            sequence_values: HashMap::new(),
+            settings: HashMap::new(),
+            default_settings: HashMap::new(),
+            parse_cache: ParseCache::new(PARSE_CACHE_CAPACITY),
+            prepared_statements: HashMap::new(),
+            portals: HashMap::new(),
+            copy_in: None,
+            activity: None,
         }
     }
 
-    #[allow(clippy::match_wild_err_arm)]
-    pub fn execute(&mut self, raw_sql_query: &str) -> SystemResult<()> {
-        let statement = match Parser::parse_sql(&PostgreSqlDialect {}, raw_sql_query) {
-            Ok(mut statements) => {
-                log::info!("stmts: {:#?}", statements);
-                statements.pop().unwrap()
-            }
-            Err(e) => {
-                log::error!("{:?} can't be parsed. Error: {:?}", raw_sql_query, e);
-                let query_error = QueryErrorBuilder::new()
-                    .syntax_error(format!("{:?} can't be parsed", raw_sql_query))
-                    .build();
+    /// registers the session, so that it and queries it runs are listed by
+    /// `pg_stat_activity` of every session in the registry
+    pub fn with_session_registry(mut self, registry: &SessionRegistry, application_name: &str) -> Self {
+        self.activity = Some(registry.register(application_name));
+        self
+    }
+
+    /// user on whose behalf queries are executed and privileges are checked
+    pub fn with_user(mut self, user: &str) -> Self {
+        self.user = Some(user.to_owned());
+        self
+    }
+
+    /// databases the session can connect to, create and drop, without a
+    /// registry there is only the default one
+    pub fn with_database_registry(mut self, registry: &DatabaseRegistry<P>) -> Self {
+        self.databases = Some(registry.clone());
+        self
+    }
+
+    /// connects the session to a database named in startup parameters, if
+    /// the database does not exist a client is sent a fatal error, `false` is
+    /// returned and the connection has to be closed
+    pub fn connect_to_database(&mut self, database: &str) -> bool {
+        let storage = match self.databases.as_ref() {
+            Some(databases) => databases.storage(database),
+            None if database == DEFAULT_DATABASE => Some(self.storage.clone()),
+            None => None,
+        };
+        match storage {
+            Some(storage) => {
+                self.storage = storage.clone();
+                self.processor.set_storage(storage);
+                self.database = database.to_owned();
+                true
+            }
+            None => {
                 self.session
-                    .send(Err(query_error))
+                    .send(Err(QueryErrorBuilder::new()
+                        .database_does_not_exist(database.to_owned())
+                        .build()))
                     .expect("To Send Query Result to Client");
-                return Ok(());
+                false
+            }
+        }
+    }
+
+    /// database the session is connected to
+    pub fn current_database(&self) -> &str {
+        self.database.as_str()
+    }
+
+    /// maximum length of schema, table and column names and whether longer
+    /// names are truncated or rejected
+    pub fn with_identifier_length(mut self, identifier_length: IdentifierLength) -> Self {
+        self.processor.set_identifier_length(identifier_length);
+        self
+    }
+
+    /// applies settings passed by a client in `options` startup parameter,
+    /// options that can't be understood are ignored with a notice
+    pub fn with_startup_options(mut self, options: &str) -> Self {
+        for option in startup_options::parse(options) {
+            let applied = match option {
+                Ok((name, value)) => self
+                    .apply_setting(&name, value)
+                    .map_err(|value| format!("{}={}", name, value)),
+                Err(option) => Err(option),
+            };
+            if let Err(option) = applied {
+                self.session
+                    .send(Err(QueryErrorBuilder::new().invalid_startup_option(option).build()))
+                    .expect("To Send Query Result to Client");
+            }
+        }
+        self.default_settings = self.settings.clone();
+        self
+    }
+
+    /// current value of a session setting set by `SET` or startup options
+    pub fn setting(&self, name: &str) -> Option<&str> {
+        self.settings.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    /// maximum number of rows a SELECT sends back, the rest of a bigger
+    /// result is dropped with a notice
+    pub fn with_max_result_rows(mut self, max_result_rows: usize) -> Self {
+        self.max_result_rows = Some(max_result_rows);
+        self
+    }
+
+    /// maximum length of a query text in bytes, longer queries are rejected
+    /// without parsing
+    pub fn with_max_query_length(mut self, max_query_length: usize) -> Self {
+        self.max_query_length = Some(max_query_length);
+        self
+    }
+
+    /// makes INSERT retry according to the policy when storage is busy with
+    /// a concurrent operation and fail with serialization failure when it
+    /// is still busy after the last retry, by default it waits
+    pub fn with_write_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.write_retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// syntax rules queries are parsed with, strict PostgreSQL by default
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// rejects UPDATE and DELETE without WHERE clause, so all records of a
+    /// table can't be rewritten or removed by mistake
+    pub fn with_safe_updates(mut self) -> Self {
+        self.safe_updates = true;
+        self
+    }
+
+    pub fn execute(&mut self, raw_sql_query: &str) -> SystemResult<()> {
+        self.logger.start(raw_sql_query);
+        if let Err(()) = self.check_query_length(raw_sql_query) {
+            return Ok(());
+        }
+        if let Some(activity) = self.activity.as_ref() {
+            activity.set_query(raw_sql_query);
+        }
+        // clients and load balancers ping the server with empty queries, they
+        // are answered right away without parsing or touching storage
+        if extension::is_empty(raw_sql_query) {
+            self.session
+                .send(Ok(QueryEvent::QueryIsEmpty))
+                .expect("To Send Query Result to Client");
+            return Ok(());
+        }
+        let statement = match self.parse_cache.get(raw_sql_query) {
+            Some(statement) => {
+                log::debug!("CACHED STATEMENT = {:?}", statement);
+                ParsedStatement::Standard(statement)
             }
+            None => match self.parse_statement(raw_sql_query) {
+                Ok(ParsedStatement::Standard(statement)) => {
+                    self.parse_cache.put(raw_sql_query, statement.clone());
+                    ParsedStatement::Standard(statement)
+                }
+                Ok(statement) => statement,
+                Err(e) => return self.report_parse_error(raw_sql_query, e),
+            },
         };
+        self.execute_statement(raw_sql_query, statement)
+    }
 
-        log::debug!("STATEMENT = {:?}", statement);
-        match self.processor.process(statement) {
+    fn parse_statement(&self, raw_sql_query: &str) -> Result<ParsedStatement, ParserError> {
+        match extension::parse(raw_sql_query) {
+            Some(Ok(statement)) => {
+                log::debug!("EXTENDED STATEMENT = {:?}", statement);
+                Ok(ParsedStatement::Extended(statement))
+            }
+            Some(Err(e)) => Err(e),
+            None => {
+                let mut statements = self
+                    .dialect
+                    .parse_sql(&extension::array_types(&extension::escape_strings(raw_sql_query)))?;
+                log::debug!("stmts: {:#?}", statements);
+                let statement = statements.pop().unwrap();
+                log::debug!("STATEMENT = {:?}", statement);
+                Ok(ParsedStatement::Standard(statement))
+            }
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    fn execute_statement(&mut self, raw_sql_query: &str, statement: ParsedStatement) -> SystemResult<()> {
+        let plan = match statement {
+            ParsedStatement::Standard(statement) => self.processor.process(statement),
+            ParsedStatement::Extended(statement) => self.processor.process_extended(statement),
+        };
+        match plan {
             Ok(Plan::CreateSchema(creation_info)) => {
                 CreateSchemaCommand::new(creation_info, self.storage.clone(), self.session.clone()).execute()
             }
+            Ok(Plan::CreateSequence(creation_info)) => {
+                CreateSequenceCommand::new(creation_info, self.storage.clone(), self.session.clone()).execute()
+            }
             Ok(Plan::CreateTable(creation_info)) => {
                 CreateTableCommand::new(creation_info, self.storage.clone(), self.session.clone()).execute()
             }
+            Ok(Plan::RenameColumn(renaming_info)) => {
+                RenameColumnCommand::new(renaming_info, self.storage.clone(), self.session.clone()).execute()
+            }
+            Ok(Plan::AlterColumnType(change_info)) => {
+                AlterColumnTypeCommand::new(change_info, self.storage.clone(), self.session.clone()).execute()
+            }
             Ok(Plan::DropSchemas(schemas)) => {
                 for schema in schemas {
                     DropSchemaCommand::new(schema, self.storage.clone(), self.session.clone()).execute()?;
@@ -90,6 +364,65 @@ impl<P: BackendStorage> QueryExecutor<P> {
                 }
                 Ok(())
             }
+            Ok(Plan::CopyTo(copy_info)) => CopyToCommand::new(
+                copy_info,
+                self.user.as_deref(),
+                self.storage.clone(),
+                self.session.clone(),
+            )
+            .execute(),
+            Ok(Plan::CopyFrom(copy_info)) => {
+                let columns = copied_columns(&self.storage, &copy_info)?;
+                self.copy_in = Some((copy_info, vec![]));
+                self.session
+                    .send(Ok(QueryEvent::CopyInStarted(columns.len())))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Ok(Plan::Vacuum(table)) => VacuumCommand::new(table, self.storage.clone(), self.session.clone()).execute(),
+            Ok(Plan::GrantSelect(privilege_info)) => {
+                GrantCommand::new(privilege_info, self.storage.clone(), self.session.clone()).execute()
+            }
+            Ok(Plan::RevokeSelect(privilege_info)) => {
+                RevokeCommand::new(privilege_info, self.storage.clone(), self.session.clone()).execute()
+            }
+            Ok(Plan::ResetVariable(name)) => self.reset_variable(name),
+            Ok(Plan::Skip(event)) => {
+                self.session.send(Ok(event)).expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Ok(Plan::InsertReturning(..)) if !self.autocommit() => self.not_supported_without_autocommit("RETURNING"),
+            Ok(Plan::InsertReturning(
+                InsertInfo {
+                    table_name,
+                    columns,
+                    source,
+                },
+                returning,
+            )) => InsertCommand::new(
+                raw_sql_query,
+                table_name,
+                columns,
+                source,
+                &mut self.sequence_values,
+                self.storage.clone(),
+                self.session.clone(),
+            )
+            .with_retry_policy(self.write_retry_policy)
+            .with_returning(returning)
+            .execute(),
+            Ok(Plan::CreateDatabase(name)) => self.create_database(name),
+            Ok(Plan::DropDatabase(name)) => self.drop_database(name),
+            Ok(Plan::ExplainAnalyze(query)) => SelectCommand::new(
+                raw_sql_query,
+                query,
+                self.user.as_deref(),
+                &mut self.sequence_values,
+                self.storage.clone(),
+                self.session.clone(),
+            )
+            .with_pending(&self.transaction)
+            .explain_analyze(),
             Ok(Plan::NotProcessed(statement)) => match statement {
                 Statement::StartTransaction { .. } => {
                     self.session
@@ -97,12 +430,30 @@ impl<P: BackendStorage> QueryExecutor<P> {
                         .expect("To Send Query Result to Client");
                     Ok(())
                 }
-                Statement::SetVariable { .. } => {
+                Statement::Commit { .. } => self.commit(),
+                Statement::Rollback { .. } => {
+                    self.transaction.take();
                     self.session
-                        .send(Ok(QueryEvent::VariableSet))
+                        .send(Ok(QueryEvent::TransactionRolledBack))
                         .expect("To Send Query Result to Client");
                     Ok(())
                 }
+                Statement::Insert {
+                    table_name,
+                    columns,
+                    source,
+                } if !self.autocommit() => self.defer_insert(
+                    raw_sql_query,
+                    InsertInfo {
+                        table_name,
+                        columns,
+                        source,
+                    },
+                ),
+                Statement::Update { .. } if !self.autocommit() => self.not_supported_without_autocommit("UPDATE"),
+                Statement::Delete { .. } if !self.autocommit() => self.not_supported_without_autocommit("DELETE"),
+                Statement::SetVariable { variable, value, .. } => self.set_variable(variable, value),
+                Statement::ShowVariable { variable } => self.show_variable(variable),
                 Statement::Drop { .. } => {
                     self.session
                         .send(Err(QueryErrorBuilder::new()
@@ -121,12 +472,41 @@ impl<P: BackendStorage> QueryExecutor<P> {
                     table_name,
                     columns,
                     source,
+                    &mut self.sequence_values,
                     self.storage.clone(),
                     self.session.clone(),
                 )
+                .with_retry_policy(self.write_retry_policy)
                 .execute(),
-                Statement::Query(query) => {
-                    SelectCommand::new(raw_sql_query, query, self.storage.clone(), self.session.clone()).execute()
+                Statement::Query(query) if self.activity.is_some() && is_stat_activity(&query) => {
+                    self.select_activity(&query)
+                }
+                Statement::Query(query) => SelectCommand::new(
+                    raw_sql_query,
+                    query,
+                    self.user.as_deref(),
+                    &mut self.sequence_values,
+                    self.storage.clone(),
+                    self.session.clone(),
+                )
+                .with_pending(&self.transaction)
+                .with_max_rows(self.max_result_rows)
+                .execute(),
+                Statement::Update { selection: None, .. } if self.safe_updates => {
+                    self.session
+                        .send(Err(QueryErrorBuilder::new()
+                            .where_clause_required("UPDATE".to_owned())
+                            .build()))
+                        .expect("To Send Query Result to Client");
+                    Ok(())
+                }
+                Statement::Delete { selection: None, .. } if self.safe_updates => {
+                    self.session
+                        .send(Err(QueryErrorBuilder::new()
+                            .where_clause_required("DELETE".to_owned())
+                            .build()))
+                        .expect("To Send Query Result to Client");
+                    Ok(())
                 }
                 Statement::Update {
                     table_name,
@@ -155,6 +535,616 @@ impl<P: BackendStorage> QueryExecutor<P> {
             Err(()) => Ok(()),
         }
     }
+
+    /// prepares a statement with `$n` placeholders, `param_types` are OIDs of
+    /// parameters types
+    pub fn parse(&mut self, statement_name: &str, sql: &str, param_types: &[i32]) -> SystemResult<()> {
+        self.logger.start(sql);
+        if let Err(()) = self.check_query_length(sql) {
+            return Ok(());
+        }
+        let sql = extension::escape_strings(sql);
+        // the statement is parsed once, parameters are bound to its syntax tree
+        let parsed = if extension::is_empty(&sql) {
+            None
+        } else {
+            match self.parse_statement(&with_placeholder_identifiers(&sql)) {
+                Ok(parsed) => Some(parsed),
+                Err(e) => return self.report_parse_error(&sql, e),
+            }
+        };
+        let mut statement = PreparedStatement::new(&sql, parsed, param_types);
+        if statement.has_unspecified_param_types() {
+            let inferred = self.inserted_param_types(&statement)?;
+            statement.infer_param_types(inferred);
+        }
+        self.prepared_statements.insert(statement_name.to_owned(), statement);
+        self.session
+            .send(Ok(QueryEvent::ParseComplete))
+            .expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    // types of columns that parameters of `INSERT ... VALUES` statement are
+    // inserted into, so that values sent in binary format could be decoded
+    fn inserted_param_types(&self, statement: &PreparedStatement) -> SystemResult<Vec<(usize, PostgreSqlType)>> {
+        let (table_name, columns, source) = match statement.statement() {
+            Some(ParsedStatement::Standard(Statement::Insert {
+                table_name,
+                columns,
+                source,
+            }))
+            | Some(ParsedStatement::Extended(ExtendedStatement::InsertReturning {
+                insert:
+                    Statement::Insert {
+                        table_name,
+                        columns,
+                        source,
+                    },
+                ..
+            })) if table_name.0.len() == 2 => (table_name, columns, source),
+            _ => return Ok(vec![]),
+        };
+        let rows = match &source.body {
+            SetExpr::Values(values) => &values.0,
+            _ => return Ok(vec![]),
+        };
+        let table_columns =
+            (self.storage.read().unwrap()).table_columns(&table_name.0[0].value, &table_name.0[1].value)?;
+        let target_types = if columns.is_empty() {
+            table_columns
+                .iter()
+                .map(|column| Some(column.sql_type().to_pg_types()))
+                .collect::<Vec<_>>()
+        } else {
+            columns
+                .iter()
+                .map(|name| {
+                    table_columns
+                        .iter()
+                        .find(|column| column.name() == name.value)
+                        .map(|column| column.sql_type().to_pg_types())
+                })
+                .collect()
+        };
+        let mut inferred = vec![];
+        for row in rows {
+            for (value, target_type) in row.iter().zip(target_types.iter()) {
+                if let (Expr::Identifier(ident), Some(target_type)) = (value, target_type) {
+                    if let Some(number) = placeholder_number(ident) {
+                        inferred.push((number, *target_type));
+                    }
+                }
+            }
+        }
+        Ok(inferred)
+    }
+
+    /// binds parameters values to a prepared statement and stores the result
+    /// as a portal to execute
+    pub fn bind(
+        &mut self,
+        portal_name: &str,
+        statement_name: &str,
+        param_formats: &[i16],
+        params: &[Option<Vec<u8>>],
+    ) -> SystemResult<()> {
+        let statement = match self.prepared_statements.get(statement_name) {
+            Some(statement) => statement,
+            None => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .prepared_statement_does_not_exist(statement_name.to_owned())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                return Ok(());
+            }
+        };
+        if param_formats.len() > 1 && param_formats.len() != params.len() {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .protocol_violation(format!(
+                        "bind message has {} parameter formats but {} parameters",
+                        param_formats.len(),
+                        params.len()
+                    ))
+                    .build()))
+                .expect("To Send Query Result to Client");
+            return Ok(());
+        }
+        if params.len() != statement.param_count() {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .protocol_violation(format!(
+                        "bind message supplies {} parameters, but prepared statement \"{}\" requires {}",
+                        params.len(),
+                        statement_name,
+                        statement.param_count()
+                    ))
+                    .build()))
+                .expect("To Send Query Result to Client");
+            return Ok(());
+        }
+        let query_result = match statement.decode(param_formats, params) {
+            Ok(values) => {
+                self.portals.insert(portal_name.to_owned(), statement.bind(&values));
+                Ok(QueryEvent::BindComplete)
+            }
+            Err((parameter, ParameterError::InvalidBinaryFormat)) => {
+                Err(QueryErrorBuilder::new().invalid_binary_format(parameter).build())
+            }
+            Err((_, ParameterError::UnsupportedFormat(format))) => Err(QueryErrorBuilder::new()
+                .protocol_violation(format!("unsupported format code: {}", format))
+                .build()),
+            Err((_, ParameterError::InvalidText)) => Err(QueryErrorBuilder::new()
+                .protocol_violation("invalid byte sequence for encoding \"UTF8\"".to_owned())
+                .build()),
+        };
+        self.session.send(query_result).expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    /// executes a portal created by `bind`, limiting number of returned rows
+    /// with `max_rows` is not supported
+    pub fn execute_portal(&mut self, portal_name: &str, max_rows: i32) -> SystemResult<()> {
+        match self.portals.get(portal_name).cloned() {
+            None => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .portal_does_not_exist(portal_name.to_owned())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Some(_) if max_rows > 0 => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .feature_not_supported(format!("Execute with {} max rows", max_rows))
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+            Some(Portal { sql, statement }) => {
+                self.logger.start(&sql);
+                if let Some(activity) = self.activity.as_ref() {
+                    activity.set_query(&sql);
+                }
+                match statement {
+                    Some(statement) => self.execute_statement(&sql, statement),
+                    None => {
+                        self.session
+                            .send(Ok(QueryEvent::QueryIsEmpty))
+                            .expect("To Send Query Result to Client");
+                        Ok(())
+                    }
+                }
+            }
+        }
+    }
+
+    /// appends a chunk of data to `COPY ... FROM STDIN` in progress, chunks
+    /// without a copy in progress are ignored
+    pub fn copy_data(&mut self, data: &[u8]) -> SystemResult<()> {
+        if let Some((_, buffer)) = self.copy_in.as_mut() {
+            buffer.extend_from_slice(data);
+        }
+        Ok(())
+    }
+
+    /// copies rows of received data into a table
+    pub fn copy_done(&mut self) -> SystemResult<()> {
+        let (copy_info, data) = match self.copy_in.take() {
+            Some(copy_in) => copy_in,
+            None => return Ok(()),
+        };
+        match String::from_utf8(data) {
+            Ok(data) => CopyFromCommand::new(copy_info, data, self.storage.clone(), self.session.clone()).execute(),
+            Err(_) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .protocol_violation("invalid byte sequence for encoding \"UTF8\"".to_owned())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(())
+            }
+        }
+    }
+
+    /// discards received data when a client aborts `COPY ... FROM STDIN`
+    pub fn copy_fail(&mut self, message: &str) -> SystemResult<()> {
+        if self.copy_in.take().is_some() {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .copy_from_stdin_failed(message.to_owned())
+                    .build()))
+                .expect("To Send Query Result to Client");
+        }
+        Ok(())
+    }
+
+    fn set_variable(&mut self, variable: Ident, value: SetVariableValue) -> SystemResult<()> {
+        let value = match value {
+            SetVariableValue::Ident(ident) => ident.value,
+            SetVariableValue::Literal(Value::SingleQuotedString(literal)) => literal,
+            SetVariableValue::Literal(literal) => literal.to_string(),
+        };
+        let query_result = match self.apply_setting(&variable.value, value) {
+            Ok(()) => {
+                if variable.value.eq_ignore_ascii_case(TIME_ZONE) {
+                    self.report_time_zone();
+                }
+                Ok(QueryEvent::VariableSet)
+            }
+            Err(value) => Err(QueryErrorBuilder::new()
+                .invalid_parameter_value(variable.value.to_lowercase(), value)
+                .build()),
+        };
+        self.session.send(query_result).expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    // `SHOW ALL` lists all settings by name
+    fn show_variable(&self, variable: Ident) -> SystemResult<()> {
+        let name = variable.value.to_lowercase();
+        let query_result = if name == "all" {
+            let mut settings = SERVER_SETTINGS
+                .iter()
+                .filter(|(name, _)| !self.settings.contains_key(*name))
+                .map(|(name, value)| vec![Some((*name).to_owned()), Some((*value).to_owned())])
+                .chain(
+                    self.settings
+                        .iter()
+                        .map(|(name, value)| vec![Some(name.clone()), Some(value.clone())]),
+                )
+                .collect::<Vec<_>>();
+            settings.sort();
+            Ok(QueryEvent::RecordsSelected((
+                vec![
+                    ("name".to_owned(), PostgreSqlType::VarChar),
+                    ("setting".to_owned(), PostgreSqlType::VarChar),
+                ],
+                settings,
+            )))
+        } else {
+            let value = self.setting(&name).or_else(|| {
+                SERVER_SETTINGS
+                    .iter()
+                    .find(|(server_setting, _)| *server_setting == name)
+                    .map(|(_, value)| *value)
+            });
+            match value {
+                Some(value) => Ok(QueryEvent::RecordsSelected((
+                    vec![(name.clone(), PostgreSqlType::VarChar)],
+                    vec![vec![Some(value.to_owned())]],
+                ))),
+                None => Err(QueryErrorBuilder::new()
+                    .unrecognized_configuration_parameter(name)
+                    .build()),
+            }
+        };
+        self.session.send(query_result).expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    fn create_database(&mut self, name: String) -> SystemResult<()> {
+        let result = match self.databases.as_ref() {
+            None => Err(QueryErrorBuilder::new()
+                .feature_not_supported("CREATE DATABASE".to_owned())
+                .build()),
+            Some(databases) => {
+                if databases.create(name.as_str())? {
+                    Ok(QueryEvent::DatabaseCreated)
+                } else {
+                    Err(QueryErrorBuilder::new().database_already_exists(name).build())
+                }
+            }
+        };
+        self.session.send(result).expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    fn drop_database(&mut self, name: String) -> SystemResult<()> {
+        let result = match self.databases.as_ref() {
+            None => Err(QueryErrorBuilder::new()
+                .feature_not_supported("DROP DATABASE".to_owned())
+                .build()),
+            Some(_) if name == self.database => Err(QueryErrorBuilder::new().current_database_dropped().build()),
+            Some(databases) => {
+                if databases.drop(name.as_str()) {
+                    Ok(QueryEvent::DatabaseDropped)
+                } else {
+                    Err(QueryErrorBuilder::new().dropped_database_does_not_exist(name).build())
+                }
+            }
+        };
+        self.session.send(result).expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    fn autocommit(&self) -> bool {
+        self.setting(AUTOCOMMIT) != Some("off")
+    }
+
+    // with autocommit off an INSERT is validated and kept until COMMIT, a
+    // client is told the number of rows that are going to be inserted
+    fn defer_insert(&mut self, raw_sql_query: &str, insert: InsertInfo) -> SystemResult<()> {
+        let InsertInfo {
+            table_name,
+            columns,
+            source,
+        } = insert;
+        if let SetExpr::Values(_) = source.body {
+            InsertCommand::new(
+                raw_sql_query,
+                table_name,
+                columns,
+                source,
+                &mut self.sequence_values,
+                self.storage.clone(),
+                self.session.clone(),
+            )
+            .deferred_to(&mut self.transaction)
+            .execute()
+        } else {
+            self.not_supported_without_autocommit("INSERT ... SELECT")
+        }
+    }
+
+    // all inserts are validated before any of them is written, so that they
+    // are written all together or, when one of them fails, not at all
+    fn commit(&mut self) -> SystemResult<()> {
+        let inserts = self.transaction.take();
+        let mut storage = match write_storage(&self.storage, self.write_retry_policy, &self.session) {
+            Ok(storage) => storage,
+            Err(()) => return Ok(()),
+        };
+        for insert in inserts.iter() {
+            if let Err(error) = storage.validate_insert(
+                &insert.schema_name,
+                &insert.table_name,
+                insert.columns.clone(),
+                insert.rows.clone(),
+            )? {
+                report_insert_error(
+                    &self.session,
+                    insert.schema_name.clone(),
+                    insert.table_name.clone(),
+                    error,
+                );
+                return Ok(());
+            }
+        }
+        for insert in inserts {
+            if let Err(error) =
+                storage.insert_into(&insert.schema_name, &insert.table_name, insert.columns, insert.rows)?
+            {
+                report_insert_error(&self.session, insert.schema_name, insert.table_name, error);
+                return Ok(());
+            }
+        }
+        self.session
+            .send(Ok(QueryEvent::TransactionCommitted))
+            .expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    // results of these statements depend on inserts that are not written yet
+    fn not_supported_without_autocommit(&self, statement: &str) -> SystemResult<()> {
+        self.session
+            .send(Err(QueryErrorBuilder::new()
+                .feature_not_supported(format!("{} with autocommit off", statement))
+                .build()))
+            .expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    // `None` resets all settings
+    fn reset_variable(&mut self, name: Option<String>) -> SystemResult<()> {
+        let names = match name {
+            Some(name) => vec![name.to_lowercase()],
+            None => {
+                let mut names = self
+                    .settings
+                    .keys()
+                    .chain(self.default_settings.keys())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                names.sort();
+                names.dedup();
+                names
+            }
+        };
+        for name in names.iter() {
+            match self.default_settings.get(name).cloned() {
+                // defaults were valid when they were applied
+                Some(value) => self.apply_setting(name, value).expect("default setting is valid"),
+                None => {
+                    if name == LOG_MIN_MESSAGES {
+                        self.logger.set_level(LevelFilter::max());
+                    }
+                    self.settings.remove(name);
+                }
+            }
+        }
+        if names.iter().any(|name| name == TIME_ZONE) {
+            self.report_time_zone();
+        }
+        self.session
+            .send(Ok(QueryEvent::VariableReset))
+            .expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    // clients are notified about time zone change, the same way as PostgreSQL
+    // does it for `TimeZone` parameter
+    fn report_time_zone(&self) {
+        let time_zone = self.setting(TIME_ZONE).unwrap_or(time_zone::DEFAULT).to_owned();
+        self.session
+            .send(Ok(QueryEvent::ParameterStatus("TimeZone".to_owned(), time_zone)))
+            .expect("To Send Query Result to Client");
+    }
+
+    // returns back a value that is not valid for the setting
+    fn apply_setting(&mut self, name: &str, value: String) -> Result<(), String> {
+        let value = if name.eq_ignore_ascii_case(LOG_MIN_MESSAGES) {
+            match log_level_filter(&value) {
+                Some(level) => self.logger.set_level(level),
+                None => return Err(value),
+            }
+            value
+        } else if name.eq_ignore_ascii_case(AUTOCOMMIT) {
+            match value.to_lowercase().as_str() {
+                "on" | "true" | "yes" | "1" => "on".to_owned(),
+                "off" | "false" | "no" | "0" => "off".to_owned(),
+                _ => return Err(value),
+            }
+        } else if name.eq_ignore_ascii_case(TIME_ZONE) {
+            match time_zone::canonical(&value) {
+                Some(time_zone) => time_zone,
+                None => return Err(value),
+            }
+        } else {
+            value
+        };
+        self.settings.insert(name.to_lowercase(), value);
+        Ok(())
+    }
+
+    // `pg_stat_activity` lists sessions of the registry the executor is part
+    // of, only columns can be selected from it
+    fn select_activity(&self, query: &Query) -> SystemResult<()> {
+        const COLUMNS: [(&str, PostgreSqlType); 3] = [
+            ("pid", PostgreSqlType::Integer),
+            ("application_name", PostgreSqlType::VarChar),
+            ("query", PostgreSqlType::VarChar),
+        ];
+        let select = match &query.body {
+            SetExpr::Select(select) if query.order_by.is_empty() && query.limit.is_none() => select,
+            _ => return self.activity_not_supported(query),
+        };
+        if select.selection.is_some() || select.distinct {
+            return self.activity_not_supported(query);
+        }
+        let mut indexes = vec![];
+        let mut non_existing_columns = vec![];
+        for item in &select.projection {
+            match item {
+                SelectItem::Wildcard => indexes.extend(0..COLUMNS.len()),
+                SelectItem::UnnamedExpr(Expr::Identifier(ident)) => {
+                    match COLUMNS.iter().position(|(name, _)| *name == ident.value.to_lowercase()) {
+                        Some(index) => indexes.push(index),
+                        None => non_existing_columns.push(ident.value.clone()),
+                    }
+                }
+                _ => return self.activity_not_supported(query),
+            }
+        }
+        if !non_existing_columns.is_empty() {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .column_does_not_exist(non_existing_columns)
+                    .build()))
+                .expect("To Send Query Result to Client");
+            return Ok(());
+        }
+        let activity = self
+            .activity
+            .as_ref()
+            .map(|session| session.registry().activity())
+            .unwrap_or_default();
+        let description = indexes
+            .iter()
+            .map(|index| (COLUMNS[*index].0.to_owned(), COLUMNS[*index].1))
+            .collect();
+        let records = activity
+            .into_iter()
+            .map(|(pid, application_name, query)| {
+                let values = [pid.to_string(), application_name, query];
+                indexes.iter().map(|index| Some(values[*index].clone())).collect()
+            })
+            .collect();
+        self.session
+            .send(Ok(QueryEvent::RecordsSelected((description, records))))
+            .expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    fn activity_not_supported(&self, query: &Query) -> SystemResult<()> {
+        self.session
+            .send(Err(QueryErrorBuilder::new()
+                .feature_not_supported(query.to_string())
+                .build()))
+            .expect("To Send Query Result to Client");
+        Ok(())
+    }
+
+    fn check_query_length(&self, raw_sql_query: &str) -> Result<(), ()> {
+        match self.max_query_length {
+            Some(max_query_length) if raw_sql_query.len() > max_query_length => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .query_too_long(raw_sql_query.len(), max_query_length)
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn report_parse_error(&self, raw_sql_query: &str, error: ParserError) -> SystemResult<()> {
+        log::error!("{:?} can't be parsed. Error: {:?}", raw_sql_query, error);
+        self.session
+            .send(Err(syntax_error(raw_sql_query, error)))
+            .expect("To Send Query Result to Client");
+        Ok(())
+    }
+}
+
+// tells a client what the parser expected and, when the tokenizer failed,
+// line and column of the failure
+fn syntax_error(raw_sql_query: &str, error: ParserError) -> QueryError {
+    let message = match error {
+        ParserError::TokenizerError(message) => message,
+        ParserError::ParserError(message) => message,
+    };
+    QueryErrorBuilder::new()
+        .syntax_error(format!("{:?}: {}", raw_sql_query, message))
+        .build()
+}
+
+// maps PostgreSQL message levels onto `log` facade filter, the most verbose
+// `debug5`-`debug2` levels are traced
+fn log_level_filter(level: &str) -> Option<LevelFilter> {
+    match level.to_lowercase().as_str() {
+        "debug5" | "debug4" | "debug3" | "debug2" => Some(LevelFilter::Trace),
+        "debug1" | "debug" => Some(LevelFilter::Debug),
+        "info" | "notice" | "log" => Some(LevelFilter::Info),
+        "warning" => Some(LevelFilter::Warn),
+        "error" | "fatal" | "panic" => Some(LevelFilter::Error),
+        _ => None,
+    }
+}
+
+// `select ... from pg_stat_activity` optionally qualified with `pg_catalog` schema
+fn is_stat_activity(query: &Query) -> bool {
+    match &query.body {
+        SetExpr::Select(select) => match select.from.as_slice() {
+            [TableWithJoins {
+                relation: TableFactor::Table { name, .. },
+                joins,
+            }] if joins.is_empty() => match name.0.as_slice() {
+                [table] => table.value.eq_ignore_ascii_case("pg_stat_activity"),
+                [schema, table] => {
+                    schema.value.eq_ignore_ascii_case("pg_catalog")
+                        && table.value.eq_ignore_ascii_case("pg_stat_activity")
+                }
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,80 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sqlparser::ast::Statement;
+use std::collections::{HashMap, VecDeque};
+
+/// Statements parsed by `sqlparser` keyed by raw query text, so repeated
+/// queries are not parsed again. Least recently used query is evicted when
+/// capacity is exceeded.
+pub(crate) struct ParseCache {
+    capacity: usize,
+    statements: HashMap<String, Statement>,
+    // least recently used query is at the front
+    usage: VecDeque<String>,
+    hits: usize,
+    misses: usize,
+}
+
+impl ParseCache {
+    pub(crate) fn new(capacity: usize) -> ParseCache {
+        ParseCache {
+            capacity,
+            statements: HashMap::new(),
+            usage: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub(crate) fn get(&mut self, raw_sql_query: &str) -> Option<Statement> {
+        match self.statements.get(raw_sql_query) {
+            Some(statement) => {
+                self.hits += 1;
+                if let Some(index) = self.usage.iter().position(|query| query == raw_sql_query) {
+                    let query = self.usage.remove(index).unwrap();
+                    self.usage.push_back(query);
+                }
+                Some(statement.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn put(&mut self, raw_sql_query: &str, statement: Statement) {
+        if self.capacity == 0 || self.statements.contains_key(raw_sql_query) {
+            return;
+        }
+        if self.statements.len() == self.capacity {
+            if let Some(evicted) = self.usage.pop_front() {
+                self.statements.remove(&evicted);
+            }
+        }
+        self.statements.insert(raw_sql_query.to_owned(), statement);
+        self.usage.push_back(raw_sql_query.to_owned());
+    }
+
+    #[cfg(test)]
+    pub(crate) fn hits(&self) -> usize {
+        self.hits
+    }
+
+    #[cfg(test)]
+    pub(crate) fn misses(&self) -> usize {
+        self.misses
+    }
+}
@@ -0,0 +1,508 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Statements of extended query protocol have `$n` placeholders, they are
+// parsed once as `"$n"` identifiers and the identifiers are replaced in the
+// syntax tree with values supplied by `Bind`.
+use crate::extension::ExtendedStatement;
+use bigdecimal::BigDecimal;
+use protocol::sql_types::PostgreSqlType;
+use sqlparser::ast::{
+    Expr, Function, Ident, Join, JoinConstraint, JoinOperator, ListAgg, OrderByExpr, Query, Select, SelectItem,
+    SetExpr, Statement, TableFactor, TableWithJoins, Value, WindowSpec,
+};
+use std::{convert::TryInto, str::FromStr};
+
+const TEXT_FORMAT: i16 = 0;
+const BINARY_FORMAT: i16 = 1;
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum ParameterError {
+    UnsupportedFormat(i16),
+    InvalidBinaryFormat,
+    InvalidText,
+}
+
+/// statement parsed either by the extension or by `sqlparser`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ParsedStatement {
+    Standard(Statement),
+    Extended(ExtendedStatement),
+}
+
+/// prepared statement with values of its parameters bound
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Portal {
+    /// text of the prepared statement
+    pub(crate) sql: String,
+    /// `None` for an empty query
+    pub(crate) statement: Option<ParsedStatement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PreparedStatement {
+    sql: String,
+    // `None` for an empty query
+    statement: Option<ParsedStatement>,
+    param_types: Vec<Option<PostgreSqlType>>,
+}
+
+impl PreparedStatement {
+    /// types of parameters are given by OIDs, `0` or not given OID leaves a
+    /// type unspecified
+    pub(crate) fn new(sql: &str, statement: Option<ParsedStatement>, param_type_oids: &[i32]) -> PreparedStatement {
+        let param_count = placeholders(sql)
+            .iter()
+            .map(|(_, _, index)| *index)
+            .max()
+            .unwrap_or(0)
+            .max(param_type_oids.len());
+        let param_types = (0..param_count)
+            .map(|index| {
+                param_type_oids
+                    .get(index)
+                    .and_then(|oid| PostgreSqlType::from_oid(*oid))
+            })
+            .collect();
+        PreparedStatement {
+            sql: sql.to_owned(),
+            statement,
+            param_types,
+        }
+    }
+
+    pub(crate) fn statement(&self) -> Option<&ParsedStatement> {
+        self.statement.as_ref()
+    }
+
+    pub(crate) fn param_count(&self) -> usize {
+        self.param_types.len()
+    }
+
+    /// decodes parameters values sent in `Bind` message, no format codes
+    /// means all values are in text format, a single code is applied to all
+    pub(crate) fn decode(
+        &self,
+        param_formats: &[i16],
+        params: &[Option<Vec<u8>>],
+    ) -> Result<Vec<Value>, (usize, ParameterError)> {
+        params
+            .iter()
+            .enumerate()
+            .map(|(index, param)| {
+                let format = match param_formats {
+                    [] => TEXT_FORMAT,
+                    [format] => *format,
+                    formats => formats.get(index).copied().unwrap_or(TEXT_FORMAT),
+                };
+                decode(format, self.param_types[index], param.as_deref()).map_err(|error| (index + 1, error))
+            })
+            .collect()
+    }
+
+    pub(crate) fn has_unspecified_param_types(&self) -> bool {
+        self.param_types.iter().any(Option::is_none)
+    }
+
+    /// sets types of parameters that were not specified by a client, e.g. to
+    /// types of columns the parameters are inserted into, a type is inferred
+    /// only if every usage of a parameter agrees on it
+    pub(crate) fn infer_param_types(&mut self, inferred: Vec<(usize, PostgreSqlType)>) {
+        let usages = placeholders(&self.sql);
+        for (index, param_type) in self.param_types.iter_mut().enumerate() {
+            if param_type.is_some() {
+                continue;
+            }
+            let number = index + 1;
+            let types = inferred
+                .iter()
+                .filter(|(param, _)| *param == number)
+                .map(|(_, pg_type)| *pg_type)
+                .collect::<Vec<_>>();
+            let usage_count = usages.iter().filter(|(_, _, param)| *param == number).count();
+            if types.len() == usage_count && types.windows(2).all(|pair| pair[0] == pair[1]) {
+                *param_type = types.first().copied();
+            }
+        }
+    }
+
+    /// portal of the statement with placeholders replaced by values in a copy
+    /// of its syntax tree, the query text is not parsed again
+    pub(crate) fn bind(&self, values: &[Value]) -> Portal {
+        let statement = self.statement.clone().map(|statement| match statement {
+            ParsedStatement::Standard(mut statement) => {
+                bind_statement(&mut statement, values);
+                ParsedStatement::Standard(statement)
+            }
+            ParsedStatement::Extended(mut statement) => {
+                match &mut statement {
+                    ExtendedStatement::InsertReturning { insert, returning } => {
+                        bind_statement(insert, values);
+                        returning.iter_mut().for_each(|item| bind_select_item(item, values));
+                    }
+                    ExtendedStatement::ExplainAnalyze(query) => bind_query(query, values),
+                    _ => {}
+                }
+                ParsedStatement::Extended(statement)
+            }
+        });
+        Portal {
+            sql: self.sql.clone(),
+            statement,
+        }
+    }
+}
+
+/// query text with placeholders replaced by `"$n"` identifiers, so that it
+/// can be parsed
+pub(crate) fn with_placeholder_identifiers(sql: &str) -> String {
+    let mut replaced = String::with_capacity(sql.len());
+    let mut position = 0;
+    for (start, end, index) in placeholders(sql) {
+        replaced.push_str(&sql[position..start]);
+        replaced.push_str(&format!("\"${}\"", index));
+        position = end;
+    }
+    replaced.push_str(&sql[position..]);
+    replaced
+}
+
+/// number of a placeholder turned into identifier by `with_placeholder_identifiers`
+pub(crate) fn placeholder_number(ident: &Ident) -> Option<usize> {
+    match ident.quote_style {
+        Some('"') if ident.value.starts_with('$') => ident.value[1..].parse().ok().filter(|number| *number > 0),
+        _ => None,
+    }
+}
+
+// (start, end, number) of `$n` placeholders outside of quoted strings and identifiers
+fn placeholders(sql: &str) -> Vec<(usize, usize, usize)> {
+    let bytes = sql.as_bytes();
+    let mut found = vec![];
+    let mut quote = None;
+    let mut position = 0;
+    while position < bytes.len() {
+        let byte = bytes[position];
+        match quote {
+            Some(quote_byte) if quote_byte == byte => quote = None,
+            Some(_) => {}
+            None if byte == b'\'' || byte == b'"' => quote = Some(byte),
+            None if byte == b'$' && (position == 0 || !is_identifier_byte(bytes[position - 1])) => {
+                let digits = bytes[position + 1..].iter().take_while(|b| b.is_ascii_digit()).count();
+                if digits > 0 {
+                    let end = position + 1 + digits;
+                    if let Ok(number) = sql[position + 1..end].parse::<usize>() {
+                        if number > 0 {
+                            found.push((position, end, number));
+                        }
+                    }
+                    position = end;
+                    continue;
+                }
+            }
+            None => {}
+        }
+        position += 1;
+    }
+    found
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$'
+}
+
+// statements other than queries and DML have no expressions to bind
+fn bind_statement(statement: &mut Statement, values: &[Value]) {
+    match statement {
+        Statement::Query(query) | Statement::Insert { source: query, .. } => bind_query(query, values),
+        Statement::Update {
+            assignments, selection, ..
+        } => {
+            for assignment in assignments.iter_mut() {
+                bind_expr(&mut assignment.value, values);
+            }
+            if let Some(selection) = selection {
+                bind_expr(selection, values);
+            }
+        }
+        Statement::Delete {
+            selection: Some(selection),
+            ..
+        } => bind_expr(selection, values),
+        _ => {}
+    }
+}
+
+fn bind_query(query: &mut Query, values: &[Value]) {
+    for cte in query.ctes.iter_mut() {
+        bind_query(&mut cte.query, values);
+    }
+    bind_set_expr(&mut query.body, values);
+    bind_order_by(&mut query.order_by, values);
+    if let Some(limit) = &mut query.limit {
+        bind_expr(limit, values);
+    }
+    if let Some(offset) = &mut query.offset {
+        bind_expr(&mut offset.value, values);
+    }
+    if let Some(quantity) = query.fetch.as_mut().and_then(|fetch| fetch.quantity.as_mut()) {
+        bind_expr(quantity, values);
+    }
+}
+
+fn bind_set_expr(body: &mut SetExpr, values: &[Value]) {
+    match body {
+        SetExpr::Select(select) => bind_select(select, values),
+        SetExpr::Query(query) => bind_query(query, values),
+        SetExpr::SetOperation { left, right, .. } => {
+            bind_set_expr(left, values);
+            bind_set_expr(right, values);
+        }
+        SetExpr::Values(rows) => {
+            for row in rows.0.iter_mut() {
+                bind_exprs(row, values);
+            }
+        }
+    }
+}
+
+fn bind_select(select: &mut Select, values: &[Value]) {
+    let Select {
+        top,
+        projection,
+        from,
+        selection,
+        group_by,
+        having,
+        ..
+    } = select;
+    if let Some(quantity) = top.as_mut().and_then(|top| top.quantity.as_mut()) {
+        bind_expr(quantity, values);
+    }
+    projection.iter_mut().for_each(|item| bind_select_item(item, values));
+    from.iter_mut().for_each(|table| bind_table_with_joins(table, values));
+    if let Some(selection) = selection {
+        bind_expr(selection, values);
+    }
+    bind_exprs(group_by, values);
+    if let Some(having) = having {
+        bind_expr(having, values);
+    }
+}
+
+fn bind_select_item(item: &mut SelectItem, values: &[Value]) {
+    match item {
+        SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => bind_expr(expr, values),
+        SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => {}
+    }
+}
+
+fn bind_table_with_joins(table: &mut TableWithJoins, values: &[Value]) {
+    bind_table_factor(&mut table.relation, values);
+    for Join {
+        relation,
+        join_operator,
+    } in table.joins.iter_mut()
+    {
+        bind_table_factor(relation, values);
+        match join_operator {
+            JoinOperator::Inner(JoinConstraint::On(on))
+            | JoinOperator::LeftOuter(JoinConstraint::On(on))
+            | JoinOperator::RightOuter(JoinConstraint::On(on))
+            | JoinOperator::FullOuter(JoinConstraint::On(on)) => bind_expr(on, values),
+            _ => {}
+        }
+    }
+}
+
+fn bind_table_factor(relation: &mut TableFactor, values: &[Value]) {
+    match relation {
+        TableFactor::Table { args, with_hints, .. } => {
+            bind_exprs(args, values);
+            bind_exprs(with_hints, values);
+        }
+        TableFactor::Derived { subquery, .. } => bind_query(subquery, values),
+        TableFactor::NestedJoin(table) => bind_table_with_joins(table, values),
+    }
+}
+
+fn bind_order_by(order_by: &mut [OrderByExpr], values: &[Value]) {
+    for OrderByExpr { expr, .. } in order_by.iter_mut() {
+        bind_expr(expr, values);
+    }
+}
+
+fn bind_exprs(exprs: &mut [Expr], values: &[Value]) {
+    exprs.iter_mut().for_each(|expr| bind_expr(expr, values));
+}
+
+fn bind_expr(expr: &mut Expr, values: &[Value]) {
+    match expr {
+        Expr::Identifier(ident) => {
+            let value = placeholder_number(ident)
+                .and_then(|number| values.get(number - 1))
+                .cloned();
+            if let Some(value) = value {
+                *expr = Expr::Value(value);
+            }
+        }
+        Expr::Wildcard
+        | Expr::QualifiedWildcard(_)
+        | Expr::CompoundIdentifier(_)
+        | Expr::Value(_)
+        | Expr::TypedString { .. } => {}
+        Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::UnaryOp { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Extract { expr, .. }
+        | Expr::Collate { expr, .. }
+        | Expr::Nested(expr) => bind_expr(expr, values),
+        Expr::InList { expr, list, .. } => {
+            bind_expr(expr, values);
+            bind_exprs(list, values);
+        }
+        Expr::InSubquery { expr, subquery, .. } => {
+            bind_expr(expr, values);
+            bind_query(subquery, values);
+        }
+        Expr::Between { expr, low, high, .. } => {
+            bind_expr(expr, values);
+            bind_expr(low, values);
+            bind_expr(high, values);
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            bind_expr(left, values);
+            bind_expr(right, values);
+        }
+        Expr::Function(Function { args, over, .. }) => {
+            bind_exprs(args, values);
+            if let Some(WindowSpec {
+                partition_by, order_by, ..
+            }) = over
+            {
+                bind_exprs(partition_by, values);
+                bind_order_by(order_by, values);
+            }
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            if let Some(operand) = operand {
+                bind_expr(operand, values);
+            }
+            bind_exprs(conditions, values);
+            bind_exprs(results, values);
+            if let Some(else_result) = else_result {
+                bind_expr(else_result, values);
+            }
+        }
+        Expr::Exists(query) | Expr::Subquery(query) => bind_query(query, values),
+        Expr::ListAgg(ListAgg {
+            expr,
+            separator,
+            within_group,
+            ..
+        }) => {
+            bind_expr(expr, values);
+            if let Some(separator) = separator {
+                bind_expr(separator, values);
+            }
+            bind_order_by(within_group, values);
+        }
+    }
+}
+
+fn decode(format: i16, pg_type: Option<PostgreSqlType>, value: Option<&[u8]>) -> Result<Value, ParameterError> {
+    let value = match value {
+        None => return Ok(Value::Null),
+        Some(value) => value,
+    };
+    match format {
+        TEXT_FORMAT => decode_text(pg_type, value),
+        BINARY_FORMAT => decode_binary(pg_type, value),
+        format => Err(ParameterError::UnsupportedFormat(format)),
+    }
+}
+
+fn decode_text(pg_type: Option<PostgreSqlType>, value: &[u8]) -> Result<Value, ParameterError> {
+    let text = std::str::from_utf8(value).map_err(|_| ParameterError::InvalidText)?;
+    let numeric = match pg_type {
+        None => true,
+        Some(pg_type) => is_numeric(pg_type),
+    };
+    match BigDecimal::from_str(text.trim()) {
+        Ok(number) if numeric => Ok(Value::Number(number)),
+        _ => Ok(Value::SingleQuotedString(text.to_owned())),
+    }
+}
+
+fn decode_binary(pg_type: Option<PostgreSqlType>, value: &[u8]) -> Result<Value, ParameterError> {
+    let text = match pg_type {
+        Some(PostgreSqlType::Bool) => match value {
+            [byte] => return Ok(Value::Boolean(*byte != 0)),
+            _ => None,
+        },
+        Some(PostgreSqlType::SmallInt) => value.try_into().ok().map(|b| i16::from_be_bytes(b).to_string()),
+        Some(PostgreSqlType::Integer) => value.try_into().ok().map(|b| i32::from_be_bytes(b).to_string()),
+        Some(PostgreSqlType::BigInt) => value.try_into().ok().map(|b| i64::from_be_bytes(b).to_string()),
+        Some(PostgreSqlType::Real) => match value.try_into().map(f32::from_be_bytes) {
+            Ok(number) if number.is_finite() => Some(number.to_string()),
+            Ok(number) => return Ok(non_finite(f64::from(number))),
+            Err(_) => None,
+        },
+        Some(PostgreSqlType::DoublePrecision) => match value.try_into().map(f64::from_be_bytes) {
+            Ok(number) if number.is_finite() => Some(number.to_string()),
+            Ok(number) => return Ok(non_finite(number)),
+            Err(_) => None,
+        },
+        Some(PostgreSqlType::Char) | Some(PostgreSqlType::VarChar) | Some(PostgreSqlType::Json) => {
+            return std::str::from_utf8(value)
+                .map(|text| Value::SingleQuotedString(text.to_owned()))
+                .map_err(|_| ParameterError::InvalidBinaryFormat);
+        }
+        _ => None,
+    };
+    text.and_then(|text| BigDecimal::from_str(&text).ok())
+        .map(Value::Number)
+        .ok_or(ParameterError::InvalidBinaryFormat)
+}
+
+// NaN and infinities have no numeric literals, they are bound as strings the
+// same way they are written in queries
+fn non_finite(number: f64) -> Value {
+    let text = if number.is_nan() {
+        "NaN"
+    } else if number > 0.0 {
+        "Infinity"
+    } else {
+        "-Infinity"
+    };
+    Value::SingleQuotedString(text.to_owned())
+}
+
+fn is_numeric(pg_type: PostgreSqlType) -> bool {
+    matches!(
+        pg_type,
+        PostgreSqlType::SmallInt
+            | PostgreSqlType::Integer
+            | PostgreSqlType::BigInt
+            | PostgreSqlType::Real
+            | PostgreSqlType::DoublePrecision
+            | PostgreSqlType::Decimal
+    )
+}
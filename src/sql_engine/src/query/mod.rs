@@ -15,21 +15,18 @@
 ///! Module for representing how a query will be executed and values represented
 ///! during runtime.
 mod plan;
+// execution nodes are going to work on typed rows instead of strings read from storage
+#[allow(dead_code)]
+mod repr;
+#[cfg(test)]
+mod tests;
 mod transform;
 
-pub use plan::{Plan, SchemaCreationInfo, TableCreationInfo};
-pub use transform::QueryProcessor;
-
-use sql_types::SqlType;
-
-/// A type of a column
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ColumnType {
-    #[allow(dead_code)]
-    nullable: bool,
-    /// the sql type
-    sql_type: SqlType,
-}
+pub use plan::{
+    ColumnRenamingInfo, ColumnTypeChangeInfo, CopyInfo, InsertInfo, Plan, PrivilegeInfo, SchemaCreationInfo,
+    SequenceCreationInfo, TableCreationInfo,
+};
+pub use transform::{IdentifierLength, QueryProcessor, MAX_IDENTIFIER_LENGTH};
 
 // this works for now, but ideally this should be usize's instead of strings.
 
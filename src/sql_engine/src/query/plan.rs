@@ -13,8 +13,13 @@
 // limitations under the License.
 
 ///! represents a plan to be executed by the engine.
-use crate::query::{SchemaId, TableId};
-use sqlparser::ast::Statement;
+use crate::{
+    extension::CopyOptions,
+    query::{SchemaId, TableId},
+};
+use protocol::results::QueryEvent;
+use sql_types::SqlType;
+use sqlparser::ast::{Ident, ObjectName, Query, SelectItem, Statement};
 use storage::ColumnDefinition;
 
 #[derive(Debug, Clone)]
@@ -29,11 +34,73 @@ pub struct SchemaCreationInfo {
     pub schema_name: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct SequenceCreationInfo {
+    pub sequence_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnRenamingInfo {
+    pub schema_name: String,
+    pub table_name: String,
+    pub old_column_name: String,
+    pub new_column_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnTypeChangeInfo {
+    pub schema_name: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub sql_type: SqlType,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrivilegeInfo {
+    pub schema_name: String,
+    pub table_name: String,
+    pub grantee: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CopyInfo {
+    pub table_id: TableId,
+    // empty for all columns of a table
+    pub columns: Vec<String>,
+    pub options: CopyOptions,
+}
+
+// parts of `INSERT` statement
+#[derive(Debug, Clone)]
+pub struct InsertInfo {
+    pub table_name: ObjectName,
+    pub columns: Vec<Ident>,
+    pub source: Box<Query>,
+}
+
 #[derive(Debug, Clone)]
 pub enum Plan {
     CreateTable(TableCreationInfo),
     CreateSchema(SchemaCreationInfo),
+    CreateSequence(SequenceCreationInfo),
+    CreateDatabase(String),
+    DropDatabase(String),
+    RenameColumn(ColumnRenamingInfo),
+    AlterColumnType(ColumnTypeChangeInfo),
     DropTables(Vec<TableId>),
     DropSchemas(Vec<SchemaId>),
+    Vacuum(TableId),
+    GrantSelect(PrivilegeInfo),
+    RevokeSelect(PrivilegeInfo),
+    ExplainAnalyze(Box<Query>),
+    CopyTo(CopyInfo),
+    CopyFrom(CopyInfo),
+    // `INSERT` statement and its returning list
+    InsertReturning(InsertInfo, Vec<SelectItem>),
+    // `None` resets all session settings
+    ResetVariable(Option<String>),
+    // there is nothing to do, e.g. `DROP TABLE IF EXISTS` of a missing table,
+    // only the event is sent
+    Skip(QueryEvent),
     NotProcessed(Statement),
 }
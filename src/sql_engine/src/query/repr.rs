@@ -12,11 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::query::RelationType;
 use ordered_float::OrderedFloat;
 use sql_types::SqlType;
+use storage::ColumnDefinition;
 
-///! Runtime cell and row representation.
+// Runtime cell and row representation.
 
 // owned parallel of Datum but owns the content.
 // pub enum Value {
@@ -200,7 +200,7 @@ macro_rules! push_copy {
     ($ptr:expr, $val:expr, $T:ty) => {{
         let t = $val;
         assert_copy(t);
-        $ptr.extend_from_slice(&unsafe { std::mem::transmute::<_, [u8; std::mem::size_of::<$T>()]>(t) })
+        $ptr.extend_from_slice(&<$T>::to_ne_bytes(t))
     }};
 }
 
@@ -326,4 +326,43 @@ impl Row {
         }
         res
     }
+
+    /// builds a row out of values read from storage, each value is parsed
+    /// once according to its column type
+    pub fn from_values(columns: &[ColumnDefinition], values: &[String]) -> Self {
+        let datums = columns
+            .iter()
+            .zip(values.iter())
+            .map(|(column, value)| match column.sql_type() {
+                SqlType::SmallInt(_) => value.parse().map(Datum::from_i16).unwrap_or(Datum::Null),
+                SqlType::Integer(_) => value.parse().map(Datum::from_i32).unwrap_or(Datum::Null),
+                SqlType::BigInt(_) => value.parse().map(Datum::from_i64).unwrap_or(Datum::Null),
+                SqlType::Bool => Datum::from_bool(value == "t"),
+                _ => Datum::from_str(value.as_str()),
+            })
+            .collect::<Vec<Datum>>();
+        Self::pack(&datums)
+    }
+
+    pub fn is_null(&self, index: usize) -> bool {
+        matches!(self.unpack().get(index), Some(Datum::Null))
+    }
+
+    /// integer value of the cell, `None` for NULL or non integer cells
+    pub fn get_int(&self, index: usize) -> Option<i64> {
+        match self.unpack().get(index) {
+            Some(Datum::Int16(val)) => Some(*val as i64),
+            Some(Datum::Int32(val)) => Some(*val as i64),
+            Some(Datum::Int64(val)) => Some(*val),
+            _ => None,
+        }
+    }
+
+    /// string value of the cell, `None` for NULL or non string cells
+    pub fn get_str(&self, index: usize) -> Option<&str> {
+        match self.unpack().into_iter().nth(index) {
+            Some(Datum::String(val)) => Some(val),
+            _ => None,
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::query::*;
+use crate::query::repr::{Datum, Row};
 
 mod scalar {
     use super::*;
@@ -28,7 +28,7 @@ mod scalar {
         let datums = vec![
             Datum::from_bool(false),
             Datum::from_i32(100000),
-            Datum::from_f32(100.134212309847),
+            Datum::from_f32(100.134_21),
         ];
         let row = Row::pack(&datums);
         assert_eq!(
@@ -75,7 +75,7 @@ mod scalar {
         let datums = vec![
             Datum::from_bool(false),
             Datum::from_i32(100000),
-            Datum::from_f32(100.134212309847),
+            Datum::from_f32(100.134_21),
         ];
         let row = Row::pack(&datums);
         assert_eq!(row.unpack(), datums);
@@ -95,3 +95,54 @@ mod scalar {
         assert_eq!(row.unpack(), datums);
     }
 }
+
+mod typed_access {
+    use super::*;
+    use sql_types::SqlType;
+    use storage::ColumnDefinition;
+
+    #[test]
+    fn integer_cells() {
+        let row = Row::pack(&[
+            Datum::from_i16(-1),
+            Datum::from_i32(100000),
+            Datum::from_i64(i64::max_value()),
+        ]);
+        assert_eq!(row.get_int(0), Some(-1));
+        assert_eq!(row.get_int(1), Some(100000));
+        assert_eq!(row.get_int(2), Some(i64::max_value()));
+        assert_eq!(row.get_str(0), None);
+    }
+
+    #[test]
+    fn string_cells() {
+        let row = Row::pack(&[Datum::from_str("hello"), Datum::from_string("world".to_owned())]);
+        assert_eq!(row.get_str(0), Some("hello"));
+        assert_eq!(row.get_str(1), Some("world"));
+        assert_eq!(row.get_int(0), None);
+    }
+
+    #[test]
+    fn null_cells() {
+        let row = Row::pack(&[Datum::from_null(), Datum::from_i32(1)]);
+        assert!(row.is_null(0));
+        assert!(!row.is_null(1));
+        assert!(!row.is_null(2));
+        assert_eq!(row.get_int(0), None);
+        assert_eq!(row.get_str(0), None);
+    }
+
+    #[test]
+    fn from_storage_values() {
+        let columns = vec![
+            ColumnDefinition::new("id", SqlType::Integer(i32::min_value())),
+            ColumnDefinition::new("name", SqlType::VarChar(10)),
+            ColumnDefinition::new("active", SqlType::Bool),
+        ];
+        let row = Row::from_values(&columns, &["42".to_owned(), "name".to_owned(), "t".to_owned()]);
+
+        assert_eq!(row.get_int(0), Some(42));
+        assert_eq!(row.get_str(1), Some("name"));
+        assert_eq!(row.unpack()[2], Datum::from_bool(true));
+    }
+}
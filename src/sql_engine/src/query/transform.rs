@@ -13,78 +13,335 @@
 // limitations under the License.
 
 ///! Module for transforming the input Query AST into representation the engine can process.
+use crate::extension::{CopyOptions, ExtendedStatement};
 use crate::query::plan::SchemaCreationInfo;
-use crate::query::{plan::Plan, SchemaId, TableCreationInfo, TableId};
-use protocol::{results::QueryErrorBuilder, Sender};
-use sql_types::SqlType;
-use sqlparser::ast::{ColumnDef, DataType, ObjectName, ObjectType, Statement};
-use std::sync::{Arc, Mutex, MutexGuard};
-use storage::{backend::BackendStorage, frontend::FrontendStorage, ColumnDefinition};
+use crate::query::{
+    plan::Plan, ColumnRenamingInfo, ColumnTypeChangeInfo, CopyInfo, InsertInfo, PrivilegeInfo, SchemaId,
+    SequenceCreationInfo, TableCreationInfo, TableId,
+};
+use protocol::{
+    results::{QueryErrorBuilder, QueryEvent},
+    Sender,
+};
+use sql_types::{ArrayElementType, Collation, SqlType};
+use sqlparser::ast::{
+    AlterTableOperation, ColumnDef, ColumnOption, DataType, Ident, ObjectName, ObjectType, SetExpr, SetVariableValue,
+    Statement, TableFactor, Value,
+};
+use std::{
+    iter,
+    sync::{Arc, RwLockReadGuard},
+};
+use storage::{
+    backend::BackendStorage,
+    frontend::{FrontendStorage, SharedStorage},
+    ColumnDefinition,
+};
 
 type Result<T> = std::result::Result<T, ()>;
 
+/// PostgreSQL `NAMEDATALEN - 1`
+pub const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+/// Maximum length of schema, table and column names in bytes and what is done
+/// with longer ones
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IdentifierLength {
+    /// longer identifiers are truncated with a notice as PostgreSQL does
+    Truncate(usize),
+    /// longer identifiers are rejected with an error
+    Reject(usize),
+}
+
+impl Default for IdentifierLength {
+    fn default() -> Self {
+        IdentifierLength::Truncate(MAX_IDENTIFIER_LENGTH)
+    }
+}
+
 // this could probably just be a function.
 /// structure for maintaining state while transforming the input statement.
 pub struct QueryProcessor<B: BackendStorage> {
     /// access to table and schema information.
-    storage: Arc<Mutex<FrontendStorage<B>>>,
+    storage: SharedStorage<B>,
     session: Arc<dyn Sender>,
+    identifier_length: IdentifierLength,
 }
 
-impl<'qp, B: BackendStorage> QueryProcessor<B> {
-    pub fn new(storage: Arc<Mutex<FrontendStorage<B>>>, session: Arc<dyn Sender>) -> Self {
-        Self { storage, session }
+impl<B: BackendStorage> QueryProcessor<B> {
+    pub fn new(storage: SharedStorage<B>, session: Arc<dyn Sender>) -> Self {
+        Self {
+            storage,
+            session,
+            identifier_length: IdentifierLength::default(),
+        }
+    }
+
+    pub fn set_storage(&mut self, storage: SharedStorage<B>) {
+        self.storage = storage;
+    }
+
+    pub fn set_identifier_length(&mut self, identifier_length: IdentifierLength) {
+        self.identifier_length = identifier_length;
     }
 
-    pub fn storage(&self) -> MutexGuard<FrontendStorage<B>> {
-        self.storage.lock().unwrap()
+    pub fn storage(&self) -> RwLockReadGuard<'_, FrontendStorage<B>> {
+        self.storage.read().unwrap()
     }
 
     pub fn process(&mut self, stmt: Statement) -> Result<Plan> {
         self.handle_statement(&stmt)
     }
 
+    pub fn process_extended(&mut self, stmt: ExtendedStatement) -> Result<Plan> {
+        match stmt {
+            ExtendedStatement::Vacuum(Some(name)) => {
+                let table_id = self.table_from_object(&name)?;
+                self.check_table_exists(&table_id)?;
+                Ok(Plan::Vacuum(table_id))
+            }
+            ExtendedStatement::Grant { table_name, grantee } => {
+                let table_id = self.table_from_object(&table_name)?;
+                self.check_table_exists(&table_id)?;
+                Ok(Plan::GrantSelect(PrivilegeInfo {
+                    schema_name: table_id.schema_name().to_owned(),
+                    table_name: table_id.name().to_owned(),
+                    grantee,
+                }))
+            }
+            ExtendedStatement::Revoke { table_name, grantee } => {
+                let table_id = self.table_from_object(&table_name)?;
+                self.check_table_exists(&table_id)?;
+                Ok(Plan::RevokeSelect(PrivilegeInfo {
+                    schema_name: table_id.schema_name().to_owned(),
+                    table_name: table_id.name().to_owned(),
+                    grantee,
+                }))
+            }
+            ExtendedStatement::CreateSequence(name) => Ok(Plan::CreateSequence(SequenceCreationInfo {
+                sequence_name: name.to_string(),
+            })),
+            ExtendedStatement::CreateSchemaIfNotExists(name) => self.handle_create_schema(&name, true),
+            ExtendedStatement::CreateDatabase(name) => Ok(Plan::CreateDatabase(name)),
+            ExtendedStatement::DropDatabase(name) => Ok(Plan::DropDatabase(name)),
+            ExtendedStatement::ExplainAnalyze(query) => match self.limit_table_names(Statement::Query(query))? {
+                Statement::Query(query) => Ok(Plan::ExplainAnalyze(query)),
+                _ => unreachable!(),
+            },
+            ExtendedStatement::SetTimeZone(time_zone) => Ok(Plan::NotProcessed(Statement::SetVariable {
+                local: false,
+                variable: Ident::new("timezone"),
+                value: SetVariableValue::Literal(Value::SingleQuotedString(time_zone)),
+            })),
+            ExtendedStatement::Reset(name) => Ok(Plan::ResetVariable(name)),
+            ExtendedStatement::InsertReturning { insert, returning } => match self.limit_table_names(insert)? {
+                Statement::Insert {
+                    table_name,
+                    columns,
+                    source,
+                } => Ok(Plan::InsertReturning(
+                    InsertInfo {
+                        table_name,
+                        columns,
+                        source,
+                    },
+                    returning,
+                )),
+                statement => {
+                    self.session
+                        .send(Err(QueryErrorBuilder::new()
+                            .feature_not_supported(format!("RETURNING of {}", statement))
+                            .build()))
+                        .expect("To Send Query Result to Client");
+                    Err(())
+                }
+            },
+            ExtendedStatement::AlterColumnType {
+                table_name,
+                column_name,
+                data_type,
+            } => {
+                let table_id = self.table_from_object(&table_name)?;
+                self.check_table_exists(&table_id)?;
+                Ok(Plan::AlterColumnType(ColumnTypeChangeInfo {
+                    schema_name: table_id.schema_name().to_owned(),
+                    table_name: table_id.name().to_owned(),
+                    column_name: self.identifier(&column_name.value)?,
+                    sql_type: self.sql_type_from_datatype(&data_type)?,
+                }))
+            }
+            ExtendedStatement::CopyTo {
+                table_name,
+                columns,
+                options,
+            } => Ok(Plan::CopyTo(self.copy_info(&table_name, columns, options)?)),
+            ExtendedStatement::CopyFrom {
+                table_name,
+                columns,
+                options,
+            } => Ok(Plan::CopyFrom(self.copy_info(&table_name, columns, options)?)),
+            ExtendedStatement::Vacuum(None) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .feature_not_supported("VACUUM without a table name".to_owned())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
+        }
+    }
+
+    fn copy_info(&self, table_name: &ObjectName, columns: Vec<Ident>, options: CopyOptions) -> Result<CopyInfo> {
+        let table_id = self.table_from_object(table_name)?;
+        self.check_table_exists(&table_id)?;
+        let mut column_names = Vec::with_capacity(columns.len());
+        for column in columns {
+            column_names.push(self.identifier(&column.value)?);
+        }
+        Ok(CopyInfo {
+            table_id,
+            columns: column_names,
+            options,
+        })
+    }
+
     // this was moved out to clean up the code. This is a good place
     // to start but should not be the final code.
     fn table_from_object(&self, object: &ObjectName) -> Result<TableId> {
-        if object.0.len() == 1 {
-            self.session
-                .send(Err(QueryErrorBuilder::new()
-                    .syntax_error(format!(
-                        "unsupported table name '{}'. All table names must be qualified",
-                        object.to_string()
-                    ))
-                    .build()))
-                .expect("To Send Query Result to Client");
-            Err(())
-        } else if object.0.len() != 2 {
-            self.session
-                .send(Err(QueryErrorBuilder::new()
-                    .syntax_error(format!("unable to process table name '{}'", object.to_string()))
-                    .build()))
-                .expect("To Send Query Result to Client");
-            Err(())
-        } else {
-            let table_name = object.0.last().unwrap().value.clone();
-            let schema_name = object.0.first().unwrap().value.clone();
-            Ok(TableId(SchemaId(schema_name), table_name))
+        self.check_table_name(object)?;
+        let schema_name = self.identifier(&object.0.first().unwrap().value)?;
+        let table_name = self.identifier(&object.0.last().unwrap().value)?;
+        Ok(TableId(SchemaId(schema_name), table_name))
+    }
+
+    // table names have to be exactly `schema.table`
+    fn check_table_name(&self, object: &ObjectName) -> Result<()> {
+        let builder = match object.0.len() {
+            2 => return Ok(()),
+            1 => QueryErrorBuilder::new().syntax_error(format!(
+                "unsupported table name '{}'. All table names must be qualified",
+                object
+            )),
+            3 => QueryErrorBuilder::new().cross_database_reference(object.to_string()),
+            _ => QueryErrorBuilder::new().syntax_error(format!("unable to process table name '{}'", object)),
+        };
+        self.session
+            .send(Err(builder.build()))
+            .expect("To Send Query Result to Client");
+        Err(())
+    }
+
+    // identifiers longer than the limit are either truncated on a character
+    // boundary or rejected
+    fn identifier(&self, identifier: &str) -> Result<String> {
+        match self.identifier_length {
+            IdentifierLength::Truncate(max_length) | IdentifierLength::Reject(max_length)
+                if identifier.len() <= max_length =>
+            {
+                Ok(identifier.to_owned())
+            }
+            IdentifierLength::Truncate(max_length) => {
+                let end = (0..=max_length)
+                    .rev()
+                    .find(|index| identifier.is_char_boundary(*index))
+                    .unwrap_or(0);
+                let truncated = identifier[..end].to_owned();
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .identifier_truncated(identifier.to_owned(), truncated.clone())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Ok(truncated)
+            }
+            IdentifierLength::Reject(max_length) => {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .name_too_long(identifier.to_owned(), max_length)
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                Err(())
+            }
         }
     }
 
+    fn limit_object_name(&self, object: &mut ObjectName) -> Result<()> {
+        for part in object.0.iter_mut() {
+            part.value = self.identifier(&part.value)?;
+        }
+        Ok(())
+    }
+
+    // tables of DML statements are looked up by limited names the same way as
+    // they were created
+    fn limit_table_names(&self, mut stmt: Statement) -> Result<Statement> {
+        match &mut stmt {
+            Statement::Insert { table_name, .. }
+            | Statement::Update { table_name, .. }
+            | Statement::Delete { table_name, .. } => {
+                self.check_table_name(table_name)?;
+                self.limit_object_name(table_name)?
+            }
+            Statement::Query(query) => {
+                if let SetExpr::Select(select) = &mut query.body {
+                    for table in select.from.iter_mut() {
+                        let relations = iter::once(&mut table.relation)
+                            .chain(table.joins.iter_mut().map(|join| &mut join.relation));
+                        for relation in relations {
+                            if let TableFactor::Table { name, .. } = relation {
+                                // only system views can be selected by unqualified names
+                                match name.0.as_slice() {
+                                    [view] if view.value.eq_ignore_ascii_case("pg_stat_activity") => {}
+                                    _ => self.check_table_name(name)?,
+                                }
+                                self.limit_object_name(name)?;
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(stmt)
+    }
+
     fn sql_type_from_datatype(&self, datatype: &DataType) -> Result<SqlType> {
         match datatype {
             DataType::SmallInt => Ok(SqlType::SmallInt(i16::min_value())),
             DataType::Int => Ok(SqlType::Integer(i32::min_value())),
             DataType::BigInt => Ok(SqlType::BigInt(i64::min_value())),
             DataType::Char(len) => Ok(SqlType::Char(len.unwrap_or(255))),
-            DataType::Varchar(len) => Ok(SqlType::VarChar(len.unwrap_or(255))),
+            DataType::Varchar(Some(len)) => Ok(SqlType::VarChar(*len)),
+            DataType::Varchar(None) => Ok(SqlType::VarChar(
+                self.storage()
+                    .config()
+                    .default_varchar_length
+                    .unwrap_or_else(u64::max_value),
+            )),
             DataType::Boolean => Ok(SqlType::Bool),
+            DataType::Uuid => Ok(SqlType::Uuid),
+            DataType::Bytea => Ok(SqlType::Bytea),
+            DataType::Date => Ok(SqlType::Date),
+            DataType::Time => Ok(SqlType::Time),
+            DataType::Timestamp => Ok(SqlType::Timestamp),
+            DataType::Interval => Ok(SqlType::Interval),
+            DataType::Real => Ok(SqlType::Real),
+            DataType::Double => Ok(SqlType::DoublePrecision),
+            // PostgreSQL takes precision in binary digits
+            DataType::Float(Some(precision)) if *precision <= 24 => Ok(SqlType::Real),
+            DataType::Float(_) => Ok(SqlType::DoublePrecision),
             DataType::Custom(name) => {
                 let name = name.to_string();
                 match name.as_str() {
                     "serial" => Ok(SqlType::Integer(1)),
                     "smallserial" => Ok(SqlType::SmallInt(1)),
                     "bigserial" => Ok(SqlType::BigInt(1)),
+                    "json" => Ok(SqlType::Json),
+                    "float4" => Ok(SqlType::Real),
+                    "float8" => Ok(SqlType::DoublePrecision),
+                    "_bool" => Ok(SqlType::Array(ArrayElementType::Bool)),
+                    "_int2" => Ok(SqlType::Array(ArrayElementType::SmallInt)),
+                    "_int4" => Ok(SqlType::Array(ArrayElementType::Integer)),
+                    "_int8" => Ok(SqlType::Array(ArrayElementType::BigInt)),
                     other_type => {
                         self.session
                             .send(Err(QueryErrorBuilder::new()
@@ -110,39 +367,71 @@ impl<'qp, B: BackendStorage> QueryProcessor<B> {
         if object.0.len() != 1 {
             self.session
                 .send(Err(QueryErrorBuilder::new()
-                    .syntax_error(format!(
-                        "only unqualified schema names are supported, '{}'",
-                        object.to_string()
-                    ))
+                    .syntax_error(format!("only unqualified schema names are supported, '{}'", object))
                     .build()))
                 .expect("To Send Query Result to Client");
             Err(())
         } else {
-            let schema_name = object.to_string();
+            let schema_name = self.identifier(&object.to_string())?;
             Ok(SchemaId(schema_name))
         }
     }
 
     fn handle_statement(&mut self, stmt: &Statement) -> Result<Plan> {
         match stmt {
-            Statement::CreateTable { name, columns, .. } => self.handle_create_table(name, columns),
-            Statement::CreateSchema { schema_name, .. } => {
-                let schema_id = self.schema_from_object(schema_name)?;
-                if self.storage().schema_exists(schema_id.name()) {
-                    self.session
-                        .send(Err(QueryErrorBuilder::new()
-                            .schema_already_exists(schema_id.name().to_string())
-                            .build()))
-                        .expect("To Send Query Result to Client");
-                    Err(())
-                } else {
-                    Ok(Plan::CreateSchema(SchemaCreationInfo {
-                        schema_name: schema_id.name().to_string(),
-                    }))
-                }
+            Statement::CreateTable {
+                name,
+                columns,
+                if_not_exists,
+                ..
+            } => self.handle_create_table(name, columns, *if_not_exists),
+            Statement::CreateSchema { schema_name } => self.handle_create_schema(schema_name, false),
+            Statement::Drop {
+                object_type,
+                names,
+                if_exists,
+                ..
+            } => self.handle_drop(object_type, names, *if_exists),
+            Statement::AlterTable {
+                name,
+                operation:
+                    AlterTableOperation::RenameColumn {
+                        old_column_name,
+                        new_column_name,
+                    },
+            } => {
+                let table_id = self.table_from_object(name)?;
+                self.check_table_exists(&table_id)?;
+                Ok(Plan::RenameColumn(ColumnRenamingInfo {
+                    schema_name: table_id.schema_name().to_owned(),
+                    table_name: table_id.name().to_owned(),
+                    old_column_name: self.identifier(&old_column_name.value)?,
+                    new_column_name: self.identifier(&new_column_name.value)?,
+                }))
             }
-            Statement::Drop { object_type, names, .. } => self.handle_drop(object_type, names),
-            _ => Ok(Plan::NotProcessed(stmt.clone())),
+            _ => Ok(Plan::NotProcessed(self.limit_table_names(stmt.clone())?)),
+        }
+    }
+
+    fn check_table_exists(&self, table_id: &TableId) -> Result<()> {
+        let schema_name = table_id.schema_name();
+        let table_name = table_id.name();
+        if !self.storage().schema_exists(schema_name) {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .schema_does_not_exist(schema_name.to_string())
+                    .build()))
+                .expect("To Send Query Result to Client");
+            Err(())
+        } else if !self.storage().table_exists(schema_name, table_name) {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .table_does_not_exist(format!("{}.{}", schema_name, table_name))
+                    .build()))
+                .expect("To Send Query Result to Client");
+            Err(())
+        } else {
+            Ok(())
         }
     }
 
@@ -150,14 +439,81 @@ impl<'qp, B: BackendStorage> QueryProcessor<B> {
         let mut column_defs = Vec::new();
         for column in columns {
             let sql_type = self.sql_type_from_datatype(&column.data_type)?;
+            let collation = match &column.collation {
+                Some(collation) => self.collation(collation, &sql_type)?,
+                None => Collation::default(),
+            };
+            let column_name = self.identifier(&column.name.value)?;
             // maybe a different type should be used to represent this instead of the storage's representation.
-            let column_definition = ColumnDefinition::new(column.name.value.as_str(), sql_type);
-            column_defs.push(column_definition);
+            let mut column_definition = match &column.data_type {
+                DataType::Custom(name) if name.to_string().ends_with("serial") => {
+                    ColumnDefinition::serial(column_name.as_str(), sql_type)
+                }
+                _ => ColumnDefinition::new(column_name.as_str(), sql_type),
+            };
+            for option in column.options.iter() {
+                if let ColumnOption::Default(expr) = &option.option {
+                    column_definition = column_definition.with_default_expression(expr.to_string());
+                }
+            }
+            column_defs.push(column_definition.with_collation(collation));
         }
         Ok(column_defs)
     }
 
-    fn handle_create_table(&mut self, name: &ObjectName, columns: &[ColumnDef]) -> Result<Plan> {
+    // only character columns can be compared according to a collation
+    fn collation(&self, name: &ObjectName, sql_type: &SqlType) -> Result<Collation> {
+        if let SqlType::Char(_) | SqlType::VarChar(_) = sql_type {
+            let name = name
+                .0
+                .iter()
+                .map(|part| part.value.as_str())
+                .collect::<Vec<&str>>()
+                .join(".");
+            match Collation::from_name(&name) {
+                Some(collation) => Ok(collation),
+                None => {
+                    self.session
+                        .send(Err(QueryErrorBuilder::new().undefined_collation(name).build()))
+                        .expect("To Send Query Result to Client");
+                    Err(())
+                }
+            }
+        } else {
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .collations_not_supported(sql_type.to_pg_types())
+                    .build()))
+                .expect("To Send Query Result to Client");
+            Err(())
+        }
+    }
+
+    fn handle_create_schema(&mut self, name: &ObjectName, if_not_exists: bool) -> Result<Plan> {
+        let schema_id = self.schema_from_object(name)?;
+        if self.storage().schema_exists(schema_id.name()) {
+            if if_not_exists {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .schema_already_exists_skipping(schema_id.name().to_string())
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                return Ok(Plan::Skip(QueryEvent::SchemaCreated));
+            }
+            self.session
+                .send(Err(QueryErrorBuilder::new()
+                    .schema_already_exists(schema_id.name().to_string())
+                    .build()))
+                .expect("To Send Query Result to Client");
+            Err(())
+        } else {
+            Ok(Plan::CreateSchema(SchemaCreationInfo {
+                schema_name: schema_id.name().to_string(),
+            }))
+        }
+    }
+
+    fn handle_create_table(&mut self, name: &ObjectName, columns: &[ColumnDef], if_not_exists: bool) -> Result<Plan> {
         let table_id = self.table_from_object(name)?;
         let schema_name = table_id.schema_name();
         let table_name = table_id.name();
@@ -169,6 +525,14 @@ impl<'qp, B: BackendStorage> QueryProcessor<B> {
                 .expect("To Send Query Result to Client");
             Err(())
         } else if self.storage().table_exists(schema_name, table_name) {
+            if if_not_exists {
+                self.session
+                    .send(Err(QueryErrorBuilder::new()
+                        .table_already_exists_skipping(format!("{}.{}", schema_name, table_name))
+                        .build()))
+                    .expect("To Send Query Result to Client");
+                return Ok(Plan::Skip(QueryEvent::TableCreated));
+            }
             self.session
                 .send(Err(QueryErrorBuilder::new()
                     .table_already_exists(format!("{}.{}", schema_name, table_name))
@@ -186,7 +550,8 @@ impl<'qp, B: BackendStorage> QueryProcessor<B> {
         }
     }
 
-    fn handle_drop(&mut self, object_type: &ObjectType, names: &[ObjectName]) -> Result<Plan> {
+    // objects that don't exist are skipped with a notice under `IF EXISTS`
+    fn handle_drop(&mut self, object_type: &ObjectType, names: &[ObjectName], if_exists: bool) -> Result<Plan> {
         match object_type {
             ObjectType::Table => {
                 let mut table_names = Vec::with_capacity(names.len());
@@ -196,7 +561,19 @@ impl<'qp, B: BackendStorage> QueryProcessor<B> {
                     let table_id = self.table_from_object(name)?;
                     let schema_name = table_id.schema_name();
                     let table_name = table_id.name();
-                    if !self.storage().schema_exists(schema_name) {
+                    if if_exists && !self.storage().schema_exists(schema_name) {
+                        self.session
+                            .send(Err(QueryErrorBuilder::new()
+                                .schema_does_not_exist_skipping(schema_name.to_string())
+                                .build()))
+                            .expect("To Send Query Result to Client");
+                    } else if if_exists && !self.storage().table_exists(schema_name, table_name) {
+                        self.session
+                            .send(Err(QueryErrorBuilder::new()
+                                .table_does_not_exist_skipping(format!("{}.{}", schema_name, table_name))
+                                .build()))
+                            .expect("To Send Query Result to Client");
+                    } else if !self.storage().schema_exists(schema_name) {
                         self.session
                             .send(Err(QueryErrorBuilder::new()
                                 .schema_does_not_exist(schema_name.to_string())
@@ -214,12 +591,24 @@ impl<'qp, B: BackendStorage> QueryProcessor<B> {
                         table_names.push(table_id);
                     }
                 }
-                Ok(Plan::DropTables(table_names))
+                if table_names.is_empty() {
+                    Ok(Plan::Skip(QueryEvent::TableDropped))
+                } else {
+                    Ok(Plan::DropTables(table_names))
+                }
             }
             ObjectType::Schema => {
                 let mut schema_names = Vec::with_capacity(names.len());
                 for name in names {
                     let schema_id = self.schema_from_object(name)?;
+                    if if_exists && !self.storage().schema_exists(schema_id.name()) {
+                        self.session
+                            .send(Err(QueryErrorBuilder::new()
+                                .schema_does_not_exist_skipping(schema_id.name().to_string())
+                                .build()))
+                            .expect("To Send Query Result to Client");
+                        continue;
+                    }
                     if !self.storage().schema_exists(schema_id.name()) {
                         self.session
                             .send(Err(QueryErrorBuilder::new()
@@ -231,7 +620,11 @@ impl<'qp, B: BackendStorage> QueryProcessor<B> {
 
                     schema_names.push(schema_id);
                 }
-                Ok(Plan::DropSchemas(schema_names))
+                if schema_names.is_empty() {
+                    Ok(Plan::Skip(QueryEvent::SchemaDropped))
+                } else {
+                    Ok(Plan::DropSchemas(schema_names))
+                }
             }
             _ => unimplemented!(),
         }
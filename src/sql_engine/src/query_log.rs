@@ -0,0 +1,118 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use log::{Level, LevelFilter};
+use protocol::{
+    results::{QueryEvent, QueryResult},
+    Sender,
+};
+use std::{
+    io,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// Forwards query results to the client session and logs how each query
+/// was executed: command, number of rows, duration and error if any.
+/// Query text is logged only on `debug` level.
+pub(crate) struct QueryLogger {
+    session: Arc<dyn Sender>,
+    started: Mutex<Option<Instant>>,
+    // `log_min_messages` of the session, it can only make the log of its
+    // queries less verbose than the level of the server
+    level: Mutex<LevelFilter>,
+}
+
+impl QueryLogger {
+    pub(crate) fn new(session: Arc<dyn Sender>) -> QueryLogger {
+        QueryLogger {
+            session,
+            started: Mutex::new(None),
+            level: Mutex::new(LevelFilter::max()),
+        }
+    }
+
+    pub(crate) fn start(&self, raw_sql_query: &str) {
+        if self.enabled(Level::Debug) {
+            log::debug!(target: "query", "sql={:?}", raw_sql_query);
+        }
+        *self.started.lock().unwrap() = Some(Instant::now());
+    }
+
+    pub(crate) fn set_level(&self, level: LevelFilter) {
+        *self.level.lock().unwrap() = level;
+    }
+
+    fn enabled(&self, level: Level) -> bool {
+        level <= *self.level.lock().unwrap()
+    }
+}
+
+impl Sender for QueryLogger {
+    fn send(&self, query_result: QueryResult) -> io::Result<()> {
+        let duration = self.started.lock().unwrap().map(|started| started.elapsed());
+        match &query_result {
+            Ok(_) if !self.enabled(Level::Info) => {}
+            Err(_) if !self.enabled(Level::Warn) => {}
+            Ok(event) => {
+                let (command, rows) = describe(event);
+                match rows {
+                    Some(rows) => log::info!(
+                        target: "query",
+                        "command={} rows={} duration={:?}",
+                        command,
+                        rows,
+                        duration
+                    ),
+                    None => log::info!(target: "query", "command={} duration={:?}", command, duration),
+                }
+            }
+            Err(error) => log::warn!(target: "query", "error={:?} duration={:?}", error, duration),
+        }
+        self.session.send(query_result)
+    }
+}
+
+fn describe(event: &QueryEvent) -> (&'static str, Option<usize>) {
+    match event {
+        QueryEvent::DatabaseCreated => ("CREATE DATABASE", None),
+        QueryEvent::DatabaseDropped => ("DROP DATABASE", None),
+        QueryEvent::SchemaCreated => ("CREATE SCHEMA", None),
+        QueryEvent::SchemaDropped => ("DROP SCHEMA", None),
+        QueryEvent::TableCreated => ("CREATE TABLE", None),
+        QueryEvent::TableDropped => ("DROP TABLE", None),
+        QueryEvent::TableAltered => ("ALTER TABLE", None),
+        QueryEvent::SequenceCreated => ("CREATE SEQUENCE", None),
+        QueryEvent::TableVacuumed => ("VACUUM", None),
+        QueryEvent::PrivilegesGranted => ("GRANT", None),
+        QueryEvent::PrivilegesRevoked => ("REVOKE", None),
+        QueryEvent::VariableSet => ("SET", None),
+        QueryEvent::VariableReset => ("RESET", None),
+        QueryEvent::TransactionStarted => ("BEGIN", None),
+        QueryEvent::TransactionCommitted => ("COMMIT", None),
+        QueryEvent::TransactionRolledBack => ("ROLLBACK", None),
+        QueryEvent::QueryIsEmpty => ("EMPTY", None),
+        QueryEvent::RecordsInserted(records) => ("INSERT", Some(*records)),
+        QueryEvent::RecordsSelected((_, records)) => ("SELECT", Some(records.len())),
+        QueryEvent::RecordsInsertedReturning((_, records)) => ("INSERT", Some(records.len())),
+        QueryEvent::RecordsUpdated(records) => ("UPDATE", Some(*records)),
+        QueryEvent::RecordsDeleted(records) => ("DELETE", Some(*records)),
+        QueryEvent::RecordsCopiedOut(_, lines) => ("COPY", Some(lines.len())),
+        QueryEvent::CopyInStarted(_) => ("COPY IN", None),
+        QueryEvent::RecordsCopiedIn(records) => ("COPY", Some(*records)),
+        QueryEvent::ParseComplete => ("PARSE", None),
+        QueryEvent::BindComplete => ("BIND", None),
+        QueryEvent::ParameterStatus(..) => ("PARAMETER STATUS", None),
+    }
+}
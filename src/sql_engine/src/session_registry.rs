@@ -0,0 +1,95 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+/// Sessions of all clients connected to a node, every query executor
+/// registered in it reports the query it runs, so that sessions can be
+/// listed by `pg_stat_activity`
+#[derive(Clone, Default)]
+pub struct SessionRegistry {
+    inner: Arc<Mutex<Registry>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    last_pid: i32,
+    sessions: BTreeMap<i32, Activity>,
+}
+
+struct Activity {
+    application_name: String,
+    query: String,
+}
+
+impl SessionRegistry {
+    pub fn new() -> SessionRegistry {
+        SessionRegistry::default()
+    }
+
+    pub(crate) fn register(&self, application_name: &str) -> Session {
+        let mut registry = self.inner.lock().unwrap();
+        registry.last_pid += 1;
+        let pid = registry.last_pid;
+        registry.sessions.insert(
+            pid,
+            Activity {
+                application_name: application_name.to_owned(),
+                query: String::new(),
+            },
+        );
+        Session {
+            pid,
+            registry: self.clone(),
+        }
+    }
+
+    /// pid, application name and the last query of every session ordered by pid
+    pub(crate) fn activity(&self) -> Vec<(i32, String, String)> {
+        self.inner
+            .lock()
+            .unwrap()
+            .sessions
+            .iter()
+            .map(|(pid, activity)| (*pid, activity.application_name.clone(), activity.query.clone()))
+            .collect()
+    }
+}
+
+/// registered session, it is removed from the registry when dropped
+pub(crate) struct Session {
+    pid: i32,
+    registry: SessionRegistry,
+}
+
+impl Session {
+    pub(crate) fn registry(&self) -> &SessionRegistry {
+        &self.registry
+    }
+
+    pub(crate) fn set_query(&self, query: &str) {
+        if let Some(activity) = self.registry.inner.lock().unwrap().sessions.get_mut(&self.pid) {
+            activity.query = query.to_owned();
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.registry.inner.lock().unwrap().sessions.remove(&self.pid);
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// `options` startup parameter holds command-line style settings separated by
+// spaces, a backslash escapes the next character, e.g.
+// `-c statement_timeout=1000 --search_path=schema_name`
+
+/// settings as `(name, value)` pairs or an option that can't be understood
+pub(crate) fn parse(options: &str) -> Vec<Result<(String, String), String>> {
+    let mut parsed = vec![];
+    let mut words = split(options).into_iter();
+    while let Some(word) = words.next() {
+        let setting = if word == "-c" {
+            match words.next() {
+                Some(setting) => setting,
+                None => {
+                    parsed.push(Err(word));
+                    continue;
+                }
+            }
+        } else if let Some(setting) = word.strip_prefix("--").or_else(|| word.strip_prefix("-c")) {
+            setting.to_owned()
+        } else {
+            parsed.push(Err(word));
+            continue;
+        };
+        parsed.push(match setting.find('=') {
+            Some(eq) if eq > 0 => Ok((setting[..eq].replace('-', "_"), setting[eq + 1..].to_owned())),
+            _ => Err(setting),
+        });
+    }
+    parsed
+}
+
+fn split(options: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut chars = options.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => word.extend(chars.next()),
+            c if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
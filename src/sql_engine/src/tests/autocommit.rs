@@ -0,0 +1,240 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use protocol::sql_types::PostgreSqlType;
+
+type Engine = (QueryExecutor<InMemoryStorage>, Arc<Collector>);
+
+fn engines_sharing_storage() -> (Engine, Engine) {
+    let storage = in_memory_storage();
+    let writer_collector = Arc::new(Collector(Mutex::new(vec![])));
+    let reader_collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut writer = QueryExecutor::new(storage.clone(), writer_collector.clone());
+    writer.execute("create schema schema_name;").expect("no system errors");
+    writer
+        .execute("create table schema_name.table_name (column_test smallint);")
+        .expect("no system errors");
+    writer.execute("set autocommit = off;").expect("no system errors");
+    writer_collector.0.lock().expect("locked").clear();
+    (
+        (writer, writer_collector),
+        (QueryExecutor::new(storage, reader_collector.clone()), reader_collector),
+    )
+}
+
+#[rstest::rstest]
+fn autocommit_is_on_by_default(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+
+    engine.execute("show autocommit;").expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("autocommit".to_owned(), PostgreSqlType::VarChar)],
+        vec![vec![Some("on".to_owned())]],
+    )))]);
+}
+
+#[rstest::rstest]
+fn set_invalid_autocommit_value(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+
+    engine.execute("set autocommit = maybe;").expect("no system errors");
+
+    assert_eq!(engine.setting("autocommit"), None);
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .invalid_parameter_value("autocommit".to_owned(), "maybe".to_owned())
+        .build())]);
+}
+
+#[test]
+fn uncommitted_insert_is_not_visible() {
+    let ((mut writer, writer_collector), (mut reader, reader_collector)) = engines_sharing_storage();
+
+    writer
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+    reader
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    writer_collector.assert_content(vec![Ok(QueryEvent::RecordsInserted(1))]);
+    reader_collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
+        vec![],
+    )))]);
+}
+
+#[test]
+fn committed_insert_is_visible() {
+    let ((mut writer, writer_collector), (mut reader, reader_collector)) = engines_sharing_storage();
+
+    writer
+        .execute("insert into schema_name.table_name values (123), (456);")
+        .expect("no system errors");
+    writer.execute("commit;").expect("no system errors");
+    reader
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    writer_collector.assert_content(vec![
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::TransactionCommitted),
+    ]);
+    reader_collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
+        vec![vec![Some("123".to_owned())], vec![Some("456".to_owned())]],
+    )))]);
+}
+
+#[test]
+fn rolled_back_insert_is_discarded() {
+    let ((mut writer, writer_collector), (mut reader, reader_collector)) = engines_sharing_storage();
+
+    writer
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+    writer.execute("rollback;").expect("no system errors");
+    writer.execute("commit;").expect("no system errors");
+    reader
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    writer_collector.assert_content(vec![
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::TransactionRolledBack),
+        Ok(QueryEvent::TransactionCommitted),
+    ]);
+    reader_collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
+        vec![],
+    )))]);
+}
+
+#[test]
+fn invalid_insert_is_reported_before_commit() {
+    let ((mut writer, writer_collector), (mut reader, reader_collector)) = engines_sharing_storage();
+    let mut builder = QueryErrorBuilder::new();
+    builder.out_of_range(PostgreSqlType::SmallInt, "column_test".to_string(), 1);
+
+    writer
+        .execute("insert into schema_name.non_existent values (123);")
+        .expect("no system errors");
+    writer
+        .execute("insert into schema_name.table_name values (32768);")
+        .expect("no system errors");
+    writer.execute("commit;").expect("no system errors");
+    reader
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    writer_collector.assert_content(vec![
+        Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.non_existent".to_owned())
+            .build()),
+        Err(builder.build()),
+        Ok(QueryEvent::TransactionCommitted),
+    ]);
+    reader_collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
+        vec![],
+    )))]);
+}
+
+#[test]
+fn failed_commit_writes_nothing() {
+    let ((mut writer, writer_collector), (mut reader, reader_collector)) = engines_sharing_storage();
+    reader
+        .execute("create table schema_name.other_table (column_test smallint);")
+        .expect("no system errors");
+
+    writer
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+    writer
+        .execute("insert into schema_name.other_table values (456);")
+        .expect("no system errors");
+    reader
+        .execute("drop table schema_name.other_table;")
+        .expect("no system errors");
+    writer.execute("commit;").expect("no system errors");
+    reader
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    writer_collector.assert_content(vec![
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.other_table".to_owned())
+            .build()),
+    ]);
+    reader_collector.assert_content(vec![
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::TableDropped),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
+            vec![],
+        ))),
+    ]);
+}
+
+#[test]
+fn uncommitted_insert_is_visible_to_session() {
+    let ((mut writer, writer_collector), _) = engines_sharing_storage();
+
+    writer
+        .execute("insert into schema_name.table_name values (123), (456);")
+        .expect("no system errors");
+    writer
+        .execute("select * from schema_name.table_name where column_test > 200;")
+        .expect("no system errors");
+    writer
+        .execute("select count(*) from schema_name.table_name;")
+        .expect("no system errors");
+    writer.execute("rollback;").expect("no system errors");
+    writer
+        .execute("select column_test from schema_name.table_name;")
+        .expect("no system errors");
+
+    writer_collector.assert_content(vec![
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("456".to_owned())]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("count".to_owned(), PostgreSqlType::BigInt)],
+            vec![vec![Some("2".to_owned())]],
+        ))),
+        Ok(QueryEvent::TransactionRolledBack),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
+            vec![],
+        ))),
+    ]);
+}
+
+#[test]
+fn update_is_not_supported_with_autocommit_off() {
+    let ((mut writer, writer_collector), _) = engines_sharing_storage();
+
+    writer
+        .execute("update schema_name.table_name set column_test = 1;")
+        .expect("no system errors");
+
+    writer_collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .feature_not_supported("UPDATE with autocommit off".to_owned())
+        .build())]);
+}
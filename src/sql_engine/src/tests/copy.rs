@@ -0,0 +1,382 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    tests::{in_memory_backend_storage::InMemoryStorage, Collector},
+    QueryExecutor,
+};
+use protocol::results::{QueryErrorBuilder, QueryEvent};
+use std::sync::Arc;
+
+#[rstest::fixture]
+fn engine_with_table(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 varchar(10));")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1, 'a'), (2, 'b');")
+        .expect("no system errors");
+    collector.0.lock().expect("locked").clear();
+    (engine, collector)
+}
+
+#[rstest::rstest]
+fn copy_table_to_stdout(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("copy schema_name.table_name to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsCopiedOut(
+        2,
+        vec!["1\ta\n".to_owned(), "2\tb\n".to_owned()],
+    ))]);
+}
+
+#[rstest::rstest]
+fn copy_columns_to_stdout(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("copy schema_name.table_name (column_2) to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsCopiedOut(
+        1,
+        vec!["a\n".to_owned(), "b\n".to_owned()],
+    ))]);
+}
+
+#[rstest::rstest]
+fn copy_non_existent_column(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("copy schema_name.table_name (column_3) to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .column_does_not_exist(vec!["column_3".to_owned()])
+        .build())]);
+}
+
+#[rstest::rstest]
+fn copy_non_existent_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("copy schema_name.non_existent to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.non_existent".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_to_stdout_with_null_option(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("copy schema_name.table_name to stdout with (null 'none');")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsCopiedOut(
+        2,
+        vec!["1\ta\n".to_owned(), "2\tb\n".to_owned()],
+    ))]);
+}
+
+// runs `COPY ... FROM STDIN` and sends its data as a single chunk
+fn copy_from(engine: &mut QueryExecutor<InMemoryStorage>, sql: &str, data: &[u8]) {
+    engine.execute(sql).expect("no system errors");
+    engine.copy_data(data).expect("no system errors");
+    engine.copy_done().expect("no system errors");
+}
+
+#[rstest::rstest]
+fn copy_from_stdin(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name from stdin;",
+        b"3\tc\n4\td\n\\.\n",
+    );
+    engine
+        .execute("copy schema_name.table_name to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(2)),
+        Ok(QueryEvent::RecordsCopiedIn(2)),
+        Ok(QueryEvent::RecordsCopiedOut(
+            2,
+            vec![
+                "1\ta\n".to_owned(),
+                "2\tb\n".to_owned(),
+                "3\tc\n".to_owned(),
+                "4\td\n".to_owned(),
+            ],
+        )),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_from_stdin_in_several_chunks(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("copy schema_name.table_name (column_2) from stdin;")
+        .expect("no system errors");
+    engine.copy_data(b"c\nd").expect("no system errors");
+    engine.copy_data(b"e\n").expect("no system errors");
+    engine.copy_done().expect("no system errors");
+    engine
+        .execute("copy schema_name.table_name (column_2) to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(1)),
+        Ok(QueryEvent::RecordsCopiedIn(2)),
+        Ok(QueryEvent::RecordsCopiedOut(
+            1,
+            vec!["a\n".to_owned(), "b\n".to_owned(), "c\n".to_owned(), "de\n".to_owned()],
+        )),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_from_stdin_failed_by_client(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("copy schema_name.table_name from stdin;")
+        .expect("no system errors");
+    engine.copy_data(b"3\tc\n").expect("no system errors");
+    engine.copy_fail("canceled by user").expect("no system errors");
+    engine.copy_done().expect("no system errors");
+    engine
+        .execute("copy schema_name.table_name to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(2)),
+        Err(QueryErrorBuilder::new()
+            .copy_from_stdin_failed("canceled by user".to_owned())
+            .build()),
+        Ok(QueryEvent::RecordsCopiedOut(
+            2,
+            vec!["1\ta\n".to_owned(), "2\tb\n".to_owned()],
+        )),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_from_stdin_invalid_utf8(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name from stdin;",
+        &[b'3', b'\t', 0xff, b'\n'],
+    );
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(2)),
+        Err(QueryErrorBuilder::new()
+            .protocol_violation("invalid byte sequence for encoding \"UTF8\"".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_from_stdin_null_marker(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    copy_from(&mut engine, "copy schema_name.table_name from stdin;", b"3\t\\N\n\\.\n");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(2)),
+        Err(QueryErrorBuilder::new()
+            .not_null_violation("column_2".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_from_stdin_custom_null_marker(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name from stdin with (null 'none');",
+        b"3\tnone\n\\.\n",
+    );
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name (column_2) from stdin with null as 'none';",
+        b"\\N\n\\.\n",
+    );
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(2)),
+        Err(QueryErrorBuilder::new()
+            .not_null_violation("column_2".to_owned())
+            .build()),
+        Ok(QueryEvent::CopyInStarted(1)),
+        Ok(QueryEvent::RecordsCopiedIn(1)),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_with_escape_string_null_marker(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name (column_2) from stdin with (null E'it\\'s');",
+        b"it's\n\\.\n",
+    );
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name (column_2) from stdin with null as E'\\x4e';",
+        b"\\N\n\\.\n",
+    );
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(1)),
+        Err(QueryErrorBuilder::new()
+            .not_null_violation("column_2".to_owned())
+            .build()),
+        Ok(QueryEvent::CopyInStarted(1)),
+        Ok(QueryEvent::RecordsCopiedIn(1)),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_from_stdin_missing_and_extra_data(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    copy_from(&mut engine, "copy schema_name.table_name from stdin;", b"3\n\\.\n");
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name from stdin;",
+        b"3\tc\td\n\\.\n",
+    );
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::CopyInStarted(2)),
+        Err(QueryErrorBuilder::new()
+            .bad_copy_file_format("missing data for column \"column_2\"".to_owned())
+            .build()),
+        Ok(QueryEvent::CopyInStarted(2)),
+        Err(QueryErrorBuilder::new()
+            .bad_copy_file_format("extra data after last expected column".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_special_characters_round_trip(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("create table schema_name.copied (column_1 smallint, column_2 varchar(10));")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.copied values (3, E'x\\ty\\nz');")
+        .expect("no system errors");
+    engine
+        .execute("copy schema_name.copied to stdout;")
+        .expect("no system errors");
+    copy_from(
+        &mut engine,
+        "copy schema_name.table_name from stdin;",
+        b"3\tx\\ty\\nz\n\\.\n",
+    );
+    engine
+        .execute("copy schema_name.table_name (column_2) to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsCopiedOut(2, vec!["3\tx\\ty\\nz\n".to_owned()])),
+        Ok(QueryEvent::CopyInStarted(2)),
+        Ok(QueryEvent::RecordsCopiedIn(1)),
+        Ok(QueryEvent::RecordsCopiedOut(
+            1,
+            vec!["a\n".to_owned(), "b\n".to_owned(), "x\\ty\\nz\n".to_owned()],
+        )),
+    ]);
+}
+
+#[cfg(test)]
+mod text_format {
+    use crate::{
+        dml::copy::{decode_row, encode_row},
+        extension::CopyOptions,
+    };
+
+    #[test]
+    fn null_is_encoded_as_null_string() {
+        assert_eq!(
+            encode_row(&[Some("1"), None], &CopyOptions::default()),
+            "1\t\\N\n".to_owned()
+        );
+    }
+
+    #[test]
+    fn null_string_is_decoded_as_null() {
+        assert_eq!(
+            decode_row("1\t\\N", &CopyOptions::default()),
+            vec![Some("1".to_owned()), None]
+        );
+    }
+
+    #[test]
+    fn custom_null_string() {
+        let options = CopyOptions {
+            null: "none".to_owned(),
+        };
+        assert_eq!(encode_row(&[None, Some("N")], &options), "none\tN\n".to_owned());
+        assert_eq!(decode_row("none\t\\N", &options), vec![None, Some("N".to_owned())]);
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            encode_row(&[Some("a\tb"), Some("c\nd\re"), Some("f\\g")], &CopyOptions::default()),
+            "a\\tb\tc\\nd\\re\tf\\\\g\n".to_owned()
+        );
+    }
+
+    #[test]
+    fn escape_sequences_are_unescaped() {
+        assert_eq!(
+            decode_row("a\\tb\tc\\nd\\re\tf\\\\g\t\\x", &CopyOptions::default()),
+            vec![
+                Some("a\tb".to_owned()),
+                Some("c\nd\re".to_owned()),
+                Some("f\\g".to_owned()),
+                Some("x".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_null_string_is_a_value() {
+        let options = CopyOptions::default();
+        let line = encode_row(&[Some("\\N"), None], &options);
+        assert_eq!(
+            decode_row(line.trim_end_matches('\n'), &options),
+            vec![Some("\\N".to_owned()), None]
+        );
+    }
+}
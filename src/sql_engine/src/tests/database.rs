@@ -0,0 +1,199 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::rstest]
+fn session_is_connected_to_default_database(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (engine, collector) = sql_engine;
+
+    assert_eq!(engine.current_database(), DEFAULT_DATABASE);
+    collector.assert_content(vec![]);
+}
+
+#[rstest::rstest]
+fn connect_to_existing_database(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+
+    assert!(engine.connect_to_database("postgres"));
+    assert_eq!(engine.current_database(), "postgres");
+    collector.assert_content(vec![]);
+}
+
+#[rstest::rstest]
+fn connect_to_non_existent_database(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+
+    assert!(!engine.connect_to_database("non_existent"));
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .database_does_not_exist("non_existent".to_owned())
+        .build())]);
+}
+
+fn database_registry() -> DatabaseRegistry<InMemoryStorage> {
+    DatabaseRegistry::new(in_memory_storage(), || FrontendStorage::new(InMemoryStorage::default()))
+}
+
+fn connected_engine(
+    databases: &DatabaseRegistry<InMemoryStorage>,
+    database: &str,
+) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut engine = QueryExecutor::new(in_memory_storage(), collector.clone()).with_database_registry(databases);
+    assert!(engine.connect_to_database(database));
+    (engine, collector)
+}
+
+#[rstest::rstest]
+fn create_database_without_registry(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+
+    engine
+        .execute("create database new_database")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .feature_not_supported("CREATE DATABASE".to_owned())
+        .build())]);
+}
+
+#[test]
+fn create_and_drop_database() {
+    let databases = database_registry();
+    let (mut engine, collector) = connected_engine(&databases, DEFAULT_DATABASE);
+
+    engine
+        .execute("create database new_database")
+        .expect("no system errors");
+    engine.execute("drop database new_database").expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::DatabaseCreated), Ok(QueryEvent::DatabaseDropped)]);
+}
+
+#[test]
+fn create_existing_database() {
+    let databases = database_registry();
+    let (mut engine, collector) = connected_engine(&databases, DEFAULT_DATABASE);
+
+    engine
+        .execute("create database new_database")
+        .expect("no system errors");
+    engine
+        .execute("create database new_database")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::DatabaseCreated),
+        Err(QueryErrorBuilder::new()
+            .database_already_exists("new_database".to_owned())
+            .build()),
+    ]);
+}
+
+#[test]
+fn drop_non_existent_database() {
+    let databases = database_registry();
+    let (mut engine, collector) = connected_engine(&databases, DEFAULT_DATABASE);
+
+    engine.execute("drop database non_existent").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .dropped_database_does_not_exist("non_existent".to_owned())
+        .build())]);
+}
+
+#[test]
+fn drop_current_database() {
+    let databases = database_registry();
+    let (mut engine, collector) = connected_engine(&databases, DEFAULT_DATABASE);
+
+    engine.execute("drop database postgres").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new().current_database_dropped().build())]);
+}
+
+#[test]
+fn dropped_database_can_not_be_connected_to() {
+    let databases = database_registry();
+    let (mut engine, _collector) = connected_engine(&databases, DEFAULT_DATABASE);
+    engine
+        .execute("create database new_database")
+        .expect("no system errors");
+    engine.execute("drop database new_database").expect("no system errors");
+
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut engine = QueryExecutor::new(in_memory_storage(), collector.clone()).with_database_registry(&databases);
+
+    assert!(!engine.connect_to_database("new_database"));
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .database_does_not_exist("new_database".to_owned())
+        .build())]);
+}
+
+#[test]
+fn same_named_schemas_of_different_databases_are_isolated() {
+    let databases = database_registry();
+    let (mut engine, _collector) = connected_engine(&databases, DEFAULT_DATABASE);
+    engine.execute("create database first").expect("no system errors");
+    engine.execute("create database second").expect("no system errors");
+
+    let (mut first, first_collector) = connected_engine(&databases, "first");
+    let (mut second, second_collector) = connected_engine(&databases, "second");
+    for engine in [&mut first, &mut second].iter_mut() {
+        engine.execute("create schema schema_name").expect("no system errors");
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint)")
+            .expect("no system errors");
+    }
+    first
+        .execute("insert into schema_name.table_name values (1)")
+        .expect("no system errors");
+    second
+        .execute("insert into schema_name.table_name values (2)")
+        .expect("no system errors");
+    first_collector.0.lock().expect("locked").clear();
+    second_collector.0.lock().expect("locked").clear();
+
+    first
+        .execute("select * from schema_name.table_name")
+        .expect("no system errors");
+    second
+        .execute("select * from schema_name.table_name")
+        .expect("no system errors");
+
+    first_collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+        vec![vec![Some("1".to_owned())]],
+    )))]);
+    second_collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+        vec![vec![Some("2".to_owned())]],
+    )))]);
+}
+
+#[test]
+fn schemas_of_a_database_are_not_seen_in_default_one() {
+    let databases = database_registry();
+    let (mut engine, collector) = connected_engine(&databases, DEFAULT_DATABASE);
+    engine
+        .execute("create database new_database")
+        .expect("no system errors");
+    let (mut other, _other_collector) = connected_engine(&databases, "new_database");
+    other.execute("create schema schema_name").expect("no system errors");
+    collector.0.lock().expect("locked").clear();
+
+    engine.execute("create schema schema_name").expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::SchemaCreated)]);
+}
@@ -67,7 +67,7 @@ fn delete_all_records(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, A
         Ok(QueryEvent::RecordsInserted(1)),
         Ok(QueryEvent::RecordsSelected((
             vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
-            vec![vec!["123".to_owned()], vec!["456".to_owned()]],
+            vec![vec![Some("123".to_owned())], vec![Some("456".to_owned())]],
         ))),
         Ok(QueryEvent::RecordsDeleted(2)),
         Ok(QueryEvent::RecordsSelected((
@@ -0,0 +1,40 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::rstest]
+fn postgresql_dialect_is_used_by_default(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("create schema #schema_name;").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .syntax_error(format!(
+            "{:?}: Expected identifier, found: #",
+            "create schema #schema_name;"
+        ))
+        .build())]);
+}
+
+#[rstest::rstest]
+fn generic_dialect_accepts_names_postgresql_rejects() {
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut engine = QueryExecutor::new(in_memory_storage(), collector.clone()).with_dialect(SqlDialect::Generic);
+    engine.execute("create schema #schema_name;").expect("no system errors");
+    engine
+        .execute("create table #schema_name.table@name (column#1 smallint);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::SchemaCreated), Ok(QueryEvent::TableCreated)]);
+}
@@ -0,0 +1,139 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::dml::{ExprResult, ExpressionEvaluation};
+use bigdecimal::BigDecimal;
+use sqlparser::{dialect::PostgreSqlDialect, parser::Parser, tokenizer::Tokenizer};
+
+fn eval(expression: &str) -> (Result<ExprResult, ()>, Arc<Collector>) {
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, expression)
+        .tokenize()
+        .expect("tokenized");
+    let expr = Parser::new(tokens).parse_expr().expect("parsed");
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    (ExpressionEvaluation::new(collector.clone()).eval(&expr), collector)
+}
+
+#[test]
+fn null_plus_number() {
+    let (result, collector) = eval("NULL + 5");
+
+    assert_eq!(result, Ok(ExprResult::Null));
+    collector.assert_content(vec![]);
+}
+
+#[test]
+fn number_multiplied_by_null() {
+    let (result, collector) = eval("2 * NULL");
+
+    assert_eq!(result, Ok(ExprResult::Null));
+    collector.assert_content(vec![]);
+}
+
+#[test]
+fn null_divided_by_zero() {
+    let (result, collector) = eval("NULL / 0");
+
+    assert_eq!(result, Ok(ExprResult::Null));
+    collector.assert_content(vec![]);
+}
+
+#[test]
+fn string_concatenated_with_null() {
+    let (result, collector) = eval("'abc' || NULL");
+
+    assert_eq!(result, Ok(ExprResult::Null));
+    collector.assert_content(vec![]);
+}
+
+#[test]
+fn null_in_nested_expression() {
+    let (result, collector) = eval("-(NULL * 3) + 1");
+
+    assert_eq!(result, Ok(ExprResult::Null));
+    collector.assert_content(vec![]);
+}
+
+#[test]
+fn expression_without_null() {
+    let (result, collector) = eval("2 * 3 + 1");
+
+    assert_eq!(result, Ok(ExprResult::Number(BigDecimal::from(7))));
+    collector.assert_content(vec![]);
+}
+
+#[rstest::rstest]
+fn null_propagated_through_selected_expressions(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("select null + 5, 2 * null, 'a' || null, null;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![
+            ("?column?".to_owned(), PostgreSqlType::VarChar),
+            ("?column?".to_owned(), PostgreSqlType::VarChar),
+            ("?column?".to_owned(), PostgreSqlType::VarChar),
+            ("?column?".to_owned(), PostgreSqlType::VarChar),
+        ],
+        vec![vec![None, None, None, None]],
+    )))]);
+}
+
+#[rstest::rstest]
+fn null_propagated_through_computed_columns(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (col smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1), (2);")
+        .expect("no system errors");
+    engine
+        .execute("select col + null, col * 2 from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("?column?".to_owned(), PostgreSqlType::VarChar),
+                ("?column?".to_owned(), PostgreSqlType::Integer),
+            ],
+            vec![vec![None, Some("2".to_owned())], vec![None, Some("4".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn null_can_not_be_inserted(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (col smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (null + 5);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new()
+            .feature_not_supported("NULL values".to_owned())
+            .build()),
+    ]);
+}
@@ -0,0 +1,362 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use protocol::sql_types::PostgreSqlType;
+
+#[rstest::fixture]
+fn engine_with_table(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 integer, column_2 varchar(10));")
+        .expect("no system errors");
+    collector.0.lock().expect("locked").clear();
+    (engine, collector)
+}
+
+#[rstest::rstest]
+fn bind_integer_parameter_in_binary_format(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse("", "insert into schema_name.table_name values ($1, 'a');", &[23])
+        .expect("no system errors");
+    engine
+        .bind("", "", &[1], &[Some(vec![0, 0, 0, 42])])
+        .expect("no system errors");
+    engine.execute_portal("", 0).expect("no system errors");
+    engine
+        .execute("select column_1 from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::Integer)],
+            vec![vec![Some("42".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn bind_binary_parameter_of_unspecified_type(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse(
+            "",
+            "insert into schema_name.table_name (column_2, column_1) values ($2, $1);",
+            &[],
+        )
+        .expect("no system errors");
+    engine
+        .bind("", "", &[1, 0], &[Some(vec![0, 0, 1, 0]), Some(b"a".to_vec())])
+        .expect("no system errors");
+    engine.execute_portal("", 0).expect("no system errors");
+    engine
+        .execute("select column_1 from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::Integer)],
+            vec![vec![Some("256".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn bind_parameters_in_text_format(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse(
+            "stmt",
+            "insert into schema_name.table_name values ($2, '$1'), (-$1, $1);",
+            &[],
+        )
+        .expect("no system errors");
+    engine
+        .bind("", "stmt", &[], &[Some(b"-7".to_vec()), Some(b"8".to_vec())])
+        .expect("no system errors");
+    engine.execute_portal("", 0).expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::Integer),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("8".to_owned()), Some("$1".to_owned())],
+                vec![Some("7".to_owned()), Some("-7".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn bind_parameter_used_twice(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse("", "insert into schema_name.table_name values ($1, $1);", &[])
+        .expect("no system errors");
+    engine
+        .bind("", "", &[], &[Some(b"5".to_vec())])
+        .expect("no system errors");
+    engine.execute_portal("", 0).expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::Integer),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![vec![Some("5".to_owned()), Some("5".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn bind_more_values_than_reused_parameters(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse("", "insert into schema_name.table_name values ($1, $1);", &[])
+        .expect("no system errors");
+    engine
+        .bind("", "", &[], &[Some(b"5".to_vec()), Some(b"6".to_vec())])
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Err(QueryErrorBuilder::new()
+            .protocol_violation("bind message supplies 2 parameters, but prepared statement \"\" requires 1".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn bind_to_not_prepared_statement(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .bind("", "stmt", &[], &[Some(b"1".to_vec())])
+        .expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .prepared_statement_does_not_exist("stmt".to_owned())
+        .build())]);
+}
+
+#[rstest::rstest]
+fn bind_wrong_number_of_parameters(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse("", "insert into schema_name.table_name values ($1, $2);", &[])
+        .expect("no system errors");
+    engine
+        .bind("", "", &[], &[Some(b"1".to_vec())])
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Err(QueryErrorBuilder::new()
+            .protocol_violation("bind message supplies 1 parameters, but prepared statement \"\" requires 2".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn bind_malformed_binary_parameter(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse("", "insert into schema_name.table_name values ($1, 'a');", &[23])
+        .expect("no system errors");
+    engine
+        .bind("", "", &[1], &[Some(vec![0, 42])])
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Err(QueryErrorBuilder::new().invalid_binary_format(1).build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn execute_not_bound_portal(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine.execute_portal("portal", 0).expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .portal_does_not_exist("portal".to_owned())
+        .build())]);
+}
+
+#[rstest::rstest]
+fn bind_non_finite_floats_in_binary_format(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("create table schema_name.floats (column_1 real, column_2 double precision);")
+        .expect("no system errors");
+    engine
+        .parse("", "insert into schema_name.floats values ($1, $2);", &[700, 701])
+        .expect("no system errors");
+    engine
+        .bind(
+            "",
+            "",
+            &[1],
+            &[
+                Some(f32::NAN.to_be_bytes().to_vec()),
+                Some(f64::NEG_INFINITY.to_be_bytes().to_vec()),
+            ],
+        )
+        .expect("no system errors");
+    engine.execute_portal("", 0).expect("no system errors");
+    engine
+        .bind(
+            "",
+            "",
+            &[1],
+            &[
+                Some(f32::INFINITY.to_be_bytes().to_vec()),
+                Some(1.5f64.to_be_bytes().to_vec()),
+            ],
+        )
+        .expect("no system errors");
+    engine.execute_portal("", 0).expect("no system errors");
+    engine
+        .execute("select * from schema_name.floats;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::ParseComplete),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::Real),
+                ("column_2".to_owned(), PostgreSqlType::DoublePrecision),
+            ],
+            vec![
+                vec![Some("NaN".to_owned()), Some("-Infinity".to_owned())],
+                vec![Some("Infinity".to_owned()), Some("1.5".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn bind_string_with_quotes(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse("", "insert into schema_name.table_name values (1, $1);", &[])
+        .expect("no system errors");
+    engine
+        .bind("", "", &[], &[Some(b"it's".to_vec())])
+        .expect("no system errors");
+    engine.execute_portal("", 0).expect("no system errors");
+    engine
+        .execute("select column_2 from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::ParseComplete),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_2".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("it's".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn bound_portals_do_not_go_through_parse_cache(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .execute("insert into schema_name.table_name values (1, 'a'), (2, 'b');")
+        .expect("no system errors");
+    let (hits, misses) = (engine.parse_cache.hits(), engine.parse_cache.misses());
+    engine
+        .parse(
+            "stmt",
+            "select column_2 from schema_name.table_name where column_1 = $1;",
+            &[23],
+        )
+        .expect("no system errors");
+    for value in 1..=2 {
+        engine
+            .bind("", "stmt", &[1], &[Some(i32::to_be_bytes(value).to_vec())])
+            .expect("no system errors");
+        engine.execute_portal("", 0).expect("no system errors");
+    }
+    assert_eq!((engine.parse_cache.hits(), engine.parse_cache.misses()), (hits, misses));
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::ParseComplete),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_2".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("a".to_owned())]],
+        ))),
+        Ok(QueryEvent::BindComplete),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_2".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("b".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn parse_invalid_statement(engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = engine_with_table;
+    engine
+        .parse("", "insert into schema_name.table_name valus ($1);", &[])
+        .expect("no system errors");
+    engine
+        .bind("", "", &[], &[Some(b"1".to_vec())])
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Err(QueryErrorBuilder::new()
+            .syntax_error(
+                "\"insert into schema_name.table_name valus ($1);\": Expected SELECT, VALUES, or a subquery in the query body, found: valus"
+                    .to_owned(),
+            )
+            .build()),
+        Err(QueryErrorBuilder::new()
+            .prepared_statement_does_not_exist("".to_owned())
+            .build()),
+    ]);
+}
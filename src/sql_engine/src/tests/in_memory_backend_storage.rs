@@ -13,7 +13,13 @@
 // limitations under the License.
 
 use kernel::{SystemError, SystemResult};
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 use storage::backend::{
     BackendStorage, CreateObjectError, DropObjectError, Key, NamespaceAlreadyExists, NamespaceDoesNotExist,
     OperationOnObjectError, ReadCursor, Result, Row, Values,
@@ -32,6 +38,14 @@ struct Namespace {
 #[derive(Default)]
 pub struct InMemoryStorage {
     namespaces: HashMap<String, Namespace>,
+    // number of rows read from tables, system objects are not counted
+    scanned_rows: Arc<AtomicUsize>,
+}
+
+impl InMemoryStorage {
+    pub fn scanned_rows(&self) -> Arc<AtomicUsize> {
+        self.scanned_rows.clone()
+    }
 }
 
 impl BackendStorage for InMemoryStorage {
@@ -123,6 +137,11 @@ impl BackendStorage for InMemoryStorage {
     }
 
     fn read(&self, namespace: &str, object_name: &str) -> SystemResult<Result<ReadCursor, OperationOnObjectError>> {
+        if namespace != "system" {
+            if let Some(object) = self.namespaces.get(namespace).and_then(|n| n.objects.get(object_name)) {
+                self.scanned_rows.fetch_add(object.records.len(), Ordering::SeqCst);
+            }
+        }
         match self.namespaces.get(namespace) {
             Some(namespace) => match namespace.objects.get(object_name) {
                 Some(object) => Ok(Ok(Box::new(
@@ -30,6 +30,40 @@ fn insert_into_nonexistent_table(sql_engine_with_schema: (QueryExecutor<InMemory
     ]);
 }
 
+#[rstest::rstest]
+fn insert_into_database_qualified_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_test smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into database_name.schema_name.table_name values (123);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new()
+            .cross_database_reference("database_name.schema_name.table_name".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_into_unqualified_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("insert into table_name values (123);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .syntax_error("unsupported table name 'table_name'. All table names must be qualified".to_owned())
+            .build()),
+    ]);
+}
+
 #[rstest::rstest]
 fn insert_value_in_non_existent_column(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
     let (mut engine, collector) = sql_engine_with_schema;
@@ -49,6 +83,88 @@ fn insert_value_in_non_existent_column(sql_engine_with_schema: (QueryExecutor<In
     ]);
 }
 
+#[rstest::rstest]
+fn insert_fewer_values_than_named_columns(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name (column_1, column_2) values (1);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new().too_many_insert_target_columns().build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_more_values_than_named_columns(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name (column_1) values (1, 2);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new().too_many_insert_expressions().build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_fewer_values_than_table_columns(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new()
+            .feature_not_supported("NULL values".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_fewer_values_than_table_columns_with_defaults(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 smallint default 7);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1);")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("column_2".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![vec![Some("1".to_owned()), Some("7".to_owned())]],
+        ))),
+    ]);
+}
+
 #[rstest::rstest]
 fn insert_and_select_single_row(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
     let (mut engine, collector) = sql_engine_with_schema;
@@ -69,7 +185,101 @@ fn insert_and_select_single_row(sql_engine_with_schema: (QueryExecutor<InMemoryS
         Ok(QueryEvent::RecordsInserted(1)),
         Ok(QueryEvent::RecordsSelected((
             vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
-            vec![vec!["123".to_owned()]],
+            vec![vec![Some("123".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_strings_with_escaped_quotes(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_test varchar(20));")
+        .expect("no system errors");
+
+    engine
+        .execute("insert into schema_name.table_name values ('it''s'), (E'it\\'s'), (E'it''s');")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(3)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_test".to_owned(), PostgreSqlType::VarChar)],
+            vec![
+                vec![Some("it's".to_owned())],
+                vec![Some("it's".to_owned())],
+                vec![Some("it's".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_escape_string(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_test varchar(20));")
+        .expect("no system errors");
+
+    engine
+        .execute(
+            "insert into schema_name.table_name values (E'line1\\nline2'), (e'tab\\there\\\\'), ('line1\\nline2');",
+        )
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(3)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_test".to_owned(), PostgreSqlType::VarChar)],
+            vec![
+                vec![Some("line1\nline2".to_owned())],
+                vec![Some("tab\there\\".to_owned())],
+                vec![Some("line1\\nline2".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_escape_string_with_numeric_escapes(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_test varchar(20));")
+        .expect("no system errors");
+
+    engine
+        .execute(
+            "insert into schema_name.table_name values \
+             (E'\\x41\\x4g'), (E'\\101\\7\\0101'), (E'\\xC3\\xA9'), (E'\\u00e9\\U0001F600'), (E'\\u12');",
+        )
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(5)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_test".to_owned(), PostgreSqlType::VarChar)],
+            vec![
+                vec![Some("A\u{4}g".to_owned())],
+                vec![Some("A\u{7}\u{8}1".to_owned())],
+                vec![Some("é".to_owned())],
+                vec![Some("é😀".to_owned())],
+                vec![Some("u12".to_owned())],
+            ],
         ))),
     ]);
 }
@@ -100,12 +310,73 @@ fn insert_and_select_multiple_rows(sql_engine_with_schema: (QueryExecutor<InMemo
         Ok(QueryEvent::RecordsInserted(1)),
         Ok(QueryEvent::RecordsSelected((
             vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
-            vec![vec!["123".to_owned()]],
+            vec![vec![Some("123".to_owned())]],
         ))),
         Ok(QueryEvent::RecordsInserted(1)),
         Ok(QueryEvent::RecordsSelected((
             vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
-            vec![vec!["123".to_owned()], vec!["456".to_owned()]],
+            vec![vec![Some("123".to_owned())], vec![Some("456".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_with_default_keyword(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (col1 smallint default 1, col2 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (DEFAULT, 5), (default, 6);")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("col1".to_owned(), PostgreSqlType::SmallInt),
+                ("col2".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("5".to_owned())],
+                vec![Some("1".to_owned()), Some("6".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn insert_default_keyword_into_column_without_default(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (col1 smallint, col2 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1, DEFAULT);")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new()
+            .feature_not_supported("NULL values".to_owned())
+            .build()),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("col1".to_owned(), PostgreSqlType::SmallInt),
+                ("col2".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![],
         ))),
     ]);
 }
@@ -134,8 +405,8 @@ fn insert_and_select_named_columns(sql_engine_with_schema: (QueryExecutor<InMemo
                 ("col3".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["3".to_owned(), "1".to_owned(), "2".to_owned()],
-                vec!["6".to_owned(), "4".to_owned(), "5".to_owned()],
+                vec![Some("3".to_owned()), Some("1".to_owned()), Some("2".to_owned())],
+                vec![Some("6".to_owned()), Some("4".to_owned()), Some("5".to_owned())],
             ],
         ))),
     ]);
@@ -165,9 +436,9 @@ fn insert_multiple_rows(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>,
                 ("column_3".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["1".to_owned(), "4".to_owned(), "7".to_owned()],
-                vec!["2".to_owned(), "5".to_owned(), "8".to_owned()],
-                vec!["3".to_owned(), "6".to_owned(), "9".to_owned()],
+                vec![Some("1".to_owned()), Some("4".to_owned()), Some("7".to_owned())],
+                vec![Some("2".to_owned()), Some("5".to_owned()), Some("8".to_owned())],
+                vec![Some("3".to_owned()), Some("6".to_owned()), Some("9".to_owned())],
             ],
         ))),
     ]);
@@ -203,16 +474,16 @@ fn insert_and_select_different_integer_types(sql_engine_with_schema: (QueryExecu
             ],
             vec![
                 vec![
-                    "-32768".to_owned(),
-                    "-2147483648".to_owned(),
-                    "-9223372036854775808".to_owned(),
-                    "1".to_owned(),
+                    Some("-32768".to_owned()),
+                    Some("-2147483648".to_owned()),
+                    Some("-9223372036854775808".to_owned()),
+                    Some("1".to_owned()),
                 ],
                 vec![
-                    "32767".to_owned(),
-                    "2147483647".to_owned(),
-                    "9223372036854775807".to_owned(),
-                    "1".to_owned(),
+                    Some("32767".to_owned()),
+                    Some("2147483647".to_owned()),
+                    Some("9223372036854775807".to_owned()),
+                    Some("1".to_owned()),
                 ],
             ],
         ))),
@@ -248,8 +519,8 @@ fn insert_and_select_different_character_types(
                 ("column_vc".to_owned(), PostgreSqlType::VarChar),
             ],
             vec![
-                vec!["12345abcde".to_owned(), "12345abcde".to_owned()],
-                vec!["12345abcde".to_owned(), "abcde".to_owned()],
+                vec![Some("12345abcde".to_owned()), Some("12345abcde".to_owned())],
+                vec![Some("12345abcde".to_owned()), Some("abcde".to_owned())],
             ],
         ))),
     ]);
@@ -280,6 +551,41 @@ fn insert_booleans(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<
     ]);
 }
 
+#[rstest::rstest]
+fn serial_column_is_assigned_when_omitted(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (id serial, name varchar(10));")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name (name) values ('a'), ('b');")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name (name) values ('c');")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("id".to_owned(), PostgreSqlType::Integer),
+                ("name".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("a".to_owned())],
+                vec![Some("2".to_owned()), Some("b".to_owned())],
+                vec![Some("3".to_owned()), Some("c".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
 #[cfg(test)]
 mod operators {
     use super::*;
@@ -320,7 +626,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["3".to_owned()]],
+                        vec![vec![Some("3".to_owned())]],
                     ))),
                 ]);
             }
@@ -341,7 +647,32 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["-1".to_owned()]],
+                        vec![vec![Some("-1".to_owned())]],
+                    ))),
+                ]);
+            }
+
+            #[rstest::rstest]
+            fn negative_operands(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+                let (mut engine, collector) = with_table;
+                engine
+                    .execute("insert into schema_name.table_name values (-5 + 10), (-(2 * 3)), (+4 * -1);")
+                    .expect("no system errors");
+                engine
+                    .execute("select * from schema_name.table_name;")
+                    .expect("no system errors");
+
+                collector.assert_content(vec![
+                    Ok(QueryEvent::SchemaCreated),
+                    Ok(QueryEvent::TableCreated),
+                    Ok(QueryEvent::RecordsInserted(3)),
+                    Ok(QueryEvent::RecordsSelected((
+                        vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
+                        vec![
+                            vec![Some("5".to_owned())],
+                            vec![Some("-6".to_owned())],
+                            vec![Some("-4".to_owned())],
+                        ],
                     ))),
                 ]);
             }
@@ -362,7 +693,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["6".to_owned()]],
+                        vec![vec![Some("6".to_owned())]],
                     ))),
                 ]);
             }
@@ -383,7 +714,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["4".to_owned()]],
+                        vec![vec![Some("4".to_owned())]],
                     ))),
                 ]);
             }
@@ -404,7 +735,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["0".to_owned()]],
+                        vec![vec![Some("0".to_owned())]],
                     ))),
                 ]);
             }
@@ -428,7 +759,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["64".to_owned()]],
+                        vec![vec![Some("64".to_owned())]],
                     ))),
                 ]);
             }
@@ -451,7 +782,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["4".to_owned()]],
+                        vec![vec![Some("4".to_owned())]],
                     ))),
                 ]);
             }
@@ -474,7 +805,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["2".to_owned()]],
+                        vec![vec![Some("2".to_owned())]],
                     ))),
                 ]);
             }
@@ -497,7 +828,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["120".to_owned()]],
+                        vec![vec![Some("120".to_owned())]],
                     ))),
                 ]);
             }
@@ -520,7 +851,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["120".to_owned()]],
+                        vec![vec![Some("120".to_owned())]],
                     ))),
                 ]);
             }
@@ -543,7 +874,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["5".to_owned()]],
+                        vec![vec![Some("5".to_owned())]],
                     ))),
                 ]);
             }
@@ -564,7 +895,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["1".to_owned()]],
+                        vec![vec![Some("1".to_owned())]],
                     ))),
                 ]);
             }
@@ -585,7 +916,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["7".to_owned()]],
+                        vec![vec![Some("7".to_owned())]],
                     ))),
                 ]);
             }
@@ -608,7 +939,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["-2".to_owned()]],
+                        vec![vec![Some("-2".to_owned())]],
                     ))),
                 ]);
             }
@@ -631,7 +962,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["16".to_owned()]],
+                        vec![vec![Some("16".to_owned())]],
                     ))),
                 ]);
             }
@@ -654,7 +985,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["2".to_owned()]],
+                        vec![vec![Some("2".to_owned())]],
                     ))),
                 ]);
             }
@@ -675,7 +1006,7 @@ mod operators {
                     Ok(QueryEvent::RecordsInserted(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["5".to_owned()]],
+                        vec![vec![Some("5".to_owned())]],
                     ))),
                 ]);
             }
@@ -714,7 +1045,7 @@ mod operators {
                 Ok(QueryEvent::RecordsInserted(1)),
                 Ok(QueryEvent::RecordsSelected((
                     vec![("strings".to_owned(), PostgreSqlType::Char)],
-                    vec![vec!["12345".to_owned()]],
+                    vec![vec![Some("12345".to_owned())]],
                 ))),
             ]);
         }
@@ -739,7 +1070,7 @@ mod operators {
                 Ok(QueryEvent::RecordsInserted(1)),
                 Ok(QueryEvent::RecordsSelected((
                     vec![("strings".to_owned(), PostgreSqlType::Char)],
-                    vec![vec!["145".to_owned()], vec!["451".to_owned()]],
+                    vec![vec![Some("145".to_owned())], vec![Some("451".to_owned())]],
                 ))),
             ]);
         }
@@ -762,3 +1093,189 @@ mod operators {
         }
     }
 }
+
+#[cfg(test)]
+mod write_retries {
+    use super::*;
+    use crate::RetryPolicy;
+    use std::{sync::mpsc, thread, time::Duration};
+
+    fn engine_with_table(
+        storage: SharedStorage<InMemoryStorage>,
+        retry_policy: RetryPolicy,
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let collector = Arc::new(Collector(Mutex::new(vec![])));
+        let mut engine = QueryExecutor::new(storage, collector.clone()).with_write_retry_policy(retry_policy);
+        engine.execute("create schema schema_name;").expect("no system errors");
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        (engine, collector)
+    }
+
+    #[test]
+    fn insert_succeeds_after_retry() {
+        let storage = in_memory_storage();
+        let (mut engine, collector) =
+            engine_with_table(storage.clone(), RetryPolicy::new(1, Duration::from_millis(500)));
+
+        let (locked, storage_is_locked) = mpsc::channel();
+        let concurrent_operation = thread::spawn(move || {
+            let guard = storage.read().expect("read lock");
+            locked.send(()).expect("test is waiting");
+            thread::sleep(Duration::from_millis(50));
+            drop(guard);
+        });
+        storage_is_locked.recv().expect("storage is locked");
+        engine
+            .execute("insert into schema_name.table_name values (1);")
+            .expect("no system errors");
+        concurrent_operation.join().expect("concurrent operation finished");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsInserted(1))]);
+    }
+
+    #[test]
+    fn insert_fails_when_retries_are_exhausted() {
+        let storage = in_memory_storage();
+        let (mut engine, collector) = engine_with_table(storage.clone(), RetryPolicy::new(2, Duration::from_millis(1)));
+
+        let guard = storage.read().expect("read lock");
+        engine
+            .execute("insert into schema_name.table_name values (1);")
+            .expect("no system errors");
+        drop(guard);
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new().serialization_failure().build())]);
+    }
+}
+
+#[cfg(test)]
+mod returning {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (id serial, name varchar(10));")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn returning_columns(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("insert into schema_name.table_name (name) values ('first'), ('second') returning id, name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsInsertedReturning((
+            vec![
+                ("id".to_owned(), PostgreSqlType::Integer),
+                ("name".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("first".to_owned())],
+                vec![Some("2".to_owned()), Some("second".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn returning_escape_strings(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("insert into schema_name.table_name (name) values (E'it\\'s') returning name, E'\\x41\\n';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsInsertedReturning((
+            vec![
+                ("name".to_owned(), PostgreSqlType::VarChar),
+                ("?column?".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![vec![Some("it's".to_owned()), Some("A\n".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn returning_expressions(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute(
+                "insert into schema_name.table_name (name) values ('hello') \
+                 returning id, name || '!' as greeting, id * 10;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsInsertedReturning((
+            vec![
+                ("id".to_owned(), PostgreSqlType::Integer),
+                ("greeting".to_owned(), PostgreSqlType::VarChar),
+                ("?column?".to_owned(), PostgreSqlType::Integer),
+            ],
+            vec![vec![
+                Some("1".to_owned()),
+                Some("hello!".to_owned()),
+                Some("10".to_owned()),
+            ]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn returning_all_columns(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("insert into schema_name.table_name values (5, 'name') returning *;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsInsertedReturning((
+            vec![
+                ("id".to_owned(), PostgreSqlType::Integer),
+                ("name".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![vec![Some("5".to_owned()), Some("name".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn returning_non_existent_column_inserts_nothing(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("insert into schema_name.table_name (name) values ('name') returning other || '!';")
+            .expect("no system errors");
+        engine
+            .execute("select name from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Err(QueryErrorBuilder::new()
+                .column_does_not_exist(vec!["other".to_owned()])
+                .build()),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("name".to_owned(), PostgreSqlType::VarChar)],
+                vec![],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn returning_from_non_existent_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("insert into schema_name.table_name values (1) returning id;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Err(QueryErrorBuilder::new()
+                .table_does_not_exist("schema_name.table_name".to_owned())
+                .build()),
+        ]);
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use protocol::sql_types::PostgreSqlType;
+
+#[rstest::fixture]
+fn limited_engine(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+    let (engine, collector) = sql_engine_with_schema;
+    let mut engine = engine.with_max_query_length(64);
+    engine
+        .execute("create table schema_name.table_name (name varchar(100));")
+        .expect("no system errors");
+    collector.0.lock().expect("locked").clear();
+    (engine, collector)
+}
+
+#[rstest::rstest]
+fn query_over_limit_is_rejected(limited_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = limited_engine;
+    let query = format!("insert into schema_name.table_name values ('{}');", "a".repeat(30));
+    engine.execute(&query).expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Err(QueryErrorBuilder::new().query_too_long(query.len(), 64).build()),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("name".to_owned(), PostgreSqlType::VarChar)],
+            vec![],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn query_of_limit_length_is_executed(limited_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = limited_engine;
+    let query = format!("insert into schema_name.table_name values ('{}');", "a".repeat(17));
+    assert_eq!(query.len(), 64);
+    engine.execute(&query).expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsInserted(1))]);
+}
+
+#[rstest::rstest]
+fn prepared_statement_over_limit_is_rejected(limited_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = limited_engine;
+    let query = format!("insert into schema_name.table_name values ('{}', $1);", "a".repeat(30));
+    engine.parse("", &query, &[]).expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .query_too_long(query.len(), 64)
+        .build())]);
+}
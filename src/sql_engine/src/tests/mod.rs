@@ -12,22 +12,56 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(test)]
+mod autocommit;
+#[cfg(test)]
+mod copy;
+#[cfg(test)]
+mod database;
 #[cfg(test)]
 mod delete;
 #[cfg(test)]
+mod dialect;
+#[cfg(test)]
+mod expression_evaluation;
+#[cfg(test)]
+mod extended_query;
+#[cfg(test)]
 mod in_memory_backend_storage;
 #[cfg(test)]
 mod insert;
 #[cfg(test)]
+mod max_query_length;
+#[cfg(test)]
+mod parse_cache;
+#[cfg(test)]
+mod parse_error;
+#[cfg(test)]
+mod ping;
+#[cfg(test)]
+mod privileges;
+#[cfg(test)]
+mod query_log;
+#[cfg(test)]
+mod safe_updates;
+#[cfg(test)]
 mod schema;
 #[cfg(test)]
 mod select;
 #[cfg(test)]
+mod sequence;
+#[cfg(test)]
+mod set_variable;
+#[cfg(test)]
+mod stat_activity;
+#[cfg(test)]
 mod table;
 #[cfg(test)]
 mod type_constraints;
 #[cfg(test)]
 mod update;
+#[cfg(test)]
+mod vacuum;
 
 use super::*;
 use crate::QueryExecutor;
@@ -36,12 +70,12 @@ use protocol::results::QueryResult;
 use std::{
     io,
     ops::Deref,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, RwLock},
 };
-use storage::frontend::FrontendStorage;
+use storage::frontend::{FrontendStorage, SharedStorage};
 
-fn in_memory_storage() -> Arc<Mutex<FrontendStorage<InMemoryStorage>>> {
-    Arc::new(Mutex::new(FrontendStorage::new(InMemoryStorage::default()).unwrap()))
+fn in_memory_storage() -> SharedStorage<InMemoryStorage> {
+    Arc::new(RwLock::new(FrontendStorage::new(InMemoryStorage::default()).unwrap()))
 }
 
 struct Collector(Mutex<Vec<QueryResult>>);
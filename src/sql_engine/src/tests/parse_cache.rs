@@ -0,0 +1,76 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::parse_cache::ParseCache;
+use protocol::sql_types::PostgreSqlType;
+use sqlparser::{dialect::PostgreSqlDialect, parser::Parser};
+
+fn statement(raw_sql_query: &str) -> Statement {
+    Parser::parse_sql(&PostgreSqlDialect {}, raw_sql_query)
+        .expect("valid query")
+        .pop()
+        .expect("single statement")
+}
+
+#[rstest::rstest]
+fn repeated_query_is_taken_from_cache(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+    let hits = engine.parse_cache.hits();
+
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+    assert_eq!(engine.parse_cache.hits(), hits);
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+    assert_eq!(engine.parse_cache.hits(), hits + 1);
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("123".to_owned())]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("123".to_owned())]],
+        ))),
+    ]);
+}
+
+#[test]
+fn least_recently_used_query_is_evicted() {
+    let mut cache = ParseCache::new(2);
+    cache.put("select 1;", statement("select 1;"));
+    cache.put("select 2;", statement("select 2;"));
+    assert_eq!(cache.get("select 1;"), Some(statement("select 1;")));
+
+    cache.put("select 3;", statement("select 3;"));
+
+    assert_eq!(cache.get("select 2;"), None);
+    assert_eq!(cache.get("select 1;"), Some(statement("select 1;")));
+    assert_eq!(cache.get("select 3;"), Some(statement("select 3;")));
+    assert_eq!(cache.hits(), 3);
+    assert_eq!(cache.misses(), 1);
+}
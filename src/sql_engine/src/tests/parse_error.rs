@@ -0,0 +1,59 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::rstest]
+fn misspelled_keyword(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("selec 1;").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .syntax_error(format!("{:?}: Expected an SQL statement, found: selec", "selec 1;"))
+        .build())]);
+}
+
+#[rstest::rstest]
+fn incomplete_statement(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("select 1 +").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .syntax_error(format!("{:?}: Expected an expression, found: EOF", "select 1 +"))
+        .build())]);
+}
+
+#[rstest::rstest]
+fn tokenizer_error_has_position(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("select 'unterminated").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .syntax_error(format!(
+            "{:?}: Unterminated string literal at Line: 1, Column 8",
+            "select 'unterminated"
+        ))
+        .build())]);
+}
+
+#[rstest::rstest]
+fn session_continues_after_syntax_error(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("create schema;").expect("no system errors");
+    collector.0.lock().expect("locked").clear();
+
+    engine.execute("create schema schema_name;").expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::SchemaCreated)]);
+}
@@ -0,0 +1,70 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use protocol::{results::QueryEvent, sql_types::PostgreSqlType};
+use std::{sync::mpsc, thread, time::Duration};
+
+// storage is locked exclusively while the query is executed, so the query
+// finishes only if it does not access storage
+fn execute_with_locked_storage(query: &'static str) -> Arc<Collector> {
+    let storage = in_memory_storage();
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut engine = QueryExecutor::new(storage.clone(), collector.clone());
+
+    let _exclusive = storage.write().unwrap();
+    let (done, finished) = mpsc::channel();
+    thread::spawn(move || {
+        engine.execute(query).expect("no system errors");
+        done.send(()).expect("test is waiting");
+    });
+    finished
+        .recv_timeout(Duration::from_secs(5))
+        .expect("query is executed without storage access");
+
+    collector
+}
+
+#[test]
+fn empty_query() {
+    execute_with_locked_storage("").assert_content(vec![Ok(QueryEvent::QueryIsEmpty)]);
+}
+
+#[test]
+fn query_with_only_semicolons() {
+    execute_with_locked_storage(" ; ;").assert_content(vec![Ok(QueryEvent::QueryIsEmpty)]);
+}
+
+#[test]
+fn select_constant() {
+    execute_with_locked_storage("select 1;").assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("?column?".to_owned(), PostgreSqlType::Decimal)],
+        vec![vec![Some("1".to_owned())]],
+    )))]);
+}
+
+#[test]
+fn query_with_only_line_comment() {
+    execute_with_locked_storage("-- ping").assert_content(vec![Ok(QueryEvent::QueryIsEmpty)]);
+}
+
+#[test]
+fn query_with_only_block_comment() {
+    execute_with_locked_storage("/* ping */").assert_content(vec![Ok(QueryEvent::QueryIsEmpty)]);
+}
+
+#[test]
+fn query_with_comments_and_semicolons() {
+    execute_with_locked_storage("/* first */ ; -- second\n;").assert_content(vec![Ok(QueryEvent::QueryIsEmpty)]);
+}
@@ -0,0 +1,144 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use protocol::sql_types::PostgreSqlType;
+
+fn engine_with_table(user: &str) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut engine = QueryExecutor::new(in_memory_storage(), collector.clone()).with_user(user);
+    engine.execute("create schema schema_name;").expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+    (engine, collector)
+}
+
+#[rstest::rstest]
+fn grant_on_non_existent_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("grant select on schema_name.table_name to user_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.table_name".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn grant_unsupported_privilege(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("grant insert on schema_name.table_name to user_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .syntax_error(format!(
+                "{:?}: Expected SELECT, found: insert",
+                "grant insert on schema_name.table_name to user_name;"
+            ))
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn select_is_gated_by_granted_privilege() {
+    let (mut engine, collector) = engine_with_table("user_name");
+    engine
+        .execute("grant select on table schema_name.table_name to user_name;")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+    engine
+        .execute("revoke select on schema_name.table_name from user_name;")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::PrivilegesGranted),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("123".to_owned())]],
+        ))),
+        Ok(QueryEvent::PrivilegesRevoked),
+        Err(QueryErrorBuilder::new()
+            .permission_denied("schema_name.table_name".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn select_by_other_user_is_denied() {
+    let (mut engine, collector) = engine_with_table("user_name");
+    engine
+        .execute("grant select on schema_name.table_name to other_user;")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::PrivilegesGranted),
+        Err(QueryErrorBuilder::new()
+            .permission_denied("schema_name.table_name".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn copy_to_is_gated_by_granted_privilege() {
+    let (mut engine, collector) = engine_with_table("user_name");
+    engine
+        .execute("grant select on schema_name.table_name to user_name;")
+        .expect("no system errors");
+    engine
+        .execute("copy schema_name.table_name to stdout;")
+        .expect("no system errors");
+    engine
+        .execute("revoke select on schema_name.table_name from user_name;")
+        .expect("no system errors");
+    engine
+        .execute("copy schema_name.table_name to stdout;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::PrivilegesGranted),
+        Ok(QueryEvent::RecordsCopiedOut(1, vec!["123\n".to_owned()])),
+        Ok(QueryEvent::PrivilegesRevoked),
+        Err(QueryErrorBuilder::new()
+            .permission_denied("schema_name.table_name".to_owned())
+            .build()),
+    ]);
+}
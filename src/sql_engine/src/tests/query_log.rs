@@ -0,0 +1,120 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::sync::Once;
+
+static CAPTURED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static INIT: Once = Once::new();
+
+struct CapturingLogger;
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target() == "query" {
+            CAPTURED
+                .lock()
+                .expect("locked")
+                .push(format!("{} {}", record.level(), record.args()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger;
+
+fn capture_logs() {
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).expect("logger is set once");
+        log::set_max_level(LevelFilter::Debug);
+    });
+}
+
+fn captured_lines() -> Vec<String> {
+    CAPTURED.lock().expect("locked").clone()
+}
+
+#[rstest::rstest]
+fn select_logs_returned_rows(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    capture_logs();
+    let (mut engine, _collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.logged_select (column_1 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.logged_select values (1), (2), (3), (4), (5), (6), (7);")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.logged_select;")
+        .expect("no system errors");
+
+    let lines = captured_lines();
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("DEBUG sql=\"select * from schema_name.logged_select;\"")));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("INFO command=INSERT rows=7 duration=")));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("INFO command=SELECT rows=7 duration=")));
+}
+
+#[rstest::rstest]
+fn failed_query_logs_error(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    capture_logs();
+    let (mut engine, _collector) = sql_engine;
+    engine
+        .execute("select * from logged_schema.logged_table;")
+        .expect("no system errors");
+
+    assert!(captured_lines()
+        .iter()
+        .any(|line| line.starts_with("WARN error=") && line.contains("logged_schema")));
+}
+
+#[rstest::rstest]
+fn log_min_messages_is_applied_to_its_session_only(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    capture_logs();
+    let (mut engine, _collector) = sql_engine_with_schema;
+    let (mut other_engine, _other_collector) = sql_engine();
+    engine
+        .execute("set log_min_messages = 'warning';")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.quiet_session;")
+        .expect("no system errors");
+    other_engine
+        .execute("select * from schema_name.verbose_session;")
+        .expect("no system errors");
+
+    let lines = captured_lines();
+    assert!(!lines
+        .iter()
+        .any(|line| line.starts_with("DEBUG sql=") && line.contains("quiet_session")));
+    assert!(lines
+        .iter()
+        .any(|line| line.contains("quiet_session") && line.starts_with("WARN error=")));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with("DEBUG sql=") && line.contains("verbose_session")));
+}
@@ -0,0 +1,94 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use protocol::sql_types::PostgreSqlType;
+
+#[rstest::fixture]
+fn safe_engine_with_table() -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut engine = QueryExecutor::new(in_memory_storage(), collector.clone()).with_safe_updates();
+    engine.execute("create schema schema_name;").expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+    (engine, collector)
+}
+
+#[rstest::rstest]
+fn delete_without_where_is_rejected(safe_engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = safe_engine_with_table;
+    engine
+        .execute("delete from schema_name.table_name;")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Err(QueryErrorBuilder::new()
+            .where_clause_required("DELETE".to_owned())
+            .build()),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("123".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn update_without_where_is_rejected(safe_engine_with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = safe_engine_with_table;
+    engine
+        .execute("update schema_name.table_name set column_1 = 456;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Err(QueryErrorBuilder::new()
+            .where_clause_required("UPDATE".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn delete_without_where_is_allowed_by_default(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+    engine
+        .execute("delete from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsDeleted(1)),
+    ]);
+}
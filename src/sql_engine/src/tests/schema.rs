@@ -56,6 +56,65 @@ fn drop_non_existent_schema(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Col
         .build())]);
 }
 
+#[rstest::rstest]
+fn drop_non_existent_schema_if_exists(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+
+    engine
+        .execute("drop schema if exists non_existent;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Err(QueryErrorBuilder::new()
+            .schema_does_not_exist_skipping("non_existent".to_owned())
+            .build()),
+        Ok(QueryEvent::SchemaDropped),
+    ]);
+}
+
+#[rstest::rstest]
+fn drop_existing_schema_if_exists(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("create schema schema_name;").expect("no system errors");
+    engine
+        .execute("drop schema if exists schema_name;")
+        .expect("no system errors");
+    engine.execute("create schema schema_name;").expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::SchemaDropped),
+        Ok(QueryEvent::SchemaCreated),
+    ]);
+}
+
+#[rstest::rstest]
+fn create_existing_schema_if_not_exists(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("create schema schema_name;").expect("no system errors");
+    engine
+        .execute("create schema if not exists schema_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .schema_already_exists_skipping("schema_name".to_owned())
+            .build()),
+        Ok(QueryEvent::SchemaCreated),
+    ]);
+}
+
+#[rstest::rstest]
+fn create_non_existent_schema_if_not_exists(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("create schema if not exists schema_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::SchemaCreated)]);
+}
+
 #[rstest::rstest]
 fn select_from_nonexistent_schema(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
     let (mut engine, collector) = sql_engine;
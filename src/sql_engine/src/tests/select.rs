@@ -14,6 +14,7 @@
 
 use super::*;
 use protocol::sql_types::PostgreSqlType;
+use std::{sync::mpsc, thread, time::Duration};
 
 #[rstest::rstest]
 fn select_from_not_existed_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
@@ -30,6 +31,38 @@ fn select_from_not_existed_table(sql_engine_with_schema: (QueryExecutor<InMemory
     ]);
 }
 
+#[rstest::rstest]
+fn select_from_database_qualified_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("select * from database_name.schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .cross_database_reference("database_name.schema_name.table_name".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn select_from_unqualified_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_test smallint);")
+        .expect("no system errors");
+    engine.execute("select * from table_name;").expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new()
+            .syntax_error("unsupported table name 'table_name'. All table names must be qualified".to_owned())
+            .build()),
+    ]);
+}
+
 #[rstest::rstest]
 fn select_named_columns_from_non_existent_table(
     sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
@@ -72,7 +105,11 @@ fn select_all_from_table_with_multiple_columns(
                 ("column_2".to_owned(), PostgreSqlType::SmallInt),
                 ("column_3".to_owned(), PostgreSqlType::SmallInt),
             ],
-            vec![vec!["123".to_owned(), "456".to_owned(), "789".to_owned()]],
+            vec![vec![
+                Some("123".to_owned()),
+                Some("456".to_owned()),
+                Some("789".to_owned()),
+            ]],
         ))),
     ]);
 }
@@ -100,10 +137,134 @@ fn select_not_all_columns(sql_engine_with_schema: (QueryExecutor<InMemoryStorage
                 ("column_2".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["7".to_owned(), "4".to_owned()],
-                vec!["8".to_owned(), "5".to_owned()],
-                vec!["9".to_owned(), "6".to_owned()],
+                vec![Some("7".to_owned()), Some("4".to_owned())],
+                vec![Some("8".to_owned()), Some("5".to_owned())],
+                vec![Some("9".to_owned()), Some("6".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn select_duplicated_columns_of_different_types(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 varchar(10), column_3 bigint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1, 'a', 100);")
+        .expect("no system errors");
+    engine
+        .execute("select column_2, column_1, column_3, column_2, column_1 from schema_name.table_name;")
+        .expect("no system errors");
+    engine
+        .execute("select column_3, column_2, column_3 from schema_name.table_name where column_1 = 1;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("column_3".to_owned(), PostgreSqlType::BigInt),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![vec![
+                Some("a".to_owned()),
+                Some("1".to_owned()),
+                Some("100".to_owned()),
+                Some("a".to_owned()),
+                Some("1".to_owned()),
+            ]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_3".to_owned(), PostgreSqlType::BigInt),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+                ("column_3".to_owned(), PostgreSqlType::BigInt),
             ],
+            vec![vec![
+                Some("100".to_owned()),
+                Some("a".to_owned()),
+                Some("100".to_owned()),
+            ]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn select_where_column_equals_literal(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 varchar(10));")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1, 'a'), (2, 'b');")
+        .expect("no system errors");
+    engine
+        .execute("select column_2 from schema_name.table_name where column_1 = 2.0;")
+        .expect("no system errors");
+    engine
+        .execute("select column_1 from schema_name.table_name where column_2 <> 'b';")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_2".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("b".to_owned())]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("1".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn select_where_column_equals_literal_of_column_type(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint, column_2 varchar(10));")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1, '1'), (2, '01'), (3, '1.0');")
+        .expect("no system errors");
+    engine
+        .execute("select column_1 from schema_name.table_name where column_2 = '1';")
+        .expect("no system errors");
+    engine
+        .execute("select column_2 from schema_name.table_name where column_1 = '1';")
+        .expect("no system errors");
+    engine
+        .execute("select column_1 from schema_name.table_name where column_2 in (select column_2 from schema_name.table_name where column_1 = 1);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(3)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("1".to_owned())]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_2".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("1".to_owned())]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("1".to_owned())]],
         ))),
     ]);
 }
@@ -129,3 +290,2159 @@ fn select_non_existing_columns_from_table(sql_engine_with_schema: (QueryExecutor
             .build()),
     ]);
 }
+
+#[rstest::rstest]
+fn select_is_not_blocked_by_concurrent_reader() {
+    let storage = in_memory_storage();
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let mut engine = QueryExecutor::new(storage.clone(), collector.clone());
+    engine.execute("create schema schema_name;").expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (column_1 smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (123);")
+        .expect("no system errors");
+
+    let concurrent_select = storage.read().expect("read lock");
+    let (done, finished) = mpsc::channel();
+    let select = thread::spawn(move || {
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+        done.send(()).expect("test is waiting");
+    });
+    finished
+        .recv_timeout(Duration::from_secs(5))
+        .expect("select is not blocked by concurrent reader");
+    drop(concurrent_select);
+    select.join().expect("select finished");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("123".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn select_version(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("select version();").expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("version".to_owned(), PostgreSqlType::VarChar)],
+        vec![vec![Some("PostgreSQL 12.4".to_owned())]],
+    )))]);
+}
+
+#[rstest::rstest]
+fn select_current_schema(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("SELECT CURRENT_SCHEMA();").expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("current_schema".to_owned(), PostgreSqlType::VarChar)],
+        vec![vec![Some("public".to_owned())]],
+    )))]);
+}
+
+#[rstest::rstest]
+fn select_builtin_with_alias(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("select version() as server_version;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![("server_version".to_owned(), PostgreSqlType::VarChar)],
+        vec![vec![Some("PostgreSQL 12.4".to_owned())]],
+    )))]);
+}
+
+#[rstest::rstest]
+fn select_unknown_function(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("select unknown();").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .syntax_error("unknown()".to_owned())
+        .build())]);
+}
+
+#[cfg(test)]
+mod division {
+    use super::*;
+
+    #[rstest::rstest]
+    fn integer_by_integer_truncates(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 5 / 2, -7 / 2;").expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("?column?".to_owned(), PostgreSqlType::Decimal),
+                ("?column?".to_owned(), PostgreSqlType::Decimal),
+            ],
+            vec![vec![Some("2".to_owned()), Some("-3".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn float_by_integer(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 5.0 / 2, 5 / 2.0;").expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("?column?".to_owned(), PostgreSqlType::Decimal),
+                ("?column?".to_owned(), PostgreSqlType::Decimal),
+            ],
+            vec![vec![Some("2.5".to_owned()), Some("2.5".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn integer_by_zero(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 5 / 0;").expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new().division_by_zero().build())]);
+    }
+
+    #[rstest::rstest]
+    fn float_by_zero(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 5.0 / 0.0;").expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new().division_by_zero().build())]);
+    }
+
+    #[rstest::rstest]
+    fn modulo_by_zero(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 5 % 0;").expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new().division_by_zero().build())]);
+    }
+}
+
+#[cfg(test)]
+mod integer_overflow {
+    use super::*;
+
+    #[rstest::rstest]
+    fn sum_of_integers(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 2147483647 + 1;").expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .integer_out_of_range(PostgreSqlType::Integer)
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn product_of_bigints(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select 9223372036854775807 * 2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .integer_out_of_range(PostgreSqlType::BigInt)
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn bigint_operand_widens_result(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select 2147483647 + 2147483648;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("4294967295".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn decimal_operand_does_not_overflow(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 2147483647 + 1.0;").expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("2147483648.0".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn inserted_sum_of_integers(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (column_1 integer);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (2147483647 + 2147483647);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(QueryErrorBuilder::new()
+                .integer_out_of_range(PostgreSqlType::Integer)
+                .build()),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod datetime_arithmetic {
+    use super::*;
+
+    #[rstest::rstest]
+    fn date_plus_interval(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select date '2020-01-31' + interval '1 month', interval '2' day + date '2020-12-31';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("?column?".to_owned(), PostgreSqlType::Timestamp),
+                ("?column?".to_owned(), PostgreSqlType::Timestamp),
+            ],
+            vec![vec![
+                Some("2020-02-29 00:00:00".to_owned()),
+                Some("2021-01-02 00:00:00".to_owned()),
+            ]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn timestamp_minus_interval(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select timestamp '2020-03-01 01:00:00' - interval '1 day 02:30:00';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Timestamp)],
+            vec![vec![Some("2020-02-28 22:30:00".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn difference_of_timestamps(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select timestamp '2020-03-01 12:00:00' - timestamp '2020-02-28 06:30:00';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Interval)],
+            vec![vec![Some("2 days 05:30:00".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn difference_of_dates(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select date '2020-03-01' - date '2020-01-31';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("30".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn stored_into_timestamp_column(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (col timestamp);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (date '2020-01-31' + interval '36 hours');")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::Timestamp)],
+                vec![vec![Some("2020-02-01 12:00:00".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn arithmetic_over_columns(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (d date, ts timestamp, i interval);")
+            .expect("no system errors");
+        engine
+            .execute(
+                "insert into schema_name.table_name values ('2020-01-31', '2020-03-01 01:00:00', '1 day 02:30:00');",
+            )
+            .expect("no system errors");
+        engine
+            .execute("select d + i, ts - i, d - d from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![
+                    ("?column?".to_owned(), PostgreSqlType::Timestamp),
+                    ("?column?".to_owned(), PostgreSqlType::Timestamp),
+                    ("?column?".to_owned(), PostgreSqlType::Decimal),
+                ],
+                vec![vec![
+                    Some("2020-02-01 02:30:00".to_owned()),
+                    Some("2020-02-28 22:30:00".to_owned()),
+                    Some("0".to_owned()),
+                ]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn negative_interval_column(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (ts timestamp, i interval);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('2020-03-01 00:00:00', '-1 mons +2 days -01:00:00');")
+            .expect("no system errors");
+        engine
+            .execute("select ts + i from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("?column?".to_owned(), PostgreSqlType::Timestamp)],
+                vec![vec![Some("2020-02-02 23:00:00".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn invalid_date(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select date '2020-02-30' + interval '1 day';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .invalid_datetime_format(PostgreSqlType::Date, "2020-02-30".to_owned())
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn result_out_of_range(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select timestamp '9999-12-31 12:00:00' + interval '1 day';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .datetime_out_of_range(PostgreSqlType::Timestamp)
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn sum_of_dates(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select date '2020-01-01' + date '2020-01-02';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .undefined_function("+".to_owned(), "DATE".to_owned(), "DATE".to_owned())
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod pagination {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_five_rows(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1), (2), (3), (4), (5);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    fn selected(values: Vec<&str>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+            values.into_iter().map(|value| vec![Some(value.to_owned())]).collect(),
+        )))
+    }
+
+    #[rstest::rstest]
+    fn limit_offset(with_five_rows: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_five_rows;
+        engine
+            .execute("select column_1 from schema_name.table_name limit 2 offset 1;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec!["2", "3"])]);
+    }
+
+    #[rstest::rstest]
+    fn offset_fetch_first(with_five_rows: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_five_rows;
+        engine
+            .execute("select column_1 from schema_name.table_name offset 1 rows fetch first 2 rows only;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec!["2", "3"])]);
+    }
+
+    #[rstest::rstest]
+    fn both_spellings_select_the_same_rows(with_five_rows: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_five_rows;
+        engine
+            .execute("select column_1 from schema_name.table_name limit 3 offset 3;")
+            .expect("no system errors");
+        engine
+            .execute("select column_1 from schema_name.table_name offset 3 rows fetch next 3 rows only;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec!["4", "5"]), selected(vec!["4", "5"])]);
+    }
+
+    #[rstest::rstest]
+    fn fetch_first_row_only(with_five_rows: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_five_rows;
+        engine
+            .execute("select column_1 from schema_name.table_name fetch first row only;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec!["1"])]);
+    }
+
+    #[rstest::rstest]
+    fn offset_without_limit(with_five_rows: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_five_rows;
+        engine
+            .execute("select column_1 from schema_name.table_name offset 4;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec!["5"])]);
+    }
+
+    #[rstest::rstest]
+    fn fetch_with_ties_is_not_supported(with_five_rows: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_five_rows;
+        engine
+            .execute("select column_1 from schema_name.table_name fetch first 2 rows with ties;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .feature_not_supported(
+                "select column_1 from schema_name.table_name fetch first 2 rows with ties;".to_owned(),
+            )
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod order_by {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_equal_keys(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (key smallint, value smallint);")
+            .expect("no system errors");
+        let values = (1..=30)
+            .map(|value| format!("({}, {})", value % 3, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        engine
+            .execute(&format!("insert into schema_name.table_name values {};", values))
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    fn selected(keys: Vec<(i32, i32)>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("key".to_owned(), PostgreSqlType::SmallInt),
+                ("value".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            keys.into_iter()
+                .map(|(key, value)| vec![Some(key.to_string()), Some(value.to_string())])
+                .collect(),
+        )))
+    }
+
+    // rows with equal keys in the order they were inserted
+    fn sorted_by_key(keys: Vec<i32>) -> Vec<(i32, i32)> {
+        keys.into_iter()
+            .flat_map(|key| {
+                (1..=30)
+                    .filter(move |value| value % 3 == key)
+                    .map(move |value| (key, value))
+            })
+            .collect()
+    }
+
+    #[rstest::rstest]
+    fn equal_keys_keep_insertion_order(with_equal_keys: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_equal_keys;
+        engine
+            .execute("select key, value from schema_name.table_name order by key;")
+            .expect("no system errors");
+        engine
+            .execute("select key, value from schema_name.table_name order by key;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            selected(sorted_by_key(vec![0, 1, 2])),
+            selected(sorted_by_key(vec![0, 1, 2])),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn descending_keys_keep_insertion_order(with_equal_keys: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_equal_keys;
+        engine
+            .execute("select key, value from schema_name.table_name order by 1 desc;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(sorted_by_key(vec![2, 1, 0]))]);
+    }
+
+    #[rstest::rstest]
+    fn numbers_are_ordered_by_value(with_equal_keys: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_equal_keys;
+        engine
+            .execute("select key, value from schema_name.table_name order by value desc limit 3;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec![(0, 30), (2, 29), (1, 28)])]);
+    }
+
+    #[rstest::rstest]
+    fn strings_are_ordered_by_collation(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (name varchar(10) collate case_insensitive);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('A'), ('b'), ('a'), ('B');")
+            .expect("no system errors");
+        engine
+            .execute("select name from schema_name.table_name order by name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(4)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("name".to_owned(), PostgreSqlType::VarChar)],
+                vec![
+                    vec![Some("A".to_owned())],
+                    vec![Some("a".to_owned())],
+                    vec![Some("b".to_owned())],
+                    vec![Some("B".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn floats_are_ordered_with_infinities(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (value real);")
+            .expect("no system errors");
+        engine
+            .execute(
+                "insert into schema_name.table_name values ('Infinity'), (2.5), ('NaN'), (-5), ('-Infinity'), (1);",
+            )
+            .expect("no system errors");
+        engine
+            .execute("select value from schema_name.table_name order by value;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(6)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("value".to_owned(), PostgreSqlType::Real)],
+                vec![
+                    vec![Some("-Infinity".to_owned())],
+                    vec![Some("-5".to_owned())],
+                    vec![Some("1".to_owned())],
+                    vec![Some("2.5".to_owned())],
+                    vec![Some("Infinity".to_owned())],
+                    vec![Some("NaN".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    fn with_nulls(values: Vec<Option<&str>>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![("n".to_owned(), PostgreSqlType::Decimal)],
+            values
+                .into_iter()
+                .map(|value| vec![value.map(ToOwned::to_owned)])
+                .collect(),
+        )))
+    }
+
+    #[rstest::rstest]
+    fn nulls_are_last_in_ascending_order(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select n from (values (2), (null), (1)) as t(n) order by n;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![with_nulls(vec![Some("1"), Some("2"), None])]);
+    }
+
+    #[rstest::rstest]
+    fn nulls_are_first_in_descending_order(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select n from (values (2), (null), (1)) as t(n) order by n desc;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![with_nulls(vec![None, Some("2"), Some("1")])]);
+    }
+
+    #[rstest::rstest]
+    fn order_by_nulls_first(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select n from (values (2), (null), (1)) as t(n) order by n nulls first;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![with_nulls(vec![None, Some("1"), Some("2")])]);
+    }
+
+    #[rstest::rstest]
+    fn order_by_descending_nulls_last(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select n from (values (2), (null), (1)) as t(n) order by n desc nulls last;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![with_nulls(vec![Some("2"), Some("1"), None])]);
+    }
+
+    #[rstest::rstest]
+    fn order_by_position_nulls_first(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select n from (values (2), (null), (1)) as t(n) order by 1 desc nulls first;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![with_nulls(vec![None, Some("2"), Some("1")])]);
+    }
+
+    #[rstest::rstest]
+    fn nulls_last_in_second_sort_key(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute(
+                "select k, n from (values (1, null), (0, 5), (1, 3), (0, null)) as t(k, n) order by k, n desc nulls last;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("k".to_owned(), PostgreSqlType::Decimal),
+                ("n".to_owned(), PostgreSqlType::Decimal),
+            ],
+            vec![
+                vec![Some("0".to_owned()), Some("5".to_owned())],
+                vec![Some("0".to_owned()), None],
+                vec![Some("1".to_owned()), Some("3".to_owned())],
+                vec![Some("1".to_owned()), None],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn order_by_non_existent_column(with_equal_keys: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_equal_keys;
+        engine
+            .execute("select key, value from schema_name.table_name order by other;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .column_does_not_exist(vec!["other".to_owned()])
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod computed_columns {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint, column_2 varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1, 'a'), (2, 'b');")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn computed_column_is_named_by_alias(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select column_1 + 1 as incremented from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("incremented".to_owned(), PostgreSqlType::Integer)],
+            vec![vec![Some("2".to_owned())], vec![Some("3".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn alias_may_repeat_column_name(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select column_1, column_1 * 10 as column_1, column_2 as column_1 from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("column_1".to_owned(), PostgreSqlType::Integer),
+                ("column_1".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("10".to_owned()), Some("a".to_owned())],
+                vec![Some("2".to_owned()), Some("20".to_owned()), Some("b".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn computed_column_without_alias(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select column_2 || '!' from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("a!".to_owned())], vec![Some("b!".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn computed_column_of_non_existent_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select column_3 + 1 as incremented from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .column_does_not_exist(vec!["column_3".to_owned()])
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod set_operations {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_two_tables(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_1 (column_1 smallint, column_2 varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("create table schema_name.table_2 (column_1 integer, column_2 varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_1 values (1, 'a'), (2, 'b'), (2, 'b');")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_2 values (2, 'b'), (3, 'c');")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    fn selected(values: Vec<(&str, &str)>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::Integer),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            values
+                .into_iter()
+                .map(|(first, second)| vec![Some(first.to_owned()), Some(second.to_owned())])
+                .collect(),
+        )))
+    }
+
+    #[rstest::rstest]
+    fn union_removes_duplicates(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select * from schema_name.table_1 union select * from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec![("1", "a"), ("2", "b"), ("3", "c")])]);
+    }
+
+    #[rstest::rstest]
+    fn union_all_keeps_duplicates(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select * from schema_name.table_1 union all select * from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec![
+            ("1", "a"),
+            ("2", "b"),
+            ("2", "b"),
+            ("2", "b"),
+            ("3", "c"),
+        ])]);
+    }
+
+    #[rstest::rstest]
+    fn union_of_expressions(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select 1 union select 2 union select 1;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("1".to_owned())], vec![Some("2".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn union_of_numbers_equal_by_value(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("select 1 union select 1.0;").expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("1".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn except_of_numbers_equal_by_value(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select 2.50 union select 3 except select 2.5;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("?column?".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("3".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn union_of_integer_and_real_columns(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("create table schema_name.table_3 (column_1 real);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_3 values (3.0), (3.5);")
+            .expect("no system errors");
+        engine
+            .execute("select column_1 from schema_name.table_2 union select column_1 from schema_name.table_3;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(2)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("column_1".to_owned(), PostgreSqlType::Real)],
+                vec![
+                    vec![Some("2".to_owned())],
+                    vec![Some("3".to_owned())],
+                    vec![Some("3.5".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn union_widens_small_int_to_integer(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select column_1 from schema_name.table_1 union all select column_1 from schema_name.table_2;")
+            .expect("no system errors");
+        engine
+            .execute("select column_1 from schema_name.table_2 union all select column_1 from schema_name.table_1;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::RecordsSelected((
+                vec![("column_1".to_owned(), PostgreSqlType::Integer)],
+                vec![
+                    vec![Some("1".to_owned())],
+                    vec![Some("2".to_owned())],
+                    vec![Some("2".to_owned())],
+                    vec![Some("2".to_owned())],
+                    vec![Some("3".to_owned())],
+                ],
+            ))),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("column_1".to_owned(), PostgreSqlType::Integer)],
+                vec![
+                    vec![Some("2".to_owned())],
+                    vec![Some("3".to_owned())],
+                    vec![Some("1".to_owned())],
+                    vec![Some("2".to_owned())],
+                    vec![Some("2".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn union_with_different_number_of_columns(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select column_1 from schema_name.table_1 union select * from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .set_operation_columns_mismatch("UNION".to_owned())
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn union_with_mismatched_types(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select column_1 from schema_name.table_1 union select column_2 from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .set_operation_types_mismatch("UNION".to_owned(), PostgreSqlType::SmallInt, PostgreSqlType::VarChar)
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn intersect_returns_common_records(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select * from schema_name.table_1 intersect select * from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec![("2", "b")])]);
+    }
+
+    #[rstest::rstest]
+    fn intersect_all_keeps_common_duplicates(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute(
+                "select * from schema_name.table_1 intersect all \
+                (select * from schema_name.table_2 union all select * from schema_name.table_2);",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec![("2", "b"), ("2", "b")])]);
+    }
+
+    #[rstest::rstest]
+    fn except_subtracts_records(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select * from schema_name.table_1 except select * from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec![("1", "a")])]);
+    }
+
+    #[rstest::rstest]
+    fn except_all_subtracts_each_occurrence(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select * from schema_name.table_1 except all select * from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![selected(vec![("1", "a"), ("2", "b")])]);
+    }
+
+    #[rstest::rstest]
+    fn except_with_mismatched_types(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select column_2 from schema_name.table_1 except select column_1 from schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .set_operation_types_mismatch("EXCEPT".to_owned(), PostgreSqlType::VarChar, PostgreSqlType::Integer)
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod cross_join {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_two_tables(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_1 (id smallint, column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("create table schema_name.table_2 (id smallint, column_2 varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_1 values (1, 10), (2, 20);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_2 values (1, 'a'), (2, 'b'), (3, 'c');")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    fn cartesian_product() -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("10".to_owned()), Some("a".to_owned())],
+                vec![Some("10".to_owned()), Some("b".to_owned())],
+                vec![Some("10".to_owned()), Some("c".to_owned())],
+                vec![Some("20".to_owned()), Some("a".to_owned())],
+                vec![Some("20".to_owned()), Some("b".to_owned())],
+                vec![Some("20".to_owned()), Some("c".to_owned())],
+            ],
+        )))
+    }
+
+    #[rstest::rstest]
+    fn comma_separated_tables(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select column_1, column_2 from schema_name.table_1, schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![cartesian_product()]);
+    }
+
+    #[rstest::rstest]
+    fn cross_join_keyword(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select column_1, column_2 from schema_name.table_1 cross join schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![cartesian_product()]);
+    }
+
+    #[rstest::rstest]
+    fn all_columns_are_merged(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select * from schema_name.table_1 cross join schema_name.table_2 limit 1;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![vec![
+                Some("1".to_owned()),
+                Some("10".to_owned()),
+                Some("1".to_owned()),
+                Some("a".to_owned()),
+            ]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn qualified_columns(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select t.id, table_2.id from schema_name.table_1 as t, schema_name.table_2 limit 2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("1".to_owned())],
+                vec![Some("1".to_owned()), Some("2".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn ambiguous_column(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select id from schema_name.table_1, schema_name.table_2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .ambiguous_column("id".to_owned())
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn one_of_tables_does_not_exist(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select * from schema_name.table_1, schema_name.non_existent;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.non_existent".to_owned())
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod left_join {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_two_tables(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_1 (id smallint, column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("create table schema_name.table_2 (id smallint, column_2 varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_1 values (1, 10), (2, 20), (3, 30);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_2 values (1, 'a'), (3, 'b'), (3, 'c');")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn unmatched_rows_are_padded_with_nulls(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute(
+                "select column_1, column_2 from schema_name.table_1 \
+                left join schema_name.table_2 on table_1.id = table_2.id;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("10".to_owned()), Some("a".to_owned())],
+                vec![Some("20".to_owned()), None],
+                vec![Some("30".to_owned()), Some("b".to_owned())],
+                vec![Some("30".to_owned()), Some("c".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn all_right_columns_are_padded(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute(
+                "select * from schema_name.table_1 as t1 \
+                left outer join schema_name.table_2 as t2 on t1.id = t2.id and t2.column_2 = 'c';",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("10".to_owned()), None, None],
+                vec![Some("2".to_owned()), Some("20".to_owned()), None, None],
+                vec![
+                    Some("3".to_owned()),
+                    Some("30".to_owned()),
+                    Some("3".to_owned()),
+                    Some("c".to_owned()),
+                ],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn where_is_applied_after_padding(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute(
+                "select column_1, column_2 from schema_name.table_1 \
+                left join schema_name.table_2 on table_1.id = table_2.id where column_1 < 30;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("column_2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("10".to_owned()), Some("a".to_owned())],
+                vec![Some("20".to_owned()), None],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn empty_right_table(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("create table schema_name.table_3 (id smallint);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        engine
+            .execute(
+                "select table_1.id, table_3.id from schema_name.table_1 \
+                left join schema_name.table_3 on table_1.id = table_3.id;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+                ("id".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![
+                vec![Some("1".to_owned()), None],
+                vec![Some("2".to_owned()), None],
+                vec![Some("3".to_owned()), None],
+            ],
+        )))]);
+    }
+}
+
+#[cfg(test)]
+mod subqueries_in_where {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_two_tables(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.customers (id smallint, name varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("create table schema_name.orders (customer_id integer, amount integer);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.customers values (1, 'alice'), (2, 'bob'), (3, 'carol');")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.orders values (1, 100), (3, 300), (3, 30);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    fn names(values: Vec<&str>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![("name".to_owned(), PostgreSqlType::VarChar)],
+            values.into_iter().map(|value| vec![Some(value.to_owned())]).collect(),
+        )))
+    }
+
+    #[rstest::rstest]
+    fn in_subquery(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select name from schema_name.customers where id in (select customer_id from schema_name.orders);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![names(vec!["alice", "carol"])]);
+    }
+
+    #[rstest::rstest]
+    fn not_in_subquery(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute(
+                "select name from schema_name.customers where id not in (select customer_id from schema_name.orders);",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![names(vec!["bob"])]);
+    }
+
+    #[rstest::rstest]
+    fn exists_with_records(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select name from schema_name.customers where exists (select * from schema_name.orders);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![names(vec!["alice", "bob", "carol"])]);
+    }
+
+    #[rstest::rstest]
+    fn exists_without_records(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute(
+                "select name from schema_name.customers \
+                where exists (select * from schema_name.orders where customer_id in (select 2));",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![names(vec![])]);
+    }
+
+    #[rstest::rstest]
+    fn in_subquery_with_many_columns(with_two_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_two_tables;
+        engine
+            .execute("select name from schema_name.customers where id in (select * from schema_name.orders);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .subquery_has_too_many_columns()
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod collations {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_tables(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute(r#"create table schema_name.exact (name varchar(10) collate "C");"#)
+            .expect("no system errors");
+        engine
+            .execute("create table schema_name.any_case (name varchar(10) collate case_insensitive);")
+            .expect("no system errors");
+        engine
+            .execute("create table schema_name.wanted (name varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.exact values ('Alice'), ('bob');")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.any_case values ('Alice'), ('bob');")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.wanted values ('ALICE'), ('bob');")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    fn names(values: Vec<&str>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![("name".to_owned(), PostgreSqlType::VarChar)],
+            values.into_iter().map(|value| vec![Some(value.to_owned())]).collect(),
+        )))
+    }
+
+    #[rstest::rstest]
+    fn byte_order_collation(with_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_tables;
+        engine
+            .execute("select name from schema_name.exact where name in (select name from schema_name.wanted);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![names(vec!["bob"])]);
+    }
+
+    #[rstest::rstest]
+    fn case_insensitive_collation(with_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_tables;
+        engine
+            .execute("select name from schema_name.any_case where name in (select name from schema_name.wanted);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![names(vec!["Alice", "bob"])]);
+    }
+
+    #[rstest::rstest]
+    fn negated_membership(with_tables: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_tables;
+        engine
+            .execute("select name from schema_name.exact where name not in (select name from schema_name.wanted);")
+            .expect("no system errors");
+        engine
+            .execute("select name from schema_name.any_case where name not in (select name from schema_name.wanted);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![names(vec!["Alice"]), names(vec![])]);
+    }
+}
+
+#[cfg(test)]
+mod scalar_subqueries {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1), (2);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn single_value_without_from(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select (select column_1 from schema_name.table_name limit 1) as first;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("first".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("1".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn single_value_for_every_record(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute(
+                "select column_1, (select column_1 from schema_name.table_name offset 1) \
+                from schema_name.table_name;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+                ("column_1".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("2".to_owned())],
+                vec![Some("2".to_owned()), Some("2".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn more_than_one_row(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select (select column_1 from schema_name.table_name);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .subquery_returned_more_than_one_row()
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn more_than_one_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select column_1, (select 1, 2) from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .subquery_has_too_many_columns()
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod explain_analyze {
+    use super::*;
+
+    fn plan(collector: &Collector) -> Vec<String> {
+        match collector.0.lock().expect("locked").pop() {
+            Some(Ok(QueryEvent::RecordsSelected((description, lines)))) => {
+                assert_eq!(description, vec![("QUERY PLAN".to_owned(), PostgreSqlType::VarChar)]);
+                lines.into_iter().filter_map(|mut line| line.remove(0)).collect()
+            }
+            other => panic!("unexpected query result {:?}", other),
+        }
+    }
+
+    #[rstest::rstest]
+    fn reports_actual_rows_of_table_scan(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1), (2), (3);")
+            .expect("no system errors");
+        engine
+            .execute("explain analyze select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        let plan = plan(&collector);
+        assert_eq!(plan.len(), 2);
+        assert!(plan[0].starts_with("Seq Scan on schema_name.table_name (actual time="));
+        assert!(plan[0].ends_with(" rows=3)"));
+        assert!(plan[1].starts_with("Execution Time: "));
+    }
+
+    #[rstest::rstest]
+    fn annotates_nested_plan_nodes(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1), (2), (3);")
+            .expect("no system errors");
+        engine
+            .execute("explain analyze select * from schema_name.table_name union all select 4 limit 2;")
+            .expect("no system errors");
+
+        let plan = plan(&collector);
+        assert_eq!(plan.len(), 5);
+        assert!(plan[0].starts_with("Limit ") && plan[0].ends_with(" rows=2)"));
+        assert!(plan[1].starts_with("  ->  SetOp UNION All ") && plan[1].ends_with(" rows=4)"));
+        assert!(plan[2].starts_with("    ->  Seq Scan on schema_name.table_name ") && plan[2].ends_with(" rows=3)"));
+        assert!(plan[3].starts_with("    ->  Result ") && plan[3].ends_with(" rows=1)"));
+    }
+
+    #[rstest::rstest]
+    fn failed_query_is_not_explained(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        collector.0.lock().expect("locked").clear();
+        engine
+            .execute("explain analyze select * from schema_name.non_existent;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.non_existent".to_owned())
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod max_result_rows {
+    use super::*;
+
+    #[rstest::rstest]
+    fn result_over_limit_is_truncated_with_notice(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) {
+        let (engine, collector) = sql_engine_with_schema;
+        let mut engine = engine.with_max_result_rows(2);
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1), (2), (3);")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(3)),
+            Err(QueryErrorBuilder::new().result_rows_truncated(2).build()),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+                vec![vec![Some("1".to_owned())], vec![Some("2".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn result_within_limit_is_sent_as_is(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (engine, collector) = sql_engine_with_schema;
+        let mut engine = engine.with_max_result_rows(3);
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1), (2), (3);")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(3)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("column_1".to_owned(), PostgreSqlType::SmallInt)],
+                vec![
+                    vec![Some("1".to_owned())],
+                    vec![Some("2".to_owned())],
+                    vec![Some("3".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod count {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn engine_with_rows() -> (QueryExecutor<InMemoryStorage>, Arc<Collector>, Arc<AtomicUsize>) {
+        let backend = InMemoryStorage::default();
+        let scanned_rows = backend.scanned_rows();
+        let storage = Arc::new(RwLock::new(FrontendStorage::new(backend).expect("no system errors")));
+        let collector = Arc::new(Collector(Mutex::new(vec![])));
+        let mut engine = QueryExecutor::new(storage, collector.clone());
+        engine.execute("create schema schema_name;").expect("no system errors");
+        engine
+            .execute("create table schema_name.table_name (column_1 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1), (2), (3);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        scanned_rows.store(0, Ordering::SeqCst);
+        (engine, collector, scanned_rows)
+    }
+
+    fn counted(name: &str, count: usize) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![(name.to_owned(), PostgreSqlType::BigInt)],
+            vec![vec![Some(count.to_string())]],
+        )))
+    }
+
+    #[test]
+    fn unfiltered_count_does_not_scan_table() {
+        let (mut engine, collector, scanned_rows) = engine_with_rows();
+        engine
+            .execute("select count(*) from schema_name.table_name;")
+            .expect("no system errors");
+        engine
+            .execute("delete from schema_name.table_name;")
+            .expect("no system errors");
+        scanned_rows.store(0, Ordering::SeqCst);
+        engine
+            .execute("select count(*) as total from schema_name.table_name;")
+            .expect("no system errors");
+
+        assert_eq!(scanned_rows.load(Ordering::SeqCst), 0);
+        collector.assert_content(vec![
+            counted("count", 3),
+            Ok(QueryEvent::RecordsDeleted(3)),
+            counted("total", 0),
+        ]);
+    }
+
+    #[test]
+    fn filtered_count_scans_table() {
+        let (mut engine, collector, scanned_rows) = engine_with_rows();
+        engine
+            .execute("select count(*) from schema_name.table_name where column_1 <> 2;")
+            .expect("no system errors");
+
+        assert_eq!(scanned_rows.load(Ordering::SeqCst), 3);
+        collector.assert_content(vec![counted("count", 2)]);
+    }
+
+    #[test]
+    fn count_of_non_existent_table() {
+        let (mut engine, collector, _scanned_rows) = engine_with_rows();
+        engine
+            .execute("select count(*) from schema_name.non_existent;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.non_existent".to_owned())
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod values_derived_table {
+    use super::*;
+
+    #[rstest::rstest]
+    fn select_aliased_columns(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select name, id from (values (1, 'a'), (2, 'b')) as t(id, name);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("name".to_owned(), PostgreSqlType::VarChar),
+                ("id".to_owned(), PostgreSqlType::Decimal),
+            ],
+            vec![
+                vec![Some("a".to_owned()), Some("1".to_owned())],
+                vec![Some("b".to_owned()), Some("2".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn columns_without_aliases_have_default_names(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select * from (values (1, 'a'), (2, 'b')) as t(id);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("id".to_owned(), PostgreSqlType::Decimal),
+                ("column2".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("a".to_owned())],
+                vec![Some("2".to_owned()), Some("b".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn filter_and_join_with_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (id smallint, price smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1, 10), (2, 20);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+
+        engine
+            .execute(
+                "select t.name, price from schema_name.table_name, (values (1, 'a'), (2, 'b')) as t(id, name) \
+                 where table_name.id = t.id;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("name".to_owned(), PostgreSqlType::VarChar),
+                ("price".to_owned(), PostgreSqlType::SmallInt),
+            ],
+            vec![
+                vec![Some("a".to_owned()), Some("10".to_owned())],
+                vec![Some("b".to_owned()), Some("20".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn derived_table_without_alias(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select * from (values (1, 'a'));")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new().subquery_without_alias().build())]);
+    }
+
+    #[rstest::rstest]
+    fn too_many_column_aliases(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select * from (values (1, 'a')) as t(id, name, price);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .too_many_column_aliases("t".to_owned(), 2, 3)
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn lists_of_different_length(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select * from (values (1, 'a'), (2)) as t;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .values_lists_length_mismatch()
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn values_of_different_types(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("select * from (values (1), ('a')) as t;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .set_operation_types_mismatch("VALUES".to_owned(), PostgreSqlType::Decimal, PostgreSqlType::VarChar)
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod subqueries_in_from {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (id smallint, price smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1, 10), (2, 20), (3, 30);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn filtered_subquery(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute(
+                "select sub.price from (select id, price from schema_name.table_name where id <> 2) as sub \
+                 where sub.id <> 3;",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("price".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("10".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn computed_columns_of_subquery(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select total from (select id, price * 2 as total from schema_name.table_name) as sub order by total desc;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("total".to_owned(), PostgreSqlType::Integer)],
+            vec![
+                vec![Some("60".to_owned())],
+                vec![Some("40".to_owned())],
+                vec![Some("20".to_owned())],
+            ],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn columns_renamed_by_alias(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select * from (select price from schema_name.table_name limit 1) as sub(cost);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("cost".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("10".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn unknown_column_of_subquery(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select price from (select id from schema_name.table_name) as sub;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .column_does_not_exist(vec!["price".to_owned()])
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn subquery_without_alias(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select * from (select id from schema_name.table_name);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new().subquery_without_alias().build())]);
+    }
+}
+
+#[cfg(test)]
+mod boolean_conditions {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (id smallint, active boolean);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1, true), (2, false), (3, true);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        (engine, collector)
+    }
+
+    fn ids(ids: Vec<&str>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![("id".to_owned(), PostgreSqlType::SmallInt)],
+            ids.into_iter().map(|id| vec![Some(id.to_owned())]).collect(),
+        )))
+    }
+
+    #[rstest::rstest]
+    fn where_true(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select id from schema_name.table_name where true;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec!["1", "2", "3"])]);
+    }
+
+    #[rstest::rstest]
+    fn where_false(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select id from schema_name.table_name where false;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec![])]);
+    }
+
+    #[rstest::rstest]
+    fn where_boolean_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select id from schema_name.table_name where active;")
+            .expect("no system errors");
+        engine
+            .execute("select id from schema_name.table_name where (table_name.active);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec!["1", "3"]), ids(vec!["1", "3"])]);
+    }
+
+    #[rstest::rstest]
+    fn where_not_boolean_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select id from schema_name.table_name where id;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .argument_must_be_boolean("WHERE".to_owned(), PostgreSqlType::SmallInt)
+            .build())]);
+    }
+}
+
+#[cfg(test)]
+mod logical_operators {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (id smallint, price smallint, active boolean);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1, 10, true), (2, 20, false), (3, 30, true);")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        (engine, collector)
+    }
+
+    fn ids(ids: Vec<&str>) -> QueryResult {
+        Ok(QueryEvent::RecordsSelected((
+            vec![("id".to_owned(), PostgreSqlType::SmallInt)],
+            ids.into_iter().map(|id| vec![Some(id.to_owned())]).collect(),
+        )))
+    }
+
+    #[rstest::rstest]
+    fn and_binds_tighter_than_or(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select id from schema_name.table_name where active and id = 3 or id = 2;")
+            .expect("no system errors");
+        engine
+            .execute("select id from schema_name.table_name where active and (id = 3 or id = 2);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec!["2", "3"]), ids(vec!["3"])]);
+    }
+
+    #[rstest::rstest]
+    fn not_binds_tighter_than_and(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select id from schema_name.table_name where not id = 1 and active;")
+            .expect("no system errors");
+        engine
+            .execute("select id from schema_name.table_name where not (id = 1 and active);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec!["3"]), ids(vec!["2", "3"])]);
+    }
+
+    #[rstest::rstest]
+    fn comparisons_combined(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("select id from schema_name.table_name where id = 1 and price > 5 or not id <= 2;")
+            .expect("no system errors");
+        engine
+            .execute("select id from schema_name.table_name where price >= 20 and price < 30;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec!["1", "3"]), ids(vec!["2"])]);
+    }
+
+    #[rstest::rstest]
+    fn comparisons_of_character_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("create table schema_name.names (id smallint, name varchar(10) collate case_insensitive);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.names values (1, '10'), (2, '9'), (3, 'b'), (4, 'B');")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        engine
+            .execute("select id from schema_name.names where name < '9';")
+            .expect("no system errors");
+        engine
+            .execute("select id from schema_name.names where name >= 'b';")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec!["1"]), ids(vec!["3", "4"])]);
+    }
+
+    #[rstest::rstest]
+    fn right_operand_is_not_evaluated_when_left_decides(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute(
+                "select id from schema_name.table_name where true or exists (select * from schema_name.non_existent);",
+            )
+            .expect("no system errors");
+        engine
+            .execute("select id from schema_name.table_name where false and exists (select * from schema_name.non_existent);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![ids(vec!["1", "2", "3"]), ids(vec![])]);
+    }
+
+    #[rstest::rstest]
+    fn right_operand_is_evaluated_when_left_does_not_decide(
+        with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute(
+                "select id from schema_name.table_name where false or exists (select * from schema_name.non_existent);",
+            )
+            .expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .table_does_not_exist("schema_name.non_existent".to_owned())
+            .build())]);
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    tests::{in_memory_backend_storage::InMemoryStorage, Collector},
+    QueryExecutor,
+};
+use protocol::{
+    results::{QueryErrorBuilder, QueryEvent},
+    sql_types::PostgreSqlType,
+};
+use std::sync::Arc;
+
+#[rstest::rstest]
+fn nextval_advances_sequence(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("create sequence seq;").expect("no system errors");
+    engine.execute("select nextval('seq');").expect("no system errors");
+    engine.execute("select nextval('seq');").expect("no system errors");
+    engine.execute("select currval('seq');").expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SequenceCreated),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("nextval".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("1".to_owned())]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("nextval".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("2".to_owned())]],
+        ))),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("currval".to_owned(), PostgreSqlType::Decimal)],
+            vec![vec![Some("2".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn create_existing_sequence(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("create sequence seq;").expect("no system errors");
+    engine.execute("create sequence seq;").expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SequenceCreated),
+        Err(QueryErrorBuilder::new()
+            .sequence_already_exists("seq".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn nextval_of_non_existent_sequence(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("select nextval('seq');").expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .sequence_does_not_exist("seq".to_owned())
+        .build())]);
+}
+
+#[rstest::rstest]
+fn currval_before_nextval(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine.execute("create sequence seq;").expect("no system errors");
+    engine.execute("select currval('seq');").expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SequenceCreated),
+        Err(QueryErrorBuilder::new()
+            .current_value_is_not_defined("seq".to_owned())
+            .build()),
+    ]);
+}
+
+#[rstest::rstest]
+fn nextval_in_insert(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine.execute("create sequence seq;").expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (id integer);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (nextval('seq')), (nextval('seq') * 10);")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::SequenceCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(2)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("id".to_owned(), PostgreSqlType::Integer)],
+            vec![vec![Some("1".to_owned())], vec![Some("20".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn nextval_default_is_evaluated_for_every_row(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine.execute("create sequence seq;").expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (id integer default nextval('seq'), name varchar(10));")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name (name) values ('a'), ('b'), ('c');")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::SequenceCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(3)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("id".to_owned(), PostgreSqlType::Integer),
+                ("name".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("1".to_owned()), Some("a".to_owned())],
+                vec![Some("2".to_owned()), Some("b".to_owned())],
+                vec![Some("3".to_owned()), Some("c".to_owned())],
+            ],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn default_keyword_and_omitted_values_use_column_default(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine.execute("create sequence seq;").expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (name varchar(10), id integer default nextval('seq') * 10);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values ('a', default), ('b', 5), ('c');")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::SequenceCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(3)),
+        Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("name".to_owned(), PostgreSqlType::VarChar),
+                ("id".to_owned(), PostgreSqlType::Integer),
+            ],
+            vec![
+                vec![Some("a".to_owned()), Some("10".to_owned())],
+                vec![Some("b".to_owned()), Some("5".to_owned())],
+                vec![Some("c".to_owned()), Some("20".to_owned())],
+            ],
+        ))),
+    ]);
+}
@@ -0,0 +1,305 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use protocol::results::QueryEvent;
+
+#[rstest::rstest]
+fn set_log_min_messages(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("set log_min_messages = 'debug';")
+        .expect("no system errors");
+    assert_eq!(engine.setting("log_min_messages"), Some("debug"));
+
+    engine
+        .execute("set log_min_messages = 'WARNING';")
+        .expect("no system errors");
+    assert_eq!(engine.setting("log_min_messages"), Some("WARNING"));
+
+    collector.assert_content(vec![Ok(QueryEvent::VariableSet), Ok(QueryEvent::VariableSet)]);
+}
+
+#[rstest::rstest]
+fn set_unknown_log_level(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("set log_min_messages = 'verbose';")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .invalid_parameter_value("log_min_messages".to_owned(), "verbose".to_owned())
+        .build())]);
+}
+
+#[rstest::rstest]
+fn set_other_variable(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("set client_min_messages = 'verbose';")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::VariableSet)]);
+}
+
+#[rstest::rstest]
+fn set_variable_is_kept_in_session(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("set statement_timeout = 500;")
+        .expect("no system errors");
+
+    assert_eq!(engine.setting("STATEMENT_TIMEOUT"), Some("500"));
+    collector.assert_content(vec![Ok(QueryEvent::VariableSet)]);
+}
+
+#[cfg(test)]
+mod time_zone {
+    use super::*;
+
+    fn time_zone_set(time_zone: &str) -> Vec<QueryResult> {
+        vec![
+            Ok(QueryEvent::ParameterStatus("TimeZone".to_owned(), time_zone.to_owned())),
+            Ok(QueryEvent::VariableSet),
+        ]
+    }
+
+    #[rstest::rstest]
+    fn set_time_zone(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("set time zone 'Europe/Berlin';")
+            .expect("no system errors");
+
+        assert_eq!(engine.setting("timezone"), Some("Europe/Berlin"));
+        collector.assert_content(time_zone_set("Europe/Berlin"));
+    }
+
+    #[rstest::rstest]
+    fn utc_aliases(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("SET TIME ZONE 'utc';").expect("no system errors");
+        engine
+            .execute("set session time zone local;")
+            .expect("no system errors");
+        engine.execute("set timezone = 'GMT';").expect("no system errors");
+
+        assert_eq!(engine.setting("timezone"), Some("UTC"));
+        collector.assert_content(
+            vec![time_zone_set("UTC"), time_zone_set("UTC"), time_zone_set("UTC")]
+                .into_iter()
+                .flatten()
+                .collect(),
+        );
+    }
+
+    #[rstest::rstest]
+    fn offset_in_hours(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("set time zone -3.5;").expect("no system errors");
+
+        assert_eq!(engine.setting("timezone"), Some("-3.5"));
+        collector.assert_content(time_zone_set("-3.5"));
+    }
+
+    #[rstest::rstest]
+    fn invalid_time_zone(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("set time zone 'Mars/Olympus';")
+            .expect("no system errors");
+        engine.execute("set time zone 20;").expect("no system errors");
+
+        assert_eq!(engine.setting("timezone"), None);
+        collector.assert_content(vec![
+            Err(QueryErrorBuilder::new()
+                .invalid_parameter_value("timezone".to_owned(), "Mars/Olympus".to_owned())
+                .build()),
+            Err(QueryErrorBuilder::new()
+                .invalid_parameter_value("timezone".to_owned(), "20".to_owned())
+                .build()),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod reset {
+    use super::*;
+
+    #[rstest::rstest]
+    fn reset_variable(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("set statement_timeout = 500;")
+            .expect("no system errors");
+        engine.execute("reset statement_timeout;").expect("no system errors");
+
+        assert_eq!(engine.setting("statement_timeout"), None);
+        collector.assert_content(vec![Ok(QueryEvent::VariableSet), Ok(QueryEvent::VariableReset)]);
+    }
+
+    #[rstest::rstest]
+    fn reset_restores_startup_option(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (engine, collector) = sql_engine;
+        let mut engine = engine.with_startup_options("-c statement_timeout=1000");
+        engine
+            .execute("set statement_timeout = 500;")
+            .expect("no system errors");
+        engine.execute("RESET STATEMENT_TIMEOUT;").expect("no system errors");
+
+        assert_eq!(engine.setting("statement_timeout"), Some("1000"));
+        collector.assert_content(vec![Ok(QueryEvent::VariableSet), Ok(QueryEvent::VariableReset)]);
+    }
+
+    #[rstest::rstest]
+    fn reset_all(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (engine, collector) = sql_engine;
+        let mut engine = engine.with_startup_options("-c search_path=schema_name");
+        engine
+            .execute("set statement_timeout = 500;")
+            .expect("no system errors");
+        engine
+            .execute("set search_path = other_schema;")
+            .expect("no system errors");
+        engine.execute("set time zone 'Asia/Tokyo';").expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        engine.execute("reset all;").expect("no system errors");
+
+        assert_eq!(engine.setting("statement_timeout"), None);
+        assert_eq!(engine.setting("search_path"), Some("schema_name"));
+        assert_eq!(engine.setting("timezone"), None);
+        collector.assert_content(vec![
+            Ok(QueryEvent::ParameterStatus("TimeZone".to_owned(), "UTC".to_owned())),
+            Ok(QueryEvent::VariableReset),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn reset_time_zone(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("set time zone 'Asia/Tokyo';").expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        engine.execute("reset time zone;").expect("no system errors");
+
+        assert_eq!(engine.setting("timezone"), None);
+        collector.assert_content(vec![
+            Ok(QueryEvent::ParameterStatus("TimeZone".to_owned(), "UTC".to_owned())),
+            Ok(QueryEvent::VariableReset),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod show {
+    use super::*;
+    use protocol::sql_types::PostgreSqlType;
+
+    #[rstest::rstest]
+    fn show_set_variable(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("set statement_timeout = 500;")
+            .expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        engine.execute("show statement_timeout;").expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("statement_timeout".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("500".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn show_server_default(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("show TimeZone;").expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![("timezone".to_owned(), PostgreSqlType::VarChar)],
+            vec![vec![Some("UTC".to_owned())]],
+        )))]);
+    }
+
+    #[rstest::rstest]
+    fn show_unknown_variable(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine.execute("show statement_timeout;").expect("no system errors");
+
+        collector.assert_content(vec![Err(QueryErrorBuilder::new()
+            .unrecognized_configuration_parameter("statement_timeout".to_owned())
+            .build())]);
+    }
+
+    #[rstest::rstest]
+    fn show_all(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine;
+        engine
+            .execute("set statement_timeout = 500;")
+            .expect("no system errors");
+        engine.execute("set time zone 'Asia/Tokyo';").expect("no system errors");
+        collector.0.lock().expect("locked").clear();
+        engine.execute("show all;").expect("no system errors");
+
+        collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+            vec![
+                ("name".to_owned(), PostgreSqlType::VarChar),
+                ("setting".to_owned(), PostgreSqlType::VarChar),
+            ],
+            vec![
+                vec![Some("autocommit".to_owned()), Some("on".to_owned())],
+                vec![Some("client_encoding".to_owned()), Some("UTF8".to_owned())],
+                vec![Some("datestyle".to_owned()), Some("ISO".to_owned())],
+                vec![Some("log_min_messages".to_owned()), Some("warning".to_owned())],
+                vec![Some("statement_timeout".to_owned()), Some("500".to_owned())],
+                vec![Some("timezone".to_owned()), Some("Asia/Tokyo".to_owned())],
+            ],
+        )))]);
+    }
+}
+
+#[cfg(test)]
+mod startup_options {
+    use super::*;
+
+    #[rstest::rstest]
+    fn settings_are_applied(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (engine, collector) = sql_engine;
+        let engine = engine
+            .with_startup_options("-c statement_timeout=1000 --search-path=schema_name -capplication_name=my\\ app");
+
+        assert_eq!(engine.setting("statement_timeout"), Some("1000"));
+        assert_eq!(engine.setting("search_path"), Some("schema_name"));
+        assert_eq!(engine.setting("application_name"), Some("my app"));
+        collector.assert_content(vec![]);
+    }
+
+    #[rstest::rstest]
+    fn malformed_options_are_ignored_with_notice(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (engine, collector) = sql_engine;
+        let engine = engine.with_startup_options("-x -c statement_timeout -c log_min_messages=verbose -c geqo=off");
+
+        assert_eq!(engine.setting("statement_timeout"), None);
+        assert_eq!(engine.setting("log_min_messages"), None);
+        assert_eq!(engine.setting("geqo"), Some("off"));
+        collector.assert_content(vec![
+            Err(QueryErrorBuilder::new().invalid_startup_option("-x".to_owned()).build()),
+            Err(QueryErrorBuilder::new()
+                .invalid_startup_option("statement_timeout".to_owned())
+                .build()),
+            Err(QueryErrorBuilder::new()
+                .invalid_startup_option("log_min_messages=verbose".to_owned())
+                .build()),
+        ]);
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::SessionRegistry;
+use protocol::sql_types::PostgreSqlType;
+
+fn connection(registry: &SessionRegistry, application_name: &str) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+    let collector = Arc::new(Collector(Mutex::new(vec![])));
+    let engine =
+        QueryExecutor::new(in_memory_storage(), collector.clone()).with_session_registry(registry, application_name);
+    (engine, collector)
+}
+
+fn activity(rows: Vec<(&str, &str, &str)>) -> QueryResult {
+    Ok(QueryEvent::RecordsSelected((
+        vec![
+            ("pid".to_owned(), PostgreSqlType::Integer),
+            ("application_name".to_owned(), PostgreSqlType::VarChar),
+            ("query".to_owned(), PostgreSqlType::VarChar),
+        ],
+        rows.into_iter()
+            .map(|(pid, application_name, query)| {
+                vec![
+                    Some(pid.to_owned()),
+                    Some(application_name.to_owned()),
+                    Some(query.to_owned()),
+                ]
+            })
+            .collect(),
+    )))
+}
+
+#[test]
+fn lists_all_connected_sessions() {
+    let registry = SessionRegistry::new();
+    let (mut first, first_collector) = connection(&registry, "first_app");
+    let (mut second, _second_collector) = connection(&registry, "second_app");
+
+    second.execute("create schema schema_name;").expect("no system errors");
+    first
+        .execute("select * from pg_stat_activity;")
+        .expect("no system errors");
+
+    first_collector.assert_content(vec![activity(vec![
+        ("1", "first_app", "select * from pg_stat_activity;"),
+        ("2", "second_app", "create schema schema_name;"),
+    ])]);
+}
+
+#[test]
+fn disconnected_session_is_not_listed() {
+    let registry = SessionRegistry::new();
+    let (mut first, first_collector) = connection(&registry, "first_app");
+    let second = connection(&registry, "second_app");
+    drop(second);
+
+    first
+        .execute("select pid, query from pg_catalog.pg_stat_activity;")
+        .expect("no system errors");
+
+    first_collector.assert_content(vec![Ok(QueryEvent::RecordsSelected((
+        vec![
+            ("pid".to_owned(), PostgreSqlType::Integer),
+            ("query".to_owned(), PostgreSqlType::VarChar),
+        ],
+        vec![vec![
+            Some("1".to_owned()),
+            Some("select pid, query from pg_catalog.pg_stat_activity;".to_owned()),
+        ]],
+    )))]);
+}
+
+#[test]
+fn non_existent_column() {
+    let registry = SessionRegistry::new();
+    let (mut engine, collector) = connection(&registry, "app");
+
+    engine
+        .execute("select pid, state from pg_stat_activity;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Err(QueryErrorBuilder::new()
+        .column_does_not_exist(vec!["state".to_owned()])
+        .build())]);
+}
@@ -74,6 +74,25 @@ fn create_same_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Ar
     ]);
 }
 
+#[rstest::rstest]
+fn create_table_with_duplicate_column_names(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_name smallint, column_name integer);")
+        .expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (column_name smallint);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .duplicate_column("column_name".to_owned())
+            .build()),
+        Ok(QueryEvent::TableCreated),
+    ]);
+}
+
 #[rstest::rstest]
 fn drop_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
     let (mut engine, collector) = sql_engine_with_schema;
@@ -110,6 +129,104 @@ fn drop_non_existent_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorag
     ]);
 }
 
+#[rstest::rstest]
+fn drop_non_existent_table_if_exists(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("drop table if exists schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Err(QueryErrorBuilder::new()
+            .table_does_not_exist_skipping("schema_name.table_name".to_owned())
+            .build()),
+        Ok(QueryEvent::TableDropped),
+    ]);
+}
+
+#[rstest::rstest]
+fn drop_existing_and_non_existent_tables_if_exist(
+    sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_name smallint);")
+        .expect("no system errors");
+    engine
+        .execute("drop table if exists schema_name.table_name, schema_name.non_existent;")
+        .expect("no system errors");
+    engine
+        .execute("create table schema_name.table_name (column_name smallint);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Err(QueryErrorBuilder::new()
+            .table_does_not_exist_skipping("schema_name.non_existent".to_owned())
+            .build()),
+        Ok(QueryEvent::TableDropped),
+        Ok(QueryEvent::TableCreated),
+    ]);
+}
+
+#[rstest::rstest]
+fn drop_table_from_non_existent_schema_if_exists(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine;
+    engine
+        .execute("drop table if exists schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Err(QueryErrorBuilder::new()
+            .schema_does_not_exist_skipping("schema_name".to_owned())
+            .build()),
+        Ok(QueryEvent::TableDropped),
+    ]);
+}
+
+#[rstest::rstest]
+fn create_existing_table_if_not_exists(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (column_name smallint);")
+        .expect("no system errors");
+    engine
+        .execute("insert into schema_name.table_name values (1);")
+        .expect("no system errors");
+    engine
+        .execute("create table if not exists schema_name.table_name (other_column varchar(10));")
+        .expect("no system errors");
+    engine
+        .execute("select * from schema_name.table_name;")
+        .expect("no system errors");
+
+    collector.assert_content(vec![
+        Ok(QueryEvent::SchemaCreated),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsInserted(1)),
+        Err(QueryErrorBuilder::new()
+            .table_already_exists_skipping("schema_name.table_name".to_owned())
+            .build()),
+        Ok(QueryEvent::TableCreated),
+        Ok(QueryEvent::RecordsSelected((
+            vec![("column_name".to_owned(), PostgreSqlType::SmallInt)],
+            vec![vec![Some("1".to_owned())]],
+        ))),
+    ]);
+}
+
+#[rstest::rstest]
+fn create_non_existent_table_if_not_exists(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table if not exists schema_name.table_name (column_name smallint);")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::SchemaCreated), Ok(QueryEvent::TableCreated)]);
+}
+
 #[cfg(test)]
 mod different_types {
     use super::*;
@@ -175,3 +292,345 @@ mod different_types {
         collector.assert_content(vec![Ok(QueryEvent::SchemaCreated), Ok(QueryEvent::TableCreated)]);
     }
 }
+
+#[cfg(test)]
+mod rename_column {
+    use super::*;
+    use protocol::sql_types::PostgreSqlType;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (col1 smallint, col2 smallint);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1, 2);")
+            .expect("no system errors");
+
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn rename_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("alter table schema_name.table_name rename column col1 to new_col;")
+            .expect("no system errors");
+        engine
+            .execute("select new_col, col2 from schema_name.table_name;")
+            .expect("no system errors");
+        engine
+            .execute("select col1 from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::TableAltered),
+            Ok(QueryEvent::RecordsSelected((
+                vec![
+                    ("new_col".to_owned(), PostgreSqlType::SmallInt),
+                    ("col2".to_owned(), PostgreSqlType::SmallInt),
+                ],
+                vec![vec![Some("1".to_owned()), Some("2".to_owned())]],
+            ))),
+            Err(QueryErrorBuilder::new()
+                .column_does_not_exist(vec!["col1".to_owned()])
+                .build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn rename_to_existing_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("alter table schema_name.table_name rename column col1 to col2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Err(QueryErrorBuilder::new()
+                .column_already_exists("col2".to_owned())
+                .build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn rename_non_existent_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("alter table schema_name.table_name rename column col3 to col4;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Err(QueryErrorBuilder::new()
+                .column_does_not_exist(vec!["col3".to_owned()])
+                .build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn rename_column_in_non_existent_table(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("alter table schema_name.table_name rename column col1 to col2;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Err(QueryErrorBuilder::new()
+                .table_does_not_exist("schema_name.table_name".to_owned())
+                .build()),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod alter_column_type {
+    use super::*;
+    use protocol::sql_types::PostgreSqlType;
+
+    #[rstest::fixture]
+    fn with_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("create table schema_name.table_name (col1 smallint, col2 varchar(10));")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values (1, 'abc');")
+            .expect("no system errors");
+
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn widen_column_type(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("alter table schema_name.table_name alter column col1 type bigint;")
+            .expect("no system errors");
+        engine
+            .execute("select col1, col2 from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::TableAltered),
+            Ok(QueryEvent::RecordsSelected((
+                vec![
+                    ("col1".to_owned(), PostgreSqlType::BigInt),
+                    ("col2".to_owned(), PostgreSqlType::VarChar),
+                ],
+                vec![vec![Some("1".to_owned()), Some("abc".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn set_data_type(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("alter table schema_name.table_name alter col1 set data type integer;")
+            .expect("no system errors");
+        engine
+            .execute("select col1 from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::TableAltered),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col1".to_owned(), PostgreSqlType::Integer)],
+                vec![vec![Some("1".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn values_do_not_fit_new_type(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("alter table schema_name.table_name alter column col2 type integer;")
+            .expect("no system errors");
+        engine
+            .execute("select col2 from schema_name.table_name;")
+            .expect("no system errors");
+
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("abc", PostgreSqlType::Integer, "col2".to_owned(), 1);
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Err(builder.build()),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col2".to_owned(), PostgreSqlType::VarChar)],
+                vec![vec![Some("abc".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn alter_type_of_non_existent_column(with_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = with_table;
+        engine
+            .execute("alter table schema_name.table_name alter column col3 type bigint;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Err(QueryErrorBuilder::new()
+                .column_does_not_exist(vec!["col3".to_owned()])
+                .build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn alter_column_type_in_non_existent_table(
+        sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>),
+    ) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute("alter table schema_name.table_name alter column col1 type bigint;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Err(QueryErrorBuilder::new()
+                .table_does_not_exist("schema_name.table_name".to_owned())
+                .build()),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod collation {
+    use super::*;
+    use protocol::sql_types::PostgreSqlType;
+
+    #[rstest::rstest]
+    fn unknown_collation(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute(r#"create table schema_name.table_name (name varchar(10) collate "en_US");"#)
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Err(QueryErrorBuilder::new().undefined_collation("en_US".to_owned()).build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn collation_of_non_character_column(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute(r#"create table schema_name.table_name (id integer collate "C");"#)
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Err(QueryErrorBuilder::new()
+                .collations_not_supported(PostgreSqlType::Integer)
+                .build()),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod identifier_length {
+    use super::*;
+    use crate::IdentifierLength;
+    use protocol::sql_types::PostgreSqlType;
+
+    const LONG_TABLE_NAME: &str = "table_name_that_is_longer_than_sixty_three_bytes_allowed_by_postgresql";
+    const TRUNCATED_TABLE_NAME: &str = "table_name_that_is_longer_than_sixty_three_bytes_allowed_by_pos";
+
+    #[rstest::rstest]
+    fn long_table_name_is_truncated(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        engine
+            .execute(format!("create table schema_name.{} (column_name smallint);", LONG_TABLE_NAME).as_str())
+            .expect("no system errors");
+        engine
+            .execute(format!("insert into schema_name.{} values (1);", LONG_TABLE_NAME).as_str())
+            .expect("no system errors");
+        engine
+            .execute(format!("select * from schema_name.{};", TRUNCATED_TABLE_NAME).as_str())
+            .expect("no system errors");
+
+        let truncated = || {
+            Err(QueryErrorBuilder::new()
+                .identifier_truncated(LONG_TABLE_NAME.to_owned(), TRUNCATED_TABLE_NAME.to_owned())
+                .build())
+        };
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            truncated(),
+            Ok(QueryEvent::TableCreated),
+            truncated(),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("column_name".to_owned(), PostgreSqlType::SmallInt)],
+                vec![vec![Some("1".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn long_column_name_is_rejected(sql_engine: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (engine, collector) = sql_engine;
+        let mut engine = engine.with_identifier_length(IdentifierLength::Reject(12));
+        engine.execute("create schema schema_name;").expect("no system errors");
+        engine
+            .execute("create table schema_name.table_name (long_column_name smallint);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Err(QueryErrorBuilder::new()
+                .name_too_long("long_column_name".to_owned(), 12)
+                .build()),
+        ]);
+    }
+}
+
+#[rstest::rstest]
+fn column_default_is_kept_as_text(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+    let (mut engine, collector) = sql_engine_with_schema;
+    engine
+        .execute("create table schema_name.table_name (id integer default 1 + 2, name varchar(10));")
+        .expect("no system errors");
+
+    collector.assert_content(vec![Ok(QueryEvent::SchemaCreated), Ok(QueryEvent::TableCreated)]);
+    let storage = engine.storage.read().unwrap();
+    assert_eq!(
+        storage
+            .column_default("schema_name", "table_name", "id")
+            .expect("no system errors"),
+        Ok(Some("1 + 2".to_owned()))
+    );
+    assert_eq!(
+        storage
+            .column_default("schema_name", "table_name", "name")
+            .expect("no system errors"),
+        Ok(None)
+    );
+}
@@ -60,6 +60,45 @@ mod insert {
         ]);
     }
 
+    #[rstest::rstest]
+    fn negative_out_of_range(int_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = int_table;
+        let mut builder = QueryErrorBuilder::new();
+        builder.out_of_range(PostgreSqlType::SmallInt, "col".to_string(), 1);
+
+        engine
+            .execute("insert into schema_name.table_name values (-32769);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn negative_lower_bound(int_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = int_table;
+
+        engine
+            .execute("insert into schema_name.table_name values (-32768);")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::SmallInt)],
+                vec![vec![Some("-32768".to_owned())]],
+            ))),
+        ]);
+    }
+
     #[rstest::rstest]
     fn type_mismatch(int_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
         let (mut engine, collector) = int_table;
@@ -77,6 +116,46 @@ mod insert {
         ]);
     }
 
+    #[rstest::rstest]
+    fn type_mismatch_reports_full_value(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("abc", PostgreSqlType::Integer, "col".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col integer);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('abc');")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn type_mismatch_in_cast(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("not a bool", PostgreSqlType::Bool, "col".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col boolean);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('not a bool'::boolean);")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
     #[rstest::rstest]
     fn value_too_long(str_table: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
         let (mut engine, collector) = str_table;
@@ -92,6 +171,322 @@ mod insert {
             Err(builder.build()),
         ]);
     }
+
+    #[rstest::rstest]
+    fn uuid_is_stored_lowercase(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+
+        engine
+            .execute("create table schema_name.table_name(col uuid);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11');")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::Uuid)],
+                vec![vec![Some("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn bytea_round_trip(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+
+        engine
+            .execute("create table schema_name.table_name(col bytea);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('\\xDEADBEEF');")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::Bytea)],
+                vec![vec![Some("\\xdeadbeef".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn integer_array_round_trip(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+
+        engine
+            .execute("create table schema_name.table_name(col integer[]);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('{1, -2,3}'), ('{}');")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(2)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::IntegerArray)],
+                vec![vec![Some("{1,-2,3}".to_owned())], vec![Some("{}".to_owned())]],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn array_element_out_of_range(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.out_of_range(PostgreSqlType::SmallInt, "col[2]".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col smallint[]);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('{1,32768}');")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn malformed_uuid(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("not-a-uuid", PostgreSqlType::Uuid, "col".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col uuid);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('not-a-uuid');")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn time_round_trip(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+
+        engine
+            .execute("create table schema_name.table_name(col time);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('08:30:00'), ('23:59:59.123450');")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(2)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::Time)],
+                vec![
+                    vec![Some("08:30:00".to_owned())],
+                    vec![Some("23:59:59.12345".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn invalid_time(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("25:00:00", PostgreSqlType::Time, "col".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col time);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('25:00:00');")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn interval_round_trip(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+
+        engine
+            .execute("create table schema_name.table_name(col interval);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('1 day'), ('2 hours 30 minutes'), ('14 mons');")
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(3)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::Interval)],
+                vec![
+                    vec![Some("1 day".to_owned())],
+                    vec![Some("02:30:00".to_owned())],
+                    vec![Some("1 year 2 mons".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn malformed_interval(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("3 fortnights", PostgreSqlType::Interval, "col".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col interval);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('3 fortnights');")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn float_special_values_round_trip(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+
+        engine
+            .execute("create table schema_name.table_name(col_1 real, col_2 double precision);")
+            .expect("no system errors");
+        engine
+            .execute(
+                "insert into schema_name.table_name values \
+                ('NaN', 'nan'), ('Infinity', 'inf'), ('-Infinity', '-INFINITY'), (1.5, -2.25);",
+            )
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(4)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![
+                    ("col_1".to_owned(), PostgreSqlType::Real),
+                    ("col_2".to_owned(), PostgreSqlType::DoublePrecision),
+                ],
+                vec![
+                    vec![Some("NaN".to_owned()), Some("NaN".to_owned())],
+                    vec![Some("Infinity".to_owned()), Some("Infinity".to_owned())],
+                    vec![Some("-Infinity".to_owned()), Some("-Infinity".to_owned())],
+                    vec![Some("1.5".to_owned()), Some("-2.25".to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn non_numeric_float(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("infinite", PostgreSqlType::DoublePrecision, "col".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col double precision);")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('infinite');")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn json_round_trip(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+
+        engine
+            .execute("create table schema_name.table_name(col json);")
+            .expect("no system errors");
+        engine
+            .execute(r#"insert into schema_name.table_name values ('{"b": 1,  "a": [true, null]}'), ('[1, "two"]');"#)
+            .expect("no system errors");
+        engine
+            .execute("select * from schema_name.table_name;")
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(2)),
+            Ok(QueryEvent::RecordsSelected((
+                vec![("col".to_owned(), PostgreSqlType::Json)],
+                vec![
+                    vec![Some(r#"{"b": 1,  "a": [true, null]}"#.to_owned())],
+                    vec![Some(r#"[1, "two"]"#.to_owned())],
+                ],
+            ))),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn malformed_json(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, Arc<Collector>)) {
+        let (mut engine, collector) = sql_engine_with_schema;
+        let mut builder = QueryErrorBuilder::new();
+        builder.type_mismatch("{\"a\": }", PostgreSqlType::Json, "col".to_string(), 1);
+
+        engine
+            .execute("create table schema_name.table_name(col json);")
+            .expect("no system errors");
+        engine
+            .execute(r#"insert into schema_name.table_name values ('{"a": }');"#)
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Err(builder.build()),
+        ]);
+    }
 }
 
 #[cfg(test)]
@@ -160,3 +555,59 @@ mod update {
         ]);
     }
 }
+
+#[cfg(test)]
+mod varchar_without_length {
+    use super::*;
+    use storage::backend::StorageConfig;
+
+    fn engine_with_config(config: StorageConfig) -> (QueryExecutor<InMemoryStorage>, Arc<Collector>) {
+        let storage = Arc::new(RwLock::new(
+            FrontendStorage::new_with_config(InMemoryStorage::default(), config).expect("no system errors"),
+        ));
+        let collector = Arc::new(Collector(Mutex::new(vec![])));
+        let mut engine = QueryExecutor::new(storage, collector.clone());
+        engine.execute("create schema schema_name;").expect("no system errors");
+        engine
+            .execute("create table schema_name.table_name (col varchar);")
+            .expect("no system errors");
+        (engine, collector)
+    }
+
+    #[rstest::rstest]
+    fn accepts_values_of_any_length_by_default() {
+        let (mut engine, collector) = engine_with_config(StorageConfig::default());
+        engine
+            .execute(&format!(
+                "insert into schema_name.table_name values ('{}');",
+                "a".repeat(1000)
+            ))
+            .expect("no system errors");
+
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+        ]);
+    }
+
+    #[rstest::rstest]
+    fn configured_default_length() {
+        let (mut engine, collector) = engine_with_config(StorageConfig::default().with_default_varchar_length(Some(5)));
+        engine
+            .execute("insert into schema_name.table_name values ('12345');")
+            .expect("no system errors");
+        engine
+            .execute("insert into schema_name.table_name values ('123456');")
+            .expect("no system errors");
+
+        let mut builder = QueryErrorBuilder::new();
+        builder.string_length_mismatch(PostgreSqlType::VarChar, 5, "col".to_string(), 1);
+        collector.assert_content(vec![
+            Ok(QueryEvent::SchemaCreated),
+            Ok(QueryEvent::TableCreated),
+            Ok(QueryEvent::RecordsInserted(1)),
+            Err(builder.build()),
+        ]);
+    }
+}
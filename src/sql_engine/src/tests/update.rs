@@ -44,12 +44,12 @@ fn update_all_records(sql_engine_with_schema: (QueryExecutor<InMemoryStorage>, A
         Ok(QueryEvent::RecordsInserted(1)),
         Ok(QueryEvent::RecordsSelected((
             vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
-            vec![vec!["123".to_owned()], vec!["456".to_owned()]],
+            vec![vec![Some("123".to_owned())], vec![Some("456".to_owned())]],
         ))),
         Ok(QueryEvent::RecordsUpdated(2)),
         Ok(QueryEvent::RecordsSelected((
             vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
-            vec![vec!["789".to_owned()], vec!["789".to_owned()]],
+            vec![vec![Some("789".to_owned())], vec![Some("789".to_owned())]],
         ))),
     ]);
 }
@@ -87,8 +87,8 @@ fn update_single_column_of_all_records(sql_engine_with_schema: (QueryExecutor<In
                 ("col2".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["123".to_owned(), "789".to_owned()],
-                vec!["456".to_owned(), "789".to_owned()],
+                vec![Some("123".to_owned()), Some("789".to_owned())],
+                vec![Some("456".to_owned()), Some("789".to_owned())],
             ],
         ))),
         Ok(QueryEvent::RecordsUpdated(2)),
@@ -98,8 +98,8 @@ fn update_single_column_of_all_records(sql_engine_with_schema: (QueryExecutor<In
                 ("col2".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["123".to_owned(), "357".to_owned()],
-                vec!["456".to_owned(), "357".to_owned()],
+                vec![Some("123".to_owned()), Some("357".to_owned())],
+                vec![Some("456".to_owned()), Some("357".to_owned())],
             ],
         ))),
     ]);
@@ -139,8 +139,8 @@ fn update_multiple_columns_of_all_records(sql_engine_with_schema: (QueryExecutor
                 ("col3".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["111".to_owned(), "222".to_owned(), "333".to_owned()],
-                vec!["444".to_owned(), "555".to_owned(), "666".to_owned()],
+                vec![Some("111".to_owned()), Some("222".to_owned()), Some("333".to_owned())],
+                vec![Some("444".to_owned()), Some("555".to_owned()), Some("666".to_owned())],
             ],
         ))),
         Ok(QueryEvent::RecordsUpdated(2)),
@@ -151,8 +151,8 @@ fn update_multiple_columns_of_all_records(sql_engine_with_schema: (QueryExecutor
                 ("col3".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["999".to_owned(), "222".to_owned(), "777".to_owned()],
-                vec!["999".to_owned(), "555".to_owned(), "777".to_owned()],
+                vec![Some("999".to_owned()), Some("222".to_owned()), Some("777".to_owned())],
+                vec![Some("999".to_owned()), Some("555".to_owned()), Some("777".to_owned())],
             ],
         ))),
     ]);
@@ -188,9 +188,9 @@ fn update_all_records_in_multiple_columns(sql_engine_with_schema: (QueryExecutor
                 ("column_3".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["1".to_owned(), "2".to_owned(), "3".to_owned()],
-                vec!["4".to_owned(), "5".to_owned(), "6".to_owned()],
-                vec!["7".to_owned(), "8".to_owned(), "9".to_owned()],
+                vec![Some("1".to_owned()), Some("2".to_owned()), Some("3".to_owned())],
+                vec![Some("4".to_owned()), Some("5".to_owned()), Some("6".to_owned())],
+                vec![Some("7".to_owned()), Some("8".to_owned()), Some("9".to_owned())],
             ],
         ))),
         Ok(QueryEvent::RecordsUpdated(3)),
@@ -201,9 +201,9 @@ fn update_all_records_in_multiple_columns(sql_engine_with_schema: (QueryExecutor
                 ("column_3".to_owned(), PostgreSqlType::SmallInt),
             ],
             vec![
-                vec!["10".to_owned(), "-20".to_owned(), "30".to_owned()],
-                vec!["10".to_owned(), "-20".to_owned(), "30".to_owned()],
-                vec!["10".to_owned(), "-20".to_owned(), "30".to_owned()],
+                vec![Some("10".to_owned()), Some("-20".to_owned()), Some("30".to_owned())],
+                vec![Some("10".to_owned()), Some("-20".to_owned()), Some("30".to_owned())],
+                vec![Some("10".to_owned()), Some("-20".to_owned()), Some("30".to_owned())],
             ],
         ))),
     ]);
@@ -246,7 +246,7 @@ fn update_non_existent_columns_of_records(sql_engine_with_schema: (QueryExecutor
         Ok(QueryEvent::RecordsInserted(1)),
         Ok(QueryEvent::RecordsSelected((
             vec![("column_test".to_owned(), PostgreSqlType::SmallInt)],
-            vec![vec!["123".to_owned()]],
+            vec![vec![Some("123".to_owned())]],
         ))),
         Err(QueryErrorBuilder::new()
             .column_does_not_exist(vec!["col1".to_owned(), "col2".to_owned()])
@@ -299,7 +299,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["3".to_owned()]],
+                        vec![vec![Some("3".to_owned())]],
                     ))),
                 ]);
             }
@@ -321,7 +321,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["-1".to_owned()]],
+                        vec![vec![Some("-1".to_owned())]],
                     ))),
                 ]);
             }
@@ -343,7 +343,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["6".to_owned()]],
+                        vec![vec![Some("6".to_owned())]],
                     ))),
                 ]);
             }
@@ -365,7 +365,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["4".to_owned()]],
+                        vec![vec![Some("4".to_owned())]],
                     ))),
                 ]);
             }
@@ -387,7 +387,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["0".to_owned()]],
+                        vec![vec![Some("0".to_owned())]],
                     ))),
                 ]);
             }
@@ -412,7 +412,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["64".to_owned()]],
+                        vec![vec![Some("64".to_owned())]],
                     ))),
                 ]);
             }
@@ -436,7 +436,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["4".to_owned()]],
+                        vec![vec![Some("4".to_owned())]],
                     ))),
                 ]);
             }
@@ -460,7 +460,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["2".to_owned()]],
+                        vec![vec![Some("2".to_owned())]],
                     ))),
                 ]);
             }
@@ -484,7 +484,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["120".to_owned()]],
+                        vec![vec![Some("120".to_owned())]],
                     ))),
                 ]);
             }
@@ -508,7 +508,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["120".to_owned()]],
+                        vec![vec![Some("120".to_owned())]],
                     ))),
                 ]);
             }
@@ -532,7 +532,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["5".to_owned()]],
+                        vec![vec![Some("5".to_owned())]],
                     ))),
                 ]);
             }
@@ -554,7 +554,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["1".to_owned()]],
+                        vec![vec![Some("1".to_owned())]],
                     ))),
                 ]);
             }
@@ -576,7 +576,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["7".to_owned()]],
+                        vec![vec![Some("7".to_owned())]],
                     ))),
                 ]);
             }
@@ -600,7 +600,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["-2".to_owned()]],
+                        vec![vec![Some("-2".to_owned())]],
                     ))),
                 ]);
             }
@@ -624,7 +624,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["16".to_owned()]],
+                        vec![vec![Some("16".to_owned())]],
                     ))),
                 ]);
             }
@@ -648,7 +648,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["2".to_owned()]],
+                        vec![vec![Some("2".to_owned())]],
                     ))),
                 ]);
             }
@@ -670,7 +670,7 @@ mod operators {
                     Ok(QueryEvent::RecordsUpdated(1)),
                     Ok(QueryEvent::RecordsSelected((
                         vec![("column_si".to_owned(), PostgreSqlType::SmallInt)],
-                        vec![vec!["5".to_owned()]],
+                        vec![vec![Some("5".to_owned())]],
                     ))),
                 ]);
             }
@@ -714,7 +714,7 @@ mod operators {
                 Ok(QueryEvent::RecordsUpdated(1)),
                 Ok(QueryEvent::RecordsSelected((
                     vec![("strings".to_owned(), PostgreSqlType::Char)],
-                    vec![vec!["12345".to_owned()]],
+                    vec![vec![Some("12345".to_owned())]],
                 ))),
             ]);
         }
@@ -742,12 +742,12 @@ mod operators {
                 Ok(QueryEvent::RecordsUpdated(1)),
                 Ok(QueryEvent::RecordsSelected((
                     vec![("strings".to_owned(), PostgreSqlType::Char)],
-                    vec![vec!["145".to_owned()]],
+                    vec![vec![Some("145".to_owned())]],
                 ))),
                 Ok(QueryEvent::RecordsUpdated(1)),
                 Ok(QueryEvent::RecordsSelected((
                     vec![("strings".to_owned(), PostgreSqlType::Char)],
-                    vec![vec!["451".to_owned()]],
+                    vec![vec![Some("451".to_owned())]],
                 ))),
             ]);
         }
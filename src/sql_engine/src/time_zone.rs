@@ -0,0 +1,72 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// There is no time zone database to look names up, so a time zone is either
+// UTC under one of its names, an offset from UTC in hours or a name from one
+// of IANA database areas.
+const UTC_NAMES: [&str; 7] = ["utc", "gmt", "z", "zulu", "universal", "uct", "etc/utc"];
+const AREAS: [&str; 11] = [
+    "africa",
+    "america",
+    "antarctica",
+    "arctic",
+    "asia",
+    "atlantic",
+    "australia",
+    "europe",
+    "indian",
+    "pacific",
+    "etc",
+];
+// the biggest offset PostgreSQL accepts
+const MAX_OFFSET_HOURS: f64 = 15.0;
+
+/// server time zone used when a client sets `DEFAULT` or `LOCAL` one
+pub(crate) const DEFAULT: &str = "UTC";
+
+/// the value a time zone setting is stored and reported with, `None` if the
+/// time zone isn't known
+pub(crate) fn canonical(time_zone: &str) -> Option<String> {
+    let lowercase = time_zone.to_lowercase();
+    if lowercase == "default" || lowercase == "local" {
+        return Some(DEFAULT.to_owned());
+    }
+    if UTC_NAMES.contains(&lowercase.as_str()) {
+        return Some(DEFAULT.to_owned());
+    }
+    if let Ok(offset) = time_zone.parse::<f64>() {
+        return if offset.abs() <= MAX_OFFSET_HOURS {
+            Some(time_zone.to_owned())
+        } else {
+            None
+        };
+    }
+    let mut parts = time_zone.splitn(2, '/');
+    match (parts.next(), parts.next()) {
+        (Some(area), Some(location))
+            if AREAS.contains(&area.to_lowercase().as_str())
+                && !location.is_empty()
+                && location
+                    .split('/')
+                    .all(|part| !part.is_empty() && part.chars().all(is_location_char)) =>
+        {
+            Some(time_zone.to_owned())
+        }
+        _ => None,
+    }
+}
+
+fn is_location_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '+'
+}
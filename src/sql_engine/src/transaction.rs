@@ -0,0 +1,57 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::mem;
+
+/// Inserts of a session with autocommit off. They are validated when they are
+/// made and written to storage all together only on COMMIT, so until then
+/// they are seen only by the session itself
+#[derive(Default)]
+pub(crate) struct Transaction {
+    // inserts in the order they were made
+    inserts: Vec<PendingInsert>,
+}
+
+impl Transaction {
+    pub(crate) fn add(&mut self, insert: PendingInsert) {
+        self.inserts.push(insert);
+    }
+
+    /// inserts made since the transaction started, the transaction is ended
+    pub(crate) fn take(&mut self) -> Vec<PendingInsert> {
+        mem::take(&mut self.inserts)
+    }
+
+    /// records of inserts into the table with values of all table columns
+    pub(crate) fn records<'t>(
+        &'t self,
+        schema_name: &'t str,
+        table_name: &'t str,
+    ) -> impl Iterator<Item = &'t Vec<String>> {
+        self.inserts
+            .iter()
+            .filter(move |insert| insert.schema_name == schema_name && insert.table_name == table_name)
+            .flat_map(|insert| insert.records.iter())
+    }
+}
+
+/// Rows of an INSERT as they are going to be written on COMMIT
+pub(crate) struct PendingInsert {
+    pub(crate) schema_name: String,
+    pub(crate) table_name: String,
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<Option<String>>>,
+    // rows as the session reads them until they are written
+    pub(crate) records: Vec<Vec<String>>,
+}
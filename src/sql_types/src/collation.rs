@@ -0,0 +1,52 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Rules of comparing values of character columns
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub enum Collation {
+    /// byte order of UTF-8 encoded strings, PostgreSQL `"C"` and `"POSIX"`
+    #[default]
+    C,
+    /// letters are compared regardless of their case, named after the
+    /// nondeterministic ICU collation commonly created in PostgreSQL
+    CaseInsensitive,
+}
+
+impl Collation {
+    /// collation by name as written in `COLLATE` clause
+    pub fn from_name(name: &str) -> Option<Collation> {
+        match name {
+            "C" | "POSIX" | "default" => Some(Collation::C),
+            "case_insensitive" => Some(Collation::CaseInsensitive),
+            _ => None,
+        }
+    }
+
+    pub fn compare(self, left: &str, right: &str) -> Ordering {
+        match self {
+            Collation::C => left.cmp(right),
+            Collation::CaseInsensitive => left
+                .chars()
+                .flat_map(char::to_lowercase)
+                .cmp(right.chars().flat_map(char::to_lowercase)),
+        }
+    }
+
+    pub fn equal(self, left: &str, right: &str) -> bool {
+        self.compare(left, right) == Ordering::Equal
+    }
+}
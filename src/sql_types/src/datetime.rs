@@ -0,0 +1,205 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{format_time_of_day, time_of_day, ConstraintError, Interval};
+use std::{
+    convert::TryInto,
+    fmt::{self, Display, Formatter},
+};
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+// only years of the common era with four digits are supported
+const MIN_YEAR: i64 = 1;
+const MAX_YEAR: i64 = 9999;
+
+/// Calendar date kept as number of days since 1970-01-01
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Date(i32);
+
+/// Date and time without time zone kept as microseconds since 1970-01-01 00:00:00
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Timestamp(i64);
+
+impl Date {
+    /// parses ISO 8601 date `YYYY-MM-DD`
+    pub fn parse(value: &str) -> Result<Date, ConstraintError> {
+        match civil_date(value) {
+            Some((year, month, day)) => Ok(Date(days_from_civil(year, month, day) as i32)),
+            None => Err(ConstraintError::TypeMismatch(value.to_owned())),
+        }
+    }
+
+    /// number of days from `other` to `self`
+    pub fn days_since(self, other: Date) -> i32 {
+        self.0 - other.0
+    }
+
+    pub(crate) fn to_bytes(self) -> Vec<u8> {
+        ((self.0 as u32) ^ 0x8000_0000).to_be_bytes().to_vec()
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Date {
+        Date((u32::from_be_bytes(bytes[0..4].try_into().unwrap()) ^ 0x8000_0000) as i32)
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.0 as i64);
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl From<Date> for Timestamp {
+    fn from(date: Date) -> Timestamp {
+        Timestamp(date.0 as i64 * MICROS_PER_DAY)
+    }
+}
+
+impl Timestamp {
+    /// parses `YYYY-MM-DD[ HH:MM:SS[.ffffff]]`, date and time could also be
+    /// separated by `T` as in ISO 8601
+    pub fn parse(value: &str) -> Result<Timestamp, ConstraintError> {
+        let (date, time) = match value.find(&[' ', 'T'][..]) {
+            Some(separator) => (&value[..separator], Some(&value[separator + 1..])),
+            None => (value, None),
+        };
+        let date = Date::parse(date).map_err(|_| ConstraintError::TypeMismatch(value.to_owned()))?;
+        let time = match time {
+            Some(time) => time_of_day(time).ok_or_else(|| ConstraintError::TypeMismatch(value.to_owned()))?,
+            None => 0,
+        };
+        Timestamp::from(date).checked_add_micros(time as i64)
+    }
+
+    /// shifts timestamp by an interval the way PostgreSQL does: months are
+    /// added first keeping day of month within the resulting month, then
+    /// days and then time
+    pub fn add_interval(self, interval: Interval) -> Result<Timestamp, ConstraintError> {
+        let mut days = self.0.div_euclid(MICROS_PER_DAY);
+        let time = self.0.rem_euclid(MICROS_PER_DAY);
+        if interval.months != 0 {
+            let (year, month, day) = civil_from_days(days);
+            let months = year * 12 + (month as i64 - 1) + interval.months as i64;
+            let (year, month) = (months.div_euclid(12), months.rem_euclid(12) as u32 + 1);
+            if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+                return Err(ConstraintError::OutOfRange);
+            }
+            days = days_from_civil(year, month, day.min(days_in_month(year, month)));
+        }
+        days += interval.days as i64;
+        Timestamp(time)
+            .checked_add_micros(days.checked_mul(MICROS_PER_DAY).ok_or(ConstraintError::OutOfRange)?)?
+            .checked_add_micros(interval.micros)
+    }
+
+    pub fn sub_interval(self, interval: Interval) -> Result<Timestamp, ConstraintError> {
+        self.add_interval(interval.negate()?)
+    }
+
+    /// difference between timestamps in days and time, never in months, as
+    /// PostgreSQL `timestamp - timestamp` returns
+    pub fn since(self, other: Timestamp) -> Interval {
+        let micros = self.0 - other.0;
+        Interval {
+            months: 0,
+            days: (micros / MICROS_PER_DAY) as i32,
+            micros: micros % MICROS_PER_DAY,
+        }
+    }
+
+    fn checked_add_micros(self, micros: i64) -> Result<Timestamp, ConstraintError> {
+        match self.0.checked_add(micros) {
+            Some(micros) if in_range(micros.div_euclid(MICROS_PER_DAY)) => Ok(Timestamp(micros)),
+            _ => Err(ConstraintError::OutOfRange),
+        }
+    }
+
+    pub(crate) fn to_bytes(self) -> Vec<u8> {
+        ((self.0 as u64) ^ 0x8000_0000_0000_0000).to_be_bytes().to_vec()
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Timestamp {
+        Timestamp((u64::from_be_bytes(bytes[0..8].try_into().unwrap()) ^ 0x8000_0000_0000_0000) as i64)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let date = Date(self.0.div_euclid(MICROS_PER_DAY) as i32);
+        let time = self.0.rem_euclid(MICROS_PER_DAY) as u64;
+        write!(f, "{} {}", date, format_time_of_day(time))
+    }
+}
+
+fn civil_date(value: &str) -> Option<(i64, u32, u32)> {
+    let parts = value.split('-').collect::<Vec<&str>>();
+    if parts.len() != 3
+        || [4, 2, 2]
+            .iter()
+            .zip(&parts)
+            .any(|(len, part)| part.len() != *len || !part.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    let year = parts[0].parse::<i64>().ok()?;
+    let month = parts[1].parse::<u32>().ok()?;
+    let day = parts[2].parse::<u32>().ok()?;
+    if year < MIN_YEAR || !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        None
+    } else {
+        Some((year, month, day))
+    }
+}
+
+fn in_range(days: i64) -> bool {
+    (days_from_civil(MIN_YEAR, 1, 1)..=days_from_civil(MAX_YEAR, 12, 31)).contains(&days)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// conversions between proleptic Gregorian calendar dates and days since
+// 1970-01-01, see http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
@@ -0,0 +1,305 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ConstraintError;
+use std::{
+    convert::TryInto,
+    fmt::{self, Display, Formatter},
+};
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+
+/// Interval kept the way PostgreSQL keeps it: months and days can't be
+/// converted into smaller units without knowing the date they are applied to
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Interval {
+    pub(crate) months: i32,
+    pub(crate) days: i32,
+    pub(crate) micros: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Unit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+}
+
+impl Unit {
+    fn parse(unit: &str) -> Option<Unit> {
+        match unit {
+            "year" | "years" | "yr" | "yrs" | "y" => Some(Unit::Year),
+            "month" | "months" | "mon" | "mons" => Some(Unit::Month),
+            "week" | "weeks" | "w" => Some(Unit::Week),
+            "day" | "days" | "d" => Some(Unit::Day),
+            "hour" | "hours" | "hr" | "hrs" | "h" => Some(Unit::Hour),
+            "minute" | "minutes" | "min" | "mins" | "m" => Some(Unit::Minute),
+            "second" | "seconds" | "sec" | "secs" | "s" => Some(Unit::Second),
+            "millisecond" | "milliseconds" | "ms" => Some(Unit::Millisecond),
+            "microsecond" | "microseconds" | "us" => Some(Unit::Microsecond),
+            _ => None,
+        }
+    }
+
+    fn micros(self) -> Option<i64> {
+        match self {
+            Unit::Hour => Some(MICROS_PER_HOUR),
+            Unit::Minute => Some(MICROS_PER_MINUTE),
+            Unit::Second => Some(MICROS_PER_SECOND),
+            Unit::Millisecond => Some(1_000),
+            Unit::Microsecond => Some(1),
+            Unit::Year | Unit::Month | Unit::Week | Unit::Day => None,
+        }
+    }
+}
+
+impl Interval {
+    /// parses PostgreSQL style input: `<quantity> <unit>` pairs optionally
+    /// followed by a `[-]HH:MM[:SS[.ffffff]]` time and `ago`, e.g.
+    /// `1 year 2 mons 3 days 04:05:06` or `2 hours 30 minutes ago`
+    pub fn parse(value: &str) -> Result<Interval, ConstraintError> {
+        let mismatch = || ConstraintError::TypeMismatch(value.to_owned());
+        let lowercase = value.to_lowercase();
+        let mut tokens = lowercase.split_whitespace().peekable();
+        if tokens.peek().is_none() {
+            return Err(mismatch());
+        }
+
+        let mut interval = Interval::default();
+        let mut seen = vec![];
+        let mut time_seen = false;
+        while let Some(token) = tokens.next() {
+            if token == "ago" {
+                if tokens.peek().is_some() {
+                    return Err(mismatch());
+                }
+                interval = interval.negate()?;
+            } else if token.contains(':') {
+                if time_seen {
+                    return Err(mismatch());
+                }
+                time_seen = true;
+                let micros = time(token).ok_or_else(mismatch)?;
+                interval.micros = interval.micros.checked_add(micros).ok_or(ConstraintError::OutOfRange)?;
+            } else {
+                let unit = tokens.next().and_then(Unit::parse).ok_or_else(mismatch)?;
+                if seen.contains(&unit) {
+                    return Err(mismatch());
+                }
+                seen.push(unit);
+                interval = interval.add(quantity(token).ok_or_else(mismatch)?, unit, value)?;
+            }
+        }
+        Ok(interval)
+    }
+
+    fn add(self, quantity: Quantity, unit: Unit, value: &str) -> Result<Interval, ConstraintError> {
+        let Quantity {
+            negative,
+            integral,
+            fraction,
+        } = quantity;
+        let out_of_range = |_| ConstraintError::OutOfRange;
+        let mut result = self;
+        match unit.micros() {
+            Some(unit_micros) => {
+                let mut micros = (integral as i128) * (unit_micros as i128);
+                if let Some(digits) = fraction {
+                    let scale = 10i128.pow(digits.len() as u32);
+                    let fraction = digits.parse::<i128>().unwrap() * (unit_micros as i128);
+                    // round half away from zero as PostgreSQL does
+                    let fraction = (fraction * 2 + scale) / (scale * 2);
+                    micros += if negative { -fraction } else { fraction };
+                }
+                let micros: i64 = micros.try_into().map_err(out_of_range)?;
+                result.micros = result.micros.checked_add(micros).ok_or(ConstraintError::OutOfRange)?;
+            }
+            None => {
+                // spreading a fraction of a month or a day is not supported
+                if fraction.is_some() {
+                    return Err(ConstraintError::TypeMismatch(value.to_owned()));
+                }
+                let factor = match unit {
+                    Unit::Year => 12,
+                    Unit::Week => 7,
+                    _ => 1,
+                };
+                let amount: i32 = integral
+                    .checked_mul(factor)
+                    .ok_or(ConstraintError::OutOfRange)?
+                    .try_into()
+                    .map_err(out_of_range)?;
+                let field = if let Unit::Year | Unit::Month = unit {
+                    &mut result.months
+                } else {
+                    &mut result.days
+                };
+                *field = field.checked_add(amount).ok_or(ConstraintError::OutOfRange)?;
+            }
+        }
+        Ok(result)
+    }
+
+    pub(crate) fn negate(self) -> Result<Interval, ConstraintError> {
+        match (
+            self.months.checked_neg(),
+            self.days.checked_neg(),
+            self.micros.checked_neg(),
+        ) {
+            (Some(months), Some(days), Some(micros)) => Ok(Interval { months, days, micros }),
+            _ => Err(ConstraintError::OutOfRange),
+        }
+    }
+
+    pub(crate) fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16);
+        bytes.extend_from_slice(&self.months.to_be_bytes());
+        bytes.extend_from_slice(&self.days.to_be_bytes());
+        bytes.extend_from_slice(&self.micros.to_be_bytes());
+        bytes
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Interval {
+        Interval {
+            months: i32::from_be_bytes(bytes[0..4].try_into().unwrap()),
+            days: i32::from_be_bytes(bytes[4..8].try_into().unwrap()),
+            micros: i64::from_be_bytes(bytes[8..16].try_into().unwrap()),
+        }
+    }
+}
+
+struct Quantity<'q> {
+    negative: bool,
+    integral: i64,
+    fraction: Option<&'q str>,
+}
+
+// `[+|-]<digits>[.<digits>]`
+fn quantity(token: &str) -> Option<Quantity<'_>> {
+    let (integral, fraction) = match token.find('.') {
+        Some(dot) => (&token[..dot], Some(&token[dot + 1..])),
+        None => (token, None),
+    };
+    let digits = integral.trim_start_matches(&['+', '-'][..]);
+    if integral.len() - digits.len() > 1 || digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    match fraction {
+        Some(fraction)
+            if fraction.is_empty() || fraction.len() > 18 || !fraction.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            None
+        }
+        _ => integral.parse::<i64>().ok().map(|parsed| Quantity {
+            negative: integral.starts_with('-'),
+            integral: parsed,
+            fraction,
+        }),
+    }
+}
+
+// `[+|-]H:MM[:SS[.ffffff]]` where hours are not limited to a day
+fn time(token: &str) -> Option<i64> {
+    let (negative, unsigned) = match token.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, token.strip_prefix('+').unwrap_or(token)),
+    };
+    let (time, fraction) = match unsigned.find('.') {
+        Some(dot) => (&unsigned[..dot], Some(&unsigned[dot + 1..])),
+        None => (unsigned, None),
+    };
+    let parts = time.split(':').collect::<Vec<&str>>();
+    if parts.len() < 2 || parts.len() > 3 || (fraction.is_some() && parts.len() != 3) {
+        return None;
+    }
+    let number = |digits: &str| -> Option<i64> {
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            None
+        } else {
+            digits.parse::<i64>().ok()
+        }
+    };
+    let hours = number(parts[0])?;
+    let minutes = number(parts[1]).filter(|minutes| parts[1].len() == 2 && *minutes < 60)?;
+    let seconds = match parts.get(2) {
+        Some(seconds) => number(seconds).filter(|value| seconds.len() == 2 && *value < 60)?,
+        None => 0,
+    };
+    let micros = match fraction {
+        None => 0,
+        Some(digits) if !digits.is_empty() && digits.len() <= 6 => number(digits)? * 10i64.pow(6 - digits.len() as u32),
+        Some(_) => return None,
+    };
+    let total = hours
+        .checked_mul(MICROS_PER_HOUR)?
+        .checked_add(minutes * MICROS_PER_MINUTE + seconds * MICROS_PER_SECOND + micros)?;
+    Some(if negative { -total } else { total })
+}
+
+// mirrors PostgreSQL `IntervalStyle = postgres` output: a sign is printed
+// before a positive field only when it follows a negative one
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        let mut is_before = false;
+        for (value, unit) in &[
+            (self.months / 12, "year"),
+            (self.months % 12, "mon"),
+            (self.days, "day"),
+        ] {
+            if *value != 0 {
+                parts.push(format!(
+                    "{}{} {}{}",
+                    if is_before && *value > 0 { "+" } else { "" },
+                    value,
+                    unit,
+                    if *value != 1 { "s" } else { "" }
+                ));
+                is_before = *value < 0;
+            }
+        }
+        if self.micros != 0 || parts.is_empty() {
+            let sign = if self.micros < 0 {
+                "-"
+            } else if is_before {
+                "+"
+            } else {
+                ""
+            };
+            let micros = self.micros.unsigned_abs();
+            let seconds = micros / MICROS_PER_SECOND as u64;
+            let fraction = micros % MICROS_PER_SECOND as u64;
+            let mut time = format!(
+                "{}{:02}:{:02}:{:02}",
+                sign,
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            );
+            if fraction != 0 {
+                time += &format!(".{}", format!("{:06}", fraction).trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
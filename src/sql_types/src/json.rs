@@ -0,0 +1,195 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Validates JSON text as defined by RFC 8259 without building any value out of
+// it, the text itself is what JSON columns store.
+
+// deeper nesting is rejected instead of overflowing the stack
+const MAX_DEPTH: usize = 512;
+
+pub(crate) fn is_valid(text: &str) -> bool {
+    let mut parser = Parser {
+        bytes: text.as_bytes(),
+        position: 0,
+    };
+    parser.skip_whitespace();
+    if !parser.value(0) {
+        return false;
+    }
+    parser.skip_whitespace();
+    parser.position == parser.bytes.len()
+}
+
+struct Parser<'p> {
+    bytes: &'p [u8],
+    position: usize,
+}
+
+impl<'p> Parser<'p> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek();
+        self.position += 1;
+        byte
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn literal(&mut self, literal: &[u8]) -> bool {
+        if self.bytes[self.position..].starts_with(literal) {
+            self.position += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> bool {
+        if depth > MAX_DEPTH {
+            return false;
+        }
+        match self.peek() {
+            Some(b'{') => self.object(depth),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => self.string(),
+            Some(b't') => self.literal(b"true"),
+            Some(b'f') => self.literal(b"false"),
+            Some(b'n') => self.literal(b"null"),
+            Some(b'-') | Some(b'0'..=b'9') => self.number(),
+            _ => false,
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> bool {
+        self.position += 1;
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return true;
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') || !self.string() {
+                return false;
+            }
+            self.skip_whitespace();
+            if self.next() != Some(b':') {
+                return false;
+            }
+            self.skip_whitespace();
+            if !self.value(depth + 1) {
+                return false;
+            }
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b'}') => return true,
+                _ => return false,
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> bool {
+        self.position += 1;
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return true;
+        }
+        loop {
+            self.skip_whitespace();
+            if !self.value(depth + 1) {
+                return false;
+            }
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b']') => return true,
+                _ => return false,
+            }
+        }
+    }
+
+    fn string(&mut self) -> bool {
+        self.position += 1;
+        loop {
+            match self.next() {
+                Some(b'"') => return true,
+                Some(b'\\') => match self.next() {
+                    Some(b'"') | Some(b'\\') | Some(b'/') | Some(b'b') | Some(b'f') | Some(b'n') | Some(b'r')
+                    | Some(b't') => {}
+                    Some(b'u') => {
+                        for _ in 0..4 {
+                            match self.next() {
+                                Some(byte) if byte.is_ascii_hexdigit() => {}
+                                _ => return false,
+                            }
+                        }
+                    }
+                    _ => return false,
+                },
+                // control characters have to be escaped
+                Some(byte) if byte < 0x20 => return false,
+                Some(_) => {}
+                None => return false,
+            }
+        }
+    }
+
+    fn number(&mut self) -> bool {
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        match self.next() {
+            Some(b'0') => {}
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return false,
+        }
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            if !self.at_least_one_digit() {
+                return false;
+            }
+        }
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            self.position += 1;
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.position += 1;
+            }
+            if !self.at_least_one_digit() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn at_least_one_digit(&mut self) -> bool {
+        let start = self.position;
+        self.digits();
+        self.position > start
+    }
+}
@@ -16,7 +16,16 @@ use protocol::sql_types::PostgreSqlType;
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Serialize, Deserialize)]
+mod collation;
+mod datetime;
+mod interval;
+mod json;
+
+pub use collation::Collation;
+pub use datetime::{Date, Timestamp};
+pub use interval::Interval;
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub enum SqlType {
     Bool,
     Char(u64),
@@ -33,6 +42,31 @@ pub enum SqlType {
     TimestampWithTimeZone,
     Date,
     Interval,
+    Uuid,
+    Bytea,
+    Json,
+    // one-dimensional array of integers or booleans
+    Array(ArrayElementType),
+}
+
+/// types of elements that array columns can have
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ArrayElementType {
+    Bool,
+    SmallInt,
+    Integer,
+    BigInt,
+}
+
+impl ArrayElementType {
+    pub fn sql_type(&self) -> SqlType {
+        match self {
+            Self::Bool => SqlType::Bool,
+            Self::SmallInt => SqlType::SmallInt(i16::min_value()),
+            Self::Integer => SqlType::Integer(i32::min_value()),
+            Self::BigInt => SqlType::BigInt(i64::min_value()),
+        }
+    }
 }
 
 impl SqlType {
@@ -41,31 +75,55 @@ impl SqlType {
     }
 
     pub fn constraint(&self) -> Box<dyn Constraint> {
-        match *self {
-            Self::Char(length) => Box::new(CharSqlTypeConstraint { length }),
-            Self::VarChar(length) => Box::new(VarCharSqlTypeConstraint { length }),
-            Self::SmallInt(min) => Box::new(SmallIntTypeConstraint { min }),
-            Self::Integer(min) => Box::new(IntegerSqlTypeConstraint { min }),
-            Self::BigInt(min) => Box::new(BigIntTypeConstraint { min }),
+        match self {
+            Self::Char(length) => Box::new(CharSqlTypeConstraint { length: *length }),
+            Self::VarChar(length) => Box::new(VarCharSqlTypeConstraint { length: *length }),
+            Self::SmallInt(min) => Box::new(SmallIntTypeConstraint { min: *min }),
+            Self::Integer(min) => Box::new(IntegerSqlTypeConstraint { min: *min }),
+            Self::BigInt(min) => Box::new(BigIntTypeConstraint { min: *min }),
             Self::Bool => Box::new(BoolSqlTypeConstraint),
+            Self::Uuid => Box::new(UuidSqlTypeConstraint),
+            Self::Bytea => Box::new(ByteaSqlTypeConstraint),
+            Self::Time => Box::new(TimeSqlTypeConstraint),
+            Self::Interval => Box::new(IntervalSqlTypeConstraint),
+            Self::Date => Box::new(DateSqlTypeConstraint),
+            Self::Timestamp => Box::new(TimestampSqlTypeConstraint),
+            Self::Json => Box::new(JsonSqlTypeConstraint),
+            Self::Real => Box::new(RealSqlTypeConstraint),
+            Self::DoublePrecision => Box::new(DoublePrecisionSqlTypeConstraint),
+            Self::Array(element) => Box::new(ArraySqlTypeConstraint {
+                element: element.sql_type().constraint(),
+            }),
             sql_type => unimplemented!("Type constraint for {:?} is not currently implemented", sql_type),
         }
     }
 
     pub fn serializer(&self) -> Box<dyn Serializer> {
-        match *self {
+        match self {
             Self::Char(_length) => Box::new(CharSqlTypeSerializer),
             Self::VarChar(_length) => Box::new(VarCharSqlTypeSerializer),
             Self::SmallInt(_min) => Box::new(SmallIntTypeSerializer),
             Self::Integer(_min) => Box::new(IntegerSqlTypeSerializer),
             Self::BigInt(_min) => Box::new(BigIntTypeSerializer),
             Self::Bool => Box::new(BoolSqlTypeSerializer),
+            Self::Uuid => Box::new(UuidSqlTypeSerializer),
+            Self::Bytea => Box::new(ByteaSqlTypeSerializer),
+            Self::Time => Box::new(TimeSqlTypeSerializer),
+            Self::Interval => Box::new(IntervalSqlTypeSerializer),
+            Self::Date => Box::new(DateSqlTypeSerializer),
+            Self::Timestamp => Box::new(TimestampSqlTypeSerializer),
+            Self::Json => Box::new(JsonSqlTypeSerializer),
+            Self::Real => Box::new(RealSqlTypeSerializer),
+            Self::DoublePrecision => Box::new(DoublePrecisionSqlTypeSerializer),
+            Self::Array(element) => Box::new(ArraySqlTypeSerializer {
+                element: element.sql_type().serializer(),
+            }),
             sql_type => unimplemented!("Type Serializer for {:?} is not currently implemented", sql_type),
         }
     }
 
     pub fn to_pg_types(&self) -> PostgreSqlType {
-        match *self {
+        match self {
             Self::Bool => PostgreSqlType::Bool,
             Self::Char(_) => PostgreSqlType::Char,
             Self::VarChar(_) => PostgreSqlType::VarChar,
@@ -81,6 +139,15 @@ impl SqlType {
             Self::TimestampWithTimeZone => PostgreSqlType::TimestampWithTimeZone,
             Self::Date => PostgreSqlType::Date,
             Self::Interval => PostgreSqlType::Interval,
+            Self::Uuid => PostgreSqlType::Uuid,
+            Self::Bytea => PostgreSqlType::Bytea,
+            Self::Json => PostgreSqlType::Json,
+            Self::Array(element) => match element {
+                ArrayElementType::Bool => PostgreSqlType::BoolArray,
+                ArrayElementType::SmallInt => PostgreSqlType::SmallIntArray,
+                ArrayElementType::Integer => PostgreSqlType::IntegerArray,
+                ArrayElementType::BigInt => PostgreSqlType::BigIntArray,
+            },
         }
     }
 }
@@ -94,6 +161,8 @@ pub enum ConstraintError {
     OutOfRange,
     TypeMismatch(String),
     ValueTooLong(u64),
+    // violation of array element at 1-based index
+    ArrayElement(usize, Box<ConstraintError>),
 }
 
 pub trait Serializer {
@@ -116,27 +185,29 @@ impl Constraint for SmallIntTypeConstraint {
                     Err(ConstraintError::OutOfRange)
                 }
             }
-            Err(e) if e.code == lexical::ErrorCode::InvalidDigit => {
-                Err(ConstraintError::TypeMismatch(in_value.to_owned()))
+            Err(e) if e.code == lexical::ErrorCode::Overflow || e.code == lexical::ErrorCode::Underflow => {
+                Err(ConstraintError::OutOfRange)
             }
-            Err(_) => Err(ConstraintError::OutOfRange),
+            Err(_) => Err(ConstraintError::TypeMismatch(in_value.to_owned())),
         }
     }
 }
 
+// integers are stored big-endian with the sign bit flipped, so comparing
+// serialized values byte by byte orders them numerically
 struct SmallIntTypeSerializer;
 
 impl Serializer for SmallIntTypeSerializer {
     #[allow(clippy::match_wild_err_arm)]
     fn ser(&self, in_value: &str) -> Vec<u8> {
         match lexical::parse::<i16, _>(in_value) {
-            Ok(parsed) => parsed.to_be_bytes().to_vec(),
+            Ok(parsed) => ((parsed as u16) ^ 0x8000).to_be_bytes().to_vec(),
             Err(_) => unreachable!(),
         }
     }
 
     fn des(&self, out_value: &[u8]) -> String {
-        i16::from_be_bytes(out_value[0..2].try_into().unwrap()).to_string()
+        ((u16::from_be_bytes(out_value[0..2].try_into().unwrap()) ^ 0x8000) as i16).to_string()
     }
 }
 
@@ -154,10 +225,10 @@ impl Constraint for IntegerSqlTypeConstraint {
                     Err(ConstraintError::OutOfRange)
                 }
             }
-            Err(e) if e.code == lexical::ErrorCode::InvalidDigit => {
-                Err(ConstraintError::TypeMismatch(in_value.to_owned()))
+            Err(e) if e.code == lexical::ErrorCode::Overflow || e.code == lexical::ErrorCode::Underflow => {
+                Err(ConstraintError::OutOfRange)
             }
-            Err(_) => Err(ConstraintError::OutOfRange),
+            Err(_) => Err(ConstraintError::TypeMismatch(in_value.to_owned())),
         }
     }
 }
@@ -168,13 +239,13 @@ impl Serializer for IntegerSqlTypeSerializer {
     #[allow(clippy::match_wild_err_arm)]
     fn ser(&self, in_value: &str) -> Vec<u8> {
         match lexical::parse::<i32, _>(in_value) {
-            Ok(parsed) => parsed.to_be_bytes().to_vec(),
+            Ok(parsed) => ((parsed as u32) ^ 0x8000_0000).to_be_bytes().to_vec(),
             Err(_) => unreachable!(),
         }
     }
 
     fn des(&self, out_value: &[u8]) -> String {
-        i32::from_be_bytes(out_value[0..4].try_into().unwrap()).to_string()
+        ((u32::from_be_bytes(out_value[0..4].try_into().unwrap()) ^ 0x8000_0000) as i32).to_string()
     }
 }
 
@@ -192,10 +263,10 @@ impl Constraint for BigIntTypeConstraint {
                     Err(ConstraintError::OutOfRange)
                 }
             }
-            Err(e) if e.code == lexical::ErrorCode::InvalidDigit => {
-                Err(ConstraintError::TypeMismatch(in_value.to_owned()))
+            Err(e) if e.code == lexical::ErrorCode::Overflow || e.code == lexical::ErrorCode::Underflow => {
+                Err(ConstraintError::OutOfRange)
             }
-            Err(_) => Err(ConstraintError::OutOfRange),
+            Err(_) => Err(ConstraintError::TypeMismatch(in_value.to_owned())),
         }
     }
 }
@@ -206,13 +277,13 @@ impl Serializer for BigIntTypeSerializer {
     #[allow(clippy::match_wild_err_arm)]
     fn ser(&self, in_value: &str) -> Vec<u8> {
         match lexical::parse::<i64, _>(in_value) {
-            Ok(parsed) => parsed.to_be_bytes().to_vec(),
+            Ok(parsed) => ((parsed as u64) ^ 0x8000_0000_0000_0000).to_be_bytes().to_vec(),
             Err(_) => unreachable!(),
         }
     }
 
     fn des(&self, out_value: &[u8]) -> String {
-        i64::from_be_bytes(out_value[0..8].try_into().unwrap()).to_string()
+        ((u64::from_be_bytes(out_value[0..8].try_into().unwrap()) ^ 0x8000_0000_0000_0000) as i64).to_string()
     }
 }
 
@@ -308,13 +379,422 @@ impl Serializer for BoolSqlTypeSerializer {
     }
 }
 
+struct UuidSqlTypeConstraint;
+
+impl Constraint for UuidSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        // canonical form only: 8-4-4-4-12 hex digits separated by hyphens
+        let groups = in_value.split('-').collect::<Vec<&str>>();
+        let well_formed = groups.len() == 5
+            && groups
+                .iter()
+                .zip(&[8, 4, 4, 4, 12])
+                .all(|(group, len)| group.len() == *len && group.chars().all(|c| c.is_ascii_hexdigit()));
+        if well_formed {
+            Ok(())
+        } else {
+            Err(ConstraintError::TypeMismatch(in_value.to_owned()))
+        }
+    }
+}
+
+struct UuidSqlTypeSerializer;
+
+impl Serializer for UuidSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        in_value.to_ascii_lowercase().as_bytes().to_vec()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        String::from_utf8(out_value.to_vec()).unwrap()
+    }
+}
+
+struct ByteaSqlTypeConstraint;
+
+impl Constraint for ByteaSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        // only the hex format is supported: `\x` followed by pairs of hex digits
+        match in_value.strip_prefix("\\x") {
+            Some(hex) if hex.len() % 2 == 0 && hex.chars().all(|c| c.is_ascii_hexdigit()) => Ok(()),
+            _ => Err(ConstraintError::TypeMismatch(in_value.to_owned())),
+        }
+    }
+}
+
+struct ByteaSqlTypeSerializer;
+
+impl Serializer for ByteaSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        let hex = in_value.strip_prefix("\\x").unwrap_or(in_value).as_bytes();
+        hex.chunks(2)
+            .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        out_value
+            .iter()
+            .fold("\\x".to_owned(), |hex, byte| hex + &format!("{:02x}", byte))
+    }
+}
+
+const MICROS_PER_SECOND: u64 = 1_000_000;
+
+// parses `HH:MM:SS[.ffffff]` into microseconds since midnight, `24:00:00` is
+// allowed as the end of a day like PostgreSQL does
+fn time_of_day(value: &str) -> Option<u64> {
+    fn component(digits: &str, max: u64) -> Option<u64> {
+        if digits.len() != 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse::<u64>().ok().filter(|parsed| *parsed <= max)
+    }
+
+    let (time, fraction) = match value.find('.') {
+        Some(dot) => (&value[..dot], Some(&value[dot + 1..])),
+        None => (value, None),
+    };
+    let parts = time.split(':').collect::<Vec<&str>>();
+    if parts.len() != 3 {
+        return None;
+    }
+    let hours = component(parts[0], 24)?;
+    let minutes = component(parts[1], 59)?;
+    let seconds = component(parts[2], 59)?;
+    let micros = match fraction {
+        None => 0,
+        Some(digits) if !digits.is_empty() && digits.len() <= 6 && digits.chars().all(|c| c.is_ascii_digit()) => {
+            digits.parse::<u64>().ok()? * 10u64.pow(6 - digits.len() as u32)
+        }
+        Some(_) => return None,
+    };
+    let total = ((hours * 60 + minutes) * 60 + seconds) * MICROS_PER_SECOND + micros;
+    if hours == 24 && total != 24 * 60 * 60 * MICROS_PER_SECOND {
+        None
+    } else {
+        Some(total)
+    }
+}
+
+struct TimeSqlTypeConstraint;
+
+impl Constraint for TimeSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        match time_of_day(in_value) {
+            Some(_) => Ok(()),
+            None => Err(ConstraintError::TypeMismatch(in_value.to_owned())),
+        }
+    }
+}
+
+// time is stored as big-endian microseconds since midnight, so serialized
+// values are ordered chronologically
+struct TimeSqlTypeSerializer;
+
+impl Serializer for TimeSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        time_of_day(in_value).unwrap().to_be_bytes().to_vec()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        format_time_of_day(u64::from_be_bytes(out_value[0..8].try_into().unwrap()))
+    }
+}
+
+// fractional seconds are printed without trailing zeros
+fn format_time_of_day(total: u64) -> String {
+    let seconds = total / MICROS_PER_SECOND;
+    let micros = total % MICROS_PER_SECOND;
+    let time = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if micros == 0 {
+        time
+    } else {
+        format!("{}.{}", time, format!("{:06}", micros).trim_end_matches('0'))
+    }
+}
+
+struct DateSqlTypeConstraint;
+
+impl Constraint for DateSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        Date::parse(in_value).map(|_| ())
+    }
+}
+
+struct DateSqlTypeSerializer;
+
+impl Serializer for DateSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        Date::parse(in_value).unwrap().to_bytes()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        Date::from_bytes(out_value).to_string()
+    }
+}
+
+struct TimestampSqlTypeConstraint;
+
+impl Constraint for TimestampSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        Timestamp::parse(in_value).map(|_| ())
+    }
+}
+
+struct TimestampSqlTypeSerializer;
+
+impl Serializer for TimestampSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        Timestamp::parse(in_value).unwrap().to_bytes()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        Timestamp::from_bytes(out_value).to_string()
+    }
+}
+
+struct IntervalSqlTypeConstraint;
+
+impl Constraint for IntervalSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        Interval::parse(in_value).map(|_| ())
+    }
+}
+
+// interval is stored normalized, so it is printed back in PostgreSQL output
+// format rather than as it was spelled on insert
+struct IntervalSqlTypeSerializer;
+
+impl Serializer for IntervalSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        Interval::parse(in_value).unwrap().to_bytes()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        Interval::from_bytes(out_value).to_string()
+    }
+}
+
+// besides numbers `NaN`, `Infinity` and `-Infinity` are accepted in any case,
+// `inf` is a short form of `Infinity`
+fn special_float(in_value: &str) -> Option<f64> {
+    match in_value.trim().to_lowercase().as_str() {
+        "nan" => Some(f64::NAN),
+        "infinity" | "+infinity" | "inf" | "+inf" => Some(f64::INFINITY),
+        "-infinity" | "-inf" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+}
+
+// numbers too big for a type are out of range rather than infinite
+fn parse_real(in_value: &str) -> Result<f32, ConstraintError> {
+    if let Some(special) = special_float(in_value) {
+        return Ok(special as f32);
+    }
+    match in_value.trim().parse::<f32>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        Ok(_) => Err(ConstraintError::OutOfRange),
+        Err(_) => Err(ConstraintError::TypeMismatch(in_value.to_owned())),
+    }
+}
+
+fn parse_double_precision(in_value: &str) -> Result<f64, ConstraintError> {
+    if let Some(special) = special_float(in_value) {
+        return Ok(special);
+    }
+    match in_value.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        Ok(_) => Err(ConstraintError::OutOfRange),
+        Err(_) => Err(ConstraintError::TypeMismatch(in_value.to_owned())),
+    }
+}
+
+// special values are spelled the way PostgreSQL prints them
+fn float_text(value: f64, finite: String) -> String {
+    if value.is_nan() {
+        "NaN".to_owned()
+    } else if value == f64::INFINITY {
+        "Infinity".to_owned()
+    } else if value == f64::NEG_INFINITY {
+        "-Infinity".to_owned()
+    } else {
+        finite
+    }
+}
+
+struct RealSqlTypeConstraint;
+
+impl Constraint for RealSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        parse_real(in_value).map(|_| ())
+    }
+}
+
+struct RealSqlTypeSerializer;
+
+impl Serializer for RealSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        parse_real(in_value).unwrap().to_bits().to_be_bytes().to_vec()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        let value = f32::from_bits(u32::from_be_bytes(out_value[0..4].try_into().unwrap()));
+        float_text(value as f64, value.to_string())
+    }
+}
+
+struct DoublePrecisionSqlTypeConstraint;
+
+impl Constraint for DoublePrecisionSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        parse_double_precision(in_value).map(|_| ())
+    }
+}
+
+struct DoublePrecisionSqlTypeSerializer;
+
+impl Serializer for DoublePrecisionSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        parse_double_precision(in_value)
+            .unwrap()
+            .to_bits()
+            .to_be_bytes()
+            .to_vec()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        let value = f64::from_bits(u64::from_be_bytes(out_value[0..8].try_into().unwrap()));
+        float_text(value, value.to_string())
+    }
+}
+
+struct JsonSqlTypeConstraint;
+
+impl Constraint for JsonSqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        if json::is_valid(in_value) {
+            Ok(())
+        } else {
+            Err(ConstraintError::TypeMismatch(in_value.to_owned()))
+        }
+    }
+}
+
+// JSON text is stored verbatim, keeping whitespace, keys order and duplicates
+struct JsonSqlTypeSerializer;
+
+impl Serializer for JsonSqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        in_value.as_bytes().to_vec()
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        String::from_utf8(out_value.to_vec()).unwrap()
+    }
+}
+
+// array values use PostgreSQL text format `{1,2,3}`, elements may be double
+// quoted; NULL elements and nested arrays are not supported
+fn array_elements(in_value: &str) -> Option<Vec<String>> {
+    let inner = in_value.trim().strip_prefix('{')?.strip_suffix('}')?;
+    if inner.trim().is_empty() {
+        return Some(vec![]);
+    }
+    let mut elements = vec![];
+    let mut chars = inner.chars().peekable();
+    loop {
+        while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+            chars.next();
+        }
+        let mut element = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => element.push(chars.next()?),
+                    c => element.push(c),
+                }
+            }
+            while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+                chars.next();
+            }
+        } else {
+            while let Some(c) = chars.peek() {
+                match c {
+                    ',' => break,
+                    '{' | '}' | '"' | '\\' => return None,
+                    _ => element.push(*c),
+                }
+                chars.next();
+            }
+            let trimmed = element.trim();
+            if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("null") {
+                return None;
+            }
+            element = trimmed.to_owned();
+        }
+        elements.push(element);
+        match chars.next() {
+            Some(',') => continue,
+            None => return Some(elements),
+            Some(_) => return None,
+        }
+    }
+}
+
+struct ArraySqlTypeConstraint {
+    element: Box<dyn Constraint>,
+}
+
+impl Constraint for ArraySqlTypeConstraint {
+    fn validate(&self, in_value: &str) -> Result<(), ConstraintError> {
+        let elements = array_elements(in_value).ok_or_else(|| ConstraintError::TypeMismatch(in_value.to_owned()))?;
+        for (index, element) in elements.iter().enumerate() {
+            self.element
+                .validate(element)
+                .map_err(|error| ConstraintError::ArrayElement(index + 1, Box::new(error)))?;
+        }
+        Ok(())
+    }
+}
+
+// every serialized element is prefixed with its length
+struct ArraySqlTypeSerializer {
+    element: Box<dyn Serializer>,
+}
+
+impl Serializer for ArraySqlTypeSerializer {
+    fn ser(&self, in_value: &str) -> Vec<u8> {
+        let mut out = vec![];
+        for element in array_elements(in_value).unwrap_or_default() {
+            let serialized = self.element.ser(&element);
+            out.extend_from_slice(&(serialized.len() as u32).to_be_bytes());
+            out.extend_from_slice(&serialized);
+        }
+        out
+    }
+
+    fn des(&self, out_value: &[u8]) -> String {
+        let mut elements = vec![];
+        let mut rest = out_value;
+        while rest.len() >= 4 {
+            let len = u32::from_be_bytes(rest[0..4].try_into().unwrap()) as usize;
+            elements.push(self.element.des(&rest[4..4 + len]));
+            rest = &rest[4 + len..];
+        }
+        format!("{{{}}}", elements.join(","))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[cfg(test)]
     mod to_postgresql_type_conversion {
-        use crate::SqlType;
+        use crate::{ArrayElementType, SqlType};
         use protocol::sql_types::PostgreSqlType;
 
         #[test]
@@ -407,6 +887,36 @@ mod tests {
         fn interval() {
             assert_eq!(SqlType::Interval.to_pg_types(), PostgreSqlType::Interval);
         }
+
+        #[test]
+        fn uuid() {
+            assert_eq!(SqlType::Uuid.to_pg_types(), PostgreSqlType::Uuid);
+        }
+
+        #[test]
+        fn bytea() {
+            assert_eq!(SqlType::Bytea.to_pg_types(), PostgreSqlType::Bytea);
+        }
+
+        #[test]
+        fn array() {
+            assert_eq!(
+                SqlType::Array(ArrayElementType::Bool).to_pg_types(),
+                PostgreSqlType::BoolArray
+            );
+            assert_eq!(
+                SqlType::Array(ArrayElementType::SmallInt).to_pg_types(),
+                PostgreSqlType::SmallIntArray
+            );
+            assert_eq!(
+                SqlType::Array(ArrayElementType::Integer).to_pg_types(),
+                PostgreSqlType::IntegerArray
+            );
+            assert_eq!(
+                SqlType::Array(ArrayElementType::BigInt).to_pg_types(),
+                PostgreSqlType::BigIntArray
+            );
+        }
     }
 
     #[cfg(test)]
@@ -428,12 +938,26 @@ mod tests {
 
                 #[rstest::rstest]
                 fn serialize(serializer: Box<dyn Serializer>) {
-                    assert_eq!(serializer.ser("1"), vec![0, 1])
+                    assert_eq!(serializer.ser("1"), vec![128, 1])
                 }
 
                 #[rstest::rstest]
                 fn deserialize(serializer: Box<dyn Serializer>) {
-                    assert_eq!(serializer.des(&[0, 1]), "1".to_owned())
+                    assert_eq!(serializer.des(&[128, 1]), "1".to_owned())
+                }
+
+                #[rstest::rstest]
+                fn keeps_numeric_order(serializer: Box<dyn Serializer>) {
+                    let mut keys = vec!["10", "9", "32767", "-1", "0", "-32768", "-10"]
+                        .into_iter()
+                        .map(|value| serializer.ser(value))
+                        .collect::<Vec<Vec<u8>>>();
+                    keys.sort();
+
+                    assert_eq!(
+                        keys.iter().map(|key| serializer.des(key)).collect::<Vec<String>>(),
+                        vec!["-32768", "-10", "-1", "0", "9", "10", "32767"]
+                    );
                 }
             }
 
@@ -479,6 +1003,14 @@ mod tests {
                     )
                 }
 
+                #[rstest::rstest]
+                fn an_empty_string(constraint: Box<dyn Constraint>) {
+                    assert_eq!(
+                        constraint.validate(""),
+                        Err(ConstraintError::TypeMismatch("".to_owned()))
+                    )
+                }
+
                 #[test]
                 fn min_bound() {
                     let constraint = SqlType::SmallInt(0).constraint();
@@ -503,12 +1035,26 @@ mod tests {
 
                 #[rstest::rstest]
                 fn serialize(serializer: Box<dyn Serializer>) {
-                    assert_eq!(serializer.ser("1"), vec![0, 0, 0, 1])
+                    assert_eq!(serializer.ser("1"), vec![128, 0, 0, 1])
                 }
 
                 #[rstest::rstest]
                 fn deserialize(serializer: Box<dyn Serializer>) {
-                    assert_eq!(serializer.des(&[0, 0, 0, 1]), "1".to_owned())
+                    assert_eq!(serializer.des(&[128, 0, 0, 1]), "1".to_owned())
+                }
+
+                #[rstest::rstest]
+                fn keeps_numeric_order(serializer: Box<dyn Serializer>) {
+                    let mut keys = vec!["10", "9", "2147483647", "-1", "0", "-2147483648", "-10"]
+                        .into_iter()
+                        .map(|value| serializer.ser(value))
+                        .collect::<Vec<Vec<u8>>>();
+                    keys.sort();
+
+                    assert_eq!(
+                        keys.iter().map(|key| serializer.des(key)).collect::<Vec<String>>(),
+                        vec!["-2147483648", "-10", "-1", "0", "9", "10", "2147483647"]
+                    );
                 }
             }
 
@@ -554,6 +1100,14 @@ mod tests {
                     )
                 }
 
+                #[rstest::rstest]
+                fn an_empty_string(constraint: Box<dyn Constraint>) {
+                    assert_eq!(
+                        constraint.validate(""),
+                        Err(ConstraintError::TypeMismatch("".to_owned()))
+                    )
+                }
+
                 #[test]
                 fn min_bound() {
                     let constraint = SqlType::Integer(0).constraint();
@@ -578,12 +1132,42 @@ mod tests {
 
                 #[rstest::rstest]
                 fn serialize(serializer: Box<dyn Serializer>) {
-                    assert_eq!(serializer.ser("1"), vec![0, 0, 0, 0, 0, 0, 0, 1])
+                    assert_eq!(serializer.ser("1"), vec![128, 0, 0, 0, 0, 0, 0, 1])
                 }
 
                 #[rstest::rstest]
                 fn deserialize(serializer: Box<dyn Serializer>) {
-                    assert_eq!(serializer.des(&[0, 0, 0, 0, 0, 0, 0, 1]), "1".to_owned())
+                    assert_eq!(serializer.des(&[128, 0, 0, 0, 0, 0, 0, 1]), "1".to_owned())
+                }
+
+                #[rstest::rstest]
+                fn keeps_numeric_order(serializer: Box<dyn Serializer>) {
+                    let mut keys = vec![
+                        "10",
+                        "9",
+                        "9223372036854775807",
+                        "-1",
+                        "0",
+                        "-9223372036854775808",
+                        "-10",
+                    ]
+                    .into_iter()
+                    .map(|value| serializer.ser(value))
+                    .collect::<Vec<Vec<u8>>>();
+                    keys.sort();
+
+                    assert_eq!(
+                        keys.iter().map(|key| serializer.des(key)).collect::<Vec<String>>(),
+                        vec![
+                            "-9223372036854775808",
+                            "-10",
+                            "-1",
+                            "0",
+                            "9",
+                            "10",
+                            "9223372036854775807"
+                        ]
+                    );
                 }
             }
 
@@ -635,6 +1219,14 @@ mod tests {
                     )
                 }
 
+                #[rstest::rstest]
+                fn an_empty_string(constraint: Box<dyn Constraint>) {
+                    assert_eq!(
+                        constraint.validate(""),
+                        Err(ConstraintError::TypeMismatch("".to_owned()))
+                    )
+                }
+
                 #[test]
                 fn min_bound() {
                     let constraint = SqlType::BigInt(0).constraint();
@@ -646,23 +1238,114 @@ mod tests {
     }
 
     #[cfg(test)]
-    mod strings {
+    mod floats {
         use super::*;
 
         #[cfg(test)]
-        mod chars {
+        mod real {
             use super::*;
 
-            #[cfg(test)]
-            mod serialization {
-                use super::*;
+            #[rstest::fixture]
+            fn sql_type() -> SqlType {
+                SqlType::Real
+            }
 
-                #[rstest::fixture]
-                fn serializer() -> Box<dyn Serializer> {
-                    SqlType::Char(10).serializer()
+            #[rstest::rstest]
+            fn round_trip(sql_type: SqlType) {
+                for (value, expected) in &[
+                    ("1.5", "1.5"),
+                    ("-0.25", "-0.25"),
+                    ("NaN", "NaN"),
+                    ("nan", "NaN"),
+                    ("Infinity", "Infinity"),
+                    ("inf", "Infinity"),
+                    ("-Infinity", "-Infinity"),
+                    ("-INF", "-Infinity"),
+                ] {
+                    assert_eq!(sql_type.constraint().validate(value), Ok(()));
+                    assert_eq!(
+                        sql_type.serializer().des(&sql_type.serializer().ser(value)),
+                        (*expected).to_owned()
+                    );
                 }
+            }
 
-                #[rstest::rstest]
+            #[rstest::rstest]
+            fn out_of_range(sql_type: SqlType) {
+                assert_eq!(sql_type.constraint().validate("1e39"), Err(ConstraintError::OutOfRange));
+            }
+
+            #[rstest::rstest]
+            fn non_numeric(sql_type: SqlType) {
+                assert_eq!(
+                    sql_type.constraint().validate("infinite"),
+                    Err(ConstraintError::TypeMismatch("infinite".to_owned()))
+                );
+            }
+        }
+
+        #[cfg(test)]
+        mod double_precision {
+            use super::*;
+
+            #[rstest::fixture]
+            fn sql_type() -> SqlType {
+                SqlType::DoublePrecision
+            }
+
+            #[rstest::rstest]
+            fn round_trip(sql_type: SqlType) {
+                for (value, expected) in &[
+                    ("1.5", "1.5"),
+                    ("1e39", "1000000000000000000000000000000000000000"),
+                    ("NaN", "NaN"),
+                    ("Infinity", "Infinity"),
+                    ("-Infinity", "-Infinity"),
+                ] {
+                    assert_eq!(sql_type.constraint().validate(value), Ok(()));
+                    assert_eq!(
+                        sql_type.serializer().des(&sql_type.serializer().ser(value)),
+                        (*expected).to_owned()
+                    );
+                }
+            }
+
+            #[rstest::rstest]
+            fn out_of_range(sql_type: SqlType) {
+                assert_eq!(
+                    sql_type.constraint().validate("1e309"),
+                    Err(ConstraintError::OutOfRange)
+                );
+            }
+
+            #[rstest::rstest]
+            fn non_numeric(sql_type: SqlType) {
+                assert_eq!(
+                    sql_type.constraint().validate("abc"),
+                    Err(ConstraintError::TypeMismatch("abc".to_owned()))
+                );
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod strings {
+        use super::*;
+
+        #[cfg(test)]
+        mod chars {
+            use super::*;
+
+            #[cfg(test)]
+            mod serialization {
+                use super::*;
+
+                #[rstest::fixture]
+                fn serializer() -> Box<dyn Serializer> {
+                    SqlType::Char(10).serializer()
+                }
+
+                #[rstest::rstest]
                 fn serialize(serializer: Box<dyn Serializer>) {
                     assert_eq!(serializer.ser("str"), vec![115, 116, 114])
                 }
@@ -827,4 +1510,577 @@ mod tests {
             }
         }
     }
+
+    #[cfg(test)]
+    mod uuid {
+        use super::*;
+
+        #[cfg(test)]
+        mod serialization {
+            use super::*;
+
+            #[rstest::fixture]
+            fn serializer() -> Box<dyn Serializer> {
+                SqlType::Uuid.serializer()
+            }
+
+            #[rstest::rstest]
+            fn normalizes_to_lowercase(serializer: Box<dyn Serializer>) {
+                assert_eq!(
+                    serializer.ser("A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11"),
+                    b"a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_vec()
+                );
+            }
+
+            #[rstest::rstest]
+            fn round_trip(serializer: Box<dyn Serializer>) {
+                let serialized = serializer.ser("a0eebc99-9C0B-4ef8-bb6d-6BB9BD380A11");
+
+                assert_eq!(
+                    serializer.des(&serialized),
+                    "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_owned()
+                );
+            }
+        }
+
+        #[cfg(test)]
+        mod validation {
+            use super::*;
+
+            #[rstest::fixture]
+            fn constraint() -> Box<dyn Constraint> {
+                SqlType::Uuid.constraint()
+            }
+
+            #[rstest::rstest]
+            fn well_formed(constraint: Box<dyn Constraint>) {
+                assert_eq!(constraint.validate("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"), Ok(()));
+                assert_eq!(constraint.validate("A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11"), Ok(()));
+            }
+
+            #[rstest::rstest]
+            fn malformed(constraint: Box<dyn Constraint>) {
+                for value in &[
+                    "",
+                    "a0eebc999c0b4ef8bb6d6bb9bd380a11",
+                    "{a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11}",
+                    "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a1",
+                    "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a111",
+                    "a0eebc9-99c0b-4ef8-bb6d-6bb9bd380a11",
+                    "g0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+                    "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11-",
+                ] {
+                    assert_eq!(
+                        constraint.validate(value),
+                        Err(ConstraintError::TypeMismatch((*value).to_owned()))
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod time {
+        use super::*;
+
+        #[cfg(test)]
+        mod serialization {
+            use super::*;
+
+            #[rstest::fixture]
+            fn serializer() -> Box<dyn Serializer> {
+                SqlType::Time.serializer()
+            }
+
+            #[rstest::rstest]
+            fn round_trip(serializer: Box<dyn Serializer>) {
+                for value in &["00:00:00", "12:34:56", "23:59:59.999999", "07:05:03.25", "24:00:00"] {
+                    assert_eq!(serializer.des(&serializer.ser(value)), (*value).to_owned());
+                }
+            }
+
+            #[rstest::rstest]
+            fn trailing_fraction_zeros_are_dropped(serializer: Box<dyn Serializer>) {
+                assert_eq!(serializer.des(&serializer.ser("10:00:00.500")), "10:00:00.5".to_owned());
+                assert_eq!(serializer.des(&serializer.ser("10:00:00.000")), "10:00:00".to_owned());
+            }
+
+            #[rstest::rstest]
+            fn ordered_chronologically(serializer: Box<dyn Serializer>) {
+                assert!(serializer.ser("09:59:59.999999") < serializer.ser("10:00:00"));
+                assert!(serializer.ser("10:00:00") < serializer.ser("10:00:00.000001"));
+            }
+        }
+
+        #[cfg(test)]
+        mod validation {
+            use super::*;
+
+            #[rstest::fixture]
+            fn constraint() -> Box<dyn Constraint> {
+                SqlType::Time.constraint()
+            }
+
+            #[rstest::rstest]
+            fn well_formed(constraint: Box<dyn Constraint>) {
+                for value in &["00:00:00", "23:59:59", "12:00:00.1", "12:00:00.123456", "24:00:00"] {
+                    assert_eq!(constraint.validate(value), Ok(()), "{}", value);
+                }
+            }
+
+            #[rstest::rstest]
+            fn malformed(constraint: Box<dyn Constraint>) {
+                for value in &[
+                    "",
+                    "12:00",
+                    "25:00:00",
+                    "24:00:01",
+                    "12:60:00",
+                    "12:00:60",
+                    "1:00:00",
+                    "12:00:00.",
+                    "12:00:00.1234567",
+                    "12:00:00.1a",
+                    "12:00:00:00",
+                    "+1:00:00",
+                ] {
+                    assert_eq!(
+                        constraint.validate(value),
+                        Err(ConstraintError::TypeMismatch((*value).to_owned())),
+                        "{}",
+                        value
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod interval {
+        use super::*;
+
+        #[cfg(test)]
+        mod serialization {
+            use super::*;
+
+            #[rstest::fixture]
+            fn serializer() -> Box<dyn Serializer> {
+                SqlType::Interval.serializer()
+            }
+
+            #[rstest::rstest]
+            fn normalized(serializer: Box<dyn Serializer>) {
+                for (value, normalized) in &[
+                    ("1 day", "1 day"),
+                    ("2 hours 30 minutes", "02:30:00"),
+                    ("1 year 14 months", "2 years 2 mons"),
+                    ("2 weeks 1 d", "15 days"),
+                    ("90 minutes 1.5 seconds", "01:30:01.5"),
+                    ("1 DAY 02:03:04.000005", "1 day 02:03:04.000005"),
+                    ("3 days ago", "-3 days"),
+                    ("-1 year 2 days -1 hour", "-1 years +2 days -01:00:00"),
+                    ("0 seconds", "00:00:00"),
+                    ("36:00", "36:00:00"),
+                ] {
+                    assert_eq!(serializer.des(&serializer.ser(value)), (*normalized).to_owned());
+                }
+            }
+
+            #[rstest::rstest]
+            fn output_is_valid_input(serializer: Box<dyn Serializer>) {
+                let printed = serializer.des(&serializer.ser("-1 year 2 days -1 hour"));
+
+                assert_eq!(serializer.des(&serializer.ser(&printed)), printed);
+            }
+        }
+
+        #[cfg(test)]
+        mod validation {
+            use super::*;
+
+            #[rstest::fixture]
+            fn constraint() -> Box<dyn Constraint> {
+                SqlType::Interval.constraint()
+            }
+
+            #[rstest::rstest]
+            fn well_formed(constraint: Box<dyn Constraint>) {
+                for value in &[
+                    "1 day",
+                    "2 hours 30 minutes",
+                    "+1 mon -2 days",
+                    "1.25 h",
+                    "00:00:01.1 ago",
+                ] {
+                    assert_eq!(constraint.validate(value), Ok(()), "{}", value);
+                }
+            }
+
+            #[rstest::rstest]
+            fn malformed(constraint: Box<dyn Constraint>) {
+                for value in &[
+                    "",
+                    "day",
+                    "1",
+                    "1 fortnight",
+                    "1 day 1 day",
+                    "ago 1 day",
+                    "1.5 days",
+                    "1 day 01:00 02:00",
+                    "12:60",
+                    "--1 day",
+                ] {
+                    assert_eq!(
+                        constraint.validate(value),
+                        Err(ConstraintError::TypeMismatch((*value).to_owned())),
+                        "{}",
+                        value
+                    );
+                }
+            }
+
+            #[rstest::rstest]
+            fn out_of_range(constraint: Box<dyn Constraint>) {
+                assert_eq!(constraint.validate("200000000 years"), Err(ConstraintError::OutOfRange));
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod date {
+        use super::*;
+
+        #[rstest::fixture]
+        fn serializer() -> Box<dyn Serializer> {
+            SqlType::Date.serializer()
+        }
+
+        #[rstest::rstest]
+        fn round_trip(serializer: Box<dyn Serializer>) {
+            for value in &["0001-01-01", "1969-12-31", "1970-01-01", "2000-02-29", "9999-12-31"] {
+                assert_eq!(serializer.des(&serializer.ser(value)), (*value).to_owned());
+            }
+        }
+
+        #[rstest::rstest]
+        fn ordered_chronologically(serializer: Box<dyn Serializer>) {
+            assert!(serializer.ser("1969-12-31") < serializer.ser("1970-01-01"));
+            assert!(serializer.ser("2020-01-31") < serializer.ser("2020-02-01"));
+        }
+
+        #[test]
+        fn malformed() {
+            let constraint = SqlType::Date.constraint();
+            for value in &[
+                "",
+                "2020-1-01",
+                "2020-13-01",
+                "2021-02-29",
+                "1900-02-29",
+                "0000-01-01",
+                "2020/01/01",
+            ] {
+                assert_eq!(
+                    constraint.validate(value),
+                    Err(ConstraintError::TypeMismatch((*value).to_owned())),
+                    "{}",
+                    value
+                );
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod timestamp {
+        use super::*;
+
+        #[rstest::fixture]
+        fn serializer() -> Box<dyn Serializer> {
+            SqlType::Timestamp.serializer()
+        }
+
+        #[rstest::rstest]
+        fn round_trip(serializer: Box<dyn Serializer>) {
+            for value in &[
+                "1969-12-31 23:59:59.5",
+                "2020-02-29 12:30:00",
+                "9999-12-31 23:59:59.999999",
+            ] {
+                assert_eq!(serializer.des(&serializer.ser(value)), (*value).to_owned());
+            }
+        }
+
+        #[rstest::rstest]
+        fn normalized(serializer: Box<dyn Serializer>) {
+            assert_eq!(
+                serializer.des(&serializer.ser("2020-02-29")),
+                "2020-02-29 00:00:00".to_owned()
+            );
+            assert_eq!(
+                serializer.des(&serializer.ser("2020-02-29T08:00:00.100")),
+                "2020-02-29 08:00:00.1".to_owned()
+            );
+            assert_eq!(
+                serializer.des(&serializer.ser("2020-02-29 24:00:00")),
+                "2020-03-01 00:00:00".to_owned()
+            );
+        }
+
+        #[test]
+        fn malformed() {
+            let constraint = SqlType::Timestamp.constraint();
+            for value in &["", "2020-02-30 00:00:00", "2020-01-01 25:00:00", "2020-01-01  00:00:00"] {
+                assert_eq!(
+                    constraint.validate(value),
+                    Err(ConstraintError::TypeMismatch((*value).to_owned())),
+                    "{}",
+                    value
+                );
+            }
+        }
+
+        #[test]
+        fn out_of_range() {
+            assert_eq!(
+                SqlType::Timestamp.constraint().validate("9999-12-31 24:00:00"),
+                Err(ConstraintError::OutOfRange)
+            );
+        }
+
+        #[test]
+        fn month_arithmetic_keeps_day_within_month() {
+            let timestamp = Timestamp::parse("2020-03-31 10:00:00").unwrap();
+
+            assert_eq!(
+                timestamp
+                    .sub_interval(Interval::parse("1 mon").unwrap())
+                    .unwrap()
+                    .to_string(),
+                "2020-02-29 10:00:00".to_owned()
+            );
+            assert_eq!(
+                timestamp
+                    .add_interval(Interval::parse("-1 year -1 day").unwrap())
+                    .unwrap()
+                    .to_string(),
+                "2019-03-30 10:00:00".to_owned()
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod collation {
+        use super::*;
+        use std::cmp::Ordering;
+
+        #[test]
+        fn by_name() {
+            assert_eq!(Collation::from_name("C"), Some(Collation::C));
+            assert_eq!(Collation::from_name("POSIX"), Some(Collation::C));
+            assert_eq!(
+                Collation::from_name("case_insensitive"),
+                Some(Collation::CaseInsensitive)
+            );
+            assert_eq!(Collation::from_name("en_US"), None);
+        }
+
+        #[test]
+        fn byte_order() {
+            assert_eq!(Collation::C.compare("B", "a"), Ordering::Less);
+            assert_eq!(Collation::C.compare("a", "b"), Ordering::Less);
+            assert!(!Collation::C.equal("Alice", "ALICE"));
+        }
+
+        #[test]
+        fn case_insensitive_order() {
+            assert_eq!(Collation::CaseInsensitive.compare("B", "a"), Ordering::Greater);
+            assert_eq!(Collation::CaseInsensitive.compare("a", "B"), Ordering::Less);
+            assert!(Collation::CaseInsensitive.equal("Alice", "ALICE"));
+            assert!(!Collation::CaseInsensitive.equal("Alice", "Alicia"));
+        }
+    }
+
+    #[cfg(test)]
+    mod bytea {
+        use super::*;
+
+        #[cfg(test)]
+        mod serialization {
+            use super::*;
+
+            #[rstest::fixture]
+            fn serializer() -> Box<dyn Serializer> {
+                SqlType::Bytea.serializer()
+            }
+
+            #[rstest::rstest]
+            fn serialize(serializer: Box<dyn Serializer>) {
+                assert_eq!(serializer.ser("\\xDEADbeef00"), vec![0xde, 0xad, 0xbe, 0xef, 0x00]);
+                assert_eq!(serializer.ser("\\x"), Vec::<u8>::new());
+            }
+
+            #[rstest::rstest]
+            fn deserialize(serializer: Box<dyn Serializer>) {
+                assert_eq!(
+                    serializer.des(&[0xde, 0xad, 0xbe, 0xef, 0x00]),
+                    "\\xdeadbeef00".to_owned()
+                );
+                assert_eq!(serializer.des(&[]), "\\x".to_owned());
+            }
+        }
+
+        #[cfg(test)]
+        mod validation {
+            use super::*;
+
+            #[rstest::fixture]
+            fn constraint() -> Box<dyn Constraint> {
+                SqlType::Bytea.constraint()
+            }
+
+            #[rstest::rstest]
+            fn hex_format(constraint: Box<dyn Constraint>) {
+                assert_eq!(constraint.validate("\\x0123456789abcdefABCDEF"), Ok(()));
+                assert_eq!(constraint.validate("\\x"), Ok(()));
+            }
+
+            #[rstest::rstest]
+            fn malformed(constraint: Box<dyn Constraint>) {
+                for value in &["deadbeef", "\\xabc", "\\xzz", "x00"] {
+                    assert_eq!(
+                        constraint.validate(value),
+                        Err(ConstraintError::TypeMismatch((*value).to_owned()))
+                    );
+                }
+            }
+        }
+    }
+
+    mod array {
+        use super::*;
+
+        #[cfg(test)]
+        mod serialization {
+            use super::*;
+
+            #[rstest::fixture]
+            fn serializer() -> Box<dyn Serializer> {
+                SqlType::Array(ArrayElementType::Integer).serializer()
+            }
+
+            #[rstest::rstest]
+            fn round_trip(serializer: Box<dyn Serializer>) {
+                assert_eq!(serializer.des(&serializer.ser("{1,-2,3}")), "{1,-2,3}".to_owned());
+                assert_eq!(serializer.des(&serializer.ser("{ 1 , \"2\" }")), "{1,2}".to_owned());
+                assert_eq!(serializer.des(&serializer.ser("{}")), "{}".to_owned());
+            }
+        }
+
+        #[cfg(test)]
+        mod validation {
+            use super::*;
+
+            #[rstest::fixture]
+            fn constraint() -> Box<dyn Constraint> {
+                SqlType::Array(ArrayElementType::SmallInt).constraint()
+            }
+
+            #[rstest::rstest]
+            fn well_formed(constraint: Box<dyn Constraint>) {
+                assert_eq!(constraint.validate("{1,2,3}"), Ok(()));
+                assert_eq!(constraint.validate("{}"), Ok(()));
+                assert_eq!(constraint.validate(" { -1 , \"2\" } "), Ok(()));
+            }
+
+            #[rstest::rstest]
+            fn element_out_of_range(constraint: Box<dyn Constraint>) {
+                assert_eq!(
+                    constraint.validate("{1,32768,3}"),
+                    Err(ConstraintError::ArrayElement(2, Box::new(ConstraintError::OutOfRange)))
+                );
+            }
+
+            #[rstest::rstest]
+            fn element_type_mismatch(constraint: Box<dyn Constraint>) {
+                assert_eq!(
+                    constraint.validate("{1,abc}"),
+                    Err(ConstraintError::ArrayElement(
+                        2,
+                        Box::new(ConstraintError::TypeMismatch("abc".to_owned()))
+                    ))
+                );
+            }
+
+            #[rstest::rstest]
+            fn malformed(constraint: Box<dyn Constraint>) {
+                for value in &["1,2", "{1,2", "{1,,2}", "{{1},{2}}", "{1,NULL}", "{\"1}"] {
+                    assert_eq!(
+                        constraint.validate(value),
+                        Err(ConstraintError::TypeMismatch((*value).to_owned()))
+                    );
+                }
+            }
+        }
+    }
+
+    mod json {
+        use super::*;
+
+        #[rstest::fixture]
+        fn constraint() -> Box<dyn Constraint> {
+            SqlType::Json.constraint()
+        }
+
+        #[rstest::rstest]
+        fn well_formed(constraint: Box<dyn Constraint>) {
+            for value in &[
+                "{}",
+                "[]",
+                "{\"a\": 1, \"b\": [true, false, null], \"c\": {\"d\": \"e\\u00e9\\n\"}}",
+                " [1, -2.5, 3e10, 0.1E-2] ",
+                "\"string\"",
+                "42",
+                "null",
+            ] {
+                assert_eq!(constraint.validate(value), Ok(()), "{}", value);
+            }
+        }
+
+        #[rstest::rstest]
+        fn malformed(constraint: Box<dyn Constraint>) {
+            for value in &[
+                "",
+                "{",
+                "{\"a\" 1}",
+                "{a: 1}",
+                "[1, 2,]",
+                "[01]",
+                "1.",
+                "'string'",
+                "\"\\x\"",
+                "{} {}",
+                "tru",
+            ] {
+                assert_eq!(
+                    constraint.validate(value),
+                    Err(ConstraintError::TypeMismatch((*value).to_owned())),
+                    "{}",
+                    value
+                );
+            }
+        }
+
+        #[rstest::rstest]
+        fn deep_nesting(constraint: Box<dyn Constraint>) {
+            let value = "[".repeat(10_000) + &"]".repeat(10_000);
+            assert_eq!(constraint.validate(&value), Err(ConstraintError::TypeMismatch(value)));
+        }
+
+        #[test]
+        fn stored_verbatim() {
+            let serializer = SqlType::Json.serializer();
+            let value = "{\"b\": 1,  \"a\": [2]}";
+            assert_eq!(serializer.des(&serializer.ser(value)), value.to_owned());
+        }
+    }
 }
@@ -13,7 +13,10 @@
 // limitations under the License.
 
 use kernel::{SystemError, SystemResult};
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+};
 
 pub type Result<T, E> = std::result::Result<T, E>;
 pub type Row = (Key, Values);
@@ -82,6 +85,22 @@ pub trait BackendStorage {
     fn is_schema_exists(&self, namespace: &str) -> bool;
 
     fn check_for_table(&self, namespace: &str, object_name: &str) -> SystemResult<Result<(), OperationOnObjectError>>;
+
+    /// approximate size of an object in bytes, it is the total length of keys
+    /// and values stored in it without any overhead of the backend
+    fn object_size(&self, namespace: &str, object_name: &str) -> SystemResult<Result<u64, OperationOnObjectError>> {
+        match self.read(namespace, object_name)? {
+            Ok(reads) => {
+                let mut size = 0;
+                for read in reads {
+                    let (key, values) = read?;
+                    size += (key.len() + values.len()) as u64;
+                }
+                Ok(Ok(size))
+            }
+            Err(error) => Ok(Err(error)),
+        }
+    }
 }
 
 pub trait StorageErrorMapper {
@@ -119,12 +138,80 @@ impl StorageErrorMapper for SledErrorMapper {
     }
 }
 
+/// number of entries fetched from sled per iteration step by default
+pub const DEFAULT_SCAN_BATCH_SIZE: usize = 256;
+
+/// Tuning knobs of the storage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StorageConfig {
+    /// number of entries a read cursor fetches from sled at once
+    pub scan_batch_size: usize,
+    /// length of `VARCHAR` columns created without one, `None` means that
+    /// they accept values of any length as in PostgreSQL
+    pub default_varchar_length: Option<u64>,
+}
+
+impl Default for StorageConfig {
+    fn default() -> StorageConfig {
+        StorageConfig {
+            scan_batch_size: DEFAULT_SCAN_BATCH_SIZE,
+            default_varchar_length: None,
+        }
+    }
+}
+
+impl StorageConfig {
+    /// sets how many entries are fetched per iteration step, at least one
+    pub fn with_scan_batch_size(mut self, scan_batch_size: usize) -> StorageConfig {
+        self.scan_batch_size = scan_batch_size.max(1);
+        self
+    }
+
+    /// sets length of `VARCHAR` columns created without one
+    pub fn with_default_varchar_length(mut self, default_varchar_length: Option<u64>) -> StorageConfig {
+        self.default_varchar_length = default_varchar_length;
+        self
+    }
+}
+
 #[derive(Default)]
 pub struct SledBackendStorage {
     namespaces: HashMap<String, sled::Db>,
+    config: StorageConfig,
+}
+
+// fills up a buffer with a batch of entries and hands them out one by one
+// before fetching the next batch
+struct BatchedCursor {
+    entries: sled::Iter,
+    batch_size: usize,
+    batch: VecDeque<Result<Row, SystemError>>,
+}
+
+impl Iterator for BatchedCursor {
+    type Item = Result<Row, SystemError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.batch.is_empty() {
+            for entry in self.entries.by_ref().take(self.batch_size) {
+                self.batch.push_back(match entry {
+                    Ok((key, values)) => Ok((key.to_vec(), values.to_vec())),
+                    Err(error) => Err(SledErrorMapper::map(error)),
+                });
+            }
+        }
+        self.batch.pop_front()
+    }
 }
 
 impl SledBackendStorage {
+    pub fn with_config(config: StorageConfig) -> SledBackendStorage {
+        SledBackendStorage {
+            namespaces: HashMap::new(),
+            config,
+        }
+    }
+
     fn new_namespace(&mut self, namespace: &str) -> SystemResult<&mut sled::Db> {
         match sled::Config::default().temporary(true).open() {
             Ok(database) => {
@@ -238,10 +325,13 @@ impl BackendStorage for SledBackendStorage {
             Some(namespace) => {
                 if namespace.tree_names().contains(&(object_name.into())) {
                     match namespace.open_tree(object_name) {
-                        Ok(object) => Ok(Ok(Box::new(object.iter().map(|item| match item {
-                            Ok((key, values)) => Ok((key.to_vec(), values.to_vec())),
-                            Err(error) => Err(Self::ErrorMapper::map(error)),
-                        })))),
+                        Ok(object) => Ok(Ok(Box::new(BatchedCursor {
+                            entries: object.iter(),
+                            batch_size: self.config.scan_batch_size.max(1),
+                            // a huge batch size must not be allocated up front,
+                            // the buffer grows only if there are that many entries
+                            batch: VecDeque::with_capacity(self.config.scan_batch_size.min(DEFAULT_SCAN_BATCH_SIZE)),
+                        }))),
                         Err(error) => Err(Self::ErrorMapper::map(error)),
                     }
                 } else {
@@ -814,6 +904,63 @@ mod tests {
             .collect()
     }
 
+    #[cfg(test)]
+    mod scan_batch_size {
+        use super::*;
+
+        fn read_all(scan_batch_size: usize, number_of_rows: u32) -> Vec<Result<Row, SystemError>> {
+            let mut storage = Storage::with_config(StorageConfig::default().with_scan_batch_size(scan_batch_size));
+            storage
+                .create_namespace_with_objects("namespace", vec!["object_name"])
+                .expect("no system errors")
+                .expect("namespace created");
+            let rows = (0..number_of_rows)
+                .map(|key| (key.to_be_bytes().to_vec(), key.to_string().into_bytes()))
+                .collect::<Vec<Row>>();
+            storage
+                .write("namespace", "object_name", rows)
+                .expect("no system errors")
+                .expect("values are written");
+
+            storage
+                .read("namespace", "object_name")
+                .expect("no system errors")
+                .expect("object exists")
+                .collect()
+        }
+
+        fn expected(number_of_rows: u32) -> Vec<Result<Row, SystemError>> {
+            (0..number_of_rows)
+                .map(|key| Ok((key.to_be_bytes().to_vec(), key.to_string().into_bytes())))
+                .collect()
+        }
+
+        #[rstest::rstest]
+        fn large_object_with_small_batch() {
+            assert_eq!(read_all(1, 5_000), expected(5_000));
+        }
+
+        #[rstest::rstest]
+        fn large_object_with_large_batch() {
+            assert_eq!(read_all(10_000, 5_000), expected(5_000));
+        }
+
+        #[rstest::rstest]
+        fn huge_batch_is_not_allocated_up_front() {
+            assert_eq!(read_all(usize::MAX, 1_000), expected(1_000));
+        }
+
+        #[rstest::rstest]
+        fn object_size_is_multiple_of_batch() {
+            assert_eq!(read_all(100, 1_000), expected(1_000));
+        }
+
+        #[rstest::rstest]
+        fn zero_batch_size_reads_by_one() {
+            assert_eq!(read_all(0, 10), expected(10));
+        }
+    }
+
     fn as_keys(items: Vec<u8>) -> Vec<Key> {
         items.into_iter().map(|key| key.to_be_bytes().to_vec()).collect()
     }
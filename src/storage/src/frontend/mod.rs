@@ -12,35 +12,65 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeSet;
+use std::{
+    collections::{BTreeSet, HashMap},
+    convert::TryInto,
+    sync::{Arc, RwLock},
+};
 
 use crate::{
     backend::{
-        self, BackendStorage, CreateObjectError, DropObjectError, NamespaceAlreadyExists, NamespaceDoesNotExist,
-        OperationOnObjectError, Row, SledBackendStorage,
+        self, BackendStorage, CreateObjectError, DropObjectError, Key, NamespaceAlreadyExists, NamespaceDoesNotExist,
+        OperationOnObjectError, Row, SledBackendStorage, StorageConfig, Values,
     },
-    ColumnDefinition, CreateTableError, DropTableError, OperationOnTableError, Projection, SchemaAlreadyExists,
-    SchemaDoesNotExist, TableDescription,
+    AlterTableError, ColumnDefinition, CreateTableError, DropTableError, OperationOnTableError, Projection,
+    SchemaAlreadyExists, SchemaDoesNotExist, SequenceAlreadyExists, SequenceDoesNotExist, TableDescription, TableStats,
 };
 use kernel::{SystemError, SystemResult};
+use sql_types::SqlType;
+
+/// Storage handle shared between client connections. Reads can proceed
+/// concurrently while a write holds the storage exclusively.
+pub type SharedStorage<P> = Arc<RwLock<FrontendStorage<P>>>;
+
+// rows ready to be written along with table columns whose sequences were advanced
+type PreparedRows = (Vec<Row>, Vec<ColumnDefinition>);
+// values of written rows along with all table columns
+type WrittenRows = (Vec<Values>, Vec<ColumnDefinition>);
 
 pub struct FrontendStorage<P: BackendStorage> {
-    key_id_generator: usize,
+    // row keys are taken from a monotonic counter and encoded in big-endian, so
+    // a backend that iterates in key order returns rows in insertion order
+    key_id_generator: u64,
     persistent: P,
+    config: StorageConfig,
+    // number of rows of each table by schema and table names, maintained by
+    // writes so that tables don't have to be scanned to count them
+    row_counts: HashMap<(String, String), usize>,
 }
 
 impl FrontendStorage<SledBackendStorage> {
     pub fn default() -> SystemResult<Self> {
         Self::new(SledBackendStorage::default())
     }
+
+    pub fn with_config(config: StorageConfig) -> SystemResult<Self> {
+        Self::new_with_config(SledBackendStorage::with_config(config), config)
+    }
 }
 
 impl<P: BackendStorage> FrontendStorage<P> {
-    pub fn new(mut persistent: P) -> SystemResult<Self> {
-        match persistent.create_namespace_with_objects("system", vec!["columns"])? {
+    pub fn new(persistent: P) -> SystemResult<Self> {
+        Self::new_with_config(persistent, StorageConfig::default())
+    }
+
+    pub fn new_with_config(mut persistent: P, config: StorageConfig) -> SystemResult<Self> {
+        match persistent.create_namespace_with_objects("system", vec!["columns", "privileges", "sequences"])? {
             Ok(()) => Ok(Self {
                 key_id_generator: 0,
                 persistent,
+                config,
+                row_counts: HashMap::new(),
             }),
             Err(NamespaceAlreadyExists) => {
                 Err(SystemError::unrecoverable("system namespace already exists".to_owned()))
@@ -78,50 +108,80 @@ impl<P: BackendStorage> FrontendStorage<P> {
 
     pub fn drop_schema(&mut self, schema_name: &str) -> SystemResult<Result<(), SchemaDoesNotExist>> {
         match self.persistent.drop_namespace(schema_name)? {
-            Ok(()) => Ok(Ok(())),
+            Ok(()) => {
+                self.row_counts.retain(|(schema, _table), _count| schema != schema_name);
+                let keys = self
+                    .privileges()?
+                    .into_iter()
+                    .map(|(key, _grantees)| key)
+                    .filter(|key| unpack(key)[0] == schema_name.as_bytes())
+                    .collect();
+                self.delete_privileges(keys).map(Ok)
+            }
             Err(NamespaceDoesNotExist) => Ok(Err(SchemaDoesNotExist)),
         }
     }
 
+    pub fn config(&self) -> &StorageConfig {
+        &self.config
+    }
+
     pub fn create_table(
         &mut self,
         schema_name: &str,
         table_name: &str,
         column_names: &[ColumnDefinition],
     ) -> SystemResult<Result<(), CreateTableError>> {
+        // columns are mapped onto record values by their names
+        for (index, column) in column_names.iter().enumerate() {
+            if column_names[..index].iter().any(|other| other.name() == column.name()) {
+                return Ok(Err(CreateTableError::DuplicateColumn(column.name())));
+            }
+        }
         match self.persistent.create_object(schema_name, table_name)? {
-            Ok(()) => self
-                .persistent
-                .write(
-                    "system",
-                    "columns",
-                    vec![(
-                        (schema_name.to_owned() + table_name).as_bytes().to_vec(),
-                        column_names
-                            .iter()
-                            .map(|column_defs| bincode::serialize(&column_defs).unwrap())
-                            .collect::<Vec<Vec<u8>>>()
-                            .join(&b'|')
-                            .to_vec(),
-                    )],
-                )?
-                .map(|_| {
-                    log::info!("column data is recorded");
-                    Ok(())
-                })
-                .map_err(|error| {
-                    let message = format!(
-                        "Can't access \"system.columns\" table to read columns metadata because of {:?}",
-                        error
-                    );
-                    log::error!("{}", message);
-                    SystemError::unrecoverable(message)
-                }),
+            Ok(()) => {
+                self.row_counts
+                    .insert((schema_name.to_owned(), table_name.to_owned()), 0);
+                self.write_table_columns(schema_name, table_name, column_names).map(Ok)
+            }
             Err(CreateObjectError::ObjectAlreadyExists) => Ok(Err(CreateTableError::TableAlreadyExists)),
             Err(CreateObjectError::NamespaceDoesNotExist) => Ok(Err(CreateTableError::SchemaDoesNotExist)),
         }
     }
 
+    fn write_table_columns(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        column_names: &[ColumnDefinition],
+    ) -> SystemResult<()> {
+        self.persistent
+            .write(
+                "system",
+                "columns",
+                vec![(
+                    (schema_name.to_owned() + table_name).as_bytes().to_vec(),
+                    pack(
+                        &column_names
+                            .iter()
+                            .map(|column_defs| bincode::serialize(&column_defs).unwrap())
+                            .collect::<Vec<Vec<u8>>>(),
+                    ),
+                )],
+            )?
+            .map(|_| {
+                log::info!("column data is recorded");
+            })
+            .map_err(|error| {
+                let message = format!(
+                    "Can't access \"system.columns\" table to read columns metadata because of {:?}",
+                    error
+                );
+                log::error!("{}", message);
+                SystemError::unrecoverable(message)
+            })
+    }
+
     pub fn table_columns(&self, schema_name: &str, table_name: &str) -> SystemResult<Vec<ColumnDefinition>> {
         self.persistent
             .read("system", "columns")?
@@ -130,9 +190,8 @@ impl<P: BackendStorage> FrontendStorage<P> {
                     .map(backend::Result::unwrap)
                     .filter(|(table, _columns)| *table == (schema_name.to_owned() + table_name).as_bytes().to_vec())
                     .map(|(_id, columns)| {
-                        columns
-                            .split(|b| *b == b'|')
-                            .filter(|v| !v.is_empty())
+                        unpack(&columns)
+                            .into_iter()
                             .map(|c| bincode::deserialize(c).unwrap())
                             .collect::<Vec<_>>()
                     })
@@ -149,9 +208,119 @@ impl<P: BackendStorage> FrontendStorage<P> {
             })
     }
 
+    /// text of the expression a column is filled with when a row is
+    /// inserted without its value, `None` if the column has no default
+    pub fn column_default(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+        column_name: &str,
+    ) -> SystemResult<Result<Option<String>, OperationOnTableError>> {
+        match self.persistent.check_for_table(schema_name, table_name)? {
+            Ok(()) => {}
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::SchemaDoesNotExist))
+            }
+            Err(OperationOnObjectError::ObjectDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::TableDoesNotExist))
+            }
+        }
+        match self
+            .table_columns(schema_name, table_name)?
+            .into_iter()
+            .find(|column| column.name() == column_name)
+        {
+            Some(column) => Ok(Ok(column.default_expression().map(str::to_owned))),
+            None => Ok(Err(OperationOnTableError::ColumnDoesNotExist(vec![
+                column_name.to_owned()
+            ]))),
+        }
+    }
+
+    pub fn rename_column(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        old_column_name: &str,
+        new_column_name: &str,
+    ) -> SystemResult<Result<(), AlterTableError>> {
+        match self.persistent.check_for_table(schema_name, table_name)? {
+            Ok(()) => {}
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => return Ok(Err(AlterTableError::SchemaDoesNotExist)),
+            Err(OperationOnObjectError::ObjectDoesNotExist) => return Ok(Err(AlterTableError::TableDoesNotExist)),
+        }
+
+        let mut all_columns = self.table_columns(schema_name, table_name)?;
+        if all_columns.iter().any(|column| column.has_name(new_column_name)) {
+            return Ok(Err(AlterTableError::ColumnAlreadyExists(new_column_name.to_owned())));
+        }
+        match all_columns.iter_mut().find(|column| column.has_name(old_column_name)) {
+            Some(column) => column.name = new_column_name.to_owned(),
+            None => return Ok(Err(AlterTableError::ColumnDoesNotExist(old_column_name.to_owned()))),
+        }
+
+        // row values are positional, so only columns metadata has to be updated
+        self.write_table_columns(schema_name, table_name, &all_columns).map(Ok)
+    }
+
+    pub fn alter_column_type(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        column_name: &str,
+        new_sql_type: SqlType,
+    ) -> SystemResult<Result<(), AlterTableError>> {
+        match self.persistent.check_for_table(schema_name, table_name)? {
+            Ok(()) => {}
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => return Ok(Err(AlterTableError::SchemaDoesNotExist)),
+            Err(OperationOnObjectError::ObjectDoesNotExist) => return Ok(Err(AlterTableError::TableDoesNotExist)),
+        }
+
+        let mut all_columns = self.table_columns(schema_name, table_name)?;
+        let index = match all_columns.iter().position(|column| column.has_name(column_name)) {
+            Some(index) => index,
+            None => return Ok(Err(AlterTableError::ColumnDoesNotExist(column_name.to_owned()))),
+        };
+        let old_sql_type = all_columns[index].sql_type();
+        all_columns[index].sql_type = new_sql_type.clone();
+
+        let reads = match self.persistent.read(schema_name, table_name)? {
+            Ok(reads) => reads,
+            _ => unreachable!("table existence has been checked before"),
+        };
+        // every value is converted before anything is written, so the table is
+        // left untouched if any of them does not fit the new type
+        let mut to_write: Vec<Row> = vec![];
+        for (row_index, (key, values)) in reads.map(backend::Result::unwrap).enumerate() {
+            let mut values: Vec<Vec<u8>> = unpack(&values).into_iter().map(ToOwned::to_owned).collect();
+            let value = old_sql_type.serializer().des(&values[index]);
+            match new_sql_type.validate_and_serialize(value.as_str()) {
+                Ok(bytes) => values[index] = bytes,
+                Err(error) => {
+                    // In SQL indexes start from 1, not 0.
+                    return Ok(Err(AlterTableError::ConstraintViolations(
+                        vec![(error, all_columns[index].clone())],
+                        row_index + 1,
+                    )));
+                }
+            }
+            to_write.push((key, pack(&values)));
+        }
+
+        match self.persistent.write(schema_name, table_name, to_write)? {
+            Ok(_size) => {}
+            _ => unreachable!("table existence has been checked before"),
+        }
+        self.write_table_columns(schema_name, table_name, &all_columns).map(Ok)
+    }
+
     pub fn drop_table(&mut self, schema_name: &str, table_name: &str) -> SystemResult<Result<(), DropTableError>> {
         match self.persistent.drop_object(schema_name, table_name)? {
-            Ok(()) => Ok(Ok(())),
+            Ok(()) => {
+                self.row_counts.remove(&(schema_name.to_owned(), table_name.to_owned()));
+                self.delete_privileges(vec![privileges_key(schema_name, table_name)])
+                    .map(Ok)
+            }
             Err(DropObjectError::ObjectDoesNotExist) => Ok(Err(DropTableError::TableDoesNotExist)),
             Err(DropObjectError::NamespaceDoesNotExist) => Ok(Err(DropTableError::SchemaDoesNotExist)),
         }
@@ -162,10 +331,111 @@ impl<P: BackendStorage> FrontendStorage<P> {
         schema_name: &str,
         table_name: &str,
         column_names: Vec<String>,
-        rows: Vec<Vec<String>>,
+        rows: Vec<Vec<Option<String>>>,
     ) -> SystemResult<Result<(), OperationOnTableError>> {
-        let all_columns = self.table_columns(schema_name, table_name)?;
-        let index_columns = if column_names.is_empty() {
+        Ok(self
+            .write_rows(schema_name, table_name, column_names, rows)?
+            .map(|_| ()))
+    }
+
+    /// inserts rows as `insert_into` does and returns written records with
+    /// values of all table columns, including values given by sequences
+    pub fn insert_into_returning(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        column_names: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<Projection, OperationOnTableError>> {
+        let (written, all_columns) = match self.write_rows(schema_name, table_name, column_names, rows)? {
+            Ok(written) => written,
+            Err(error) => return Ok(Err(error)),
+        };
+        Ok(Ok(records_of(written.iter(), all_columns)))
+    }
+
+    fn write_rows(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        column_names: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<WrittenRows, OperationOnTableError>> {
+        let (to_write, all_columns) = match self.prepare_rows(schema_name, table_name, column_names, rows)? {
+            Ok(prepared) => prepared,
+            Err(error) => return Ok(Err(error)),
+        };
+        self.key_id_generator += to_write.len() as u64;
+        if all_columns.iter().any(ColumnDefinition::is_serial) {
+            // sequences were advanced for the rows that omitted SERIAL columns
+            self.write_table_columns(schema_name, table_name, &all_columns)?;
+        }
+
+        let written = to_write
+            .iter()
+            .map(|(_key, values)| values.clone())
+            .collect::<Vec<Values>>();
+        match self.persistent.write(schema_name, table_name, to_write)? {
+            Ok(_size) => {
+                if let Some(count) = self
+                    .row_counts
+                    .get_mut(&(schema_name.to_owned(), table_name.to_owned()))
+                {
+                    *count += written.len();
+                }
+                Ok(Ok((written, all_columns)))
+            }
+            Err(OperationOnObjectError::ObjectDoesNotExist) => Ok(Err(OperationOnTableError::TableDoesNotExist)),
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => Ok(Err(OperationOnTableError::SchemaDoesNotExist)),
+        }
+    }
+
+    pub fn validate_insert(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+        column_names: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<(), OperationOnTableError>> {
+        Ok(self
+            .preview_insert(schema_name, table_name, column_names, rows)?
+            .map(|_| ()))
+    }
+
+    /// checks rows as `validate_insert` does and returns records they would be
+    /// written as, with values of all table columns. Nothing is persisted
+    pub fn preview_insert(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+        column_names: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<Projection, OperationOnTableError>> {
+        let (to_write, all_columns) = match self.prepare_rows(schema_name, table_name, column_names, rows)? {
+            Ok(prepared) => prepared,
+            Err(error) => return Ok(Err(error)),
+        };
+
+        match self.persistent.check_for_table(schema_name, table_name)? {
+            Ok(()) => Ok(Ok(records_of(
+                to_write.iter().map(|(_key, values)| values),
+                all_columns,
+            ))),
+            Err(OperationOnObjectError::ObjectDoesNotExist) => Ok(Err(OperationOnTableError::TableDoesNotExist)),
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => Ok(Err(OperationOnTableError::SchemaDoesNotExist)),
+        }
+    }
+
+    fn prepare_rows(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+        column_names: Vec<String>,
+        rows: Vec<Vec<Option<String>>>,
+    ) -> SystemResult<Result<PreparedRows, OperationOnTableError>> {
+        let mut all_columns = self.table_columns(schema_name, table_name)?;
+        let column_names_are_implicit = column_names.is_empty();
+        let index_columns = if column_names_are_implicit {
             let mut index_cols = vec![];
             for (index, column_definition) in all_columns.iter().cloned().enumerate() {
                 index_cols.push((index, column_definition));
@@ -204,17 +474,30 @@ impl<P: BackendStorage> FrontendStorage<P> {
             let mut errors = Vec::new();
 
             for (row_index, row) in rows.iter().enumerate() {
-                if row.len() > all_columns.len() {
+                if row.len() > index_columns.len() {
                     // clear anything that could have been processed already.
                     to_write.clear();
                     return Ok(Err(OperationOnTableError::InsertTooManyExpressions));
                 }
+                // without a column list missing values are defaulted, but every
+                // named column has to be given an expression
+                if !column_names_are_implicit && row.len() < index_columns.len() {
+                    to_write.clear();
+                    return Ok(Err(OperationOnTableError::InsertTooManyTargetColumns));
+                }
 
-                let key = self.key_id_generator.to_be_bytes().to_vec();
+                let key = (self.key_id_generator + row_index as u64).to_be_bytes().to_vec();
 
                 // TODO: The default value or NULL should be initialized for SQL types of all columns.
                 let mut record = vec![vec![0, 0]; all_columns.len()];
+                let mut assigned = vec![false; all_columns.len()];
                 for (item, (index, column_definition)) in row.iter().zip(index_columns.iter()) {
+                    // `None` stands for an explicit DEFAULT and keeps the column initial value
+                    let item = match item {
+                        Some(item) => item,
+                        None => continue,
+                    };
+                    assigned[*index] = true;
                     match column_definition.sql_type().validate_and_serialize(item.as_str()) {
                         Ok(bytes) => {
                             record[*index] = bytes;
@@ -225,26 +508,34 @@ impl<P: BackendStorage> FrontendStorage<P> {
                     }
                 }
 
+                for (index, column_definition) in all_columns.iter_mut().enumerate() {
+                    if let (Some(next), false) = (column_definition.sequence, assigned[index]) {
+                        match column_definition
+                            .sql_type()
+                            .validate_and_serialize(next.to_string().as_str())
+                        {
+                            Ok(bytes) => record[index] = bytes,
+                            Err(e) => errors.push((e, column_definition.clone())),
+                        }
+                        column_definition.sequence = Some(next + 1);
+                    }
+                }
+
                 // if there was an error then exit the loop.
                 if !errors.is_empty() {
                     // In SQL indexes start from 1, not 0.
                     return Ok(Err(OperationOnTableError::ConstraintViolations(errors, row_index + 1)));
                 }
 
-                to_write.push((key, record.join(&b'|')));
-                self.key_id_generator += 1;
+                to_write.push((key, pack(&record)));
             }
         }
 
-        match self.persistent.write(schema_name, table_name, to_write)? {
-            Ok(_size) => Ok(Ok(())),
-            Err(OperationOnObjectError::ObjectDoesNotExist) => Ok(Err(OperationOnTableError::TableDoesNotExist)),
-            Err(OperationOnObjectError::NamespaceDoesNotExist) => Ok(Err(OperationOnTableError::SchemaDoesNotExist)),
-        }
+        Ok(Ok((to_write, all_columns)))
     }
 
     pub fn select_all_from(
-        &mut self,
+        &self,
         schema_name: &str,
         table_name: &str,
         column_names: Vec<String>,
@@ -280,7 +571,7 @@ impl<P: BackendStorage> FrontendStorage<P> {
                     .map(|bytes| {
                         let mut values = vec![];
                         for (i, (origin, ord)) in column_indexes.iter().enumerate() {
-                            for (index, value) in bytes.split(|b| *b == b'|').enumerate() {
+                            for (index, value) in unpack(&bytes).into_iter().enumerate() {
                                 if index == *origin {
                                     values.push((ord, description[i].sql_type().serializer().des(value)))
                                 }
@@ -352,12 +643,12 @@ impl<P: BackendStorage> FrontendStorage<P> {
                 let to_update: Vec<Row> = reads
                     .map(backend::Result::unwrap)
                     .map(|(key, values)| {
-                        let mut values: Vec<&[u8]> = values.split(|b| *b == b'|').collect();
+                        let mut values: Vec<&[u8]> = unpack(&values);
                         for (index, updated_value) in &index_value_pairs {
                             values[*index] = updated_value;
                         }
 
-                        (key, values.join(&b'|'))
+                        (key, pack(&values))
                     })
                     .collect();
 
@@ -374,6 +665,56 @@ impl<P: BackendStorage> FrontendStorage<P> {
         }
     }
 
+    /// number of rows in a table, it is read from a counter maintained by
+    /// writes, only tables that were not created through this storage are
+    /// scanned
+    pub fn row_count(&self, schema_name: &str, table_name: &str) -> SystemResult<Result<usize, OperationOnTableError>> {
+        match self.persistent.check_for_table(schema_name, table_name)? {
+            Ok(()) => {}
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::SchemaDoesNotExist))
+            }
+            Err(OperationOnObjectError::ObjectDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::TableDoesNotExist))
+            }
+        }
+        if let Some(count) = self.row_counts.get(&(schema_name.to_owned(), table_name.to_owned())) {
+            return Ok(Ok(*count));
+        }
+        match self.persistent.read(schema_name, table_name)? {
+            Ok(reads) => Ok(Ok(reads.count())),
+            Err(OperationOnObjectError::ObjectDoesNotExist) => Ok(Err(OperationOnTableError::TableDoesNotExist)),
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => Ok(Err(OperationOnTableError::SchemaDoesNotExist)),
+        }
+    }
+
+    /// number of rows and columns of a table along with approximate size of
+    /// its rows in the backend
+    pub fn table_stats(
+        &self,
+        schema_name: &str,
+        table_name: &str,
+    ) -> SystemResult<Result<TableStats, OperationOnTableError>> {
+        let row_count = match self.row_count(schema_name, table_name)? {
+            Ok(row_count) => row_count,
+            Err(error) => return Ok(Err(error)),
+        };
+        let byte_size = match self.persistent.object_size(schema_name, table_name)? {
+            Ok(byte_size) => byte_size,
+            Err(OperationOnObjectError::ObjectDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::TableDoesNotExist))
+            }
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::SchemaDoesNotExist))
+            }
+        };
+        Ok(Ok(TableStats {
+            row_count,
+            byte_size,
+            column_count: self.table_columns(schema_name, table_name)?.len(),
+        }))
+    }
+
     pub fn delete_all_from(
         &mut self,
         schema_name: &str,
@@ -383,7 +724,50 @@ impl<P: BackendStorage> FrontendStorage<P> {
             Ok(reads) => {
                 let keys = reads.map(backend::Result::unwrap).map(|(key, _)| key).collect();
                 match self.persistent.delete(schema_name, table_name, keys)? {
-                    Ok(len) => Ok(Ok(len)),
+                    Ok(len) => {
+                        if let Some(count) = self
+                            .row_counts
+                            .get_mut(&(schema_name.to_owned(), table_name.to_owned()))
+                        {
+                            *count = count.saturating_sub(len);
+                        }
+                        Ok(Ok(len))
+                    }
+                    _ => unreachable!(
+                        "all errors that make code fall in here should have been handled in read operation"
+                    ),
+                }
+            }
+            Err(OperationOnObjectError::ObjectDoesNotExist) => Ok(Err(OperationOnTableError::TableDoesNotExist)),
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => Ok(Err(OperationOnTableError::SchemaDoesNotExist)),
+        }
+    }
+
+    pub fn compact(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+    ) -> SystemResult<Result<usize, OperationOnTableError>> {
+        match self.persistent.read(schema_name, table_name)? {
+            Ok(reads) => {
+                let (keys, values): (Vec<Key>, Vec<Values>) = reads.map(backend::Result::unwrap).unzip();
+                // rows are rewritten under fresh contiguous keys in the same order,
+                // so insertion order is preserved
+                let to_write: Vec<Row> = values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, values)| ((self.key_id_generator + index as u64).to_be_bytes().to_vec(), values))
+                    .collect();
+                let len = to_write.len();
+                self.key_id_generator += len as u64;
+                match self.persistent.delete(schema_name, table_name, keys)? {
+                    Ok(_) => {}
+                    _ => unreachable!(
+                        "all errors that make code fall in here should have been handled in read operation"
+                    ),
+                }
+                match self.persistent.write(schema_name, table_name, to_write)? {
+                    Ok(_) => Ok(Ok(len)),
                     _ => unreachable!(
                         "all errors that make code fall in here should have been handled in read operation"
                     ),
@@ -394,14 +778,228 @@ impl<P: BackendStorage> FrontendStorage<P> {
         }
     }
 
+    pub fn grant_select(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        user_name: &str,
+    ) -> SystemResult<Result<(), OperationOnTableError>> {
+        match self.persistent.check_for_table(schema_name, table_name)? {
+            Ok(()) => {}
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::SchemaDoesNotExist))
+            }
+            Err(OperationOnObjectError::ObjectDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::TableDoesNotExist))
+            }
+        }
+
+        let mut grantees = self.select_grantees(schema_name, table_name)?.unwrap_or_default();
+        if !grantees.iter().any(|grantee| grantee == user_name) {
+            grantees.push(user_name.to_owned());
+        }
+        self.write_select_grantees(schema_name, table_name, &grantees).map(Ok)
+    }
+
+    pub fn revoke_select(
+        &mut self,
+        schema_name: &str,
+        table_name: &str,
+        user_name: &str,
+    ) -> SystemResult<Result<(), OperationOnTableError>> {
+        match self.persistent.check_for_table(schema_name, table_name)? {
+            Ok(()) => {}
+            Err(OperationOnObjectError::NamespaceDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::SchemaDoesNotExist))
+            }
+            Err(OperationOnObjectError::ObjectDoesNotExist) => {
+                return Ok(Err(OperationOnTableError::TableDoesNotExist))
+            }
+        }
+
+        // an empty list is still written, the table stays restricted
+        let mut grantees = self.select_grantees(schema_name, table_name)?.unwrap_or_default();
+        grantees.retain(|grantee| grantee != user_name);
+        self.write_select_grantees(schema_name, table_name, &grantees).map(Ok)
+    }
+
+    /// users allowed to select from the table, `None` if access to the table
+    /// has never been restricted by GRANT or REVOKE
+    pub fn select_grantees(&self, schema_name: &str, table_name: &str) -> SystemResult<Option<Vec<String>>> {
+        let key = privileges_key(schema_name, table_name);
+        Ok(self
+            .privileges()?
+            .into_iter()
+            .find(|(table, _grantees)| *table == key)
+            .map(|(_table, grantees)| {
+                unpack(&grantees)
+                    .into_iter()
+                    .map(|grantee| String::from_utf8_lossy(grantee).into_owned())
+                    .collect()
+            }))
+    }
+
+    fn privileges(&self) -> SystemResult<Vec<Row>> {
+        self.persistent
+            .read("system", "privileges")?
+            .map(|reads| reads.map(backend::Result::unwrap).collect())
+            .map_err(|error| {
+                let message = format!(
+                    "Can't access \"system.privileges\" table to read privileges because of {:?}",
+                    error
+                );
+                log::error!("{}", message);
+                SystemError::unrecoverable(message)
+            })
+    }
+
+    fn write_select_grantees(&mut self, schema_name: &str, table_name: &str, grantees: &[String]) -> SystemResult<()> {
+        self.persistent
+            .write(
+                "system",
+                "privileges",
+                vec![(privileges_key(schema_name, table_name), pack(grantees))],
+            )?
+            .map(|_| {
+                log::info!("privileges are recorded");
+            })
+            .map_err(|error| {
+                let message = format!(
+                    "Can't access \"system.privileges\" table to write privileges because of {:?}",
+                    error
+                );
+                log::error!("{}", message);
+                SystemError::unrecoverable(message)
+            })
+    }
+
+    fn delete_privileges(&mut self, keys: Vec<Key>) -> SystemResult<()> {
+        self.persistent
+            .delete("system", "privileges", keys)?
+            .map(|_| {
+                log::info!("privileges are removed");
+            })
+            .map_err(|error| {
+                let message = format!(
+                    "Can't access \"system.privileges\" table to remove privileges because of {:?}",
+                    error
+                );
+                log::error!("{}", message);
+                SystemError::unrecoverable(message)
+            })
+    }
+
+    pub fn create_sequence(&mut self, sequence_name: &str) -> SystemResult<Result<(), SequenceAlreadyExists>> {
+        if self.sequence_value(sequence_name)?.is_some() {
+            return Ok(Err(SequenceAlreadyExists));
+        }
+        self.write_sequence_value(sequence_name, 0).map(Ok)
+    }
+
+    /// advances the sequence and returns its new value
+    pub fn next_value(&mut self, sequence_name: &str) -> SystemResult<Result<i64, SequenceDoesNotExist>> {
+        match self.sequence_value(sequence_name)? {
+            Some(last_value) => {
+                let next_value = last_value + 1;
+                self.write_sequence_value(sequence_name, next_value)?;
+                Ok(Ok(next_value))
+            }
+            None => Ok(Err(SequenceDoesNotExist)),
+        }
+    }
+
+    // sequences keep the last value handed out, zero until `next_value` is called
+    fn sequence_value(&self, sequence_name: &str) -> SystemResult<Option<i64>> {
+        self.persistent
+            .read("system", "sequences")?
+            .map(|reads| {
+                reads
+                    .map(backend::Result::unwrap)
+                    .find(|(sequence, _value)| *sequence == sequence_name.as_bytes())
+                    .map(|(_sequence, value)| i64::from_be_bytes(value[0..8].try_into().unwrap()))
+            })
+            .map_err(|error| {
+                let message = format!(
+                    "Can't access \"system.sequences\" table to read sequence because of {:?}",
+                    error
+                );
+                log::error!("{}", message);
+                SystemError::unrecoverable(message)
+            })
+    }
+
+    fn write_sequence_value(&mut self, sequence_name: &str, value: i64) -> SystemResult<()> {
+        self.persistent
+            .write(
+                "system",
+                "sequences",
+                vec![(sequence_name.as_bytes().to_vec(), value.to_be_bytes().to_vec())],
+            )?
+            .map(|_| {
+                log::info!("sequence value is recorded");
+            })
+            .map_err(|error| {
+                let message = format!(
+                    "Can't access \"system.sequences\" table to write sequence because of {:?}",
+                    error
+                );
+                log::error!("{}", message);
+                SystemError::unrecoverable(message)
+            })
+    }
+
+    /// whether a schema exists, a caller does not need to handle errors of
+    /// operations on a schema to find it out
     pub fn schema_exists(&self, schema_name: &str) -> bool {
         self.persistent.is_schema_exists(schema_name)
     }
 
+    /// whether a table exists, it is `false` when the schema does not exist
     pub fn table_exists(&self, schema_name: &str, table_name: &str) -> bool {
         self.persistent.is_table_exists(schema_name, table_name)
     }
 }
 
+// values of packed rows deserialized as they are read from the table
+fn records_of<'v, I: Iterator<Item = &'v Values>>(packed: I, all_columns: Vec<ColumnDefinition>) -> Projection {
+    let records = packed
+        .map(|values| {
+            unpack(values)
+                .into_iter()
+                .zip(all_columns.iter())
+                .map(|(value, column_definition)| column_definition.sql_type().serializer().des(value))
+                .collect()
+        })
+        .collect();
+    (all_columns, records)
+}
+
+fn privileges_key(schema_name: &str, table_name: &str) -> Key {
+    pack(&[schema_name, table_name])
+}
+
+// column values are prefixed with their length instead of being joined with a
+// separator byte, as binary encoded values can contain any byte
+fn pack<V: AsRef<[u8]>>(values: &[V]) -> Vec<u8> {
+    let mut packed = vec![];
+    for value in values {
+        let value = value.as_ref();
+        packed.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        packed.extend_from_slice(value);
+    }
+    packed
+}
+
+fn unpack(packed: &[u8]) -> Vec<&[u8]> {
+    let mut values = vec![];
+    let mut rest = packed;
+    while rest.len() >= 4 {
+        let len = u32::from_be_bytes(rest[0..4].try_into().unwrap()) as usize;
+        values.push(&rest[4..4 + len]);
+        rest = &rest[4 + len..];
+    }
+    values
+}
+
 #[cfg(test)]
 mod tests;
@@ -13,13 +13,17 @@
 // limitations under the License.
 
 use super::*;
-use sql_types::SqlType;
+use sql_types::{Collation, SqlType};
 
 #[cfg(test)]
 mod queries;
 #[cfg(test)]
 mod schema;
 #[cfg(test)]
+mod sequence;
+#[cfg(test)]
+mod shared;
+#[cfg(test)]
 mod table;
 
 type PersistentStorage = FrontendStorage<SledBackendStorage>;
@@ -63,6 +67,9 @@ fn column_definition(name: &'static str, sql_type: SqlType) -> ColumnDefinition
     ColumnDefinition {
         name: name.to_owned(),
         sql_type,
+        sequence: None,
+        collation: Collation::default(),
+        default_expression: None,
     }
 }
 
@@ -78,7 +85,7 @@ fn insert_into<P: backend::BackendStorage>(
             schema_name,
             table_name,
             columns.into_iter().map(ToOwned::to_owned).collect(),
-            vec![values.into_iter().map(ToOwned::to_owned).collect()],
+            vec![values.into_iter().map(|value| Some(value.to_owned())).collect()],
         )
         .expect("no system errors")
         .expect("values are inserted");
@@ -19,7 +19,12 @@ use sql_types::SqlType;
 fn insert_into_non_existent_schema(mut storage: PersistentStorage) {
     assert_eq!(
         storage
-            .insert_into("non_existent", "not_existed", vec![], vec![vec!["123".to_owned()]])
+            .insert_into(
+                "non_existent",
+                "not_existed",
+                vec![],
+                vec![vec![Some("123".to_owned())]]
+            )
             .expect("no system errors"),
         Err(OperationOnTableError::SchemaDoesNotExist)
     );
@@ -29,7 +34,12 @@ fn insert_into_non_existent_schema(mut storage: PersistentStorage) {
 fn insert_into_non_existent_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
     assert_eq!(
         storage_with_schema
-            .insert_into(default_schema_name, "not_existed", vec![], vec![vec!["123".to_owned()]])
+            .insert_into(
+                default_schema_name,
+                "not_existed",
+                vec![],
+                vec![vec![Some("123".to_owned())]]
+            )
             .expect("no system errors"),
         Err(OperationOnTableError::TableDoesNotExist)
     );
@@ -201,6 +211,41 @@ fn insert_named_columns(default_schema_name: &str, mut storage_with_schema: Pers
     );
 }
 
+#[rstest::rstest]
+fn insert_default_values(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![
+            column_definition("column_1", SqlType::SmallInt(i16::min_value())),
+            column_definition("column_2", SqlType::SmallInt(i16::min_value())),
+        ],
+    );
+
+    assert_eq!(
+        storage_with_schema
+            .insert_into(
+                default_schema_name,
+                "table_name",
+                vec![],
+                vec![vec![None, Some("1".to_owned())], vec![None, Some("2".to_owned())]],
+            )
+            .expect("no system errors"),
+        Ok(())
+    );
+
+    assert_eq!(
+        storage_with_schema
+            .select_all_from(default_schema_name, "table_name", vec!["column_2".to_owned()])
+            .expect("no system errors"),
+        Ok((
+            vec![column_definition("column_2", SqlType::SmallInt(i16::min_value()))],
+            vec![vec!["1".to_owned()], vec!["2".to_owned()]]
+        ))
+    );
+}
+
 #[rstest::rstest]
 fn insert_named_not_existed_column(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
     create_table(
@@ -227,7 +272,12 @@ fn insert_named_not_existed_column(default_schema_name: &str, mut storage_with_s
                 default_schema_name,
                 "table_name",
                 columns,
-                vec![vec!["1".to_owned(), "2".to_owned(), "3".to_owned(), "4".to_owned()]],
+                vec![vec![
+                    Some("1".to_owned()),
+                    Some("2".to_owned()),
+                    Some("3".to_owned()),
+                    Some("4".to_owned())
+                ]],
             )
             .expect("no system errors"),
         Err(OperationOnTableError::ColumnDoesNotExist(
@@ -247,7 +297,12 @@ fn insert_row_into_table(default_schema_name: &str, mut storage_with_schema: Per
 
     assert_eq!(
         storage_with_schema
-            .insert_into(default_schema_name, "table_name", vec![], vec![vec!["123".to_owned()]])
+            .insert_into(
+                default_schema_name,
+                "table_name",
+                vec![],
+                vec![vec![Some("123".to_owned())]]
+            )
             .expect("no system errors"),
         Ok(())
     );
@@ -291,7 +346,12 @@ fn insert_too_many_expressions(default_schema_name: &str, mut storage_with_schem
                 default_schema_name,
                 "table_name",
                 columns,
-                vec![vec!["1".to_owned(), "2".to_owned(), "3".to_owned(), "4".to_owned()]],
+                vec![vec![
+                    Some("1".to_owned()),
+                    Some("2".to_owned()),
+                    Some("3".to_owned()),
+                    Some("4".to_owned())
+                ]],
             )
             .expect("no system errors"),
         Err(OperationOnTableError::InsertTooManyExpressions)
@@ -340,7 +400,12 @@ fn insert_too_many_expressions_labeled(default_schema_name: &str, mut storage_wi
                 default_schema_name,
                 "table_name",
                 columns,
-                vec![vec!["1".to_owned(), "2".to_owned(), "3".to_owned(), "4".to_owned()]],
+                vec![vec![
+                    Some("1".to_owned()),
+                    Some("2".to_owned()),
+                    Some("3".to_owned()),
+                    Some("4".to_owned())
+                ]],
             )
             .expect("no system errors"),
         Err(OperationOnTableError::InsertTooManyExpressions)
@@ -368,6 +433,87 @@ fn insert_too_many_expressions_labeled(default_schema_name: &str, mut storage_wi
     );
 }
 
+#[rstest::rstest]
+fn insert_too_many_target_columns(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![
+            column_definition("column_1", SqlType::SmallInt(i16::min_value())),
+            column_definition("column_2", SqlType::SmallInt(i16::min_value())),
+        ],
+    );
+
+    assert_eq!(
+        storage_with_schema
+            .insert_into(
+                default_schema_name,
+                "table_name",
+                vec!["column_2".to_owned(), "column_1".to_owned()],
+                vec![vec![Some("1".to_owned())]],
+            )
+            .expect("no system errors"),
+        Err(OperationOnTableError::InsertTooManyTargetColumns)
+    );
+}
+
+#[rstest::rstest]
+fn insert_assigns_serial_values_to_omitted_column(
+    default_schema_name: &str,
+    mut storage_with_schema: PersistentStorage,
+) {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![
+            ColumnDefinition::serial("id", SqlType::Integer(1)),
+            column_definition("name", SqlType::VarChar(10)),
+        ],
+    );
+
+    insert_into(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec!["name"],
+        vec!["a"],
+    );
+    insert_into(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec!["name"],
+        vec!["b"],
+    );
+    insert_into(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec!["name"],
+        vec!["c"],
+    );
+
+    let (_columns, values) = storage_with_schema
+        .select_all_from(
+            default_schema_name,
+            "table_name",
+            vec!["id".to_owned(), "name".to_owned()],
+        )
+        .expect("no system errors")
+        .expect("values are selected");
+
+    assert_eq!(
+        values,
+        vec![
+            vec!["1".to_owned(), "a".to_owned()],
+            vec!["2".to_owned(), "b".to_owned()],
+            vec!["3".to_owned(), "c".to_owned()],
+        ]
+    );
+}
+
 #[cfg(test)]
 mod constraints {
     use super::*;
@@ -416,7 +562,11 @@ mod constraints {
                     default_schema_name,
                     "table_name",
                     vec![],
-                    vec![vec!["-32769".to_owned(), "100".to_owned(), "100".to_owned()]],
+                    vec![vec![
+                        Some("-32769".to_owned()),
+                        Some("100".to_owned()),
+                        Some("100".to_owned())
+                    ]],
                 )
                 .expect("no system errors"),
             Err(OperationOnTableError::ConstraintViolations(
@@ -437,7 +587,11 @@ mod constraints {
                     default_schema_name,
                     "table_name",
                     vec![],
-                    vec![vec!["abc".to_owned(), "100".to_owned(), "100".to_owned()]],
+                    vec![vec![
+                        Some("abc".to_owned()),
+                        Some("100".to_owned()),
+                        Some("100".to_owned())
+                    ]],
                 )
                 .expect("no system errors"),
             Err(OperationOnTableError::ConstraintViolations(
@@ -458,7 +612,7 @@ mod constraints {
                     default_schema_name,
                     "table_name",
                     vec![],
-                    vec![vec!["12345678901".to_owned(), "100".to_owned()]],
+                    vec![vec![Some("12345678901".to_owned()), Some("100".to_owned())]],
                 )
                 .expect("no system errors"),
             Err(OperationOnTableError::ConstraintViolations(
@@ -482,7 +636,11 @@ mod constraints {
                     default_schema_name,
                     "table_name",
                     vec![],
-                    vec![vec!["-32769".to_owned(), "-2147483649".to_owned(), "100".to_owned()]],
+                    vec![vec![
+                        Some("-32769".to_owned()),
+                        Some("-2147483649".to_owned()),
+                        Some("100".to_owned())
+                    ]],
                 )
                 .expect("no system errors"),
             Err(OperationOnTableError::ConstraintViolations(
@@ -513,11 +671,15 @@ mod constraints {
                     "table_name",
                     vec![],
                     vec![
-                        vec!["-32769".to_owned(), "-2147483649".to_owned(), "100".to_owned()],
                         vec![
-                            "100".to_owned(),
-                            "-2147483649".to_owned(),
-                            "-9223372036854775809".to_owned()
+                            Some("-32769".to_owned()),
+                            Some("-2147483649".to_owned()),
+                            Some("100".to_owned())
+                        ],
+                        vec![
+                            Some("100".to_owned()),
+                            Some("-2147483649".to_owned()),
+                            Some("-9223372036854775809".to_owned())
                         ],
                     ],
                 )
@@ -539,3 +701,156 @@ mod constraints {
         )
     }
 }
+
+#[cfg(test)]
+mod validate_only {
+    use super::*;
+    use sql_types::ConstraintError;
+
+    #[rstest::fixture]
+    fn storage_with_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) -> PersistentStorage {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_test", SqlType::SmallInt(i16::min_value()))],
+        );
+        storage_with_schema
+    }
+
+    #[rstest::rstest]
+    fn validate_insert_into_non_existent_table(default_schema_name: &str, storage_with_schema: PersistentStorage) {
+        assert_eq!(
+            storage_with_schema
+                .validate_insert(
+                    default_schema_name,
+                    "not_existed",
+                    vec![],
+                    vec![vec![Some("123".to_owned())]]
+                )
+                .expect("no system errors"),
+            Err(OperationOnTableError::TableDoesNotExist)
+        );
+    }
+
+    #[rstest::rstest]
+    fn valid_row_is_not_persisted(default_schema_name: &str, storage_with_table: PersistentStorage) {
+        assert_eq!(
+            storage_with_table
+                .validate_insert(
+                    default_schema_name,
+                    "table_name",
+                    vec![],
+                    vec![vec![Some("123".to_owned())]]
+                )
+                .expect("no system errors"),
+            Ok(())
+        );
+
+        assert_eq!(
+            storage_with_table
+                .select_all_from(default_schema_name, "table_name", vec!["column_test".to_owned()])
+                .expect("no system errors"),
+            Ok((
+                vec![column_definition("column_test", SqlType::SmallInt(i16::min_value()))],
+                vec![]
+            ))
+        );
+    }
+
+    #[rstest::rstest]
+    fn invalid_row_is_reported_and_not_persisted(default_schema_name: &str, storage_with_table: PersistentStorage) {
+        assert_eq!(
+            storage_with_table
+                .validate_insert(
+                    default_schema_name,
+                    "table_name",
+                    vec![],
+                    vec![vec![Some("123".to_owned())], vec![Some("-32769".to_owned())]]
+                )
+                .expect("no system errors"),
+            Err(OperationOnTableError::ConstraintViolations(
+                vec![(
+                    ConstraintError::OutOfRange,
+                    column_definition("column_test", SqlType::SmallInt(i16::min_value()))
+                )],
+                2
+            ))
+        );
+
+        assert_eq!(
+            storage_with_table
+                .select_all_from(default_schema_name, "table_name", vec!["column_test".to_owned()])
+                .expect("no system errors"),
+            Ok((
+                vec![column_definition("column_test", SqlType::SmallInt(i16::min_value()))],
+                vec![]
+            ))
+        );
+    }
+
+    #[rstest::rstest]
+    fn previewed_rows_are_not_persisted(default_schema_name: &str, storage_with_table: PersistentStorage) {
+        assert_eq!(
+            storage_with_table
+                .preview_insert(
+                    default_schema_name,
+                    "table_name",
+                    vec![],
+                    vec![vec![Some("0123".to_owned())], vec![Some("-1".to_owned())]]
+                )
+                .expect("no system errors"),
+            Ok((
+                vec![column_definition("column_test", SqlType::SmallInt(i16::min_value()))],
+                vec![vec!["123".to_owned()], vec!["-1".to_owned()]]
+            ))
+        );
+
+        assert_eq!(
+            storage_with_table
+                .select_all_from(default_schema_name, "table_name", vec!["column_test".to_owned()])
+                .expect("no system errors"),
+            Ok((
+                vec![column_definition("column_test", SqlType::SmallInt(i16::min_value()))],
+                vec![]
+            ))
+        );
+    }
+}
+
+#[rstest::rstest]
+fn insert_returns_written_records(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+    let id = ColumnDefinition {
+        sequence: Some(1),
+        ..column_definition("id", SqlType::Integer(i32::min_value()))
+    };
+    let name = column_definition("name", SqlType::VarChar(10));
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![id.clone(), name.clone()],
+    );
+
+    let (columns, records) = storage_with_schema
+        .insert_into_returning(
+            default_schema_name,
+            "table_name",
+            vec!["name".to_owned()],
+            vec![vec![Some("first".to_owned())], vec![Some("second".to_owned())]],
+        )
+        .expect("no system errors")
+        .expect("values are inserted");
+
+    assert_eq!(
+        columns.iter().map(ColumnDefinition::name).collect::<Vec<String>>(),
+        vec!["id".to_owned(), "name".to_owned()]
+    );
+    assert_eq!(
+        records,
+        vec![
+            vec!["1".to_owned(), "first".to_owned()],
+            vec!["2".to_owned(), "second".to_owned()]
+        ]
+    );
+}
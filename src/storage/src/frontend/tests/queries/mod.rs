@@ -19,6 +19,10 @@ mod delete;
 #[cfg(test)]
 mod insert;
 #[cfg(test)]
+mod row_count;
+#[cfg(test)]
 mod select;
 #[cfg(test)]
+mod table_stats;
+#[cfg(test)]
 mod update;
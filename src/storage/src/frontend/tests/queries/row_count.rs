@@ -0,0 +1,99 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use sql_types::SqlType;
+
+#[rstest::fixture]
+fn with_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) -> PersistentStorage {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![column_definition("column_test", SqlType::SmallInt(i16::min_value()))],
+    );
+    storage_with_schema
+}
+
+#[rstest::rstest]
+fn row_count_of_non_existent_schema(storage: PersistentStorage) {
+    assert_eq!(
+        storage
+            .row_count("non_existent", "table_name")
+            .expect("no system errors"),
+        Err(OperationOnTableError::SchemaDoesNotExist)
+    );
+}
+
+#[rstest::rstest]
+fn row_count_of_non_existent_table(default_schema_name: &str, storage_with_schema: PersistentStorage) {
+    assert_eq!(
+        storage_with_schema
+            .row_count(default_schema_name, "non_existent")
+            .expect("no system errors"),
+        Err(OperationOnTableError::TableDoesNotExist)
+    );
+}
+
+#[rstest::rstest]
+fn row_count_follows_inserts_and_deletes(default_schema_name: &str, mut with_table: PersistentStorage) {
+    assert_eq!(
+        with_table
+            .row_count(default_schema_name, "table_name")
+            .expect("no system errors"),
+        Ok(0)
+    );
+
+    insert_into(&mut with_table, default_schema_name, "table_name", vec![], vec!["1"]);
+    insert_into(&mut with_table, default_schema_name, "table_name", vec![], vec!["2"]);
+    assert_eq!(
+        with_table
+            .row_count(default_schema_name, "table_name")
+            .expect("no system errors"),
+        Ok(2)
+    );
+
+    with_table
+        .delete_all_from(default_schema_name, "table_name")
+        .expect("no system errors")
+        .expect("rows are deleted");
+    assert_eq!(
+        with_table
+            .row_count(default_schema_name, "table_name")
+            .expect("no system errors"),
+        Ok(0)
+    );
+}
+
+#[rstest::rstest]
+fn row_count_of_recreated_table(default_schema_name: &str, mut with_table: PersistentStorage) {
+    insert_into(&mut with_table, default_schema_name, "table_name", vec![], vec!["1"]);
+    with_table
+        .drop_table(default_schema_name, "table_name")
+        .expect("no system errors")
+        .expect("table is dropped");
+    create_table(
+        &mut with_table,
+        default_schema_name,
+        "table_name",
+        vec![column_definition("column_test", SqlType::SmallInt(i16::min_value()))],
+    );
+
+    assert_eq!(
+        with_table
+            .row_count(default_schema_name, "table_name")
+            .expect("no system errors"),
+        Ok(0)
+    );
+}
@@ -31,7 +31,7 @@ fn with_small_ints_table(default_schema_name: &str, mut storage_with_schema: Per
 }
 
 #[rstest::rstest]
-fn select_from_table_from_non_existent_schema(mut storage: PersistentStorage) {
+fn select_from_table_from_non_existent_schema(storage: PersistentStorage) {
     assert_eq!(
         storage
             .select_all_from("non_existent", "table_name", vec![])
@@ -41,7 +41,7 @@ fn select_from_table_from_non_existent_schema(mut storage: PersistentStorage) {
 }
 
 #[rstest::rstest]
-fn select_from_table_that_does_not_exist(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+fn select_from_table_that_does_not_exist(default_schema_name: &str, storage_with_schema: PersistentStorage) {
     let table_columns = storage_with_schema
         .table_columns(default_schema_name, "not_existed")
         .expect("no system errors")
@@ -261,6 +261,62 @@ fn select_with_column_name_duplication(default_schema_name: &str, mut with_small
     );
 }
 
+#[rstest::rstest]
+fn select_with_duplication_of_columns_of_different_types(
+    default_schema_name: &str,
+    mut storage_with_schema: PersistentStorage,
+) {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![
+            column_definition("column_si", SqlType::SmallInt(i16::min_value())),
+            column_definition("column_vc", SqlType::VarChar(10)),
+            column_definition("column_bi", SqlType::BigInt(i64::min_value())),
+        ],
+    );
+    insert_into(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![],
+        vec!["1", "abc", "1000"],
+    );
+
+    assert_eq!(
+        storage_with_schema
+            .select_all_from(
+                default_schema_name,
+                "table_name",
+                vec![
+                    "column_vc".to_owned(),
+                    "column_si".to_owned(),
+                    "column_bi".to_owned(),
+                    "column_vc".to_owned(),
+                    "column_si".to_owned()
+                ]
+            )
+            .expect("no system errors"),
+        Ok((
+            vec![
+                column_definition("column_vc", SqlType::VarChar(10)),
+                column_definition("column_si", SqlType::SmallInt(i16::min_value())),
+                column_definition("column_bi", SqlType::BigInt(i64::min_value())),
+                column_definition("column_vc", SqlType::VarChar(10)),
+                column_definition("column_si", SqlType::SmallInt(i16::min_value()))
+            ],
+            vec![vec![
+                "abc".to_owned(),
+                "1".to_owned(),
+                "1000".to_owned(),
+                "abc".to_owned(),
+                "1".to_owned()
+            ]],
+        ))
+    );
+}
+
 #[rstest::rstest]
 fn select_different_integer_types(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
     create_table(
@@ -374,3 +430,44 @@ fn select_different_character_strings_types(default_schema_name: &str, mut stora
         ))
     );
 }
+
+#[rstest::rstest]
+fn select_returns_rows_in_insertion_order(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![column_definition("column_1", SqlType::Integer(i32::min_value()))],
+    );
+
+    // enough rows for row keys to span more than a single byte
+    let values = (0..300).rev().map(|value| value.to_string()).collect::<Vec<String>>();
+    storage_with_schema
+        .insert_into(
+            default_schema_name,
+            "table_name",
+            vec![],
+            values.iter().take(150).map(|value| vec![Some(value.clone())]).collect(),
+        )
+        .expect("no system errors")
+        .expect("values are inserted");
+    for value in values.iter().skip(150) {
+        insert_into(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![],
+            vec![value.as_str()],
+        );
+    }
+
+    assert_eq!(
+        storage_with_schema
+            .select_all_from(default_schema_name, "table_name", vec!["column_1".to_owned()])
+            .expect("no system errors"),
+        Ok((
+            vec![column_definition("column_1", SqlType::Integer(i32::min_value()))],
+            values.into_iter().map(|value| vec![value]).collect()
+        ))
+    );
+}
@@ -0,0 +1,105 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use sql_types::SqlType;
+
+#[rstest::fixture]
+fn with_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) -> PersistentStorage {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![
+            column_definition("column_1", SqlType::SmallInt(i16::min_value())),
+            column_definition("column_2", SqlType::SmallInt(i16::min_value())),
+        ],
+    );
+    storage_with_schema
+}
+
+#[rstest::rstest]
+fn stats_of_non_existent_schema(storage: PersistentStorage) {
+    assert_eq!(
+        storage
+            .table_stats("non_existent", "table_name")
+            .expect("no system errors"),
+        Err(OperationOnTableError::SchemaDoesNotExist)
+    );
+}
+
+#[rstest::rstest]
+fn stats_of_non_existent_table(default_schema_name: &str, storage_with_schema: PersistentStorage) {
+    assert_eq!(
+        storage_with_schema
+            .table_stats(default_schema_name, "non_existent")
+            .expect("no system errors"),
+        Err(OperationOnTableError::TableDoesNotExist)
+    );
+}
+
+#[rstest::rstest]
+fn stats_of_empty_table(default_schema_name: &str, with_table: PersistentStorage) {
+    assert_eq!(
+        with_table
+            .table_stats(default_schema_name, "table_name")
+            .expect("no system errors"),
+        Ok(TableStats {
+            row_count: 0,
+            byte_size: 0,
+            column_count: 2,
+        })
+    );
+}
+
+#[rstest::rstest]
+fn stats_reflect_inserted_rows(default_schema_name: &str, mut with_table: PersistentStorage) {
+    insert_into(
+        &mut with_table,
+        default_schema_name,
+        "table_name",
+        vec![],
+        vec!["1", "2"],
+    );
+    let one_row = with_table
+        .table_stats(default_schema_name, "table_name")
+        .expect("no system errors")
+        .expect("table exists");
+
+    insert_into(
+        &mut with_table,
+        default_schema_name,
+        "table_name",
+        vec![],
+        vec!["3", "4"],
+    );
+    insert_into(
+        &mut with_table,
+        default_schema_name,
+        "table_name",
+        vec![],
+        vec!["5", "6"],
+    );
+    let three_rows = with_table
+        .table_stats(default_schema_name, "table_name")
+        .expect("no system errors")
+        .expect("table exists");
+
+    assert_eq!(one_row.row_count, 1);
+    assert_eq!(one_row.column_count, 2);
+    assert!(one_row.byte_size > 0);
+    assert_eq!(three_rows.row_count, 3);
+    assert_eq!(three_rows.column_count, 2);
+    assert_eq!(three_rows.byte_size, one_row.byte_size * 3);
+}
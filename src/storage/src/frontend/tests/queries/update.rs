@@ -142,7 +142,11 @@ mod constraints {
                 default_schema_name,
                 "table_name",
                 vec![],
-                vec![vec!["100".to_owned(), "100".to_owned(), "100".to_owned()]],
+                vec![vec![
+                    Some("100".to_owned()),
+                    Some("100".to_owned()),
+                    Some("100".to_owned()),
+                ]],
             )
             .expect("no system errors")
             .expect("record inserted");
@@ -175,7 +179,11 @@ mod constraints {
                 default_schema_name,
                 "table_name",
                 vec![],
-                vec![vec!["100".to_owned(), "100".to_owned(), "100".to_owned()]],
+                vec![vec![
+                    Some("100".to_owned()),
+                    Some("100".to_owned()),
+                    Some("100".to_owned()),
+                ]],
             )
             .expect("no system errors")
             .expect("record inserted");
@@ -208,7 +216,7 @@ mod constraints {
                 default_schema_name,
                 "table_name",
                 vec![],
-                vec![vec!["100".to_owned(), "100".to_owned()]],
+                vec![vec![Some("100".to_owned()), Some("100".to_owned())]],
             )
             .expect("no system errors")
             .expect("record inserted");
@@ -240,7 +248,11 @@ mod constraints {
                 default_schema_name,
                 "table_name",
                 vec![],
-                vec![vec!["100".to_owned(), "100".to_owned(), "100".to_owned()]],
+                vec![vec![
+                    Some("100".to_owned()),
+                    Some("100".to_owned()),
+                    Some("100".to_owned()),
+                ]],
             )
             .expect("no system errors")
             .expect("records inserted");
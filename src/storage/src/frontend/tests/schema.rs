@@ -31,6 +31,22 @@ fn create_schema_with_existing_name(default_schema_name: &str, mut storage_with_
     );
 }
 
+#[rstest::rstest]
+fn schema_exists(default_schema_name: &str, storage_with_schema: PersistentStorage) {
+    assert!(storage_with_schema.schema_exists(default_schema_name));
+    assert!(!storage_with_schema.schema_exists("non_existent"));
+}
+
+#[rstest::rstest]
+fn dropped_schema_does_not_exist(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+    storage_with_schema
+        .drop_schema(default_schema_name)
+        .expect("no system errors")
+        .expect("schema is dropped");
+
+    assert!(!storage_with_schema.schema_exists(default_schema_name));
+}
+
 #[rstest::rstest]
 fn same_table_names_with_different_columns_in_different_schemas(mut storage: PersistentStorage) {
     create_schema(&mut storage, "schema_name_1");
@@ -0,0 +1,47 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+#[rstest::rstest]
+fn create_sequence_with_existing_name(mut storage: PersistentStorage) {
+    assert_eq!(
+        storage.create_sequence("sequence_name").expect("no system errors"),
+        Ok(())
+    );
+    assert_eq!(
+        storage.create_sequence("sequence_name").expect("no system errors"),
+        Err(SequenceAlreadyExists)
+    );
+}
+
+#[rstest::rstest]
+fn next_value_of_non_existent_sequence(mut storage: PersistentStorage) {
+    assert_eq!(
+        storage.next_value("sequence_name").expect("no system errors"),
+        Err(SequenceDoesNotExist)
+    );
+}
+
+#[rstest::rstest]
+fn next_value_advances_sequence(mut storage: PersistentStorage) {
+    storage
+        .create_sequence("sequence_name")
+        .expect("no system errors")
+        .expect("sequence is created");
+
+    assert_eq!(storage.next_value("sequence_name").expect("no system errors"), Ok(1));
+    assert_eq!(storage.next_value("sequence_name").expect("no system errors"), Ok(2));
+    assert_eq!(storage.next_value("sequence_name").expect("no system errors"), Ok(3));
+}
@@ -0,0 +1,48 @@
+// Copyright 2020 Alex Dukhno
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use std::{
+    sync::{Arc, Barrier, RwLock, TryLockError},
+    thread,
+};
+
+#[rstest::rstest]
+fn reads_proceed_concurrently_while_write_is_exclusive(storage: PersistentStorage) {
+    let shared: SharedStorage<SledBackendStorage> = Arc::new(RwLock::new(storage));
+    let both_read = Arc::new(Barrier::new(2));
+    let write_checked = Arc::new(Barrier::new(2));
+
+    let reader = {
+        let shared = shared.clone();
+        let both_read = both_read.clone();
+        let write_checked = write_checked.clone();
+        thread::spawn(move || {
+            let storage = shared.read().expect("read lock");
+            both_read.wait();
+            assert!(storage.schema_exists("system"));
+            write_checked.wait();
+        })
+    };
+
+    let storage = shared.read().expect("read lock");
+    both_read.wait();
+    assert!(storage.schema_exists("system"));
+    assert!(matches!(shared.try_write(), Err(TryLockError::WouldBlock)));
+    write_checked.wait();
+    drop(storage);
+
+    reader.join().expect("reader finished");
+    assert!(shared.try_write().is_ok());
+}
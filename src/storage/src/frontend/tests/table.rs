@@ -72,6 +72,39 @@ fn create_table_with_the_same_name(default_schema_name: &str, mut storage_with_s
     );
 }
 
+#[rstest::rstest]
+fn create_table_with_duplicate_column_names(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+    assert_eq!(
+        storage_with_schema
+            .create_table(
+                default_schema_name,
+                "table_name",
+                &[
+                    column_definition("column_1", SqlType::SmallInt(i16::min_value())),
+                    column_definition("column_2", SqlType::SmallInt(i16::min_value())),
+                    column_definition("column_1", SqlType::Integer(i32::min_value())),
+                ]
+            )
+            .expect("no system errors"),
+        Err(CreateTableError::DuplicateColumn("column_1".to_owned()))
+    );
+    assert!(!storage_with_schema.table_exists(default_schema_name, "table_name"));
+}
+
+#[rstest::rstest]
+fn table_exists(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+    create_table(
+        &mut storage_with_schema,
+        default_schema_name,
+        "table_name",
+        vec![column_definition("column_name", SqlType::SmallInt(i16::min_value()))],
+    );
+
+    assert!(storage_with_schema.table_exists(default_schema_name, "table_name"));
+    assert!(!storage_with_schema.table_exists(default_schema_name, "non_existent"));
+    assert!(!storage_with_schema.table_exists("non_existent", "table_name"));
+}
+
 #[rstest::rstest]
 fn create_table_with_the_same_name_in_different_schemas(mut storage: PersistentStorage) {
     create_schema(&mut storage, "schema_name_1");
@@ -157,3 +190,536 @@ fn drop_not_created_table(default_schema_name: &str, mut storage_with_schema: Pe
         Err(DropTableError::TableDoesNotExist)
     );
 }
+
+#[cfg(test)]
+mod rename_column {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) -> PersistentStorage {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![
+                column_definition("column_1", SqlType::SmallInt(i16::min_value())),
+                column_definition("column_2", SqlType::SmallInt(i16::min_value())),
+            ],
+        );
+        storage_with_schema
+    }
+
+    #[rstest::rstest]
+    fn rename_column_in_non_existent_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        assert_eq!(
+            storage_with_schema
+                .rename_column(default_schema_name, "not_existed", "column_1", "new_column")
+                .expect("no system errors"),
+            Err(AlterTableError::TableDoesNotExist)
+        );
+    }
+
+    #[rstest::rstest]
+    fn rename_column(default_schema_name: &str, mut with_table: PersistentStorage) {
+        assert_eq!(
+            with_table
+                .rename_column(default_schema_name, "table_name", "column_1", "new_column")
+                .expect("no system errors"),
+            Ok(())
+        );
+
+        assert_eq!(
+            with_table
+                .table_columns(default_schema_name, "table_name")
+                .expect("no system errors"),
+            vec![
+                column_definition("new_column", SqlType::SmallInt(i16::min_value())),
+                column_definition("column_2", SqlType::SmallInt(i16::min_value())),
+            ]
+        );
+    }
+
+    #[rstest::rstest]
+    fn rename_to_existing_column(default_schema_name: &str, mut with_table: PersistentStorage) {
+        assert_eq!(
+            with_table
+                .rename_column(default_schema_name, "table_name", "column_1", "column_2")
+                .expect("no system errors"),
+            Err(AlterTableError::ColumnAlreadyExists("column_2".to_owned()))
+        );
+    }
+
+    #[rstest::rstest]
+    fn rename_non_existent_column(default_schema_name: &str, mut with_table: PersistentStorage) {
+        assert_eq!(
+            with_table
+                .rename_column(default_schema_name, "table_name", "not_existed", "new_column")
+                .expect("no system errors"),
+            Err(AlterTableError::ColumnDoesNotExist("not_existed".to_owned()))
+        );
+    }
+}
+
+#[cfg(test)]
+mod alter_column_type {
+    use super::*;
+    use sql_types::ConstraintError;
+
+    #[rstest::rstest]
+    fn alter_column_type_in_non_existent_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        assert_eq!(
+            storage_with_schema
+                .alter_column_type(
+                    default_schema_name,
+                    "not_existed",
+                    "column_1",
+                    SqlType::BigInt(i64::min_value())
+                )
+                .expect("no system errors"),
+            Err(AlterTableError::TableDoesNotExist)
+        );
+    }
+
+    #[rstest::rstest]
+    fn alter_type_of_non_existent_column(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::SmallInt(i16::min_value()))],
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .alter_column_type(
+                    default_schema_name,
+                    "table_name",
+                    "not_existed",
+                    SqlType::BigInt(i64::min_value())
+                )
+                .expect("no system errors"),
+            Err(AlterTableError::ColumnDoesNotExist("not_existed".to_owned()))
+        );
+    }
+
+    #[rstest::rstest]
+    fn widen_integer_column(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![
+                column_definition("column_1", SqlType::SmallInt(i16::min_value())),
+                column_definition("column_2", SqlType::VarChar(10)),
+            ],
+        );
+        insert_into(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![],
+            vec!["-32768", "first"],
+        );
+        insert_into(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![],
+            vec!["32767", "second"],
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .alter_column_type(
+                    default_schema_name,
+                    "table_name",
+                    "column_1",
+                    SqlType::BigInt(i64::min_value())
+                )
+                .expect("no system errors"),
+            Ok(())
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .select_all_from(
+                    default_schema_name,
+                    "table_name",
+                    vec!["column_1".to_owned(), "column_2".to_owned()]
+                )
+                .expect("no system errors"),
+            Ok((
+                vec![
+                    column_definition("column_1", SqlType::BigInt(i64::min_value())),
+                    column_definition("column_2", SqlType::VarChar(10)),
+                ],
+                vec![
+                    vec!["-32768".to_owned(), "first".to_owned()],
+                    vec!["32767".to_owned(), "second".to_owned()],
+                ]
+            ))
+        );
+    }
+
+    #[rstest::rstest]
+    fn incompatible_conversion_keeps_table_untouched(
+        default_schema_name: &str,
+        mut storage_with_schema: PersistentStorage,
+    ) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::VarChar(10))],
+        );
+        insert_into(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![],
+            vec!["123"],
+        );
+        insert_into(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![],
+            vec!["abc"],
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .alter_column_type(
+                    default_schema_name,
+                    "table_name",
+                    "column_1",
+                    SqlType::Integer(i32::min_value())
+                )
+                .expect("no system errors"),
+            Err(AlterTableError::ConstraintViolations(
+                vec![(
+                    ConstraintError::TypeMismatch("abc".to_owned()),
+                    column_definition("column_1", SqlType::Integer(i32::min_value()))
+                )],
+                2
+            ))
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .select_all_from(default_schema_name, "table_name", vec!["column_1".to_owned()])
+                .expect("no system errors"),
+            Ok((
+                vec![column_definition("column_1", SqlType::VarChar(10))],
+                vec![vec!["123".to_owned()], vec!["abc".to_owned()]]
+            ))
+        );
+    }
+}
+
+#[cfg(test)]
+mod compact {
+    use super::*;
+
+    #[rstest::rstest]
+    fn compact_non_existent_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        assert_eq!(
+            storage_with_schema
+                .compact(default_schema_name, "not_existed")
+                .expect("no system errors"),
+            Err(OperationOnTableError::TableDoesNotExist)
+        );
+    }
+
+    #[rstest::rstest]
+    fn compact_keeps_rows_in_insertion_order(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::Integer(i32::min_value()))],
+        );
+        insert_into(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![],
+            vec!["1"],
+        );
+        storage_with_schema
+            .delete_all_from(default_schema_name, "table_name")
+            .expect("no system errors")
+            .expect("records are deleted");
+        for value in 2..5 {
+            insert_into(
+                &mut storage_with_schema,
+                default_schema_name,
+                "table_name",
+                vec![],
+                vec![value.to_string().as_str()],
+            );
+        }
+
+        assert_eq!(
+            storage_with_schema
+                .compact(default_schema_name, "table_name")
+                .expect("no system errors"),
+            Ok(3)
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .select_all_from(default_schema_name, "table_name", vec!["column_1".to_owned()])
+                .expect("no system errors"),
+            Ok((
+                vec![column_definition("column_1", SqlType::Integer(i32::min_value()))],
+                vec![vec!["2".to_owned()], vec!["3".to_owned()], vec!["4".to_owned()]]
+            ))
+        );
+    }
+}
+
+#[cfg(test)]
+mod privileges {
+    use super::*;
+
+    #[rstest::rstest]
+    fn grant_on_non_existent_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        assert_eq!(
+            storage_with_schema
+                .grant_select(default_schema_name, "not_existed", "user_name")
+                .expect("no system errors"),
+            Err(OperationOnTableError::TableDoesNotExist)
+        );
+    }
+
+    #[rstest::rstest]
+    fn table_is_not_restricted_by_default(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::SmallInt(i16::min_value()))],
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .select_grantees(default_schema_name, "table_name")
+                .expect("no system errors"),
+            None
+        );
+    }
+
+    #[rstest::rstest]
+    fn grant_and_revoke_select(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::SmallInt(i16::min_value()))],
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .grant_select(default_schema_name, "table_name", "user_1")
+                .expect("no system errors"),
+            Ok(())
+        );
+        assert_eq!(
+            storage_with_schema
+                .grant_select(default_schema_name, "table_name", "user_2")
+                .expect("no system errors"),
+            Ok(())
+        );
+        assert_eq!(
+            storage_with_schema
+                .select_grantees(default_schema_name, "table_name")
+                .expect("no system errors"),
+            Some(vec!["user_1".to_owned(), "user_2".to_owned()])
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .revoke_select(default_schema_name, "table_name", "user_1")
+                .expect("no system errors"),
+            Ok(())
+        );
+        assert_eq!(
+            storage_with_schema
+                .revoke_select(default_schema_name, "table_name", "user_2")
+                .expect("no system errors"),
+            Ok(())
+        );
+        assert_eq!(
+            storage_with_schema
+                .select_grantees(default_schema_name, "table_name")
+                .expect("no system errors"),
+            Some(vec![])
+        );
+    }
+
+    #[rstest::rstest]
+    fn dropping_table_removes_its_privileges(default_schema_name: &str, mut storage_with_schema: PersistentStorage) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::SmallInt(i16::min_value()))],
+        );
+        storage_with_schema
+            .grant_select(default_schema_name, "table_name", "user_name")
+            .expect("no system errors")
+            .expect("privilege is granted");
+        storage_with_schema
+            .drop_table(default_schema_name, "table_name")
+            .expect("no system errors")
+            .expect("table is dropped");
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::SmallInt(i16::min_value()))],
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .select_grantees(default_schema_name, "table_name")
+                .expect("no system errors"),
+            None
+        );
+    }
+
+    #[rstest::rstest]
+    fn dropping_schema_removes_privileges_of_its_tables(
+        default_schema_name: &str,
+        mut storage_with_schema: PersistentStorage,
+    ) {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::SmallInt(i16::min_value()))],
+        );
+        storage_with_schema
+            .grant_select(default_schema_name, "table_name", "user_name")
+            .expect("no system errors")
+            .expect("privilege is granted");
+        storage_with_schema
+            .drop_schema(default_schema_name)
+            .expect("no system errors")
+            .expect("schema is dropped");
+        create_schema(&mut storage_with_schema, default_schema_name);
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![column_definition("column_1", SqlType::SmallInt(i16::min_value()))],
+        );
+
+        assert_eq!(
+            storage_with_schema
+                .select_grantees(default_schema_name, "table_name")
+                .expect("no system errors"),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod column_default {
+    use super::*;
+
+    #[rstest::fixture]
+    fn with_table(default_schema_name: &str, mut storage_with_schema: PersistentStorage) -> PersistentStorage {
+        create_table(
+            &mut storage_with_schema,
+            default_schema_name,
+            "table_name",
+            vec![
+                column_definition("with_default", SqlType::SmallInt(i16::min_value()))
+                    .with_default_expression("1 + 2".to_owned()),
+                column_definition("without_default", SqlType::SmallInt(i16::min_value())),
+            ],
+        );
+        storage_with_schema
+    }
+
+    #[rstest::rstest]
+    fn column_with_default(default_schema_name: &str, with_table: PersistentStorage) {
+        assert_eq!(
+            with_table
+                .column_default(default_schema_name, "table_name", "with_default")
+                .expect("no system errors"),
+            Ok(Some("1 + 2".to_owned()))
+        );
+    }
+
+    #[rstest::rstest]
+    fn column_without_default(default_schema_name: &str, with_table: PersistentStorage) {
+        assert_eq!(
+            with_table
+                .column_default(default_schema_name, "table_name", "without_default")
+                .expect("no system errors"),
+            Ok(None)
+        );
+    }
+
+    #[rstest::rstest]
+    fn non_existent_column(default_schema_name: &str, with_table: PersistentStorage) {
+        assert_eq!(
+            with_table
+                .column_default(default_schema_name, "table_name", "non_existent")
+                .expect("no system errors"),
+            Err(OperationOnTableError::ColumnDoesNotExist(vec![
+                "non_existent".to_owned()
+            ]))
+        );
+    }
+
+    #[rstest::rstest]
+    fn non_existent_table(default_schema_name: &str, storage_with_schema: PersistentStorage) {
+        assert_eq!(
+            storage_with_schema
+                .column_default(default_schema_name, "non_existent", "with_default")
+                .expect("no system errors"),
+            Err(OperationOnTableError::TableDoesNotExist)
+        );
+    }
+}
+
+#[cfg(test)]
+mod column_definition {
+    use super::*;
+
+    #[test]
+    fn var_char_type_catalog_values() {
+        let column = ColumnDefinition::new("column_name", SqlType::VarChar(20));
+
+        assert_eq!(column.type_oid(), 1043);
+        assert_eq!(column.type_size(), -1);
+        assert_eq!(column.type_modifier(), 24);
+    }
+
+    #[test]
+    fn unlimited_var_char_type_modifier() {
+        let column = ColumnDefinition::new("column_name", SqlType::VarChar(u64::MAX));
+
+        assert_eq!(column.type_modifier(), -1);
+    }
+
+    #[test]
+    fn var_char_length_that_does_not_fit_type_modifier() {
+        let column = ColumnDefinition::new("column_name", SqlType::VarChar(i32::MAX as u64 - 1));
+
+        assert_eq!(column.type_modifier(), -1);
+    }
+
+    #[test]
+    fn small_int_type_catalog_values() {
+        let column = ColumnDefinition::new("column_name", SqlType::SmallInt(i16::min_value()));
+
+        assert_eq!(column.type_oid(), 21);
+        assert_eq!(column.type_size(), 2);
+        assert_eq!(column.type_modifier(), -1);
+    }
+}
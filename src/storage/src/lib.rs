@@ -17,7 +17,8 @@ extern crate log;
 extern crate sql_types;
 
 use serde::{Deserialize, Serialize};
-use sql_types::{ConstraintError, SqlType};
+use sql_types::{Collation, ConstraintError, SqlType};
+use std::convert::TryFrom;
 
 pub mod backend;
 pub mod frontend;
@@ -29,10 +30,17 @@ pub struct SchemaAlreadyExists;
 #[derive(Debug, PartialEq)]
 pub struct SchemaDoesNotExist;
 
+#[derive(Debug, PartialEq)]
+pub struct SequenceAlreadyExists;
+#[derive(Debug, PartialEq)]
+pub struct SequenceDoesNotExist;
+
 #[derive(Debug, PartialEq)]
 pub enum CreateTableError {
     SchemaDoesNotExist,
     TableAlreadyExists,
+    // Returns the first column name that is specified more than once.
+    DuplicateColumn(String),
 }
 
 #[derive(Debug, PartialEq)]
@@ -41,17 +49,38 @@ pub enum DropTableError {
     TableDoesNotExist,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum AlterTableError {
+    SchemaDoesNotExist,
+    TableDoesNotExist,
+    ColumnDoesNotExist(String),
+    ColumnAlreadyExists(String),
+    // Returns vector of (error, column) and a row index.
+    ConstraintViolations(Vec<(ConstraintError, ColumnDefinition)>, usize),
+}
+
 #[derive(Debug, PartialEq)]
 pub enum OperationOnTableError {
     SchemaDoesNotExist,
     TableDoesNotExist,
     InsertTooManyExpressions,
+    // Named target columns are not all given a value.
+    InsertTooManyTargetColumns,
     // Returns non existing columns.
     ColumnDoesNotExist(Vec<String>),
     // Returns vector of (error, column) and a row index.
     ConstraintViolations(Vec<(ConstraintError, ColumnDefinition)>, usize),
 }
 
+/// Size of a table as it is known to the storage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableStats {
+    pub row_count: usize,
+    /// approximate number of bytes occupied by rows of the table
+    pub byte_size: u64,
+    pub column_count: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct TableDescription {
     schema_name: String,
@@ -74,7 +103,7 @@ impl TableDescription {
 
     pub fn column_type(&self, column_idx: usize) -> SqlType {
         if let Some(column) = self.column_data.get(column_idx) {
-            column.sql_type
+            column.sql_type.clone()
         } else {
             panic!("attempting to access type of invalid column index")
         }
@@ -84,7 +113,7 @@ impl TableDescription {
         self.column_data
             .iter()
             .find(|column| column.name == name)
-            .map(|column| column.sql_type)
+            .map(|column| column.sql_type.clone())
     }
 
     pub fn column_data(&self) -> &[ColumnDefinition] {
@@ -108,6 +137,11 @@ impl TableDescription {
 pub struct ColumnDefinition {
     name: String,
     sql_type: SqlType,
+    // next value handed out to a row that omits the column, set only for SERIAL columns
+    sequence: Option<i64>,
+    collation: Collation,
+    // SQL text of `DEFAULT` expression evaluated for every row that omits the column
+    default_expression: Option<String>,
 }
 
 impl ColumnDefinition {
@@ -115,11 +149,72 @@ impl ColumnDefinition {
         Self {
             name: name.to_string(),
             sql_type,
+            sequence: None,
+            collation: Collation::default(),
+            default_expression: None,
+        }
+    }
+
+    pub fn serial(name: &str, sql_type: SqlType) -> Self {
+        Self {
+            name: name.to_string(),
+            sql_type,
+            sequence: Some(1),
+            collation: Collation::default(),
+            default_expression: None,
         }
     }
 
+    /// collation of a character column set by `COLLATE` clause
+    pub fn with_collation(mut self, collation: Collation) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    /// expression set by `DEFAULT` clause, kept as SQL text
+    pub fn with_default_expression(mut self, expression: String) -> Self {
+        self.default_expression = Some(expression);
+        self
+    }
+
+    pub fn default_expression(&self) -> Option<&str> {
+        self.default_expression.as_deref()
+    }
+
+    pub fn collation(&self) -> Collation {
+        self.collation
+    }
+
+    pub fn is_serial(&self) -> bool {
+        self.sequence.is_some()
+    }
+
     pub fn sql_type(&self) -> SqlType {
-        self.sql_type
+        self.sql_type.clone()
+    }
+
+    /// PostgreSQL OID of the column type
+    pub fn type_oid(&self) -> i32 {
+        self.sql_type.to_pg_types().type_info().oid
+    }
+
+    /// PostgreSQL size of the column type, `-1` for variable length types
+    pub fn type_size(&self) -> i16 {
+        self.sql_type.to_pg_types().type_info().size
+    }
+
+    /// PostgreSQL `atttypmod` of the column, character types store declared
+    /// length plus 4 bytes of the length header, other types have `-1`
+    pub fn type_modifier(&self) -> i32 {
+        match self.sql_type {
+            // as in PostgreSQL the modifier includes the 4 byte header, -1
+            // means that the length is not limited
+            SqlType::Char(length) | SqlType::VarChar(length) => i32::try_from(length)
+                .ok()
+                .and_then(|length| length.checked_add(4))
+                .unwrap_or(-1),
+            _ => self.sql_type.to_pg_types().type_info().modifier,
+        }
     }
 
     fn has_name(&self, other_name: &str) -> bool {